@@ -17,8 +17,8 @@ fn main() -> Result<(), Box<dyn Error>> {
         files.extend(get_files(path?)?);
     }
 
+    // Server code is generated so that external signing services (and tests) can implement remote signer service
     tonic_build::configure()
-        .build_server(false)
         .extern_path(
             ".cosmos.auth.v1beta1",
             "::cosmos_sdk_proto::cosmos::auth::v1beta1",
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS light_client_attack_evidence;
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS light_client_attack_evidence (
+    id BIGSERIAL PRIMARY KEY,
+    chain_id TEXT NOT NULL,
+    evidence JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS light_client_attack_evidence_chain_id_idx ON light_client_attack_evidence (chain_id);
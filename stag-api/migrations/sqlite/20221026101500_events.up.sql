@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_type TEXT NOT NULL,
+    chain_id TEXT,
+    request_id TEXT,
+    event TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS events_chain_id_idx ON events (chain_id);
+CREATE INDEX IF NOT EXISTS events_request_id_idx ON events (request_id);
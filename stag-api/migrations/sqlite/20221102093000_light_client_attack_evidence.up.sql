@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS light_client_attack_evidence (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    chain_id TEXT NOT NULL,
+    evidence TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS light_client_attack_evidence_chain_id_idx ON light_client_attack_evidence (chain_id);
@@ -0,0 +1,197 @@
+//! Utilities for failing over between multiple RPC and gRPC endpoints of a chain.
+use std::{collections::BTreeMap, future::Future, sync::Mutex, time::Duration};
+
+use anyhow::{anyhow, Error, Result};
+use chrono::{DateTime, Utc};
+use url::Url;
+
+use crate::{
+    retry_util::{is_retriable, retry_if},
+    time_util::now_utc,
+    types::chain_state::RetryPolicy,
+};
+
+/// Duration for which an endpoint is deprioritized after it is marked as unhealthy
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Endpoints which recently failed (mapped to the time until which they are considered unhealthy)
+static UNHEALTHY_ENDPOINTS: Mutex<BTreeMap<Url, DateTime<Utc>>> = Mutex::new(BTreeMap::new());
+
+/// Runs the given operation against given endpoints (in decreasing order of priority) until it succeeds on one of
+/// them. Operation on each endpoint is retried based on the retry policy and the next endpoint is tried only when
+/// the failure is transient. Endpoints which recently failed are tried after healthy endpoints.
+pub async fn with_failover<'a, T, F, Fut>(
+    endpoints: impl IntoIterator<Item = &'a Url>,
+    retry_policy: &RetryPolicy,
+    operation: F,
+) -> Result<T>
+where
+    F: FnMut(&'a Url) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    with_failover_if(endpoints, retry_policy, is_retriable, operation).await
+}
+
+/// Same as [`with_failover`] but the operation is retried (or the next endpoint is tried) only when `should_retry`
+/// returns true for the error (e.g., to re-send non-idempotent requests only when the endpoint was unreachable)
+pub async fn with_failover_if<'a, T, F, Fut>(
+    endpoints: impl IntoIterator<Item = &'a Url>,
+    retry_policy: &RetryPolicy,
+    should_retry: fn(&Error) -> bool,
+    mut operation: F,
+) -> Result<T>
+where
+    F: FnMut(&'a Url) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut last_error = None;
+
+    for endpoint in prioritize(endpoints) {
+        match retry_if(retry_policy, should_retry, || operation(endpoint)).await {
+            Ok(value) => {
+                mark_healthy(endpoint);
+                return Ok(value);
+            }
+            Err(err) if should_retry(&err) => {
+                mark_unhealthy(endpoint);
+                last_error = Some(err.context(format!("endpoint {} is unavailable", endpoint)));
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow!("no endpoint available")))
+}
+
+/// Orders given endpoints such that healthy endpoints come first (while preserving their relative priority)
+pub fn prioritize<'a>(endpoints: impl IntoIterator<Item = &'a Url>) -> Vec<&'a Url> {
+    let mut prioritized: Vec<&Url> = Vec::new();
+
+    for endpoint in endpoints {
+        if !prioritized.contains(&endpoint) {
+            prioritized.push(endpoint);
+        }
+    }
+
+    prioritized.sort_by_key(|endpoint| !is_healthy(endpoint));
+    prioritized
+}
+
+/// Returns false if given endpoint failed recently
+pub fn is_healthy(endpoint: &Url) -> bool {
+    let mut unhealthy_endpoints = UNHEALTHY_ENDPOINTS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+
+    match unhealthy_endpoints.get(endpoint) {
+        Some(unhealthy_until) if *unhealthy_until > now_utc() => false,
+        Some(_) => {
+            unhealthy_endpoints.remove(endpoint);
+            true
+        }
+        None => true,
+    }
+}
+
+/// Marks given endpoint as unhealthy so that other endpoints are preferred over it for some time
+pub fn mark_unhealthy(endpoint: &Url) {
+    let unhealthy_until = now_utc()
+        + chrono::Duration::from_std(UNHEALTHY_COOLDOWN).expect("valid unhealthy cooldown");
+
+    UNHEALTHY_ENDPOINTS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(endpoint.clone(), unhealthy_until);
+}
+
+/// Marks given endpoint as healthy
+pub fn mark_healthy(endpoint: &Url) {
+    UNHEALTHY_ENDPOINTS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .remove(endpoint);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use tonic::Status;
+
+    use super::*;
+
+    fn test_retry_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 2,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+            backoff_multiplier: 1,
+        }
+    }
+
+    #[test]
+    fn test_prioritize() {
+        let first: Url = "http://prioritize-first:26657".parse().unwrap();
+        let second: Url = "http://prioritize-second:26657".parse().unwrap();
+        let third: Url = "http://prioritize-third:26657".parse().unwrap();
+
+        mark_unhealthy(&first);
+
+        assert_eq!(
+            prioritize([&first, &second, &first, &third]),
+            vec![&second, &third, &first]
+        );
+
+        mark_healthy(&first);
+
+        assert_eq!(
+            prioritize([&first, &second, &third]),
+            vec![&first, &second, &third]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_failover() {
+        let primary: Url = "http://failover-primary:26657".parse().unwrap();
+        let fallback: Url = "http://failover-fallback:26657".parse().unwrap();
+
+        let attempts = AtomicU32::new(0);
+
+        let result = with_failover([&primary, &fallback], &test_retry_policy(), |endpoint| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            let is_primary = endpoint == &primary;
+
+            async move {
+                if is_primary {
+                    Err(Status::unavailable("node is down").into())
+                } else {
+                    Ok(endpoint.clone())
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), fallback);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert!(!is_healthy(&primary));
+        assert!(is_healthy(&fallback));
+    }
+
+    #[tokio::test]
+    async fn test_with_failover_non_retriable_failure() {
+        let primary: Url = "http://non-retriable-primary:26657".parse().unwrap();
+        let fallback: Url = "http://non-retriable-fallback:26657".parse().unwrap();
+
+        let attempts = AtomicU32::new(0);
+
+        let result: Result<()> = with_failover([&primary, &fallback], &test_retry_policy(), |_| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async { Err(anyhow!("invalid request")) }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+        assert!(is_healthy(&primary));
+    }
+}
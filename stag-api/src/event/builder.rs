@@ -1,11 +1,42 @@
+use std::sync::Arc;
+#[cfg(feature = "webhook-event-handler")]
+use std::time::Duration;
+
+#[cfg(feature = "metrics-event-handler")]
+use anyhow::Result;
+#[cfg(feature = "metrics-event-handler")]
+use prometheus::Registry;
 use sealed::sealed;
+#[cfg(feature = "webhook-event-handler")]
+use url::Url;
 
-use crate::trait_util::Base;
+#[cfg(all(feature = "webhook-event-handler", feature = "postgres-storage"))]
+use crate::storage::Postgres;
+#[cfg(all(
+    feature = "webhook-event-handler",
+    any(feature = "sqlite-storage", feature = "postgres-storage")
+))]
+use crate::storage::SharedSqlDbStorage;
+#[cfg(all(feature = "webhook-event-handler", feature = "sqlite-storage"))]
+use crate::storage::Sqlite;
+#[cfg(feature = "webhook-event-handler")]
+use crate::types::chain_state::RetryPolicy;
+use crate::{storage::StorageConfig, trait_util::Base};
 
 use super::EventHandler;
 
+#[cfg(feature = "event-dispatcher")]
+use super::event_dispatcher::{
+    log_failure, Backpressure, DispatchFailure, EventDispatcher as EventDispatcherImpl, FailureHook,
+};
+#[cfg(feature = "metrics-event-handler")]
+use super::metrics_event_handler::{Metrics, MetricsEventHandler as MetricsEventHandlerImpl};
+use super::multi_event_handler::{EventFilter, MultiEventHandler as MultiEventHandlerImpl};
+use super::storage_event_handler::StorageEventHandler as StorageEventHandlerImpl;
 #[cfg(feature = "tracing-event-handler")]
 use super::tracing_event_handler::TracingEventHandler as TracingEventHandlerImpl;
+#[cfg(feature = "webhook-event-handler")]
+use super::webhook_event_handler::{Outbox, WebhookEventHandler as WebhookEventHandlerImpl};
 
 #[sealed]
 /// Configuration for event handler
@@ -15,6 +46,139 @@ pub trait EventHandlerConfig: Base {
 
     /// Create concrete event handler from this config
     fn into_event_handler(self) -> Self::EventHandler;
+
+    /// Returns the filter applied to events when this event handler is added to a multi event handler or an event
+    /// dispatcher without a filter (selects all the events except telemetry events by default)
+    fn default_filter(&self) -> EventFilter {
+        EventFilter::All
+    }
+}
+
+#[sealed]
+impl EventHandlerConfig for Arc<dyn EventHandler> {
+    type EventHandler = Self;
+
+    fn into_event_handler(self) -> Self::EventHandler {
+        self
+    }
+}
+
+#[derive(Clone, Default)]
+/// Event handler backend which sends events to multiple event handlers (with optional filters for each event handler)
+pub struct MultiEventHandler {
+    handlers: Vec<(Arc<dyn EventHandler>, EventFilter)>,
+}
+
+impl MultiEventHandler {
+    /// Creates a new instance of multi event handler without any event handler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an event handler which receives the events selected by its default filter (all the events except
+    /// telemetry events for all the event handlers other than metrics and storage event handlers)
+    pub fn with_handler<H>(self, handler: H) -> Self
+    where
+        H: EventHandlerConfig,
+        H::EventHandler: 'static,
+    {
+        let filter = handler.default_filter();
+        self.with_filtered_handler(handler, filter)
+    }
+
+    /// Adds an event handler which receives only the events selected by given filter
+    pub fn with_filtered_handler<H>(mut self, handler: H, filter: EventFilter) -> Self
+    where
+        H: EventHandlerConfig,
+        H::EventHandler: 'static,
+    {
+        self.handlers
+            .push((Arc::new(handler.into_event_handler()), filter));
+        self
+    }
+}
+
+#[sealed]
+impl EventHandlerConfig for MultiEventHandler {
+    type EventHandler = MultiEventHandlerImpl;
+
+    fn into_event_handler(self) -> Self::EventHandler {
+        MultiEventHandlerImpl::new(self.handlers)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Event handler backend which appends all the events to `events` table of given storage backend (use the same
+/// database as the one used for Stag's storage so that events can be queried and replayed using `Stag::get_events`
+/// and `Stag::replay_events`)
+pub struct StorageEventHandler<S> {
+    storage: S,
+}
+
+impl<S> StorageEventHandler<S>
+where
+    S: StorageConfig + Clone,
+{
+    /// Creates a new instance of storage event handler (storage backend is initialized when the first event is
+    /// handled)
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+}
+
+#[sealed]
+impl<S> EventHandlerConfig for StorageEventHandler<S>
+where
+    S: StorageConfig + Clone,
+{
+    type EventHandler = StorageEventHandlerImpl<S>;
+
+    fn into_event_handler(self) -> Self::EventHandler {
+        StorageEventHandlerImpl::new(self.storage)
+    }
+
+    fn default_filter(&self) -> EventFilter {
+        EventFilter::AllWithTelemetry
+    }
+}
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "metrics-event-handler")))]
+#[cfg(feature = "metrics-event-handler")]
+#[derive(Clone)]
+/// Event handler backend which collects Prometheus metrics from events (metrics are shared by all the clones of this
+/// config and the event handlers created from them)
+pub struct MetricsEventHandler {
+    metrics: Metrics,
+}
+
+#[cfg(feature = "metrics-event-handler")]
+impl MetricsEventHandler {
+    /// Creates a new instance of metrics event handler with a fresh set of metrics
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            metrics: Metrics::new()?,
+        })
+    }
+
+    /// Registers all the metrics in given registry (fails if the metrics are already registered in it)
+    pub fn register(&self, registry: &Registry) -> Result<()> {
+        self.metrics.register(registry)
+    }
+}
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "metrics-event-handler")))]
+#[cfg(feature = "metrics-event-handler")]
+#[sealed]
+impl EventHandlerConfig for MetricsEventHandler {
+    type EventHandler = MetricsEventHandlerImpl;
+
+    fn into_event_handler(self) -> Self::EventHandler {
+        MetricsEventHandlerImpl::new(self.metrics)
+    }
+
+    fn default_filter(&self) -> EventFilter {
+        EventFilter::AllWithTelemetry
+    }
 }
 
 #[cfg_attr(feature = "doc", doc(cfg(feature = "tracing-event-handler")))]
@@ -34,6 +198,219 @@ impl EventHandlerConfig for TracingEventHandler {
     }
 }
 
+#[cfg(feature = "event-dispatcher")]
+const DEFAULT_EVENT_DISPATCHER_CAPACITY: usize = 1024;
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "event-dispatcher")))]
+#[cfg(feature = "event-dispatcher")]
+#[derive(Clone)]
+/// Event handler backend which dispatches events to other event handlers in background tasks (so that slow or failing
+/// event handlers do not block or fail the operations emitting the events)
+pub struct EventDispatcher {
+    handlers: Vec<(Arc<dyn EventHandler>, EventFilter)>,
+    capacity: usize,
+    backpressure: Backpressure,
+    on_failure: Option<FailureHook>,
+}
+
+#[cfg(feature = "event-dispatcher")]
+impl Default for EventDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "event-dispatcher")]
+impl EventDispatcher {
+    /// Creates a new instance of event dispatcher without any event handler
+    pub fn new() -> Self {
+        Self {
+            handlers: Vec::new(),
+            capacity: DEFAULT_EVENT_DISPATCHER_CAPACITY,
+            backpressure: Backpressure::default(),
+            on_failure: None,
+        }
+    }
+
+    /// Adds an event handler which receives the events selected by its default filter (all the events except
+    /// telemetry events for all the event handlers other than metrics and storage event handlers)
+    pub fn with_handler<H>(self, handler: H) -> Self
+    where
+        H: EventHandlerConfig,
+        H::EventHandler: 'static,
+    {
+        let filter = handler.default_filter();
+        self.with_filtered_handler(handler, filter)
+    }
+
+    /// Adds an event handler which receives only the events selected by given filter
+    pub fn with_filtered_handler<H>(mut self, handler: H, filter: EventFilter) -> Self
+    where
+        H: EventHandlerConfig,
+        H::EventHandler: 'static,
+    {
+        self.handlers
+            .push((Arc::new(handler.into_event_handler()), filter));
+        self
+    }
+
+    /// Sets the maximum number of pending events in queue of each event handler (default: 1024, minimum: 1)
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets the policy applied when queue of an event handler is full (default: [`Backpressure::Wait`])
+    pub fn with_backpressure(mut self, backpressure: Backpressure) -> Self {
+        self.backpressure = backpressure;
+        self
+    }
+
+    /// Sets the callback invoked for every event which could not be delivered to an event handler (failures are
+    /// logged using `tracing` by default when `tracing-event-handler` feature is enabled)
+    pub fn with_failure_hook(
+        mut self,
+        on_failure: impl Fn(DispatchFailure) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_failure = Some(Arc::new(on_failure));
+        self
+    }
+}
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "event-dispatcher")))]
+#[cfg(feature = "event-dispatcher")]
+#[sealed]
+impl EventHandlerConfig for EventDispatcher {
+    type EventHandler = EventDispatcherImpl;
+
+    fn into_event_handler(self) -> Self::EventHandler {
+        EventDispatcherImpl::new(
+            self.handlers,
+            self.capacity,
+            self.backpressure,
+            self.on_failure.unwrap_or_else(|| Arc::new(log_failure)),
+        )
+    }
+}
+
+#[cfg(feature = "webhook-event-handler")]
+const DEFAULT_WEBHOOK_MAX_ATTEMPTS: u32 = 10;
+
+#[cfg(feature = "webhook-event-handler")]
+const DEFAULT_WEBHOOK_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+#[cfg(feature = "webhook-event-handler")]
+const DEFAULT_WEBHOOK_MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+#[cfg(feature = "webhook-event-handler")]
+const DEFAULT_WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "webhook-event-handler")))]
+#[cfg(feature = "webhook-event-handler")]
+#[derive(Clone)]
+/// Event handler backend which POSTs JSON serialized events to webhook URLs
+///
+/// Every request contains `X-Stag-Delivery` (ID of delivery, same across retries) and `X-Stag-Timestamp` (unix
+/// timestamp in seconds) headers. When a secret is set, `X-Stag-Signature` header contains `sha256=<hex encoded
+/// HMAC-SHA256 of "<timestamp>.<body>">`. Events are added to outbox and delivered by a background task, and failed
+/// deliveries are retried with exponential backoff, so, an event may be delivered more than once (receivers should
+/// deduplicate deliveries using `X-Stag-Delivery` header).
+pub struct WebhookEventHandler {
+    urls: Vec<Url>,
+    secret: Option<Vec<u8>>,
+    retry_policy: RetryPolicy,
+    timeout: Duration,
+    #[cfg(any(feature = "sqlite-storage", feature = "postgres-storage"))]
+    outbox_storage: Option<SharedSqlDbStorage>,
+}
+
+#[cfg(feature = "webhook-event-handler")]
+impl WebhookEventHandler {
+    /// Creates a new instance of webhook event handler which sends all the events to given URLs
+    pub fn new(urls: Vec<Url>) -> Self {
+        Self {
+            urls,
+            secret: None,
+            retry_policy: RetryPolicy {
+                max_attempts: DEFAULT_WEBHOOK_MAX_ATTEMPTS,
+                initial_backoff: DEFAULT_WEBHOOK_INITIAL_BACKOFF,
+                max_backoff: DEFAULT_WEBHOOK_MAX_BACKOFF,
+                backoff_multiplier: 2,
+            },
+            timeout: DEFAULT_WEBHOOK_TIMEOUT,
+            #[cfg(any(feature = "sqlite-storage", feature = "postgres-storage"))]
+            outbox_storage: None,
+        }
+    }
+
+    /// Sets the secret used for signing requests with HMAC-SHA256
+    pub fn with_secret(mut self, secret: impl Into<Vec<u8>>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+
+    /// Sets the retry policy of deliveries (default: 10 attempts with backoff starting from 1s, doubling after every
+    /// failed attempt, up to 5m)
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets the timeout of each request (default: 10s)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Persists pending deliveries in `webhook_outbox` table of given SQLite storage (shares the connection pool with
+    /// all the clones of storage config) so that they are retried after a restart (pending deliveries are kept in
+    /// memory by default)
+    #[cfg_attr(feature = "doc", doc(cfg(feature = "sqlite-storage")))]
+    #[cfg(feature = "sqlite-storage")]
+    pub fn with_outbox(mut self, storage: &Sqlite) -> Self {
+        self.outbox_storage = Some(storage.shared_storage());
+        self
+    }
+
+    /// Persists pending deliveries in `webhook_outbox` table of given Postgres storage (shares the connection pool
+    /// with all the clones of storage config) so that they are retried after a restart (pending deliveries are kept
+    /// in memory by default)
+    #[cfg_attr(feature = "doc", doc(cfg(feature = "postgres-storage")))]
+    #[cfg(feature = "postgres-storage")]
+    pub fn with_outbox(mut self, storage: &Postgres) -> Self {
+        self.outbox_storage = Some(storage.shared_storage());
+        self
+    }
+}
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "webhook-event-handler")))]
+#[cfg(feature = "webhook-event-handler")]
+#[sealed]
+impl EventHandlerConfig for WebhookEventHandler {
+    type EventHandler = WebhookEventHandlerImpl;
+
+    fn into_event_handler(self) -> Self::EventHandler {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "sqlite-storage", feature = "postgres-storage"))] {
+                let outbox = match self.outbox_storage {
+                    Some(storage) => Outbox::sql(storage),
+                    None => Outbox::memory(),
+                };
+            } else {
+                let outbox = Outbox::memory();
+            }
+        }
+
+        WebhookEventHandlerImpl::new(
+            self.urls,
+            self.secret,
+            self.retry_policy,
+            self.timeout,
+            outbox,
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -44,4 +421,30 @@ mod tests {
         let event_handler = config.into_event_handler();
         assert_eq!(event_handler, TracingEventHandlerImpl {});
     }
+
+    #[test]
+    fn test_default_filter() {
+        let config = MultiEventHandler::new()
+            .with_handler(TracingEventHandler)
+            .with_filtered_handler(TracingEventHandler, EventFilter::only(["Warning"]));
+
+        assert_eq!(config.handlers[0].1, EventFilter::All);
+        assert_eq!(config.handlers[1].1, EventFilter::only(["Warning"]));
+    }
+
+    #[cfg(feature = "sqlite-storage")]
+    #[test]
+    fn test_storage_default_filter() {
+        let config = MultiEventHandler::new().with_handler(StorageEventHandler::new(
+            crate::storage::Sqlite::new("sqlite::memory:"),
+        ));
+        assert_eq!(config.handlers[0].1, EventFilter::AllWithTelemetry);
+    }
+
+    #[cfg(feature = "metrics-event-handler")]
+    #[test]
+    fn test_metrics_default_filter() {
+        let config = MultiEventHandler::new().with_handler(MetricsEventHandler::new().unwrap());
+        assert_eq!(config.handlers[0].1, EventFilter::AllWithTelemetry);
+    }
 }
@@ -0,0 +1,397 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{anyhow, Error, Result};
+use async_trait::async_trait;
+use tokio::sync::mpsc::{channel, error::TrySendError, Receiver, Sender};
+
+use super::{Event, EventFilter, EventHandler};
+
+/// Callback invoked for every event which could not be delivered to an event handler
+pub type FailureHook = Arc<dyn Fn(DispatchFailure) + Send + Sync>;
+
+/// Policy applied when queue of an event handler is full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backpressure {
+    /// Waits for space in the queue (slows down the operation emitting the event but no event is dropped)
+    #[default]
+    Wait,
+    /// Drops the event and reports it as a dispatch failure
+    Drop,
+}
+
+/// Failure in delivering an event to an event handler
+#[derive(Debug)]
+pub enum DispatchFailure {
+    /// Event was dropped because queue of event handler was full
+    QueueFull {
+        /// Index of event handler (in the order in which event handlers were added)
+        handler: usize,
+        /// Dropped event
+        event: Event,
+    },
+    /// Event handler failed to handle the event
+    HandlerFailed {
+        /// Index of event handler (in the order in which event handlers were added)
+        handler: usize,
+        /// Failed event
+        event: Event,
+        /// Error returned by event handler
+        error: Error,
+    },
+}
+
+impl fmt::Display for DispatchFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::QueueFull { handler, event } => write!(
+                f,
+                "dropped event {} because queue of event handler {} is full",
+                event.event_type(),
+                handler
+            ),
+            Self::HandlerFailed {
+                handler,
+                event,
+                error,
+            } => write!(
+                f,
+                "event handler {} failed to handle event {}: {:#}",
+                handler,
+                event.event_type(),
+                error
+            ),
+        }
+    }
+}
+
+/// Event handler which dispatches events to other event handlers in background tasks
+///
+/// Each event handler has its own bounded queue and background task, so, a slow event handler does not delay other
+/// event handlers. Failures are reported to failure hook and are never returned to the operation emitting the event.
+pub struct EventDispatcher {
+    queues: Vec<HandlerQueue>,
+    backpressure: Backpressure,
+    on_failure: FailureHook,
+}
+
+struct HandlerQueue {
+    filter: EventFilter,
+    sender: Sender<Event>,
+    /// Taken when background task is started
+    worker: Mutex<Option<Worker>>,
+}
+
+struct Worker {
+    receiver: Receiver<Event>,
+    handler: Arc<dyn EventHandler>,
+}
+
+impl EventDispatcher {
+    /// Creates a new event dispatcher (background tasks are started when called within a tokio runtime or when the
+    /// first event is dispatched)
+    pub fn new(
+        handlers: Vec<(Arc<dyn EventHandler>, EventFilter)>,
+        capacity: usize,
+        backpressure: Backpressure,
+        on_failure: FailureHook,
+    ) -> Self {
+        let queues = handlers
+            .into_iter()
+            .map(|(handler, filter)| {
+                let (sender, receiver) = channel(capacity.max(1));
+
+                HandlerQueue {
+                    filter,
+                    sender,
+                    worker: Mutex::new(Some(Worker { receiver, handler })),
+                }
+            })
+            .collect();
+
+        let event_dispatcher = Self {
+            queues,
+            backpressure,
+            on_failure,
+        };
+
+        if tokio::runtime::Handle::try_current().is_ok() {
+            event_dispatcher.start_workers();
+        }
+
+        event_dispatcher
+    }
+
+    /// Starts background tasks for event handlers (if not already started)
+    fn start_workers(&self) {
+        for (index, queue) in self.queues.iter().enumerate() {
+            let worker = queue
+                .worker
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .take();
+
+            if let Some(worker) = worker {
+                tokio::spawn(run_worker(index, worker, self.on_failure.clone()));
+            }
+        }
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl EventHandler for EventDispatcher {
+    async fn handle_event(&self, event: Event) -> Result<()> {
+        self.start_workers();
+
+        for (index, queue) in self.queues.iter().enumerate() {
+            if !queue.filter.matches(&event) {
+                continue;
+            }
+
+            let result = match self.backpressure {
+                Backpressure::Wait => queue
+                    .sender
+                    .send(event.clone())
+                    .await
+                    .map_err(|err| worker_stopped(index, err.0)),
+                Backpressure::Drop => {
+                    queue
+                        .sender
+                        .try_send(event.clone())
+                        .map_err(|err| match err {
+                            TrySendError::Full(event) => DispatchFailure::QueueFull {
+                                handler: index,
+                                event,
+                            },
+                            TrySendError::Closed(event) => worker_stopped(index, event),
+                        })
+                }
+            };
+
+            if let Err(failure) = result {
+                (self.on_failure)(failure);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Handles events from queue of an event handler until event dispatcher is dropped
+async fn run_worker(index: usize, mut worker: Worker, on_failure: FailureHook) {
+    while let Some(event) = worker.receiver.recv().await {
+        if let Err(error) = worker.handler.handle_event(event.clone()).await {
+            on_failure(DispatchFailure::HandlerFailed {
+                handler: index,
+                event,
+                error,
+            });
+        }
+    }
+}
+
+fn worker_stopped(index: usize, event: Event) -> DispatchFailure {
+    DispatchFailure::HandlerFailed {
+        handler: index,
+        event,
+        error: anyhow!("background task of event handler stopped"),
+    }
+}
+
+/// Default failure hook (logs failures using `tracing` when it is enabled)
+pub fn log_failure(_failure: DispatchFailure) {
+    #[cfg(feature = "tracing")]
+    tracing::error!("{}", _failure);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::{
+        sync::{
+            mpsc::{unbounded_channel, UnboundedSender},
+            Semaphore,
+        },
+        time::timeout,
+    };
+
+    use super::*;
+    use crate::event::NoopEventHandler;
+
+    /// Event handler which sends handled events to a channel (after acquiring a permit from semaphore)
+    struct ChannelEventHandler {
+        sender: UnboundedSender<Event>,
+        permits: Arc<Semaphore>,
+    }
+
+    #[async_trait]
+    impl EventHandler for ChannelEventHandler {
+        async fn handle_event(&self, event: Event) -> Result<()> {
+            self.permits.acquire().await?.forget();
+            self.sender.send(event)?;
+            Ok(())
+        }
+    }
+
+    fn failure_hook() -> (FailureHook, tokio::sync::mpsc::UnboundedReceiver<String>) {
+        let (sender, receiver) = unbounded_channel();
+        let hook: FailureHook = Arc::new(move |failure| {
+            let _ = sender.send(failure.to_string());
+        });
+
+        (hook, receiver)
+    }
+
+    #[tokio::test]
+    async fn test_event_dispatcher_fan_out() {
+        let (sender, mut receiver) = unbounded_channel();
+        let (on_failure, mut failures) = failure_hook();
+
+        let event_dispatcher = EventDispatcher::new(
+            vec![
+                (Arc::new(NoopEventHandler), EventFilter::All),
+                (
+                    Arc::new(ChannelEventHandler {
+                        sender,
+                        permits: Arc::new(Semaphore::new(2)),
+                    }),
+                    EventFilter::except(["Warning"]),
+                ),
+            ],
+            10,
+            Backpressure::Wait,
+            on_failure,
+        );
+
+        assert!(event_dispatcher.handle_event(Event::Test).await.is_ok());
+        assert!(event_dispatcher
+            .handle_event(Event::Warning {
+                message: "warning".to_owned()
+            })
+            .await
+            .is_ok());
+
+        let event = timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(event, Event::Test));
+
+        let failure = timeout(Duration::from_secs(5), failures.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            failure,
+            "event handler 0 failed to handle event Test: NoopEventHandler cannot handle events"
+        );
+
+        let failure = timeout(Duration::from_secs(5), failures.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            failure,
+            "event handler 0 failed to handle event Warning: NoopEventHandler cannot handle events"
+        );
+
+        // Filtered event should not be dispatched to second event handler
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_event_dispatcher_drop_backpressure() {
+        let (sender, mut receiver) = unbounded_channel();
+        let (on_failure, mut failures) = failure_hook();
+        let permits = Arc::new(Semaphore::new(0));
+
+        let event_dispatcher = EventDispatcher::new(
+            vec![(
+                Arc::new(ChannelEventHandler {
+                    sender,
+                    permits: permits.clone(),
+                }),
+                EventFilter::All,
+            )],
+            1,
+            Backpressure::Drop,
+            on_failure,
+        );
+
+        // First event is taken by blocked event handler, second one fills the queue and third one is dropped
+        for _ in 0..2 {
+            event_dispatcher.handle_event(Event::Test).await.unwrap();
+            tokio::task::yield_now().await;
+        }
+        event_dispatcher.handle_event(Event::Test).await.unwrap();
+
+        assert_eq!(
+            failures.try_recv().unwrap(),
+            "dropped event Test because queue of event handler 0 is full"
+        );
+
+        permits.add_permits(3);
+
+        for _ in 0..2 {
+            timeout(Duration::from_secs(5), receiver.recv())
+                .await
+                .unwrap()
+                .unwrap();
+        }
+
+        assert!(failures.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_event_dispatcher_wait_backpressure() {
+        let (sender, mut receiver) = unbounded_channel();
+        let permits = Arc::new(Semaphore::new(0));
+
+        let event_dispatcher = Arc::new(EventDispatcher::new(
+            vec![(
+                Arc::new(ChannelEventHandler {
+                    sender,
+                    permits: permits.clone(),
+                }),
+                EventFilter::All,
+            )],
+            1,
+            Backpressure::Wait,
+            Arc::new(log_failure),
+        ));
+
+        for _ in 0..2 {
+            event_dispatcher.handle_event(Event::Test).await.unwrap();
+            tokio::task::yield_now().await;
+        }
+
+        // Third event waits until there is space in the queue
+        let dispatch = tokio::spawn({
+            let event_dispatcher = event_dispatcher.clone();
+            async move { event_dispatcher.handle_event(Event::Test).await }
+        });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!dispatch.is_finished());
+
+        permits.add_permits(3);
+
+        timeout(Duration::from_secs(5), dispatch)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+
+        for _ in 0..3 {
+            timeout(Duration::from_secs(5), receiver.recv())
+                .await
+                .unwrap()
+                .unwrap();
+        }
+    }
+}
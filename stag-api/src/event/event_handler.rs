@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 
@@ -31,10 +33,16 @@ impl<C> EventHandler for C
 where
     C: StagContext,
 {
+    /// Handles an event using configured event handler (failure of event handler is logged, when `tracing` is
+    /// enabled, and not returned because the operation emitting the event has already been committed; use
+    /// `EventDispatcher` to handle events in background tasks with failure reporting)
     async fn handle_event(&self, event: Event) -> Result<()> {
-        // TODO: send event via a channel to not block the current task (i.e. the caller)
         if let Some(event_handler) = self.event_handler() {
-            event_handler.handle_event(event).await?;
+            #[allow(unused_variables)]
+            if let Err(err) = event_handler.handle_event(event).await {
+                #[cfg(feature = "tracing")]
+                tracing::error!("failed to handle event: {:#}", err);
+            }
         }
 
         Ok(())
@@ -56,6 +64,17 @@ where
     }
 }
 
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl<T> EventHandler for Arc<T>
+where
+    T: EventHandler + ?Sized,
+{
+    async fn handle_event(&self, event: Event) -> Result<()> {
+        (**self).handle_event(event).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,6 +97,12 @@ mod tests {
         assert!(event_handler.handle_event(Event::Test).await.is_err());
     }
 
+    #[tokio::test]
+    async fn test_arc_event_handler() {
+        let event_handler: Arc<dyn EventHandler> = Arc::new(NoopEventHandler);
+        assert!(event_handler.handle_event(Event::Test).await.is_err());
+    }
+
     struct DummyContext {
         event_handler: Option<NoopEventHandler>,
     }
@@ -128,7 +153,8 @@ mod tests {
         let context = DummyContext {
             event_handler: Some(NoopEventHandler),
         };
-        assert!(context.handle_event(Event::Test).await.is_err());
+        // Failure of event handler should not be returned to the caller
+        assert!(context.handle_event(Event::Test).await.is_ok());
     }
 
     #[tokio::test]
@@ -1,5 +1,6 @@
 use primitive_types::U256;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use url::Url;
 
 use crate::types::{
     chain_state::{ChannelDetails, ConnectionDetails},
@@ -119,40 +120,88 @@ pub enum Event {
         /// Hash of transaction on IBC enabled chain (in hex)
         transaction_hash: String,
     },
+    /// Detected solo machine misbehaviour (i.e., conflicting data signed at the same sequence)
+    MisbehaviourDetected {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Client ID of solo machine client on IBC enabled chain
+        client_id: ClientId,
+        /// Sequence at which conflicting data was signed
+        sequence: u64,
+    },
+    /// Froze solo machine client on IBC enabled chain by submitting misbehaviour
+    SoloMachineClientFrozen {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
+        /// Client ID of solo machine client on IBC enabled chain
+        client_id: ClientId,
+        /// Hash of transaction on IBC enabled chain (in hex)
+        transaction_hash: String,
+    },
 
     // ----- IBC connection handshake events ----- //
     /// Created solo machine client on IBC enabled chain
     CreatedSoloMachineClient {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Client ID of solo machine client on IBC enabled chain
         client_id: ClientId,
     },
     /// Created tendermint client on solo machine
     CreatedTendermintClient {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Client ID of IBC enabled chain on solo machine
         client_id: ClientId,
     },
     /// Initialized connection on IBC enabled chain
     InitializedConnectionOnTendermint {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Connection ID of solo machine client on IBC enabled chain
         connection_id: ConnectionId,
     },
     /// Initialized connection on solo machine
     InitializedConnectionOnSoloMachine {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Connection ID of IBC enabled chain on solo machine
         connection_id: ConnectionId,
     },
     /// Confirmed connection on IBC enabled chain
     ConfirmedConnectionOnTendermint {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Connection ID of solo machine client on IBC enabled chain
         connection_id: ConnectionId,
     },
     /// Confirmed connection on solo machine
     ConfirmedConnectionOnSoloMachine {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Connection ID of IBC enabled chain on solo machine
         connection_id: ConnectionId,
     },
     /// Initialized channel on IBC enabled chain
     InitializedChannelOnTendermint {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Channel ID of solo machine client on IBC enabled chain
         channel_id: ChannelId,
         /// Port ID of channel
@@ -160,6 +209,10 @@ pub enum Event {
     },
     /// Initialized channel on solo machine
     InitializedChannelOnSoloMachine {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Channel ID of IBC enabled chain on solo machine
         channel_id: ChannelId,
         /// Port ID of channel
@@ -167,6 +220,10 @@ pub enum Event {
     },
     /// Confirmed channel on IBC enabled chain
     ConfirmedChannelOnTendermint {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Channel ID of solo machine client on IBC enabled chain
         channel_id: ChannelId,
         /// Port ID of channel
@@ -174,6 +231,10 @@ pub enum Event {
     },
     /// Confirmed channel on solo machine
     ConfirmedChannelOnSoloMachine {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Channel ID of IBC enabled chain on solo machine
         channel_id: ChannelId,
         /// Port ID of channel
@@ -183,6 +244,8 @@ pub enum Event {
     ConnectionEstablished {
         /// Chain ID of IBC enabled chain
         chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Connection details
         connection_details: ConnectionDetails,
     },
@@ -190,6 +253,8 @@ pub enum Event {
     ChannelCreated {
         /// Chain ID of IBC enabled chain
         chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Channel details
         channel_details: ChannelDetails,
     },
@@ -197,6 +262,8 @@ pub enum Event {
     CloseChannelOnTendermint {
         /// Chain ID of IBC enabled chain
         chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Port ID of channel
         port_id: PortId,
     },
@@ -204,6 +271,8 @@ pub enum Event {
     CloseChannelOnSoloMachine {
         /// Chain ID of IBC enabled chain
         chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Port ID of channel
         port_id: PortId,
     },
@@ -211,6 +280,8 @@ pub enum Event {
     ChannelClosed {
         /// Chain ID of IBC enabled chain
         chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Channel details
         channel_details: ChannelDetails,
     },
@@ -222,6 +293,75 @@ pub enum Event {
         chain_id: ChainId,
     },
 
+    // ----- Light client events ----- //
+    /// Detected a light client attack (i.e., a witness returned a header conflicting with a header verified from
+    /// primary) on IBC enabled chain
+    LightClientAttackDetected {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Height of conflicting headers
+        height: u64,
+        /// Node ID of primary which provided the verified header
+        primary: String,
+        /// Hash of header verified from primary (in hex)
+        primary_header_hash: String,
+        /// Address of witness which provided the conflicting header
+        witness: Url,
+        /// Hash of conflicting header provided by witness (in hex)
+        witness_header_hash: String,
+    },
+
+    // ----- Failure events ----- //
+    /// Failed to broadcast a transaction (or transaction failed on-chain)
+    BroadcastFailed {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Error message
+        error: String,
+    },
+    /// Signer failed to sign a message
+    SignerFailed {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
+        /// Error message
+        error: String,
+    },
+
+    // ----- Telemetry events (only sent to event handlers which select them, see `EventFilter`) ----- //
+    /// Finished an operation (successfully or with an error)
+    OperationFinished {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
+        /// Name of operation (e.g., `mint`, `connect`)
+        operation: String,
+        /// Time taken by operation (in milliseconds)
+        duration_ms: u64,
+        /// Error message (if operation failed)
+        error: Option<String>,
+    },
+    /// Verified a header of IBC enabled chain using light client
+    LightClientVerified {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Height of verified header
+        height: u64,
+        /// Time taken by verification (in milliseconds)
+        duration_ms: u64,
+    },
+    /// Updated the number of packets which are yet to be acknowledged on IBC enabled chain
+    PacketBacklogUpdated {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Port ID of channel
+        port_id: PortId,
+        /// Number of packets which are yet to be acknowledged
+        pending_packets: u64,
+    },
+
     // ----- Other events ----- //
     /// Warning
     Warning {
@@ -235,6 +375,125 @@ pub enum Event {
     Test,
 }
 
+impl Event {
+    /// Returns the type of event (same as `type` field of JSON serialized event)
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::TokensMinted { .. } => "TokensMinted",
+            Self::TokensBurnt { .. } => "TokensBurnt",
+            Self::SignerUpdated { .. } => "SignerUpdated",
+            Self::TokensSentFromIca { .. } => "TokensSentFromIca",
+            Self::TokensDelegatedFromIca { .. } => "TokensDelegatedFromIca",
+            Self::TokensUndelegatedToIca { .. } => "TokensUndelegatedToIca",
+            Self::MisbehaviourDetected { .. } => "MisbehaviourDetected",
+            Self::SoloMachineClientFrozen { .. } => "SoloMachineClientFrozen",
+            Self::CreatedSoloMachineClient { .. } => "CreatedSoloMachineClient",
+            Self::CreatedTendermintClient { .. } => "CreatedTendermintClient",
+            Self::InitializedConnectionOnTendermint { .. } => "InitializedConnectionOnTendermint",
+            Self::InitializedConnectionOnSoloMachine { .. } => "InitializedConnectionOnSoloMachine",
+            Self::ConfirmedConnectionOnTendermint { .. } => "ConfirmedConnectionOnTendermint",
+            Self::ConfirmedConnectionOnSoloMachine { .. } => "ConfirmedConnectionOnSoloMachine",
+            Self::InitializedChannelOnTendermint { .. } => "InitializedChannelOnTendermint",
+            Self::InitializedChannelOnSoloMachine { .. } => "InitializedChannelOnSoloMachine",
+            Self::ConfirmedChannelOnTendermint { .. } => "ConfirmedChannelOnTendermint",
+            Self::ConfirmedChannelOnSoloMachine { .. } => "ConfirmedChannelOnSoloMachine",
+            Self::ConnectionEstablished { .. } => "ConnectionEstablished",
+            Self::ChannelCreated { .. } => "ChannelCreated",
+            Self::CloseChannelOnTendermint { .. } => "CloseChannelOnTendermint",
+            Self::CloseChannelOnSoloMachine { .. } => "CloseChannelOnSoloMachine",
+            Self::ChannelClosed { .. } => "ChannelClosed",
+            Self::ChainAdded { .. } => "ChainAdded",
+            Self::LightClientAttackDetected { .. } => "LightClientAttackDetected",
+            Self::OperationFinished { .. } => "OperationFinished",
+            Self::BroadcastFailed { .. } => "BroadcastFailed",
+            Self::SignerFailed { .. } => "SignerFailed",
+            Self::LightClientVerified { .. } => "LightClientVerified",
+            Self::PacketBacklogUpdated { .. } => "PacketBacklogUpdated",
+            Self::Warning { .. } => "Warning",
+            #[cfg(test)]
+            Self::Test => "Test",
+        }
+    }
+
+    /// Returns `true` if the event is only a metric sample (e.g., duration of an operation) and is not a part of solo
+    /// machine's event log (failures, e.g., `BroadcastFailed`, are not telemetry events)
+    pub fn is_telemetry(&self) -> bool {
+        matches!(
+            self,
+            Self::OperationFinished { .. }
+                | Self::LightClientVerified { .. }
+                | Self::PacketBacklogUpdated { .. }
+        )
+    }
+
+    /// Returns chain ID of IBC enabled chain associated with the event (warnings do not carry a chain ID)
+    pub fn chain_id(&self) -> Option<&ChainId> {
+        match self {
+            Self::TokensMinted { chain_id, .. }
+            | Self::TokensBurnt { chain_id, .. }
+            | Self::SignerUpdated { chain_id, .. }
+            | Self::TokensSentFromIca { chain_id, .. }
+            | Self::TokensDelegatedFromIca { chain_id, .. }
+            | Self::TokensUndelegatedToIca { chain_id, .. }
+            | Self::MisbehaviourDetected { chain_id, .. }
+            | Self::SoloMachineClientFrozen { chain_id, .. }
+            | Self::CreatedSoloMachineClient { chain_id, .. }
+            | Self::CreatedTendermintClient { chain_id, .. }
+            | Self::InitializedConnectionOnTendermint { chain_id, .. }
+            | Self::InitializedConnectionOnSoloMachine { chain_id, .. }
+            | Self::ConfirmedConnectionOnTendermint { chain_id, .. }
+            | Self::ConfirmedConnectionOnSoloMachine { chain_id, .. }
+            | Self::InitializedChannelOnTendermint { chain_id, .. }
+            | Self::InitializedChannelOnSoloMachine { chain_id, .. }
+            | Self::ConfirmedChannelOnTendermint { chain_id, .. }
+            | Self::ConfirmedChannelOnSoloMachine { chain_id, .. }
+            | Self::ConnectionEstablished { chain_id, .. }
+            | Self::ChannelCreated { chain_id, .. }
+            | Self::CloseChannelOnTendermint { chain_id, .. }
+            | Self::CloseChannelOnSoloMachine { chain_id, .. }
+            | Self::ChannelClosed { chain_id, .. }
+            | Self::ChainAdded { chain_id }
+            | Self::LightClientAttackDetected { chain_id, .. }
+            | Self::OperationFinished { chain_id, .. }
+            | Self::BroadcastFailed { chain_id, .. }
+            | Self::SignerFailed { chain_id, .. }
+            | Self::LightClientVerified { chain_id, .. }
+            | Self::PacketBacklogUpdated { chain_id, .. } => Some(chain_id),
+            _ => None,
+        }
+    }
+
+    /// Returns request ID associated with the event (if any)
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            Self::TokensMinted { request_id, .. }
+            | Self::TokensBurnt { request_id, .. }
+            | Self::TokensSentFromIca { request_id, .. }
+            | Self::TokensDelegatedFromIca { request_id, .. }
+            | Self::TokensUndelegatedToIca { request_id, .. }
+            | Self::SoloMachineClientFrozen { request_id, .. }
+            | Self::CreatedSoloMachineClient { request_id, .. }
+            | Self::CreatedTendermintClient { request_id, .. }
+            | Self::InitializedConnectionOnTendermint { request_id, .. }
+            | Self::InitializedConnectionOnSoloMachine { request_id, .. }
+            | Self::ConfirmedConnectionOnTendermint { request_id, .. }
+            | Self::ConfirmedConnectionOnSoloMachine { request_id, .. }
+            | Self::InitializedChannelOnTendermint { request_id, .. }
+            | Self::InitializedChannelOnSoloMachine { request_id, .. }
+            | Self::ConfirmedChannelOnTendermint { request_id, .. }
+            | Self::ConfirmedChannelOnSoloMachine { request_id, .. }
+            | Self::ConnectionEstablished { request_id, .. }
+            | Self::ChannelCreated { request_id, .. }
+            | Self::CloseChannelOnTendermint { request_id, .. }
+            | Self::CloseChannelOnSoloMachine { request_id, .. }
+            | Self::ChannelClosed { request_id, .. }
+            | Self::OperationFinished { request_id, .. }
+            | Self::SignerFailed { request_id, .. } => request_id.as_deref(),
+            _ => None,
+        }
+    }
+}
+
 fn serialize_u256<S>(value: &U256, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
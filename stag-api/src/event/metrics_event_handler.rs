@@ -0,0 +1,241 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use prometheus::{
+    Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry,
+};
+
+use super::{Event, EventHandler};
+
+/// Buckets (in seconds) used for operation duration and light client verification duration histograms
+const DURATION_BUCKETS: &[f64] = &[
+    0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0, 300.0,
+];
+
+/// Prometheus metrics collected from events
+#[derive(Clone)]
+pub struct Metrics {
+    events_total: IntCounterVec,
+    operations_total: IntCounterVec,
+    operation_duration_seconds: HistogramVec,
+    broadcast_failures_total: IntCounterVec,
+    light_client_verification_duration_seconds: HistogramVec,
+    packet_backlog: IntGaugeVec,
+    signer_errors_total: IntCounterVec,
+}
+
+impl Metrics {
+    /// Creates all the metrics (metrics are not registered in any registry)
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            events_total: IntCounterVec::new(
+                Opts::new("stag_events_total", "Number of events emitted by Stag"),
+                &["event_type"],
+            )?,
+            operations_total: IntCounterVec::new(
+                Opts::new("stag_operations_total", "Number of finished operations"),
+                &["operation", "chain_id", "status"],
+            )?,
+            operation_duration_seconds: HistogramVec::new(
+                HistogramOpts::new(
+                    "stag_operation_duration_seconds",
+                    "Time taken by operations (in seconds)",
+                )
+                .buckets(DURATION_BUCKETS.to_vec()),
+                &["operation", "chain_id"],
+            )?,
+            broadcast_failures_total: IntCounterVec::new(
+                Opts::new(
+                    "stag_broadcast_failures_total",
+                    "Number of failed transaction broadcasts",
+                ),
+                &["chain_id"],
+            )?,
+            light_client_verification_duration_seconds: HistogramVec::new(
+                HistogramOpts::new(
+                    "stag_light_client_verification_duration_seconds",
+                    "Time taken by light client verification of headers (in seconds)",
+                )
+                .buckets(DURATION_BUCKETS.to_vec()),
+                &["chain_id"],
+            )?,
+            packet_backlog: IntGaugeVec::new(
+                Opts::new(
+                    "stag_packet_backlog",
+                    "Number of packets which are yet to be acknowledged",
+                ),
+                &["chain_id", "port_id"],
+            )?,
+            signer_errors_total: IntCounterVec::new(
+                Opts::new("stag_signer_errors_total", "Number of signer failures"),
+                &["chain_id"],
+            )?,
+        })
+    }
+
+    /// Registers all the metrics in given registry
+    pub fn register(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.events_total.clone()))?;
+        registry.register(Box::new(self.operations_total.clone()))?;
+        registry.register(Box::new(self.operation_duration_seconds.clone()))?;
+        registry.register(Box::new(self.broadcast_failures_total.clone()))?;
+        registry.register(Box::new(
+            self.light_client_verification_duration_seconds.clone(),
+        ))?;
+        registry.register(Box::new(self.packet_backlog.clone()))?;
+        registry.register(Box::new(self.signer_errors_total.clone()))?;
+
+        Ok(())
+    }
+
+    fn observe(&self, event: &Event) {
+        self.events_total
+            .with_label_values(&[event.event_type()])
+            .inc();
+
+        match event {
+            Event::OperationFinished {
+                chain_id,
+                operation,
+                duration_ms,
+                error,
+                ..
+            } => {
+                let chain_id = chain_id.to_string();
+                let status = if error.is_some() {
+                    "failure"
+                } else {
+                    "success"
+                };
+
+                self.operations_total
+                    .with_label_values(&[operation, &chain_id, status])
+                    .inc();
+                observe_duration(
+                    &self
+                        .operation_duration_seconds
+                        .with_label_values(&[operation, &chain_id]),
+                    *duration_ms,
+                );
+            }
+            Event::BroadcastFailed { chain_id, .. } => {
+                self.broadcast_failures_total
+                    .with_label_values(&[&chain_id.to_string()])
+                    .inc();
+            }
+            Event::LightClientVerified {
+                chain_id,
+                duration_ms,
+                ..
+            } => {
+                observe_duration(
+                    &self
+                        .light_client_verification_duration_seconds
+                        .with_label_values(&[&chain_id.to_string()]),
+                    *duration_ms,
+                );
+            }
+            Event::PacketBacklogUpdated {
+                chain_id,
+                port_id,
+                pending_packets,
+            } => {
+                self.packet_backlog
+                    .with_label_values(&[&chain_id.to_string(), &port_id.to_string()])
+                    .set(i64::try_from(*pending_packets).unwrap_or(i64::MAX));
+            }
+            Event::SignerFailed { chain_id, .. } => {
+                self.signer_errors_total
+                    .with_label_values(&[&chain_id.to_string()])
+                    .inc();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn observe_duration(histogram: &Histogram, duration_ms: u64) {
+    histogram.observe(duration_ms as f64 / 1000.0);
+}
+
+/// Event handler which collects Prometheus metrics from events
+pub struct MetricsEventHandler {
+    metrics: Metrics,
+}
+
+impl MetricsEventHandler {
+    pub fn new(metrics: Metrics) -> Self {
+        Self { metrics }
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl EventHandler for MetricsEventHandler {
+    async fn handle_event(&self, event: Event) -> Result<()> {
+        self.metrics.observe(&event);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prometheus::{Encoder, TextEncoder};
+
+    use super::*;
+
+    fn gather(registry: &Registry) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&registry.gather(), &mut buffer)
+            .unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_metrics_event_handler() {
+        let metrics = Metrics::new().unwrap();
+        let registry = Registry::new();
+        metrics.register(&registry).unwrap();
+
+        let event_handler = MetricsEventHandler::new(metrics);
+
+        event_handler
+            .handle_event(Event::OperationFinished {
+                chain_id: "test-1".parse().unwrap(),
+                request_id: None,
+                operation: "mint".to_owned(),
+                duration_ms: 1500,
+                error: Some("failed".to_owned()),
+            })
+            .await
+            .unwrap();
+        event_handler
+            .handle_event(Event::PacketBacklogUpdated {
+                chain_id: "test-1".parse().unwrap(),
+                port_id: "transfer".parse().unwrap(),
+                pending_packets: 3,
+            })
+            .await
+            .unwrap();
+
+        let output = gather(&registry);
+
+        assert!(output.contains(
+            r#"stag_operations_total{chain_id="test-1",operation="mint",status="failure"} 1"#
+        ));
+        assert!(output.contains(
+            r#"stag_operation_duration_seconds_sum{chain_id="test-1",operation="mint"} 1.5"#
+        ));
+        assert!(output.contains(r#"stag_packet_backlog{chain_id="test-1",port_id="transfer"} 3"#));
+        assert!(output.contains(r#"stag_events_total{event_type="OperationFinished"} 1"#));
+    }
+
+    #[test]
+    fn test_duplicate_registration() {
+        let metrics = Metrics::new().unwrap();
+        let registry = Registry::new();
+
+        assert!(metrics.register(&registry).is_ok());
+        assert!(metrics.register(&registry).is_err());
+    }
+}
@@ -1,14 +1,41 @@
 //! Events generated by Stag API
 mod builder;
+#[cfg(feature = "event-dispatcher")]
+mod event_dispatcher;
 mod event_handler;
 mod event_types;
+#[cfg(feature = "metrics-event-handler")]
+mod metrics_event_handler;
+mod multi_event_handler;
+#[cfg(test)]
+mod recording_event_handler;
+mod storage_event_handler;
 #[cfg(feature = "tracing-event-handler")]
 mod tracing_event_handler;
+#[cfg(feature = "webhook-event-handler")]
+mod webhook_event_handler;
 
+#[cfg(feature = "metrics-event-handler")]
+pub use self::builder::MetricsEventHandler;
 #[cfg(feature = "tracing-event-handler")]
 pub use self::builder::TracingEventHandler;
+#[cfg(feature = "webhook-event-handler")]
+pub use self::builder::WebhookEventHandler;
+#[cfg(test)]
+pub(crate) use self::recording_event_handler::RecordingEventHandler;
+#[cfg(all(
+    feature = "webhook-event-handler",
+    any(feature = "sqlite-storage", feature = "postgres-storage")
+))]
+pub(crate) use self::webhook_event_handler::OutboxEntry;
+#[cfg(feature = "event-dispatcher")]
 pub use self::{
-    builder::EventHandlerConfig,
+    builder::EventDispatcher,
+    event_dispatcher::{Backpressure, DispatchFailure, FailureHook},
+};
+pub use self::{
+    builder::{EventHandlerConfig, MultiEventHandler, StorageEventHandler},
     event_handler::{EventHandler, NoopEventHandler},
     event_types::Event,
+    multi_event_handler::EventFilter,
 };
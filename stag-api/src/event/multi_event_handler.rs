@@ -0,0 +1,190 @@
+use std::{collections::HashSet, sync::Arc};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+
+use super::{Event, EventHandler};
+
+/// Filter for selecting events by their type (same as `type` field of JSON serialized event, e.g., `TokensMinted`)
+///
+/// Telemetry events (see [`Event::is_telemetry`]) are only selected by [`EventFilter::AllWithTelemetry`] or when their
+/// types are given to [`EventFilter::Only`], so that metric samples do not reach webhooks or subscribers by default
+/// (storage and metrics event handlers select them by default).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum EventFilter {
+    /// Selects all the events except telemetry events
+    #[default]
+    All,
+    /// Selects all the events including telemetry events
+    AllWithTelemetry,
+    /// Selects only the events of given types
+    Only(HashSet<String>),
+    /// Selects all the events except telemetry events and the ones of given types
+    Except(HashSet<String>),
+}
+
+impl EventFilter {
+    /// Creates a filter which selects only the events of given types
+    pub fn only<I, S>(event_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Only(event_types.into_iter().map(Into::into).collect())
+    }
+
+    /// Creates a filter which selects all the events except the ones of given types
+    pub fn except<I, S>(event_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Except(event_types.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns `true` if given event is selected by the filter
+    pub fn matches(&self, event: &Event) -> bool {
+        match self {
+            Self::All => !event.is_telemetry(),
+            Self::AllWithTelemetry => true,
+            Self::Only(event_types) => event_types.contains(event.event_type()),
+            Self::Except(event_types) => {
+                !event.is_telemetry() && !event_types.contains(event.event_type())
+            }
+        }
+    }
+}
+
+/// Event handler which sends events to multiple event handlers (in the order in which they were added)
+pub struct MultiEventHandler {
+    handlers: Vec<(Arc<dyn EventHandler>, EventFilter)>,
+}
+
+impl MultiEventHandler {
+    /// Creates a new multi event handler
+    pub fn new(handlers: Vec<(Arc<dyn EventHandler>, EventFilter)>) -> Self {
+        Self { handlers }
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl EventHandler for MultiEventHandler {
+    /// Sends event to all the event handlers whose filter matches the event (failure of one event handler does not
+    /// stop other event handlers from handling the event)
+    async fn handle_event(&self, event: Event) -> Result<()> {
+        let mut errors = Vec::new();
+
+        for (index, (handler, filter)) in self.handlers.iter().enumerate() {
+            if !filter.matches(&event) {
+                continue;
+            }
+
+            if let Err(err) = handler.handle_event(event.clone()).await {
+                errors.push(format!("event handler {}: {:#}", index, err));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "failed to handle event {}: {}",
+                event.event_type(),
+                errors.join("; ")
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{NoopEventHandler, RecordingEventHandler};
+
+    fn warning() -> Event {
+        Event::Warning {
+            message: "warning".to_owned(),
+        }
+    }
+
+    fn broadcast_failed() -> Event {
+        Event::BroadcastFailed {
+            chain_id: "test-1".parse().unwrap(),
+            error: "error".to_owned(),
+        }
+    }
+
+    fn operation_finished() -> Event {
+        Event::OperationFinished {
+            chain_id: "test-1".parse().unwrap(),
+            request_id: None,
+            operation: "mint".to_owned(),
+            duration_ms: 10,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_event_filter() {
+        assert!(EventFilter::All.matches(&Event::Test));
+        assert!(EventFilter::All.matches(&broadcast_failed()));
+        assert!(!EventFilter::All.matches(&operation_finished()));
+
+        assert!(EventFilter::AllWithTelemetry.matches(&Event::Test));
+        assert!(EventFilter::AllWithTelemetry.matches(&operation_finished()));
+
+        let filter = EventFilter::only(["Warning"]);
+        assert!(filter.matches(&warning()));
+        assert!(!filter.matches(&Event::Test));
+        assert!(!filter.matches(&operation_finished()));
+
+        let filter = EventFilter::only(["OperationFinished"]);
+        assert!(filter.matches(&operation_finished()));
+
+        let filter = EventFilter::except(["Warning"]);
+        assert!(!filter.matches(&warning()));
+        assert!(filter.matches(&Event::Test));
+        assert!(filter.matches(&broadcast_failed()));
+        assert!(!filter.matches(&operation_finished()));
+    }
+
+    #[tokio::test]
+    async fn test_multi_event_handler() {
+        let all = Arc::new(RecordingEventHandler::default());
+        let warnings = Arc::new(RecordingEventHandler::default());
+
+        let event_handler = MultiEventHandler::new(vec![
+            (all.clone(), EventFilter::All),
+            (warnings.clone(), EventFilter::only(["Warning"])),
+        ]);
+
+        event_handler.handle_event(Event::Test).await.unwrap();
+        event_handler.handle_event(warning()).await.unwrap();
+
+        assert_eq!(all.event_types(), vec!["Test", "Warning"]);
+        assert_eq!(warnings.event_types(), vec!["Warning"]);
+    }
+
+    #[tokio::test]
+    async fn test_multi_event_handler_failure() {
+        let recording = Arc::new(RecordingEventHandler::default());
+
+        let event_handler = MultiEventHandler::new(vec![
+            (Arc::new(NoopEventHandler), EventFilter::except(["Warning"])),
+            (recording.clone(), EventFilter::All),
+        ]);
+
+        // Failure of first event handler should not stop second event handler
+        let err = event_handler.handle_event(Event::Test).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "failed to handle event Test: event handler 0: NoopEventHandler cannot handle events"
+        );
+        assert_eq!(recording.event_types(), vec!["Test"]);
+
+        // First event handler should not receive filtered events
+        assert!(event_handler.handle_event(warning()).await.is_ok());
+        assert_eq!(recording.event_types(), vec!["Test", "Warning"]);
+    }
+}
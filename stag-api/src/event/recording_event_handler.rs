@@ -0,0 +1,38 @@
+//! Event handler recording all the events (used in tests)
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use super::{Event, EventHandler};
+
+/// Event handler which records all the events (clones share the recorded events)
+#[derive(Clone, Default)]
+pub struct RecordingEventHandler {
+    events: Arc<Mutex<Vec<Event>>>,
+}
+
+impl RecordingEventHandler {
+    /// Returns all the recorded events
+    pub fn events(&self) -> Vec<Event> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// Returns types of all the recorded events
+    pub fn event_types(&self) -> Vec<&'static str> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .map(Event::event_type)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EventHandler for RecordingEventHandler {
+    async fn handle_event(&self, event: Event) -> Result<()> {
+        self.events.lock().unwrap().push(event);
+        Ok(())
+    }
+}
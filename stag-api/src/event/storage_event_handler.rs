@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tokio::sync::OnceCell;
+
+use crate::storage::{Storage, StorageConfig};
+
+use super::{Event, EventHandler};
+
+/// Event handler which appends all the events to `events` table of a storage backend
+pub struct StorageEventHandler<S>
+where
+    S: StorageConfig,
+{
+    config: S,
+    /// Storage backend is initialized lazily when the first event is handled
+    storage: OnceCell<S::Storage>,
+}
+
+impl<S> StorageEventHandler<S>
+where
+    S: StorageConfig,
+{
+    /// Creates a new storage event handler
+    pub fn new(config: S) -> Self {
+        Self {
+            config,
+            storage: OnceCell::new(),
+        }
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl<S> EventHandler for StorageEventHandler<S>
+where
+    S: StorageConfig + Clone,
+{
+    async fn handle_event(&self, event: Event) -> Result<()> {
+        let storage = self
+            .storage
+            .get_or_try_init(|| self.config.clone().into_storage())
+            .await
+            .context("unable to initialize storage for events")?;
+
+        storage.add_event(&event).await.map(|_| ())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite-storage"))]
+mod tests {
+    use crate::{storage::Sqlite, types::event_record::EventQuery};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_storage_event_handler() {
+        let event_handler = StorageEventHandler::new(Sqlite::new("sqlite::memory:"));
+
+        event_handler.handle_event(Event::Test).await.unwrap();
+        event_handler
+            .handle_event(Event::Warning {
+                message: "warning".to_owned(),
+            })
+            .await
+            .unwrap();
+
+        let storage = event_handler.storage.get().unwrap();
+
+        let events = storage
+            .get_events(&EventQuery::default(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0].event, Event::Test));
+        assert!(matches!(events[1].event, Event::Warning { ref message } if message == "warning"));
+        assert!(events[0].id < events[1].id);
+
+        let query = EventQuery {
+            from_id: Some(events[1].id),
+            ..Default::default()
+        };
+        let events = storage.get_events(&query, None, None).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0].event, Event::Warning { .. }));
+
+        let query = EventQuery {
+            event_types: vec!["Test".to_owned()],
+            ..Default::default()
+        };
+        let events = storage.get_events(&query, None, None).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0].event, Event::Test));
+    }
+}
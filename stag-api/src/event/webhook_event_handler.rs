@@ -0,0 +1,730 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, Weak,
+    },
+    time::Duration,
+};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use reqwest::{header::CONTENT_TYPE, Client};
+use sha2::Sha256;
+use tokio::sync::Notify;
+use url::Url;
+
+#[cfg(any(feature = "sqlite-storage", feature = "postgres-storage"))]
+use crate::storage::{SharedSqlDbStorage, SqlDbStorage};
+use crate::{time_util::now_utc, types::chain_state::RetryPolicy};
+
+use super::{Event, EventHandler};
+
+/// Header containing ID of delivery (same across retries)
+const DELIVERY_HEADER: &str = "x-stag-delivery";
+/// Header containing unix timestamp (in seconds) of delivery attempt
+const TIMESTAMP_HEADER: &str = "x-stag-timestamp";
+/// Header containing HMAC-SHA256 signature of `<timestamp>.<body>`
+const SIGNATURE_HEADER: &str = "x-stag-signature";
+
+/// Maximum number of outbox entries retried in one batch
+const BATCH_SIZE: u32 = 100;
+/// Maximum time for which background worker waits before checking outbox again
+const MAX_WORKER_WAIT: Duration = Duration::from_secs(5);
+
+/// A pending delivery of an event to a webhook URL
+#[derive(Debug, Clone)]
+pub struct OutboxEntry {
+    /// ID of delivery
+    pub id: i64,
+    /// Webhook URL
+    pub url: Url,
+    /// JSON serialized event
+    pub payload: String,
+    /// Number of failed delivery attempts
+    pub attempts: u32,
+}
+
+/// Outbox for pending webhook deliveries
+pub enum Outbox {
+    /// Pending deliveries are stored in memory (and lost on crash)
+    Memory(Mutex<MemoryOutbox>),
+    /// Pending deliveries are stored in `webhook_outbox` table of database (database is connected lazily) and
+    /// exhausted deliveries are moved to `webhook_dead_letters` table
+    #[cfg(any(feature = "sqlite-storage", feature = "postgres-storage"))]
+    Sql(SharedSqlDbStorage),
+}
+
+#[derive(Default)]
+pub struct MemoryOutbox {
+    next_id: i64,
+    entries: Vec<MemoryOutboxEntry>,
+}
+
+struct MemoryOutboxEntry {
+    entry: OutboxEntry,
+    next_attempt_at: i64,
+}
+
+impl Outbox {
+    /// Creates a new in-memory outbox
+    pub fn memory() -> Self {
+        Self::Memory(Default::default())
+    }
+
+    /// Creates a new outbox persisted in given database storage
+    #[cfg(any(feature = "sqlite-storage", feature = "postgres-storage"))]
+    pub(crate) fn sql(storage: SharedSqlDbStorage) -> Self {
+        Self::Sql(storage)
+    }
+
+    #[cfg(any(feature = "sqlite-storage", feature = "postgres-storage"))]
+    async fn storage(storage: &SharedSqlDbStorage) -> Result<&SqlDbStorage> {
+        storage
+            .get()
+            .await
+            .context("unable to initialize webhook outbox")
+    }
+
+    async fn add(&self, url: &Url, payload: &str, next_attempt_at: i64) -> Result<i64> {
+        match self {
+            Self::Memory(outbox) => {
+                let mut outbox = outbox.lock().unwrap_or_else(|err| err.into_inner());
+
+                outbox.next_id += 1;
+                let entry = OutboxEntry {
+                    id: outbox.next_id,
+                    url: url.clone(),
+                    payload: payload.to_owned(),
+                    attempts: 0,
+                };
+                outbox.entries.push(MemoryOutboxEntry {
+                    entry,
+                    next_attempt_at,
+                });
+
+                Ok(outbox.next_id)
+            }
+            #[cfg(any(feature = "sqlite-storage", feature = "postgres-storage"))]
+            Self::Sql(storage) => {
+                Self::storage(storage)
+                    .await?
+                    .add_outbox_entry(url.as_str(), payload, next_attempt_at)
+                    .await
+            }
+        }
+    }
+
+    async fn due(&self, now: i64, max_attempts: u32) -> Result<Vec<OutboxEntry>> {
+        match self {
+            Self::Memory(outbox) => {
+                let outbox = outbox.lock().unwrap_or_else(|err| err.into_inner());
+
+                Ok(outbox
+                    .entries
+                    .iter()
+                    .filter(|entry| {
+                        entry.next_attempt_at <= now && entry.entry.attempts < max_attempts
+                    })
+                    .take(BATCH_SIZE as usize)
+                    .map(|entry| entry.entry.clone())
+                    .collect())
+            }
+            #[cfg(any(feature = "sqlite-storage", feature = "postgres-storage"))]
+            Self::Sql(storage) => {
+                Self::storage(storage)
+                    .await?
+                    .get_due_outbox_entries(now, max_attempts, BATCH_SIZE)
+                    .await
+            }
+        }
+    }
+
+    async fn next_attempt_at(&self, max_attempts: u32) -> Result<Option<i64>> {
+        match self {
+            Self::Memory(outbox) => {
+                let outbox = outbox.lock().unwrap_or_else(|err| err.into_inner());
+
+                Ok(outbox
+                    .entries
+                    .iter()
+                    .filter(|entry| entry.entry.attempts < max_attempts)
+                    .map(|entry| entry.next_attempt_at)
+                    .min())
+            }
+            #[cfg(any(feature = "sqlite-storage", feature = "postgres-storage"))]
+            Self::Sql(storage) => {
+                Self::storage(storage)
+                    .await?
+                    .get_next_outbox_attempt_at(max_attempts)
+                    .await
+            }
+        }
+    }
+
+    async fn reschedule(&self, id: i64, error: &str, next_attempt_at: i64) -> Result<()> {
+        match self {
+            Self::Memory(outbox) => {
+                let mut outbox = outbox.lock().unwrap_or_else(|err| err.into_inner());
+
+                let entry = outbox
+                    .entries
+                    .iter_mut()
+                    .find(|entry| entry.entry.id == id)
+                    .ok_or_else(|| anyhow!("webhook outbox entry {} not found", id))?;
+
+                entry.entry.attempts += 1;
+                entry.next_attempt_at = next_attempt_at;
+
+                // Errors of failed attempts are only recorded in persistent outbox
+                let _ = error;
+
+                Ok(())
+            }
+            #[cfg(any(feature = "sqlite-storage", feature = "postgres-storage"))]
+            Self::Sql(storage) => {
+                Self::storage(storage)
+                    .await?
+                    .reschedule_outbox_entry(id, error, next_attempt_at)
+                    .await
+            }
+        }
+    }
+
+    async fn delete(&self, id: i64) -> Result<()> {
+        match self {
+            Self::Memory(outbox) => {
+                let mut outbox = outbox.lock().unwrap_or_else(|err| err.into_inner());
+                outbox.entries.retain(|entry| entry.entry.id != id);
+                Ok(())
+            }
+            #[cfg(any(feature = "sqlite-storage", feature = "postgres-storage"))]
+            Self::Sql(storage) => Self::storage(storage).await?.delete_outbox_entry(id).await,
+        }
+    }
+
+    /// Removes deliveries with at least `max_attempts` failed attempts from outbox (they're moved to dead letters in a
+    /// persistent outbox) and returns the number of removed deliveries
+    async fn remove_exhausted(&self, max_attempts: u32) -> Result<u64> {
+        match self {
+            Self::Memory(outbox) => {
+                let mut outbox = outbox.lock().unwrap_or_else(|err| err.into_inner());
+
+                let len = outbox.entries.len();
+                outbox
+                    .entries
+                    .retain(|entry| entry.entry.attempts < max_attempts);
+
+                Ok((len - outbox.entries.len()) as u64)
+            }
+            #[cfg(any(feature = "sqlite-storage", feature = "postgres-storage"))]
+            Self::Sql(storage) => {
+                Self::storage(storage)
+                    .await?
+                    .move_exhausted_outbox_entries(max_attempts)
+                    .await
+            }
+        }
+    }
+}
+
+/// Event handler which POSTs JSON serialized events to webhook URLs
+///
+/// Every event is added to outbox and delivered by a background task (so that slow webhooks do not delay event
+/// handling). Deliveries are removed from outbox once they're delivered or their attempts are exhausted and failed
+/// deliveries are retried with exponential backoff (pending deliveries in a persistent outbox are also retried after a
+/// restart).
+#[derive(Clone)]
+pub struct WebhookEventHandler {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    client: Client,
+    urls: Vec<Url>,
+    secret: Option<Vec<u8>>,
+    retry_policy: RetryPolicy,
+    timeout: Duration,
+    outbox: Outbox,
+    notify: Arc<Notify>,
+    worker_started: AtomicBool,
+}
+
+impl WebhookEventHandler {
+    /// Creates a new webhook event handler (starts retrying pending deliveries when called within a tokio runtime)
+    pub fn new(
+        urls: Vec<Url>,
+        secret: Option<Vec<u8>>,
+        retry_policy: RetryPolicy,
+        timeout: Duration,
+        outbox: Outbox,
+    ) -> Self {
+        let event_handler = Self {
+            inner: Arc::new(Inner {
+                client: Client::new(),
+                urls,
+                secret,
+                retry_policy,
+                timeout,
+                outbox,
+                notify: Default::default(),
+                worker_started: AtomicBool::new(false),
+            }),
+        };
+
+        event_handler.start_worker();
+        event_handler
+    }
+
+    /// Starts background task retrying failed deliveries (if not already started)
+    fn start_worker(&self) {
+        if tokio::runtime::Handle::try_current().is_err()
+            || self.inner.worker_started.swap(true, Ordering::SeqCst)
+        {
+            return;
+        }
+
+        tokio::spawn(run_worker(
+            Arc::downgrade(&self.inner),
+            self.inner.notify.clone(),
+        ));
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl EventHandler for WebhookEventHandler {
+    async fn handle_event(&self, event: Event) -> Result<()> {
+        let payload = serde_json::to_string(&event)?;
+        let now = now_millis();
+
+        for url in self.inner.urls.iter() {
+            self.inner.outbox.add(url, &payload, now).await?;
+        }
+
+        self.start_worker();
+        self.inner.notify.notify_one();
+
+        Ok(())
+    }
+}
+
+impl Inner {
+    /// Attempts delivery of given outbox entry and schedules a retry if it fails (returns an error only when outbox
+    /// cannot be updated)
+    async fn attempt(&self, entry: OutboxEntry) -> Result<()> {
+        match self.deliver(&entry).await {
+            Ok(()) => self.outbox.delete(entry.id).await,
+            Err(err) => {
+                let next_attempt_at =
+                    now_millis() + millis(self.retry_policy.backoff(entry.attempts + 1));
+
+                self.outbox
+                    .reschedule(entry.id, &format!("{:#}", err), next_attempt_at)
+                    .await?;
+
+                if entry.attempts + 1 >= self.retry_policy.max_attempts {
+                    self.outbox
+                        .remove_exhausted(self.retry_policy.max_attempts)
+                        .await?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    async fn deliver(&self, entry: &OutboxEntry) -> Result<()> {
+        let timestamp = now_utc().timestamp();
+
+        let mut request = self
+            .client
+            .post(entry.url.clone())
+            .timeout(self.timeout)
+            .header(CONTENT_TYPE, "application/json")
+            .header(DELIVERY_HEADER, entry.id)
+            .header(TIMESTAMP_HEADER, timestamp);
+
+        if let Some(ref secret) = self.secret {
+            request = request.header(
+                SIGNATURE_HEADER,
+                format!("sha256={}", sign(secret, timestamp, &entry.payload)?),
+            );
+        }
+
+        let response = request
+            .body(entry.payload.clone())
+            .send()
+            .await
+            .with_context(|| format!("failed to send event to webhook {}", entry.url))?;
+
+        ensure!(
+            response.status().is_success(),
+            "webhook {} responded with status {}",
+            entry.url,
+            response.status()
+        );
+
+        Ok(())
+    }
+
+    /// Retries all the due deliveries and returns the time to wait before next retry
+    async fn retry_due(&self) -> Result<Duration> {
+        // Deliveries may already be exhausted, e.g., when maximum number of attempts was lowered after a restart
+        self.outbox
+            .remove_exhausted(self.retry_policy.max_attempts)
+            .await?;
+
+        let entries = self
+            .outbox
+            .due(now_millis(), self.retry_policy.max_attempts)
+            .await?;
+        let batch_full = entries.len() == BATCH_SIZE as usize;
+
+        for entry in entries {
+            self.attempt(entry).await?;
+        }
+
+        if batch_full {
+            return Ok(Duration::ZERO);
+        }
+
+        let wait = match self
+            .outbox
+            .next_attempt_at(self.retry_policy.max_attempts)
+            .await?
+        {
+            Some(next_attempt_at) => Duration::from_millis(
+                next_attempt_at
+                    .saturating_sub(now_millis())
+                    .try_into()
+                    .unwrap_or_default(),
+            ),
+            None => MAX_WORKER_WAIT,
+        };
+
+        Ok(wait.min(MAX_WORKER_WAIT))
+    }
+}
+
+/// Retries failed deliveries until the event handler is dropped
+async fn run_worker(inner: Weak<Inner>, notify: Arc<Notify>) {
+    loop {
+        let wait = match inner.upgrade() {
+            Some(inner) => inner.retry_due().await.unwrap_or(MAX_WORKER_WAIT),
+            None => return,
+        };
+
+        let _ = tokio::time::timeout(wait, notify.notified()).await;
+    }
+}
+
+/// Returns hex encoded HMAC-SHA256 of `<timestamp>.<payload>`
+fn sign(secret: &[u8], timestamp: i64, payload: &str) -> Result<String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret)
+        .map_err(|err| anyhow!("invalid webhook secret: {}", err))?;
+
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(payload.as_bytes());
+
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
+
+fn now_millis() -> i64 {
+    now_utc().timestamp_millis()
+}
+
+fn millis(duration: Duration) -> i64 {
+    duration.as_millis().try_into().unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        sync::mpsc::{unbounded_channel, UnboundedReceiver},
+        time::timeout,
+    };
+
+    use super::*;
+    #[cfg(feature = "sqlite-storage")]
+    use crate::storage::{Sqlite, StorageConfig};
+
+    const SECRET: &[u8] = b"secret";
+
+    struct Request {
+        headers: Vec<(String, String)>,
+        body: String,
+    }
+
+    impl Request {
+        fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(header, _)| header.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    /// Starts a HTTP server which responds with given statuses (in order, last status is repeated)
+    async fn start_server(statuses: Vec<u16>) -> (Url, UnboundedReceiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        let (sender, receiver) = unbounded_channel();
+
+        tokio::spawn(async move {
+            let mut statuses = statuses.into_iter().peekable();
+            let mut status = 200;
+
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+
+                let mut buf = Vec::new();
+                let header_end = loop {
+                    let mut chunk = [0; 1024];
+                    let len = stream.read(&mut chunk).await.unwrap();
+                    buf.extend_from_slice(&chunk[..len]);
+
+                    if let Some(pos) = buf.windows(4).position(|window| window == b"\r\n\r\n") {
+                        break pos + 4;
+                    }
+                };
+
+                let head = String::from_utf8(buf[..header_end].to_vec()).unwrap();
+                let headers: Vec<(String, String)> = head
+                    .lines()
+                    .skip(1)
+                    .filter_map(|line| line.split_once(": "))
+                    .map(|(name, value)| (name.to_owned(), value.to_owned()))
+                    .collect();
+                let content_length: usize = headers
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+                    .map(|(_, value)| value.parse().unwrap())
+                    .unwrap_or_default();
+
+                while buf.len() < header_end + content_length {
+                    let mut chunk = [0; 1024];
+                    let len = stream.read(&mut chunk).await.unwrap();
+                    buf.extend_from_slice(&chunk[..len]);
+                }
+
+                let body = String::from_utf8(buf[header_end..].to_vec()).unwrap();
+
+                if let Some(next_status) = statuses.next() {
+                    status = next_status;
+                }
+
+                stream
+                    .write_all(
+                        format!(
+                            "HTTP/1.1 {} Status\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                            status
+                        )
+                        .as_bytes(),
+                    )
+                    .await
+                    .unwrap();
+
+                let _ = sender.send(Request { headers, body });
+            }
+        });
+
+        (url, receiver)
+    }
+
+    fn retry_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(50),
+            backoff_multiplier: 2,
+        }
+    }
+
+    async fn pending_deliveries(event_handler: &WebhookEventHandler) -> Option<i64> {
+        event_handler
+            .inner
+            .outbox
+            .next_attempt_at(event_handler.inner.retry_policy.max_attempts)
+            .await
+            .unwrap()
+    }
+
+    async fn wait_until_delivered(event_handler: &WebhookEventHandler) {
+        timeout(Duration::from_secs(5), async {
+            while pending_deliveries(event_handler).await.is_some() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_webhook_event_handler() {
+        let (url, mut requests) = start_server(vec![200]).await;
+
+        let event_handler = WebhookEventHandler::new(
+            vec![url],
+            Some(SECRET.to_vec()),
+            retry_policy(),
+            Duration::from_secs(5),
+            Outbox::memory(),
+        );
+
+        event_handler.handle_event(Event::Test).await.unwrap();
+
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.body, serde_json::to_string(&Event::Test).unwrap());
+        assert_eq!(request.header("content-type"), Some("application/json"));
+        assert_eq!(request.header(DELIVERY_HEADER), Some("1"));
+
+        let timestamp: i64 = request.header(TIMESTAMP_HEADER).unwrap().parse().unwrap();
+        assert_eq!(
+            request.header(SIGNATURE_HEADER).unwrap(),
+            format!("sha256={}", sign(SECRET, timestamp, &request.body).unwrap())
+        );
+
+        wait_until_delivered(&event_handler).await;
+    }
+
+    #[tokio::test]
+    async fn test_webhook_event_handler_does_not_wait_for_delivery() {
+        // Webhook which accepts connections but never responds
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+
+        tokio::spawn(async move {
+            let mut streams = Vec::new();
+
+            while let Ok((stream, _)) = listener.accept().await {
+                streams.push(stream);
+            }
+        });
+
+        let event_handler = WebhookEventHandler::new(
+            vec![url],
+            None,
+            retry_policy(),
+            Duration::from_secs(5),
+            Outbox::memory(),
+        );
+
+        timeout(
+            Duration::from_secs(1),
+            event_handler.handle_event(Event::Test),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert!(pending_deliveries(&event_handler).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_event_handler_retry() {
+        let (url, mut requests) = start_server(vec![500, 503, 200]).await;
+
+        let event_handler = WebhookEventHandler::new(
+            vec![url],
+            None,
+            retry_policy(),
+            Duration::from_secs(5),
+            Outbox::memory(),
+        );
+
+        event_handler.handle_event(Event::Test).await.unwrap();
+        assert!(pending_deliveries(&event_handler).await.is_some());
+
+        for _ in 0..3 {
+            let request = timeout(Duration::from_secs(5), requests.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(request.header(DELIVERY_HEADER), Some("1"));
+            assert!(request.header(SIGNATURE_HEADER).is_none());
+        }
+
+        wait_until_delivered(&event_handler).await;
+    }
+
+    #[cfg(feature = "sqlite-storage")]
+    #[tokio::test]
+    async fn test_webhook_event_handler_sql_outbox() {
+        let (url, mut requests) = start_server(vec![500, 500, 500]).await;
+        let storage = Sqlite::new("sqlite::memory:");
+
+        let event_handler = WebhookEventHandler::new(
+            vec![url],
+            None,
+            retry_policy(),
+            Duration::from_secs(5),
+            Outbox::sql(storage.shared_storage()),
+        );
+
+        event_handler.handle_event(Event::Test).await.unwrap();
+
+        for _ in 0..3 {
+            timeout(Duration::from_secs(5), requests.recv())
+                .await
+                .unwrap()
+                .unwrap();
+        }
+
+        // Deliveries with exhausted attempts are not retried and moved to dead letters of the shared database
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(requests.try_recv().is_err());
+        assert!(pending_deliveries(&event_handler).await.is_none());
+
+        let dead_letters = storage
+            .into_storage()
+            .await
+            .unwrap()
+            .get_webhook_dead_letters()
+            .await
+            .unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].attempts, 3);
+        assert_eq!(
+            dead_letters[0].payload,
+            serde_json::to_string(&Event::Test).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_webhook_event_handler_drops_exhausted_deliveries() {
+        let (url, mut requests) = start_server(vec![500, 500, 500]).await;
+
+        let event_handler = WebhookEventHandler::new(
+            vec![url],
+            None,
+            retry_policy(),
+            Duration::from_secs(5),
+            Outbox::memory(),
+        );
+
+        event_handler.handle_event(Event::Test).await.unwrap();
+
+        for _ in 0..3 {
+            timeout(Duration::from_secs(5), requests.recv())
+                .await
+                .unwrap()
+                .unwrap();
+        }
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        match event_handler.inner.outbox {
+            Outbox::Memory(ref outbox) => assert!(outbox.lock().unwrap().entries.is_empty()),
+            #[cfg(any(feature = "sqlite-storage", feature = "postgres-storage"))]
+            _ => unreachable!(),
+        }
+    }
+}
@@ -13,12 +13,16 @@ compile_error!("only one of the features ['wasm', 'non-wasm'] can be enabled");
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
+pub mod endpoint_util;
 pub mod event;
+pub mod retry_util;
 pub mod service;
 pub mod signer;
 pub mod stag;
 pub mod storage;
 pub mod tendermint;
+#[cfg(all(test, feature = "mnemonic-signer", feature = "sqlite-storage"))]
+mod test_util;
 pub mod time_util;
 pub mod trait_util;
 pub mod transaction_builder;
@@ -0,0 +1,230 @@
+//! Utilities for retrying transient RPC and gRPC failures.
+use std::future::Future;
+
+use anyhow::{Error, Result};
+use tonic::{Code, Status};
+
+use crate::{time_util::sleep, types::chain_state::RetryPolicy};
+
+/// Runs the given operation until it succeeds, fails with a non-retriable error or the maximum number of attempts in
+/// the retry policy is exhausted (sleeping with exponential backoff between attempts)
+pub async fn retry<T, F, Fut>(retry_policy: &RetryPolicy, operation: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    retry_if(retry_policy, is_retriable, operation).await
+}
+
+/// Runs the given operation until it succeeds, fails with an error for which `should_retry` returns false or the
+/// maximum number of attempts in the retry policy is exhausted (sleeping with exponential backoff between attempts)
+pub async fn retry_if<T, F, Fut>(
+    retry_policy: &RetryPolicy,
+    should_retry: fn(&Error) -> bool,
+    mut operation: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt < retry_policy.max_attempts && should_retry(&err) => {
+                sleep(retry_policy.backoff(attempt)).await;
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Returns true if given error is caused by a transient network failure and the failed call can be retried
+pub fn is_retriable(error: &Error) -> bool {
+    error.chain().any(|cause| {
+        #[cfg(feature = "reqwest-client")]
+        if let Some(err) = cause.downcast_ref::<reqwest::Error>() {
+            return is_retriable_reqwest_error(err);
+        }
+
+        #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+        if cause.downcast_ref::<tonic::transport::Error>().is_some() {
+            return true;
+        }
+
+        #[cfg(feature = "websocket-client")]
+        if cause
+            .downcast_ref::<crate::tendermint::WebSocketError>()
+            .is_some()
+        {
+            return true;
+        }
+
+        #[cfg(feature = "websocket-client")]
+        if let Some(err) = cause.downcast_ref::<tokio_tungstenite::tungstenite::Error>() {
+            return is_retriable_websocket_error(err);
+        }
+
+        if let Some(status) = cause.downcast_ref::<Status>() {
+            return matches!(
+                status.code(),
+                Code::Unavailable
+                    | Code::DeadlineExceeded
+                    | Code::ResourceExhausted
+                    | Code::Aborted
+            );
+        }
+
+        false
+    })
+}
+
+/// Returns true if given error is caused by a failure to connect to the server (i.e., the request was never sent, so
+/// it is safe to re-send even non-idempotent requests, like transaction broadcasts)
+#[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+pub fn is_connect_error(error: &Error) -> bool {
+    error.chain().any(|cause| {
+        #[cfg(feature = "reqwest-client")]
+        if let Some(err) = cause.downcast_ref::<reqwest::Error>() {
+            return err.is_connect();
+        }
+
+        if let Some(err) = cause.downcast_ref::<std::io::Error>() {
+            return err.kind() == std::io::ErrorKind::ConnectionRefused;
+        }
+
+        false
+    })
+}
+
+/// Returns true if given error is caused by a failure to connect to the server (always false on wasm because browsers
+/// do not expose whether a request was sent before it failed)
+#[cfg(feature = "wasm")]
+pub fn is_connect_error(_error: &Error) -> bool {
+    false
+}
+
+#[cfg(feature = "reqwest-client")]
+fn is_retriable_reqwest_error(err: &reqwest::Error) -> bool {
+    if err.is_timeout() {
+        return true;
+    }
+
+    #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+    if err.is_connect() {
+        return true;
+    }
+
+    err.status()
+        .map(|status| status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS)
+        .unwrap_or(false)
+}
+
+#[cfg(feature = "websocket-client")]
+fn is_retriable_websocket_error(err: &tokio_tungstenite::tungstenite::Error) -> bool {
+    use tokio_tungstenite::tungstenite::Error;
+
+    match err {
+        Error::ConnectionClosed | Error::AlreadyClosed | Error::Io(_) => true,
+        Error::Http(response) => {
+            response.status().is_server_error()
+                || response.status()
+                    == tokio_tungstenite::tungstenite::http::StatusCode::TOO_MANY_REQUESTS
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicU32, Ordering},
+        time::Duration,
+    };
+
+    use anyhow::{anyhow, Context};
+
+    use super::*;
+
+    fn test_retry_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(2),
+            backoff_multiplier: 2,
+        }
+    }
+
+    #[test]
+    fn test_backoff() {
+        let retry_policy = RetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(3),
+            backoff_multiplier: 2,
+        };
+
+        assert_eq!(retry_policy.backoff(1), Duration::from_millis(500));
+        assert_eq!(retry_policy.backoff(2), Duration::from_secs(1));
+        assert_eq!(retry_policy.backoff(3), Duration::from_secs(2));
+        assert_eq!(retry_policy.backoff(4), Duration::from_secs(3));
+        assert_eq!(retry_policy.backoff(100), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_is_retriable() {
+        assert!(is_retriable(&Status::unavailable("node is down").into()));
+        assert!(is_retriable(
+            &Error::from(Status::deadline_exceeded("timeout")).context("failed to query balance")
+        ));
+        assert!(!is_retriable(&Status::not_found("no account").into()));
+        assert!(!is_retriable(&anyhow!("error jsonrpc response")));
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_failure() {
+        let attempts = AtomicU32::new(0);
+
+        let result = retry(&test_retry_policy(), || async {
+            if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(Status::unavailable("node is down")).context("failed to query balance")
+            } else {
+                Ok(42)
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_exhausts_attempts() {
+        let attempts = AtomicU32::new(0);
+
+        let result: Result<()> = retry(&test_retry_policy(), || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(Status::unavailable("node is down").into())
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_non_retriable_failure() {
+        let attempts = AtomicU32::new(0);
+
+        let result: Result<()> = retry(&test_retry_policy(), || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(anyhow!("invalid request"))
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+}
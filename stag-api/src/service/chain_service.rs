@@ -1,15 +1,19 @@
 use anyhow::{bail, Context, Result};
 use rust_decimal::Decimal;
 use tendermint::node::Id as NodeId;
+use url::Url;
 
 use crate::{
+    endpoint_util::{mark_healthy, mark_unhealthy},
     event::{Event, EventHandler},
     signer::{GetPublicKey, Signer},
     stag::{StagContext, WithTransaction},
     storage::{Storage, Transaction, TransactionProvider},
     tendermint::TendermintClient,
     types::{
-        chain_state::{ChainConfig, ChainKey, ChainState},
+        chain_state::{
+            grpc_health_check, ChainConfig, ChainKey, ChainState, EndpointHealth, EndpointType,
+        },
         ics::core::ics24_host::identifier::{ChainId, Identifier, PortId},
         operation::Operation,
     },
@@ -23,7 +27,11 @@ where
     C::Storage: TransactionProvider,
     C::RpcClient: TendermintClient,
 {
-    let status = context.rpc_client().status(&config.rpc_addr).await?;
+    let status = context
+        .rpc_client()
+        .for_chain(config)
+        .status(&config.rpc_addr)
+        .await?;
 
     let chain_id: ChainId = status.node_info.network.to_string().parse()?;
     let node_id: NodeId = status.node_info.id;
@@ -186,3 +194,47 @@ where
         .get_operations(chain_id, limit, offset)
         .await
 }
+
+/// Checks health of all the RPC and gRPC endpoints of given chain (unhealthy endpoints are deprioritized when failing
+/// over between endpoints)
+pub async fn check_endpoints<C>(context: &C, chain_id: &ChainId) -> Result<Vec<EndpointHealth>>
+where
+    C: StagContext,
+    C::Storage: Storage,
+    C::RpcClient: TendermintClient,
+{
+    let chain_state = get_chain(context, chain_id)
+        .await?
+        .context("chain details not found when checking endpoints")?;
+
+    let mut endpoints = Vec::new();
+
+    for rpc_addr in chain_state.config.rpc_addrs() {
+        let result = context.rpc_client().health_check(rpc_addr).await;
+        endpoints.push(to_endpoint_health(EndpointType::Rpc, rpc_addr, result));
+    }
+
+    for grpc_addr in chain_state.config.grpc_addrs() {
+        let result = grpc_health_check(grpc_addr).await;
+        endpoints.push(to_endpoint_health(EndpointType::Grpc, grpc_addr, result));
+    }
+
+    Ok(endpoints)
+}
+
+fn to_endpoint_health(
+    endpoint_type: EndpointType,
+    addr: &Url,
+    result: Result<()>,
+) -> EndpointHealth {
+    match result {
+        Ok(()) => mark_healthy(addr),
+        Err(_) => mark_unhealthy(addr),
+    }
+
+    EndpointHealth {
+        endpoint_type,
+        addr: addr.clone(),
+        error: result.err().map(|err| format!("{:#}", err)),
+    }
+}
@@ -0,0 +1,170 @@
+use anyhow::{Context, Result};
+
+use crate::{
+    event::EventHandler,
+    stag::StagContext,
+    storage::Storage,
+    types::event_record::{EventQuery, EventRecord},
+};
+
+/// Number of events fetched from storage in one batch when replaying events
+const REPLAY_BATCH_SIZE: u32 = 100;
+
+/// Gets events selected by given query from storage (in increasing order of ID)
+pub async fn get_events<C>(
+    context: &C,
+    query: &EventQuery,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<EventRecord>>
+where
+    C: StagContext,
+    C::Storage: Storage,
+{
+    context.storage().get_events(query, limit, offset).await
+}
+
+/// Re-delivers events selected by given query from storage to given event handler (in increasing order of ID) and
+/// returns the ID of last replayed event
+///
+/// Replay stops at the first event which event handler fails to handle (the error contains ID of that event, so, the
+/// replay can be resumed from it).
+pub async fn replay_events<C, H>(
+    context: &C,
+    query: &EventQuery,
+    event_handler: &H,
+) -> Result<Option<i64>>
+where
+    C: StagContext,
+    C::Storage: Storage,
+    H: EventHandler + ?Sized,
+{
+    let mut query = query.clone();
+    let mut last_id = None;
+
+    loop {
+        let records = context
+            .storage()
+            .get_events(&query, Some(REPLAY_BATCH_SIZE), None)
+            .await?;
+        let is_last_batch = records.len() < REPLAY_BATCH_SIZE as usize;
+
+        for record in records {
+            event_handler
+                .handle_event(record.event)
+                .await
+                .with_context(|| format!("unable to replay event with id {}", record.id))?;
+
+            last_id = Some(record.id);
+            query.from_id = Some(record.id + 1);
+        }
+
+        if is_last_batch {
+            return Ok(last_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{anyhow, ensure};
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::{
+        event::{Event, RecordingEventHandler},
+        tendermint::MockRpcClient,
+        test_util::{test_context, CHAIN_ID},
+    };
+
+    /// Event handler which fails to handle warnings (and records all the other events)
+    struct FailOnWarning(RecordingEventHandler);
+
+    #[async_trait]
+    impl EventHandler for FailOnWarning {
+        async fn handle_event(&self, event: Event) -> Result<()> {
+            ensure!(
+                !matches!(event, Event::Warning { .. }),
+                "unable to handle warning"
+            );
+            self.0.handle_event(event).await
+        }
+    }
+
+    fn chain_added() -> Event {
+        Event::ChainAdded {
+            chain_id: CHAIN_ID.parse().unwrap(),
+        }
+    }
+
+    fn warning() -> Event {
+        Event::Warning {
+            message: "warning".to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_events() {
+        let (context, _) = test_context(MockRpcClient::new(|_, method, _| {
+            Err(anyhow!("unexpected request: {}", method))
+        }))
+        .await;
+
+        let handler = RecordingEventHandler::default();
+        let last_id = replay_events(&context, &EventQuery::default(), &handler)
+            .await
+            .unwrap();
+        assert_eq!(last_id, None);
+        assert!(handler.events().is_empty());
+
+        // Exactly two full batches of events of the chain (followed by a warning which is not selected by the query)
+        let mut ids = Vec::new();
+        for _ in 0..2 * REPLAY_BATCH_SIZE {
+            ids.push(context.storage().add_event(&chain_added()).await.unwrap());
+        }
+        context.storage().add_event(&warning()).await.unwrap();
+
+        let query = EventQuery {
+            chain_id: Some(CHAIN_ID.parse().unwrap()),
+            ..Default::default()
+        };
+
+        let last_id = replay_events(&context, &query, &handler).await.unwrap();
+        assert_eq!(last_id, ids.last().copied());
+        assert_eq!(handler.events().len(), ids.len());
+
+        // Replay can start from a given event ID
+        let handler = RecordingEventHandler::default();
+        let query = EventQuery {
+            from_id: Some(ids[150]),
+            ..query
+        };
+
+        let last_id = replay_events(&context, &query, &handler).await.unwrap();
+        assert_eq!(last_id, ids.last().copied());
+        assert_eq!(handler.events().len(), ids.len() - 150);
+    }
+
+    #[tokio::test]
+    async fn test_replay_events_stops_at_failed_event() {
+        let (context, _) = test_context(MockRpcClient::new(|_, method, _| {
+            Err(anyhow!("unexpected request: {}", method))
+        }))
+        .await;
+
+        context.storage().add_event(&chain_added()).await.unwrap();
+        let warning_id = context.storage().add_event(&warning()).await.unwrap();
+        context.storage().add_event(&chain_added()).await.unwrap();
+
+        let handler = FailOnWarning(RecordingEventHandler::default());
+        let err = replay_events(&context, &EventQuery::default(), &handler)
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!("unable to replay event with id {}", warning_id)
+        );
+        assert_eq!(handler.0.events().len(), 1);
+    }
+}
@@ -11,8 +11,9 @@ use tonic_web_wasm_client::Client;
 use url::Url;
 
 use crate::{
+    endpoint_util::with_failover,
     event::{Event, EventHandler},
-    service::ibc_service::common::{ensure_response_success, extract_attribute},
+    service::ibc_service::common::{broadcast_tx, extract_attribute},
     signer::Signer,
     stag::StagContext,
     storage::{Storage, Transaction},
@@ -25,6 +26,14 @@ use crate::{
 };
 
 /// Opens an ICA channel on IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "open_ica_channel",
+        skip_all,
+        fields(chain_id = %chain_state.id, request_id = request_id)
+    )
+)]
 pub async fn open_channel<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -62,6 +71,8 @@ where
 
     context
         .handle_event(Event::InitializedChannelOnSoloMachine {
+            chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             channel_id: solo_machine_channel_id.clone(),
             port_id: solo_machine_port_id.clone(),
         })
@@ -83,6 +94,8 @@ where
 
     context
         .handle_event(Event::InitializedChannelOnTendermint {
+            chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             channel_id: tendermint_channel_id.clone(),
             port_id: tendermint_port_id.clone(),
         })
@@ -101,6 +114,8 @@ where
 
     context
         .handle_event(Event::ConfirmedChannelOnSoloMachine {
+            chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             channel_id: solo_machine_channel_id.clone(),
             port_id: solo_machine_port_id.clone(),
         })
@@ -120,6 +135,8 @@ where
 
     context
         .handle_event(Event::ConfirmedChannelOnTendermint {
+            chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             channel_id: tendermint_channel_id.clone(),
             port_id: tendermint_port_id.clone(),
         })
@@ -134,6 +151,7 @@ where
     })
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
 async fn channel_open_init<C>(
     context: &C,
     solo_machine_connection_id: &ConnectionId,
@@ -167,6 +185,10 @@ where
 }
 
 #[allow(clippy::too_many_arguments)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 async fn channel_open_try<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -200,12 +222,7 @@ where
     )
     .await?;
 
-    let response = context
-        .rpc_client()
-        .broadcast_tx(&chain_state.config.rpc_addr, msg)
-        .await?;
-
-    ensure_response_success(&response)?;
+    let response = broadcast_tx(context, chain_state, msg).await?;
 
     extract_attribute(
         &response.deliver_tx.events,
@@ -216,6 +233,7 @@ where
 }
 
 #[allow(clippy::too_many_arguments)]
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(chain_id = %chain_state.id)))]
 async fn channel_open_ack<C>(
     context: &C,
     chain_state: &ChainState,
@@ -276,6 +294,10 @@ where
 }
 
 #[allow(clippy::too_many_arguments)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 async fn channel_open_confirm<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -304,12 +326,7 @@ where
     )
     .await?;
 
-    let response = context
-        .rpc_client()
-        .broadcast_tx(&chain_state.config.rpc_addr, msg)
-        .await?;
-
-    ensure_response_success(&response)?;
+    broadcast_tx(context, chain_state, msg).await?;
 
     Ok(())
 }
@@ -319,23 +336,32 @@ async fn get_ica_version(
     tendermint_channel_id: &ChannelId,
     tendermint_port_id: &PortId,
 ) -> Result<String> {
-    let mut query_client = get_channel_query_client(chain_state.config.grpc_addr.clone()).await?;
+    let request = QueryChannelRequest {
+        channel_id: tendermint_channel_id.to_string(),
+        port_id: tendermint_port_id.to_string(),
+    };
 
-    let channel = query_client
-        .channel(QueryChannelRequest {
-            channel_id: tendermint_channel_id.to_string(),
-            port_id: tendermint_port_id.to_string(),
-        })
-        .await?
-        .into_inner()
-        .channel
-        .ok_or_else(|| {
-            anyhow!(
-                "tendermint channel not found with id {} and port {}",
-                tendermint_channel_id,
-                tendermint_port_id
-            )
-        })?;
+    let channel = with_failover(
+        chain_state.config.grpc_addrs(),
+        &chain_state.config.retry_policy,
+        |grpc_addr| {
+            let request = request.clone();
+
+            async move {
+                let mut query_client = get_channel_query_client(grpc_addr.clone()).await?;
+                Ok(query_client.channel(request).await?.into_inner())
+            }
+        },
+    )
+    .await?
+    .channel
+    .ok_or_else(|| {
+        anyhow!(
+            "tendermint channel not found with id {} and port {}",
+            tendermint_channel_id,
+            tendermint_port_id
+        )
+    })?;
 
     Ok(channel.version)
 }
@@ -3,9 +3,7 @@ use primitive_types::U256;
 
 use crate::{
     event::{Event, EventHandler},
-    service::ibc_service::common::{
-        ensure_response_success, extract_attribute, get_packet_acknowledgement,
-    },
+    service::ibc_service::common::{broadcast_tx, extract_attribute, get_packet_acknowledgement},
     signer::Signer,
     stag::{StagContext, WithTransaction},
     storage::{Storage, Transaction, TransactionProvider},
@@ -18,6 +16,14 @@ use crate::{
 };
 
 /// Delegates some tokens from ICA account on host chain to given validator address
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "ica_delegate",
+        skip_all,
+        fields(chain_id = %chain_id, request_id = request_id.as_deref())
+    )
+)]
 pub async fn delegate<C>(
     context: &C,
     chain_id: ChainId,
@@ -55,12 +61,8 @@ where
     )
     .await?;
 
-    let response = transaction_context
-        .rpc_client()
-        .broadcast_tx(&chain_state.config.rpc_addr, msg)
-        .await?;
-
-    let transaction_hash = ensure_response_success(&response)?;
+    let response = broadcast_tx(&transaction_context, &chain_state, msg).await?;
+    let transaction_hash = response.hash.to_string();
 
     transaction_context
         .storage()
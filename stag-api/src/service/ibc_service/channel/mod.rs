@@ -19,9 +19,13 @@ use crate::{
     },
 };
 
-use super::common::ensure_response_success;
+use super::common::broadcast_tx;
 
 /// Closes an existing channel with given port id
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn close_channel<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -47,6 +51,7 @@ where
     context
         .handle_event(Event::CloseChannelOnTendermint {
             chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             port_id: channel_details.solo_machine_port_id.clone(),
         })
         .await?;
@@ -66,6 +71,7 @@ where
     context
         .handle_event(Event::CloseChannelOnTendermint {
             chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             port_id: channel_details.tendermint_port_id.clone(),
         })
         .await?;
@@ -74,6 +80,10 @@ where
 }
 
 #[allow(clippy::too_many_arguments)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 async fn channel_close_confirm<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -102,14 +112,10 @@ where
     )
     .await?;
 
-    let response = context
-        .rpc_client()
-        .broadcast_tx(&chain_state.config.rpc_addr, msg)
-        .await?;
-
-    ensure_response_success(&response).map(|_| ())
+    broadcast_tx(context, chain_state, msg).await.map(|_| ())
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
 async fn close_channel_init<C>(context: &C, port_id: &PortId, channel_id: &ChannelId) -> Result<()>
 where
     C: StagContext,
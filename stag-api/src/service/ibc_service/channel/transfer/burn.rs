@@ -4,7 +4,7 @@ use primitive_types::U256;
 use crate::{
     event::{Event, EventHandler},
     service::ibc_service::{
-        common::ensure_response_success,
+        common::broadcast_tx,
         packet::{extract_packets, process_packets},
     },
     signer::{GetPublicKey, Signer},
@@ -19,6 +19,10 @@ use crate::{
 };
 
 /// Burns tokens on given chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_id, request_id = request_id.as_deref()))
+)]
 pub async fn burn_tokens<C>(
     context: &C,
     chain_id: ChainId,
@@ -54,12 +58,8 @@ where
     )
     .await?;
 
-    let response = transaction_context
-        .rpc_client()
-        .broadcast_tx(&chain_state.config.rpc_addr, msg)
-        .await?;
-
-    let transaction_hash = ensure_response_success(&response)?;
+    let response = broadcast_tx(&transaction_context, &chain_state, msg).await?;
+    let transaction_hash = response.hash.to_string();
 
     let port_id = PortId::transfer();
 
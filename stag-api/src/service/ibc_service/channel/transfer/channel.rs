@@ -5,7 +5,7 @@ use cosmos_sdk_proto::ibc::core::channel::v1::{
 
 use crate::{
     event::{Event, EventHandler},
-    service::ibc_service::common::{ensure_response_success, extract_attribute},
+    service::ibc_service::common::{broadcast_tx, extract_attribute},
     signer::Signer,
     stag::StagContext,
     storage::{Storage, Transaction},
@@ -18,6 +18,14 @@ use crate::{
 };
 
 /// Opens an IBC transfer channel on IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "open_transfer_channel",
+        skip_all,
+        fields(chain_id = %chain_state.id, request_id = request_id)
+    )
+)]
 pub async fn open_channel<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -46,6 +54,8 @@ where
 
     context
         .handle_event(Event::InitializedChannelOnTendermint {
+            chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             channel_id: tendermint_channel_id.clone(),
             port_id: port_id.clone(),
         })
@@ -61,6 +71,8 @@ where
 
     context
         .handle_event(Event::InitializedChannelOnSoloMachine {
+            chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             channel_id: solo_machine_channel_id.clone(),
             port_id: port_id.clone(),
         })
@@ -80,6 +92,8 @@ where
 
     context
         .handle_event(Event::ConfirmedChannelOnTendermint {
+            chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             channel_id: tendermint_channel_id.clone(),
             port_id: port_id.clone(),
         })
@@ -89,6 +103,8 @@ where
 
     context
         .handle_event(Event::ConfirmedChannelOnSoloMachine {
+            chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             channel_id: solo_machine_channel_id.clone(),
             port_id: port_id.clone(),
         })
@@ -103,6 +119,10 @@ where
     })
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 async fn channel_open_init<C>(
     context: &C,
     chain_state: &ChainState,
@@ -129,12 +149,7 @@ where
     )
     .await?;
 
-    let response = context
-        .rpc_client()
-        .broadcast_tx(&chain_state.config.rpc_addr, msg)
-        .await?;
-
-    ensure_response_success(&response)?;
+    let response = broadcast_tx(context, chain_state, msg).await?;
 
     extract_attribute(
         &response.deliver_tx.events,
@@ -144,6 +159,7 @@ where
     .parse()
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
 async fn channel_open_try<C>(
     context: &C,
     port_id: &PortId,
@@ -176,6 +192,10 @@ where
 }
 
 #[allow(clippy::too_many_arguments)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 async fn channel_open_ack<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -204,16 +224,12 @@ where
     )
     .await?;
 
-    let response = context
-        .rpc_client()
-        .broadcast_tx(&chain_state.config.rpc_addr, msg)
-        .await?;
-
-    ensure_response_success(&response)?;
+    broadcast_tx(context, chain_state, msg).await?;
 
     Ok(())
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
 async fn channel_open_confirm<C>(
     context: &C,
     port_id: &PortId,
@@ -3,11 +3,11 @@ use primitive_types::U256;
 
 use crate::{
     event::{Event, EventHandler},
-    service::ibc_service::common::{ensure_response_success, get_packet_acknowledgement},
+    service::ibc_service::common::{broadcast_tx, get_packet_acknowledgement},
     signer::{GetPublicKey, Signer},
     stag::{StagContext, WithTransaction},
     storage::{Storage, Transaction, TransactionProvider},
-    tendermint::{JsonRpcClient, TendermintClient},
+    tendermint::JsonRpcClient,
     transaction_builder,
     types::{
         ics::core::ics24_host::identifier::{ChainId, Identifier, PortId},
@@ -16,6 +16,10 @@ use crate::{
 };
 
 /// Mints tokens on given chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_id, request_id = request_id.as_deref()))
+)]
 pub async fn mint_tokens<C>(
     context: &C,
     chain_id: ChainId,
@@ -53,12 +57,8 @@ where
     )
     .await?;
 
-    let response = transaction_context
-        .rpc_client()
-        .broadcast_tx(&chain_state.config.rpc_addr, msg)
-        .await?;
-
-    let transaction_hash = ensure_response_success(&response)?;
+    let response = broadcast_tx(&transaction_context, &chain_state, msg).await?;
+    let transaction_hash = response.hash.to_string();
 
     transaction_context
         .storage()
@@ -2,11 +2,12 @@ use anyhow::{Context, Result};
 
 use crate::{
     event::{Event, EventHandler},
-    service::ibc_service::common::{ensure_response_success, extract_attribute},
+    service::ibc_service::common::{broadcast_tx, extract_attribute},
     signer::Signer,
     stag::StagContext,
     storage::{Storage, Transaction},
     tendermint::{LightClient, TendermintClient},
+    time_util::now_utc,
     transaction_builder,
     types::{
         chain_state::ChainState,
@@ -14,6 +15,10 @@ use crate::{
     },
 };
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn create_client<C>(
     context: &C,
     chain_state: &ChainState,
@@ -31,6 +36,8 @@ where
 
     context
         .handle_event(Event::CreatedSoloMachineClient {
+            chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             client_id: tendermint_client_id.clone(),
         })
         .await?;
@@ -39,6 +46,8 @@ where
 
     context
         .handle_event(Event::CreatedTendermintClient {
+            chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             client_id: solo_machine_client_id.clone(),
         })
         .await?;
@@ -46,6 +55,10 @@ where
     Ok((solo_machine_client_id, tendermint_client_id))
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 async fn create_solo_machine_client<C>(
     context: &C,
     chain_state: &ChainState,
@@ -61,28 +74,24 @@ where
         transaction_builder::msg_create_solo_machine_client(context, chain_state, memo, request_id)
             .await?;
 
-    let response = context
-        .rpc_client()
-        .broadcast_tx(&chain_state.config.rpc_addr, msg)
-        .await?;
-
-    ensure_response_success(&response)?;
+    let response = broadcast_tx(context, chain_state, msg).await?;
 
     extract_attribute(&response.deliver_tx.events, "create_client", "client_id")?.parse()
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(chain_id = %chain_state.id)))]
 async fn create_tendermint_client<C>(context: &C, chain_state: &ChainState) -> Result<ClientId>
 where
     C: StagContext,
     C::Storage: Transaction,
     C::RpcClient: TendermintClient,
 {
-    let light_client = LightClient::new(
-        chain_state.config.rpc_addr.clone(),
-        context.rpc_client(),
-        chain_state,
-    )
-    .await?;
+    let evidence = context
+        .storage()
+        .get_light_client_attack_evidence(&chain_state.id)
+        .await?;
+    let light_client = LightClient::new(context.rpc_client(), chain_state, evidence).await?;
+    let started_at = now_utc();
 
     let (client_state, consensus_state) =
         transaction_builder::msg_create_tendermint_client(chain_state, &light_client).await?;
@@ -93,6 +102,14 @@ where
         .as_ref()
         .context("latest height cannot be absent in client state")?;
 
+    context
+        .handle_event(Event::LightClientVerified {
+            chain_id: chain_state.id.clone(),
+            height: latest_height.revision_height,
+            duration_ms: (now_utc() - started_at).num_milliseconds().max(0) as u64,
+        })
+        .await?;
+
     context
         .storage()
         .add_tendermint_client_state(&client_id, &client_state)
@@ -1,10 +1,19 @@
-use anyhow::{anyhow, ensure, Result};
+use anyhow::{anyhow, ensure, Error, Result};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
 use tendermint::abci::{
     tag::{Key, Tag},
     Event as AbciEvent,
 };
 use tendermint_rpc::endpoint::broadcast::tx_commit::Response as TxCommitResponse;
 
+use crate::{
+    event::{Event, EventHandler},
+    stag::StagContext,
+    storage::Storage,
+    tendermint::{LightClientAttackEvidence, TendermintClient},
+    types::{chain_state::ChainState, ics::core::ics24_host::identifier::ChainId},
+};
+
 pub fn extract_attribute(events: &[AbciEvent], event_type: &str, key: &str) -> Result<String> {
     let mut attribute = None;
 
@@ -54,6 +63,79 @@ pub fn ensure_response_success(response: &TxCommitResponse) -> Result<String> {
     Ok(response.hash.to_string())
 }
 
+/// Broadcasts given transaction to chain and ensures that it was successful (emits `BroadcastFailed` event on failure)
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(chain_id = %chain_state.id)))]
+pub async fn broadcast_tx<C>(
+    context: &C,
+    chain_state: &ChainState,
+    msg: TxRaw,
+) -> Result<TxCommitResponse>
+where
+    C: StagContext,
+    C::RpcClient: TendermintClient,
+{
+    let result = context
+        .rpc_client()
+        .broadcast_tx_to_chain(&chain_state.config, msg)
+        .await
+        .and_then(|response| ensure_response_success(&response).map(|_| response));
+
+    if let Err(ref err) = result {
+        context
+            .handle_event(Event::BroadcastFailed {
+                chain_id: chain_state.id.clone(),
+                error: format!("{:#}", err),
+            })
+            .await?;
+    }
+
+    result
+}
+
+/// Records evidence of light client attack carried by given error (if any) in storage and emits
+/// `LightClientAttackDetected` event. This must be called with a non-transactional context so that the evidence
+/// survives the rollback of failed operation.
+pub async fn record_light_client_attack<C>(
+    context: &C,
+    chain_id: &ChainId,
+    err: &Error,
+) -> Result<()>
+where
+    C: StagContext,
+    C::Storage: Storage,
+{
+    let evidence = match err.downcast_ref::<LightClientAttackEvidence>() {
+        Some(evidence) => evidence,
+        None => return Ok(()),
+    };
+
+    if context
+        .storage()
+        .get_light_client_attack_evidence(chain_id)
+        .await?
+        .is_some()
+    {
+        // Light client refused to verify because of the evidence which is already recorded
+        return Ok(());
+    }
+
+    context
+        .storage()
+        .add_light_client_attack_evidence(chain_id, evidence)
+        .await?;
+
+    context
+        .handle_event(Event::LightClientAttackDetected {
+            chain_id: chain_id.clone(),
+            height: evidence.height,
+            primary: evidence.primary.to_string(),
+            primary_header_hash: evidence.primary_header_hash.to_string(),
+            witness: evidence.witness.clone(),
+            witness_header_hash: evidence.witness_header_hash.to_string(),
+        })
+        .await
+}
+
 pub fn get_packet_acknowledgement(events: &[AbciEvent]) -> Result<serde_json::Value> {
     let acknowledgement = extract_attribute(events, "write_acknowledgement", "packet_ack")?;
     let acknowledgement: serde_json::Value = serde_json::from_str(&acknowledgement)?;
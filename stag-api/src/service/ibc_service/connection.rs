@@ -9,7 +9,7 @@ use cosmos_sdk_proto::ibc::core::{
 
 use crate::{
     event::{Event, EventHandler},
-    service::ibc_service::common::{ensure_response_success, extract_attribute},
+    service::ibc_service::common::{broadcast_tx, extract_attribute},
     signer::Signer,
     stag::StagContext,
     storage::{Storage, Transaction},
@@ -21,6 +21,10 @@ use crate::{
     },
 };
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn establish_connection<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -47,6 +51,8 @@ where
 
     context
         .handle_event(Event::InitializedConnectionOnTendermint {
+            chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             connection_id: tendermint_connection_id.clone(),
         })
         .await?;
@@ -61,6 +67,8 @@ where
 
     context
         .handle_event(Event::InitializedConnectionOnSoloMachine {
+            chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             connection_id: solo_machine_connection_id.clone(),
         })
         .await?;
@@ -78,6 +86,8 @@ where
 
     context
         .handle_event(Event::ConfirmedConnectionOnTendermint {
+            chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             connection_id: tendermint_connection_id.clone(),
         })
         .await?;
@@ -86,6 +96,8 @@ where
 
     context
         .handle_event(Event::ConfirmedConnectionOnSoloMachine {
+            chain_id: chain_state.id.clone(),
+            request_id: request_id.map(ToOwned::to_owned),
             connection_id: solo_machine_connection_id.clone(),
         })
         .await?;
@@ -93,6 +105,10 @@ where
     Ok((solo_machine_connection_id, tendermint_connection_id))
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 async fn connection_open_init<C>(
     context: &C,
     chain_state: &ChainState,
@@ -116,12 +132,7 @@ where
     )
     .await?;
 
-    let response = context
-        .rpc_client()
-        .broadcast_tx(&chain_state.config.rpc_addr, msg)
-        .await?;
-
-    ensure_response_success(&response)?;
+    let response = broadcast_tx(context, chain_state, msg).await?;
 
     extract_attribute(
         &response.deliver_tx.events,
@@ -131,6 +142,7 @@ where
     .parse()
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
 async fn connection_open_try<C>(
     context: &C,
     tendermint_client_id: &ClientId,
@@ -168,6 +180,10 @@ where
     Ok(connection_id)
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 async fn connection_open_ack<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -194,16 +210,12 @@ where
     )
     .await?;
 
-    let response = context
-        .rpc_client()
-        .broadcast_tx(&chain_state.config.rpc_addr, msg)
-        .await?;
-
-    ensure_response_success(&response)?;
+    broadcast_tx(context, chain_state, msg).await?;
 
     Ok(())
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
 async fn connection_open_confirm<C>(context: &C, connection_id: &ConnectionId) -> Result<()>
 where
     C: StagContext,
@@ -15,10 +15,15 @@ use crate::{
 use super::{
     channel::{self, ica, transfer},
     client::create_client,
+    common::record_light_client_attack,
     connection::establish_connection,
 };
 
 /// Creates IBC client and connection with an IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_id, request_id = request_id.as_deref()))
+)]
 pub async fn connect<C>(
     context: &C,
     chain_id: ChainId,
@@ -32,9 +37,9 @@ where
     C::Storage: TransactionProvider,
     C::RpcClient: TendermintClient,
 {
-    let context = context.with_transaction().await?;
+    let transaction_context = context.with_transaction().await?;
 
-    let mut chain_state = context
+    let mut chain_state = transaction_context
         .storage()
         .get_chain_state(&chain_id)
         .await?
@@ -43,18 +48,48 @@ where
     if !chain_state.is_connected() || force {
         chain_state.sequence = 1;
 
-        let (solo_machine_client_id, tendermint_client_id) =
-            create_client(&context, &chain_state, request_id.as_deref(), memo.clone()).await?;
-
-        let (solo_machine_connection_id, tendermint_connection_id) = establish_connection(
-            &context,
-            &mut chain_state,
-            request_id.as_deref(),
-            memo.clone(),
-            &solo_machine_client_id,
-            &tendermint_client_id,
-        )
-        .await?;
+        let result = async {
+            let (solo_machine_client_id, tendermint_client_id) = create_client(
+                &transaction_context,
+                &chain_state,
+                request_id.as_deref(),
+                memo.clone(),
+            )
+            .await?;
+
+            let (solo_machine_connection_id, tendermint_connection_id) = establish_connection(
+                &transaction_context,
+                &mut chain_state,
+                request_id.as_deref(),
+                memo.clone(),
+                &solo_machine_client_id,
+                &tendermint_client_id,
+            )
+            .await?;
+
+            Ok::<_, anyhow::Error>((
+                solo_machine_client_id,
+                tendermint_client_id,
+                solo_machine_connection_id,
+                tendermint_connection_id,
+            ))
+        }
+        .await;
+
+        let (
+            solo_machine_client_id,
+            tendermint_client_id,
+            solo_machine_connection_id,
+            tendermint_connection_id,
+        ) = match result {
+            Ok(ids) => ids,
+            Err(err) => {
+                // Evidence is recorded outside of the transaction (which is rolled back on drop)
+                drop(transaction_context);
+                record_light_client_attack(context, &chain_id, &err).await?;
+                return Err(err);
+            }
+        };
 
         let connection_details = ConnectionDetails {
             solo_machine_client_id,
@@ -66,14 +101,18 @@ where
 
         chain_state.connection_details = Some(connection_details);
 
-        context.storage().update_chain_state(&chain_state).await?;
+        transaction_context
+            .storage()
+            .update_chain_state(&chain_state)
+            .await?;
 
-        let (_, transaction, _, event_handler) = context.unwrap();
+        let (_, transaction, _, event_handler) = transaction_context.unwrap();
         transaction.done().await?;
 
         event_handler
             .handle_event(Event::ConnectionEstablished {
                 chain_id,
+                request_id,
                 connection_details: chain_state.connection_details.as_ref().unwrap().clone(),
             })
             .await
@@ -83,6 +122,10 @@ where
 }
 
 /// Creates IBC transfer channel with an IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_id, request_id = request_id.as_deref()))
+)]
 pub async fn create_transfer_channel<C>(
     context: &C,
     chain_id: ChainId,
@@ -141,12 +184,17 @@ where
     event_handler
         .handle_event(Event::ChannelCreated {
             chain_id,
+            request_id,
             channel_details,
         })
         .await
 }
 
 /// Creates ICA (Interchain Accounts) channel with an IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_id, request_id = request_id.as_deref()))
+)]
 pub async fn create_ica_channel<C>(
     context: &C,
     chain_id: ChainId,
@@ -205,11 +253,16 @@ where
     event_handler
         .handle_event(Event::ChannelCreated {
             chain_id,
+            request_id,
             channel_details,
         })
         .await
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_id, request_id = request_id.as_deref()))
+)]
 pub async fn close_channel<C>(
     context: &C,
     chain_id: ChainId,
@@ -259,6 +312,7 @@ where
     event_handler
         .handle_event(Event::ChannelClosed {
             chain_id,
+            request_id,
             channel_details,
         })
         .await
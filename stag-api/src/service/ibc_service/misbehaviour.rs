@@ -0,0 +1,361 @@
+use anyhow::{anyhow, Result};
+use cosmos_sdk_proto::ibc::lightclients::solomachine::v2::{
+    Misbehaviour, SignBytes, SignatureAndData,
+};
+
+use crate::{
+    event::{Event, EventHandler},
+    signer::Signer,
+    stag::StagContext,
+    storage::Storage,
+    tendermint::TendermintClient,
+    transaction_builder::{self, is_conflicting},
+    types::{
+        chain_state::ChainState,
+        ics::core::ics24_host::identifier::{ChainId, ClientId},
+    },
+};
+
+use super::common::broadcast_tx;
+
+/// Checks given signed bytes against signature history of solo machine and returns misbehaviour (which can be
+/// submitted to IBC enabled chain to freeze solo machine client) if data conflicting with them was already signed at
+/// the same sequence
+pub async fn detect_misbehaviour<C>(
+    context: &C,
+    chain_id: &ChainId,
+    sign_bytes: SignBytes,
+    signature: Vec<u8>,
+) -> Result<Option<Misbehaviour>>
+where
+    C: StagContext,
+    C::Storage: Storage,
+{
+    let chain_state = get_chain_state(context, chain_id).await?;
+
+    let recorded = match context
+        .storage()
+        .get_signature_and_data(chain_id, sign_bytes.sequence)
+        .await?
+    {
+        Some(recorded) if is_conflicting(&recorded, &sign_bytes) => recorded,
+        _ => return Ok(None),
+    };
+
+    let sequence = sign_bytes.sequence;
+    let signature_and_data = SignatureAndData {
+        signature,
+        data_type: sign_bytes.data_type,
+        data: sign_bytes.data,
+        timestamp: sign_bytes.timestamp,
+    };
+
+    let misbehaviour =
+        transaction_builder::misbehaviour(&chain_state, sequence, recorded, signature_and_data)?;
+
+    context
+        .handle_event(Event::MisbehaviourDetected {
+            chain_id: chain_id.clone(),
+            client_id: misbehaviour.client_id.parse()?,
+            sequence,
+        })
+        .await?;
+
+    Ok(Some(misbehaviour))
+}
+
+/// Submits given misbehaviour to IBC enabled chain (which freezes solo machine client) and returns the transaction
+/// hash
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_id, request_id = request_id.as_deref()))
+)]
+pub async fn submit_misbehaviour<C>(
+    context: &C,
+    chain_id: ChainId,
+    request_id: Option<String>,
+    misbehaviour: Misbehaviour,
+    memo: String,
+) -> Result<String>
+where
+    C: StagContext,
+    C::Signer: Signer,
+    C::Storage: Storage,
+    C::RpcClient: TendermintClient,
+{
+    let chain_state = get_chain_state(context, &chain_id).await?;
+    let client_id: ClientId = misbehaviour.client_id.parse()?;
+
+    let msg = transaction_builder::msg_submit_misbehaviour(
+        context,
+        &chain_state,
+        misbehaviour,
+        memo,
+        request_id.as_deref(),
+    )
+    .await?;
+
+    let response = broadcast_tx(context, &chain_state, msg).await?;
+    let transaction_hash = response.hash.to_string();
+
+    context
+        .handle_event(Event::SoloMachineClientFrozen {
+            chain_id,
+            request_id,
+            client_id,
+            transaction_hash: transaction_hash.clone(),
+        })
+        .await?;
+
+    Ok(transaction_hash)
+}
+
+/// Freezes solo machine client on IBC enabled chain by submitting misbehaviour signed by current signer (should only
+/// be used in emergencies, e.g., when signer's key is compromised, as a frozen client cannot be used anymore) and
+/// returns the transaction hash
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_id, request_id = request_id.as_deref()))
+)]
+pub async fn freeze_client<C>(
+    context: &C,
+    chain_id: ChainId,
+    request_id: Option<String>,
+    memo: String,
+) -> Result<String>
+where
+    C: StagContext,
+    C::Signer: Signer,
+    C::Storage: Storage,
+    C::RpcClient: TendermintClient,
+{
+    let chain_state = get_chain_state(context, &chain_id).await?;
+
+    let misbehaviour =
+        transaction_builder::freeze_misbehaviour(context, &chain_state, request_id.as_deref())
+            .await?;
+
+    submit_misbehaviour(context, chain_id, request_id, misbehaviour, memo).await
+}
+
+async fn get_chain_state<C>(context: &C, chain_id: &ChainId) -> Result<ChainState>
+where
+    C: StagContext,
+    C::Storage: Storage,
+{
+    context
+        .storage()
+        .get_chain_state(chain_id)
+        .await?
+        .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))
+}
+
+#[cfg(all(test, feature = "mnemonic-signer", feature = "sqlite-storage"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use anyhow::anyhow;
+    use cosmos_sdk_proto::{
+        cosmos::tx::v1beta1::{TxBody, TxRaw},
+        ibc::{core::client::v1::MsgSubmitMisbehaviour, lightclients::solomachine::v2::DataType},
+    };
+    use prost::Message;
+    use serde_json::json;
+    use tonic::transport::Server;
+
+    use crate::{
+        tendermint::MockRpcClient,
+        test_util::{add_connected_chain, auth_service, serve, test_context, CHAIN_ID},
+        transaction_builder::signing::sign,
+        types::{chain_state::ChainConfig, proto_util::AnyConvert},
+    };
+
+    use super::*;
+
+    fn sign_bytes(sequence: u64, data: &[u8]) -> SignBytes {
+        SignBytes {
+            sequence,
+            timestamp: 100,
+            diversifier: "stag".to_owned(),
+            data_type: DataType::PacketCommitment.into(),
+            data: data.to_vec(),
+        }
+    }
+
+    /// Returns a mock RPC client which commits all the broadcasted transactions (when `success` is true) and records
+    /// them
+    fn broadcast_client(success: bool) -> (MockRpcClient, Arc<Mutex<Vec<TxRaw>>>) {
+        let transactions: Arc<Mutex<Vec<TxRaw>>> = Default::default();
+        let recorded = transactions.clone();
+
+        let client = MockRpcClient::new(move |_, method, params| match method {
+            "broadcast_tx_commit" => {
+                let tx = base64::decode(params["tx"].as_str().unwrap()).unwrap();
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push(TxRaw::decode(tx.as_slice()).unwrap());
+
+                Ok(json!({
+                    "check_tx": {},
+                    "deliver_tx": if success { json!({}) } else { json!({ "code": 5, "log": "insufficient funds" }) },
+                    "hash": "0000000000000000000000000000000000000000000000000000000000000000",
+                    "height": "10",
+                }))
+            }
+            _ => Err(anyhow!("unexpected request: {}", method)),
+        });
+
+        (client, transactions)
+    }
+
+    #[tokio::test]
+    async fn test_detect_misbehaviour() {
+        let (context, event_handler) = test_context(broadcast_client(true).0).await;
+        let chain_id: ChainId = CHAIN_ID.parse().unwrap();
+        add_connected_chain(
+            &context,
+            ChainConfig::for_test("http://0.0.0.0:26657", "http://0.0.0.0:9090"),
+        )
+        .await;
+
+        // Nothing was signed at this sequence yet
+        assert!(
+            detect_misbehaviour(&context, &chain_id, sign_bytes(1, b"data"), b"sig".to_vec())
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let signature = sign(&context, None, &chain_id, sign_bytes(1, b"data"), None)
+            .await
+            .unwrap();
+
+        // Same data as the signed one is not a misbehaviour
+        assert!(detect_misbehaviour(
+            &context,
+            &chain_id,
+            sign_bytes(1, b"data"),
+            signature.clone()
+        )
+        .await
+        .unwrap()
+        .is_none());
+        assert!(event_handler.events().is_empty());
+
+        let misbehaviour = detect_misbehaviour(
+            &context,
+            &chain_id,
+            sign_bytes(1, b"other data"),
+            b"other signature".to_vec(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(misbehaviour.client_id, "06-solomachine-0");
+        assert_eq!(misbehaviour.sequence, 1);
+
+        let signature_one = misbehaviour.signature_one.unwrap();
+        assert_eq!(signature_one.signature, signature);
+        assert_eq!(signature_one.data, b"data");
+
+        let signature_two = misbehaviour.signature_two.unwrap();
+        assert_eq!(signature_two.signature, b"other signature");
+        assert_eq!(signature_two.data, b"other data");
+
+        let events = event_handler.events();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            Event::MisbehaviourDetected { chain_id, client_id, sequence: 1 }
+                if chain_id.to_string() == CHAIN_ID && client_id.to_string() == "06-solomachine-0"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_freeze_client() {
+        let (rpc_client, transactions) = broadcast_client(true);
+        let (context, event_handler) = test_context(rpc_client).await;
+        let chain_id: ChainId = CHAIN_ID.parse().unwrap();
+        let chain_state = add_connected_chain(
+            &context,
+            ChainConfig::for_test(
+                "http://0.0.0.0:26657",
+                serve(Server::builder().add_service(auth_service(1, 0)))
+                    .await
+                    .as_str(),
+            ),
+        )
+        .await;
+
+        let hash = freeze_client(
+            &context,
+            chain_id.clone(),
+            Some("freeze".to_owned()),
+            "".to_owned(),
+        )
+        .await
+        .unwrap();
+
+        let transactions = transactions.lock().unwrap().clone();
+        assert_eq!(transactions.len(), 1);
+
+        let body = TxBody::decode(transactions[0].body_bytes.as_slice()).unwrap();
+        assert_eq!(body.messages.len(), 1);
+
+        let message = MsgSubmitMisbehaviour::from_any(&body.messages[0]).unwrap();
+        assert_eq!(message.client_id, "06-solomachine-0");
+
+        let misbehaviour = Misbehaviour::from_any(&message.misbehaviour.unwrap()).unwrap();
+        assert_eq!(misbehaviour.sequence, u64::from(chain_state.sequence));
+
+        let signature_one = misbehaviour.signature_one.unwrap();
+        let signature_two = misbehaviour.signature_two.unwrap();
+        assert_eq!(signature_one.data_type, signature_two.data_type);
+        assert_ne!(signature_one.data, signature_two.data);
+        assert_ne!(signature_one.signature, signature_two.signature);
+
+        // Signatures used for freezing the client must not be recorded in signature history
+        assert!(context
+            .storage()
+            .get_signature_and_data(&chain_id, chain_state.sequence.into())
+            .await
+            .unwrap()
+            .is_none());
+
+        let events = event_handler.events();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            Event::SoloMachineClientFrozen { request_id, client_id, transaction_hash, .. }
+                if request_id.as_deref() == Some("freeze")
+                    && client_id.to_string() == "06-solomachine-0"
+                    && transaction_hash == &hash
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_freeze_client_with_failed_broadcast() {
+        let (context, event_handler) = test_context(broadcast_client(false).0).await;
+        let chain_id: ChainId = CHAIN_ID.parse().unwrap();
+        add_connected_chain(
+            &context,
+            ChainConfig::for_test(
+                "http://0.0.0.0:26657",
+                serve(Server::builder().add_service(auth_service(1, 0)))
+                    .await
+                    .as_str(),
+            ),
+        )
+        .await;
+
+        assert!(freeze_client(&context, chain_id, None, "".to_owned())
+            .await
+            .is_err());
+
+        let events = event_handler.events();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], Event::BroadcastFailed { .. }));
+    }
+}
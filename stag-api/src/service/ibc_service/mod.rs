@@ -3,11 +3,15 @@ mod client;
 mod common;
 mod connection;
 mod handshake;
+mod misbehaviour;
 mod packet;
+mod rotate_key;
 mod update_signer;
 
 pub use self::{
     channel::{ica, transfer},
     handshake::{close_channel, connect, create_ica_channel, create_transfer_channel},
+    misbehaviour::{detect_misbehaviour, freeze_client, submit_misbehaviour},
+    rotate_key::{rotate_key, ChainKeyRotation, KeyRotationReport, KeyRotationStatus},
     update_signer::update_signer,
 };
@@ -5,6 +5,7 @@ use cosmos_sdk_proto::ibc::core::{channel::v1::Packet, client::v1::Height};
 use tendermint_rpc::endpoint::broadcast::tx_commit::Response as TxCommitResponse;
 
 use crate::{
+    event::{Event, EventHandler},
     service::ibc_service::common::ensure_response_success,
     signer::Signer,
     stag::StagContext,
@@ -13,7 +14,10 @@ use crate::{
     transaction_builder,
     types::{
         chain_state::ChainState,
-        ics::core::{ics02_client::height::IHeight, ics24_host::identifier::PortId},
+        ics::core::{
+            ics02_client::height::IHeight,
+            ics24_host::identifier::{ChainId, PortId},
+        },
     },
 };
 
@@ -70,6 +74,13 @@ pub fn extract_packets(response: &TxCommitResponse) -> Result<Vec<Packet>> {
     Ok(packets)
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        skip_all,
+        fields(chain_id = %chain_state.id, request_id = request_id.as_deref())
+    )
+)]
 pub async fn process_packets<C>(
     context: &C,
     chain_state: &ChainState,
@@ -102,6 +113,9 @@ where
     let solo_machine_port_id = channel_details.solo_machine_port_id.clone();
     let tendermint_port_id = channel_details.tendermint_port_id.clone();
 
+    let mut pending_packets = packets.len() as u64;
+    update_packet_backlog(context, &chain_state.id, port_id, pending_packets).await?;
+
     for packet in packets {
         let mut chain_state = context
             .storage()
@@ -143,13 +157,34 @@ where
 
         let response = context
             .rpc_client()
-            .broadcast_tx(&chain_state.config.rpc_addr, msg)
+            .broadcast_tx_to_chain(&chain_state.config, msg)
             .await?;
 
         context.storage().update_chain_state(&chain_state).await?;
 
         ensure_response_success(&response)?;
+
+        pending_packets -= 1;
+        update_packet_backlog(context, &chain_state.id, port_id, pending_packets).await?;
     }
 
     Ok(())
 }
+
+async fn update_packet_backlog<C>(
+    context: &C,
+    chain_id: &ChainId,
+    port_id: &PortId,
+    pending_packets: u64,
+) -> Result<()>
+where
+    C: StagContext,
+{
+    context
+        .handle_event(Event::PacketBacklogUpdated {
+            chain_id: chain_id.clone(),
+            port_id: port_id.clone(),
+            pending_packets,
+        })
+        .await
+}
@@ -0,0 +1,668 @@
+use anyhow::{anyhow, ensure, Context, Result};
+use cosmos_sdk_proto::ibc::{
+    core::client::v1::{query_client::QueryClient as ClientQueryClient, QueryClientStateRequest},
+    lightclients::solomachine::v2::ClientState as SoloMachineClientState,
+};
+#[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+use tonic::transport::Channel;
+#[cfg(feature = "wasm")]
+use tonic_web_wasm_client::Client;
+use url::Url;
+
+use crate::{
+    endpoint_util::with_failover,
+    event::{Event, EventHandler},
+    signer::{GetPublicKey, Signer},
+    stag::{StagContext, WithTransaction},
+    storage::{Storage, Transaction, TransactionProvider},
+    tendermint::TendermintClient,
+    types::{
+        chain_state::ChainState, ics::core::ics24_host::identifier::ChainId,
+        proto_util::AnyConvert, public_key::PublicKey,
+    },
+};
+
+use super::update_signer::update_signer;
+
+/// Report of a key rotation across all the connected chains
+#[derive(Debug, Clone, Default)]
+pub struct KeyRotationReport {
+    /// Key rotation result for each connected chain
+    pub chains: Vec<ChainKeyRotation>,
+}
+
+impl KeyRotationReport {
+    /// Returns `true` if the key was rotated and verified on all the connected chains
+    pub fn is_complete(&self) -> bool {
+        self.chains
+            .iter()
+            .all(|chain| matches!(chain.status, KeyRotationStatus::Rotated))
+    }
+
+    /// Returns `true` if signer of given chain must be switched to new key (i.e., solo machine client was updated with
+    /// new public key or the chain is not connected)
+    pub fn should_switch(&self, chain_id: &ChainId) -> bool {
+        !self
+            .chains
+            .iter()
+            .any(|chain| &chain.chain_id == chain_id && !chain.status.is_updated())
+    }
+}
+
+/// Key rotation result of a single chain
+#[derive(Debug, Clone)]
+pub struct ChainKeyRotation {
+    /// Chain ID of IBC enabled chain
+    pub chain_id: ChainId,
+    /// New public key of signer (`None` if new signer was unable to provide a public key for chain)
+    pub new_public_key: Option<PublicKey>,
+    /// Status of key rotation
+    pub status: KeyRotationStatus,
+}
+
+/// Status of key rotation on a single chain
+#[derive(Debug, Clone)]
+pub enum KeyRotationStatus {
+    /// Solo machine client was updated and the chain reports new public key
+    Rotated,
+    /// Solo machine client was updated but the chain does not report new public key (contains verification error)
+    Unverified(String),
+    /// Solo machine client was not updated (contains error)
+    Failed(String),
+}
+
+impl KeyRotationStatus {
+    /// Returns `true` if solo machine client was updated with new public key
+    pub fn is_updated(&self) -> bool {
+        !matches!(self, Self::Failed(_))
+    }
+}
+
+/// Rotates signer's public key on all the connected IBC enabled chains and verifies that the chains report the new
+/// public key (failure on one chain does not stop rotation on other chains)
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(request_id = request_id.as_deref()))
+)]
+pub async fn rotate_key<C, S>(
+    context: &C,
+    new_signer: &S,
+    request_id: Option<String>,
+    memo: String,
+) -> Result<KeyRotationReport>
+where
+    C: StagContext + WithTransaction,
+    C::Signer: Signer,
+    C::Storage: TransactionProvider,
+    C::RpcClient: TendermintClient,
+    S: GetPublicKey,
+{
+    let chain_states = context.storage().get_all_chain_states(None, None).await?;
+
+    let mut report = KeyRotationReport::default();
+
+    for chain_state in chain_states {
+        if chain_state.connection_details.is_none() {
+            continue;
+        }
+
+        let chain_id = chain_state.id;
+
+        let new_public_key = match new_signer.get_public_key(&chain_id).await {
+            Ok(new_public_key) => new_public_key,
+            Err(err) => {
+                report.chains.push(ChainKeyRotation {
+                    chain_id,
+                    new_public_key: None,
+                    status: KeyRotationStatus::Failed(format!(
+                        "failed to get new public key: {:?}",
+                        err
+                    )),
+                });
+                continue;
+            }
+        };
+
+        let status = match update_signer(
+            context,
+            chain_id.clone(),
+            request_id.clone(),
+            new_public_key.clone(),
+            memo.clone(),
+        )
+        .await
+        {
+            Ok(()) => match verify_public_key(context, &chain_id, &new_public_key).await {
+                Ok(()) => KeyRotationStatus::Rotated,
+                Err(err) => KeyRotationStatus::Unverified(format!("{:?}", err)),
+            },
+            // Outcome of broadcast may be unknown (e.g., the transaction was committed after the node timed out), so
+            // the chain is asked for its public key before reporting a failure
+            Err(err) => match recover_signer_update(context, &chain_id, &new_public_key).await {
+                Ok(true) => KeyRotationStatus::Rotated,
+                Ok(false) => KeyRotationStatus::Failed(format!("{:?}", err)),
+                Err(recovery_err) => KeyRotationStatus::Failed(format!(
+                    "{:?} (failed to check public key on chain: {:?})",
+                    err, recovery_err
+                )),
+            },
+        };
+
+        report.chains.push(ChainKeyRotation {
+            chain_id,
+            new_public_key: Some(new_public_key),
+            status,
+        });
+    }
+
+    Ok(report)
+}
+
+/// Records the update of signer when solo machine client on IBC enabled chain already reports given public key even
+/// though `update_signer` failed (sequence of solo machine is resynced with the chain). Returns `false` if the chain
+/// does not report given public key.
+async fn recover_signer_update<C>(
+    context: &C,
+    chain_id: &ChainId,
+    new_public_key: &PublicKey,
+) -> Result<bool>
+where
+    C: StagContext + WithTransaction,
+    C::Signer: Signer,
+    C::Storage: TransactionProvider,
+{
+    let context = context.with_transaction().await?;
+
+    let mut chain_state = context
+        .storage()
+        .get_chain_state(chain_id)
+        .await?
+        .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+    let client_state = query_client_state(&chain_state).await?;
+
+    if get_public_key(&client_state)?.to_string() != new_public_key.to_string() {
+        return Ok(false);
+    }
+
+    chain_state.sequence = client_state
+        .sequence
+        .try_into()
+        .context("invalid sequence of solo machine client")?;
+
+    context
+        .storage()
+        .add_chain_key(chain_id, &new_public_key.to_string())
+        .await?;
+    context.storage().update_chain_state(&chain_state).await?;
+
+    let (signer, transaction, _, event_handler) = context.unwrap();
+    transaction.done().await?;
+
+    event_handler
+        .handle_event(Event::SignerUpdated {
+            chain_id: chain_id.clone(),
+            old_public_key: signer.get_public_key(chain_id).await?,
+            new_public_key: new_public_key.clone(),
+        })
+        .await?;
+
+    Ok(true)
+}
+
+/// Verifies that the solo machine client on IBC enabled chain reports given public key
+async fn verify_public_key<C>(
+    context: &C,
+    chain_id: &ChainId,
+    expected_public_key: &PublicKey,
+) -> Result<()>
+where
+    C: StagContext,
+    C::Storage: Storage,
+{
+    let chain_state = context
+        .storage()
+        .get_chain_state(chain_id)
+        .await?
+        .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+    let public_key = get_public_key(&query_client_state(&chain_state).await?)?;
+
+    ensure!(
+        public_key.to_string() == expected_public_key.to_string(),
+        "public key reported by chain {} ({}) does not match new public key ({})",
+        chain_id,
+        public_key,
+        expected_public_key
+    );
+
+    Ok(())
+}
+
+/// Queries the state of solo machine client from IBC enabled chain
+async fn query_client_state(chain_state: &ChainState) -> Result<SoloMachineClientState> {
+    let connection_details = chain_state.connection_details.as_ref().ok_or_else(|| {
+        anyhow!(
+            "connection details not found for chain with id {}",
+            chain_state.id
+        )
+    })?;
+
+    let request = QueryClientStateRequest {
+        client_id: connection_details.tendermint_client_id.to_string(),
+    };
+
+    let any_client_state = with_failover(
+        chain_state.config.grpc_addrs(),
+        &chain_state.config.retry_policy,
+        |grpc_addr| {
+            let request = request.clone();
+
+            async move {
+                let mut query_client = get_client_query_client(grpc_addr.clone()).await?;
+                Ok(query_client.client_state(request).await?.into_inner())
+            }
+        },
+    )
+    .await?
+    .client_state
+    .ok_or_else(|| {
+        anyhow!(
+            "solo machine client state not found with id {}",
+            connection_details.tendermint_client_id
+        )
+    })?;
+
+    SoloMachineClientState::from_any(&any_client_state)
+        .context("failed to decode solo machine client state")
+}
+
+/// Returns the public key of solo machine client's consensus state
+fn get_public_key(client_state: &SoloMachineClientState) -> Result<PublicKey> {
+    let any_public_key = client_state
+        .consensus_state
+        .as_ref()
+        .and_then(|consensus_state| consensus_state.public_key.as_ref())
+        .ok_or_else(|| anyhow!("public key not found in solo machine client state"))?;
+
+    PublicKey::from_any(any_public_key)
+}
+
+#[cfg(feature = "wasm")]
+async fn get_client_query_client(grpc_addr: Url) -> Result<ClientQueryClient<Client>> {
+    let mut url = grpc_addr.to_string();
+
+    if url.ends_with('/') {
+        url.pop();
+    }
+
+    let grpc_client = Client::new(url);
+    Ok(ClientQueryClient::new(grpc_client))
+}
+
+#[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+async fn get_client_query_client(grpc_addr: Url) -> Result<ClientQueryClient<Channel>> {
+    ClientQueryClient::connect(grpc_addr.to_string())
+        .await
+        .context("error when initializing grpc client")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{convert::Infallible, time::Duration};
+
+    use anyhow::anyhow;
+    use cosmos_sdk_proto::ibc::{
+        core::client::v1::{QueryClientStateRequest, QueryClientStateResponse},
+        lightclients::solomachine::v2::ConsensusState as SoloMachineConsensusState,
+    };
+    use prost_types::Any;
+    use serde_json::json;
+    use tonic::{
+        body::BoxBody,
+        codec::ProstCodec,
+        codegen::{empty_body, http, BoxFuture, Context, Poll, Service},
+        server::{Grpc, NamedService, UnaryService},
+        transport::{Body, Server},
+        Request, Response, Status,
+    };
+
+    use crate::{
+        event::RecordingEventHandler,
+        signer::{MnemonicSigner, SignerConfig},
+        tendermint::MockRpcClient,
+        test_util::{
+            add_connected_chain, auth_service, serve, test_context, TestContext, CHAIN_ID, MNEMONIC,
+        },
+        types::chain_state::ChainConfig,
+    };
+
+    use super::*;
+
+    /// IBC client query service which returns given client state for all the client IDs (`cosmos-sdk-proto` does not
+    /// generate server side of IBC query services, so only `ClientState` method is implemented by hand)
+    #[derive(Clone)]
+    struct MockClientQueryService {
+        client_state: Any,
+    }
+
+    impl NamedService for MockClientQueryService {
+        const NAME: &'static str = "ibc.core.client.v1.Query";
+    }
+
+    impl UnaryService<QueryClientStateRequest> for MockClientQueryService {
+        type Response = QueryClientStateResponse;
+        type Future = BoxFuture<Response<Self::Response>, Status>;
+
+        fn call(&mut self, _: Request<QueryClientStateRequest>) -> Self::Future {
+            let response = QueryClientStateResponse {
+                client_state: Some(self.client_state.clone()),
+                proof: Vec::new(),
+                proof_height: None,
+            };
+
+            Box::pin(async move { Ok(Response::new(response)) })
+        }
+    }
+
+    impl Service<http::Request<Body>> for MockClientQueryService {
+        type Response = http::Response<BoxBody>;
+        type Error = Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+            let service = self.clone();
+
+            Box::pin(async move {
+                if request.uri().path() != "/ibc.core.client.v1.Query/ClientState" {
+                    return Ok(http::Response::builder()
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(empty_body())
+                        .unwrap());
+                }
+
+                Ok(Grpc::new(ProstCodec::default())
+                    .unary(service, request)
+                    .await)
+            })
+        }
+    }
+
+    /// Returns a mock RPC client which commits all the broadcasted transactions (when `committed` is true) or times
+    /// out without committing them
+    fn broadcast_client(committed: bool) -> MockRpcClient {
+        MockRpcClient::new(move |_, method, _| match (method, committed) {
+            ("broadcast_tx_commit", true) => Ok(json!({
+                "check_tx": {},
+                "deliver_tx": {},
+                "hash": "0000000000000000000000000000000000000000000000000000000000000000",
+                "height": "10",
+            })),
+            ("broadcast_tx_commit", false) => {
+                Err(Status::unavailable("timed out waiting for tx").into())
+            }
+            _ => Err(anyhow!("unexpected request: {}", method)),
+        })
+    }
+
+    /// Returns a signer which derives a key with given HD path from the test mnemonic
+    fn signer(hd_path: Option<&str>) -> impl GetPublicKey {
+        let mut signer = MnemonicSigner::new();
+        signer
+            .add_chain_config(CHAIN_ID.parse().unwrap(), MNEMONIC, hd_path, None, None)
+            .unwrap();
+
+        signer.into_signer().unwrap()
+    }
+
+    /// Returns a signer which derives a new key (with a different HD path) from the test mnemonic
+    fn new_signer() -> impl GetPublicKey {
+        signer(Some("m/44'/118'/0'/0/1"))
+    }
+
+    async fn old_public_key() -> PublicKey {
+        signer(None)
+            .get_public_key(&CHAIN_ID.parse().unwrap())
+            .await
+            .unwrap()
+    }
+
+    fn client_state(public_key: &PublicKey, sequence: u64) -> Any {
+        SoloMachineClientState {
+            sequence,
+            is_frozen: false,
+            consensus_state: Some(SoloMachineConsensusState {
+                public_key: Some(public_key.to_any().unwrap()),
+                diversifier: "stag".to_owned(),
+                timestamp: 1,
+            }),
+            allow_update_after_proposal: false,
+        }
+        .to_any()
+        .unwrap()
+    }
+
+    /// Returns a context with a connected chain whose solo machine client reports given public key (new key when
+    /// `None`) at given sequence
+    async fn setup(
+        committed: bool,
+        on_chain_public_key: Option<PublicKey>,
+        on_chain_sequence: u64,
+    ) -> (TestContext, RecordingEventHandler) {
+        let (context, event_handler) = test_context(broadcast_client(committed)).await;
+        let chain_id: ChainId = CHAIN_ID.parse().unwrap();
+
+        let public_key = match on_chain_public_key {
+            Some(public_key) => public_key,
+            None => new_signer().get_public_key(&chain_id).await.unwrap(),
+        };
+
+        let grpc_addr = serve(
+            Server::builder()
+                .add_service(auth_service(1, 0))
+                .add_service(MockClientQueryService {
+                    client_state: client_state(&public_key, on_chain_sequence),
+                }),
+        )
+        .await;
+
+        // Transaction is looked up until RPC timeout elapses after a failed broadcast
+        let mut config = ChainConfig::for_test("http://0.0.0.0:26657", grpc_addr.as_str());
+        config.rpc_timeout = Duration::from_millis(50);
+
+        add_connected_chain(&context, config).await;
+
+        (context, event_handler)
+    }
+
+    async fn sequence(context: &TestContext) -> u32 {
+        context
+            .storage()
+            .get_chain_state(&CHAIN_ID.parse().unwrap())
+            .await
+            .unwrap()
+            .unwrap()
+            .sequence
+    }
+
+    async fn chain_keys(context: &TestContext) -> Vec<String> {
+        context
+            .storage()
+            .get_chain_keys(&CHAIN_ID.parse().unwrap(), None, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|chain_key| chain_key.public_key)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_rotate_key_rotated() {
+        let (context, event_handler) = setup(true, None, 1).await;
+        let new_public_key = new_signer()
+            .get_public_key(&CHAIN_ID.parse().unwrap())
+            .await
+            .unwrap();
+
+        let report = rotate_key(&context, &new_signer(), None, String::new())
+            .await
+            .unwrap();
+
+        assert!(report.is_complete());
+        assert_eq!(report.chains.len(), 1);
+        assert!(matches!(
+            report.chains[0].status,
+            KeyRotationStatus::Rotated
+        ));
+        assert_eq!(
+            report.chains[0]
+                .new_public_key
+                .as_ref()
+                .unwrap()
+                .to_string(),
+            new_public_key.to_string()
+        );
+
+        assert_eq!(sequence(&context).await, 2);
+        assert!(chain_keys(&context)
+            .await
+            .contains(&new_public_key.to_string()));
+        assert!(matches!(
+            event_handler.events().last(),
+            Some(Event::SignerUpdated { new_public_key: key, .. }) if key.to_string() == new_public_key.to_string()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_key_unverified() {
+        // Transaction is committed but the chain still reports the old public key
+        let (context, event_handler) = setup(true, Some(old_public_key().await), 2).await;
+
+        let report = rotate_key(&context, &new_signer(), None, String::new())
+            .await
+            .unwrap();
+
+        assert!(!report.is_complete());
+        assert!(matches!(
+            report.chains[0].status,
+            KeyRotationStatus::Unverified(ref err) if err.contains("does not match new public key")
+        ));
+        assert!(report.should_switch(&CHAIN_ID.parse().unwrap()));
+
+        // Update was recorded after the successful broadcast
+        assert_eq!(sequence(&context).await, 2);
+        assert!(matches!(
+            event_handler.events().last(),
+            Some(Event::SignerUpdated { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_key_failed() {
+        // Transaction is not found on chain after a failed broadcast and the chain reports the old public key
+        let (context, event_handler) = setup(false, Some(old_public_key().await), 1).await;
+        let keys_before = chain_keys(&context).await;
+
+        let report = rotate_key(&context, &new_signer(), None, String::new())
+            .await
+            .unwrap();
+
+        assert!(!report.is_complete());
+        assert!(matches!(
+            report.chains[0].status,
+            KeyRotationStatus::Failed(ref err) if err.contains("was not found on chain")
+        ));
+        assert!(!report.should_switch(&CHAIN_ID.parse().unwrap()));
+
+        // Failed update is rolled back
+        assert_eq!(sequence(&context).await, 1);
+        assert_eq!(chain_keys(&context).await, keys_before);
+        assert!(!event_handler
+            .events()
+            .iter()
+            .any(|event| matches!(event, Event::SignerUpdated { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_key_recovers_failed_broadcast() {
+        // Broadcast failed but the chain reports new public key (i.e., the transaction was committed eventually)
+        let (context, event_handler) = setup(false, None, 5).await;
+        let new_public_key = new_signer()
+            .get_public_key(&CHAIN_ID.parse().unwrap())
+            .await
+            .unwrap();
+
+        let report = rotate_key(&context, &new_signer(), None, String::new())
+            .await
+            .unwrap();
+
+        assert!(report.is_complete());
+        assert!(report.should_switch(&CHAIN_ID.parse().unwrap()));
+
+        // Sequence is resynced with the chain
+        assert_eq!(sequence(&context).await, 5);
+        assert!(chain_keys(&context)
+            .await
+            .contains(&new_public_key.to_string()));
+        assert!(matches!(
+            event_handler.events().last(),
+            Some(Event::SignerUpdated { new_public_key: key, .. }) if key.to_string() == new_public_key.to_string()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_public_key() {
+        let (context, _) = setup(true, None, 0).await;
+        let chain_id: ChainId = CHAIN_ID.parse().unwrap();
+
+        let new_public_key = new_signer().get_public_key(&chain_id).await.unwrap();
+        let old_public_key = old_public_key().await;
+
+        verify_public_key(&context, &chain_id, &new_public_key)
+            .await
+            .unwrap();
+
+        let err = verify_public_key(&context, &chain_id, &old_public_key)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("does not match new public key"));
+
+        assert!(
+            verify_public_key(&context, &"unknown-1".parse().unwrap(), &new_public_key)
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_should_switch() {
+        let chain = |chain_id: &str, status| ChainKeyRotation {
+            chain_id: chain_id.parse().unwrap(),
+            new_public_key: None,
+            status,
+        };
+
+        let report = KeyRotationReport {
+            chains: vec![
+                chain("rotated-1", KeyRotationStatus::Rotated),
+                chain(
+                    "unverified-1",
+                    KeyRotationStatus::Unverified("error".to_owned()),
+                ),
+                chain("failed-1", KeyRotationStatus::Failed("error".to_owned())),
+            ],
+        };
+
+        assert!(report.should_switch(&"rotated-1".parse().unwrap()));
+        assert!(report.should_switch(&"unverified-1".parse().unwrap()));
+        assert!(!report.should_switch(&"failed-1".parse().unwrap()));
+        // Chains which are not connected are switched to new key
+        assert!(report.should_switch(&"unconnected-1".parse().unwrap()));
+        assert!(!report.is_complete());
+    }
+}
@@ -10,9 +10,13 @@ use crate::{
     types::{ics::core::ics24_host::identifier::ChainId, public_key::PublicKey},
 };
 
-use super::common::ensure_response_success;
+use super::common::broadcast_tx;
 
 /// Updates signer's public key on IBC enabled chain for future messages from solo machine
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_id, request_id = request_id.as_deref()))
+)]
 pub async fn update_signer<C>(
     context: &C,
     chain_id: ChainId,
@@ -48,12 +52,7 @@ where
     )
     .await?;
 
-    let response = context
-        .rpc_client()
-        .broadcast_tx(&chain_state.config.rpc_addr, msg)
-        .await?;
-
-    ensure_response_success(&response)?;
+    broadcast_tx(&context, &chain_state, msg).await?;
 
     context.storage().update_chain_state(&chain_state).await?;
 
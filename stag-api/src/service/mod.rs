@@ -1,5 +1,6 @@
 //! Traits for performing different operations related to solo machine.
 mod chain_service;
+mod event_service;
 mod ibc_service;
 
-pub use self::{chain_service::*, ibc_service::*};
+pub use self::{chain_service::*, event_service::*, ibc_service::*};
@@ -0,0 +1,283 @@
+//! Conversion of protobuf `SignDoc` to amino JSON `StdSignDoc` (used by signers which cannot sign in
+//! `SIGN_MODE_DIRECT`, e.g., ledger)
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use cosmos_sdk_proto::{
+    cosmos::{
+        base::v1beta1::Coin,
+        tx::v1beta1::{AuthInfo, SignDoc, TxBody},
+    },
+    ibc::{applications::transfer::v1::MsgTransfer, core::client::v1::Height},
+};
+use prost::Message;
+use prost_types::Any;
+use serde_json::{json, Map, Value};
+
+use crate::types::ics::applications::transfer::msg_transfer;
+
+/// Prefix of type URLs of IBC core messages (client, connection and channel messages)
+const IBC_CORE_TYPE_URL_PREFIX: &str = "/ibc.core.";
+
+/// Converts protobuf encoded `SignDoc` to amino JSON encoded `StdSignDoc` (keys are sorted and there is no whitespace)
+pub fn to_std_sign_doc(sign_doc: &[u8]) -> Result<Vec<u8>> {
+    let sign_doc = SignDoc::decode(sign_doc).context("failed to decode sign doc")?;
+    let tx_body = TxBody::decode(sign_doc.body_bytes.as_slice())
+        .context("failed to decode transaction body")?;
+    let auth_info = AuthInfo::decode(sign_doc.auth_info_bytes.as_slice())
+        .context("failed to decode auth info")?;
+
+    ensure!(
+        auth_info.signer_infos.len() == 1,
+        "amino JSON sign doc can only be created for transactions with exactly one signer"
+    );
+    ensure!(
+        tx_body.extension_options.is_empty() && tx_body.non_critical_extension_options.is_empty(),
+        "amino JSON sign doc cannot be created for transactions with extension options"
+    );
+
+    let fee = auth_info
+        .fee
+        .ok_or_else(|| anyhow!("fee not found in auth info"))?;
+
+    let mut json_fee = Map::new();
+    json_fee.insert("amount".to_owned(), coins(&fee.amount));
+    json_fee.insert("gas".to_owned(), fee.gas_limit.to_string().into());
+    insert_str(&mut json_fee, "payer", &fee.payer);
+    insert_str(&mut json_fee, "granter", &fee.granter);
+
+    let msgs = tx_body
+        .messages
+        .iter()
+        .map(message)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut std_sign_doc = Map::new();
+    std_sign_doc.insert(
+        "account_number".to_owned(),
+        sign_doc.account_number.to_string().into(),
+    );
+    std_sign_doc.insert("chain_id".to_owned(), sign_doc.chain_id.into());
+    std_sign_doc.insert("fee".to_owned(), Value::Object(json_fee));
+    std_sign_doc.insert("memo".to_owned(), tx_body.memo.into());
+    std_sign_doc.insert("msgs".to_owned(), Value::Array(msgs));
+    std_sign_doc.insert(
+        "sequence".to_owned(),
+        auth_info.signer_infos[0].sequence.to_string().into(),
+    );
+    insert_u64(&mut std_sign_doc, "timeout_height", tx_body.timeout_height);
+
+    serde_json::to_vec(&Value::Object(std_sign_doc)).context("failed to serialize sign doc")
+}
+
+/// Converts a message to its amino JSON representation (`{"type": <amino name>, "value": <message>}`)
+///
+/// Only the messages registered with legacy amino codec by ibc-go can be converted. IBC core messages (e.g.,
+/// `MsgUpdateClient`, `MsgRecvPacket`, `MsgAcknowledgement` and the handshake messages) are not registered (their
+/// `GetSignBytes` panics with "IBC messages do not support amino" and cosmos-sdk 0.50 requires an `amino.name` option
+/// for messages packed in `Any`), so a chain can never verify an amino JSON signature over them.
+fn message(any: &Any) -> Result<Value> {
+    let (amino_name, value) = match any.type_url.as_str() {
+        msg_transfer::TYPE_URL => {
+            let msg = MsgTransfer::decode(any.value.as_slice())?;
+
+            let mut value = Map::new();
+            insert_str(&mut value, "source_port", &msg.source_port);
+            insert_str(&mut value, "source_channel", &msg.source_channel);
+            value.insert(
+                "token".to_owned(),
+                msg.token.as_ref().map(coin).unwrap_or_else(|| json!({})),
+            );
+            insert_str(&mut value, "sender", &msg.sender);
+            insert_str(&mut value, "receiver", &msg.receiver);
+            value.insert(
+                "timeout_height".to_owned(),
+                height(msg.timeout_height.as_ref()),
+            );
+            insert_u64(&mut value, "timeout_timestamp", msg.timeout_timestamp);
+
+            // `RegisterLegacyAminoCodec` of ibc-go transfer module
+            ("cosmos-sdk/MsgTransfer", value)
+        }
+        type_url if type_url.starts_with(IBC_CORE_TYPE_URL_PREFIX) => bail!(
+            "IBC core message {} does not support amino JSON sign mode (it can only be signed in direct sign mode)",
+            type_url
+        ),
+        type_url => bail!("message type {} is not supported in amino JSON", type_url),
+    };
+
+    Ok(json!({ "type": amino_name, "value": value }))
+}
+
+fn height(height: Option<&Height>) -> Value {
+    let mut value = Map::new();
+
+    if let Some(height) = height {
+        insert_u64(&mut value, "revision_number", height.revision_number);
+        insert_u64(&mut value, "revision_height", height.revision_height);
+    }
+
+    Value::Object(value)
+}
+
+fn coins(coins: &[Coin]) -> Value {
+    Value::Array(coins.iter().map(coin).collect())
+}
+
+fn coin(coin: &Coin) -> Value {
+    json!({ "amount": coin.amount, "denom": coin.denom })
+}
+
+// Amino JSON omits fields with default values
+
+fn insert_str(map: &mut Map<String, Value>, key: &str, value: &str) {
+    if !value.is_empty() {
+        map.insert(key.to_owned(), value.into());
+    }
+}
+
+fn insert_u64(map: &mut Map<String, Value>, key: &str, value: u64) {
+    if value != 0 {
+        map.insert(key.to_owned(), value.to_string().into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmos_sdk_proto::{
+        cosmos::tx::v1beta1::{Fee, SignerInfo},
+        ibc::core::{
+            channel::v1::{MsgAcknowledgement, MsgChannelOpenInit, MsgRecvPacket},
+            client::v1::MsgUpdateClient,
+            connection::v1::MsgConnectionOpenInit,
+        },
+    };
+
+    use super::*;
+    use crate::types::proto_util::{proto_encode, AnyConvert};
+
+    fn sign_doc(messages: Vec<Any>) -> Vec<u8> {
+        let body = TxBody {
+            messages,
+            memo: "memo".to_owned(),
+            ..Default::default()
+        };
+
+        let auth_info = AuthInfo {
+            signer_infos: vec![SignerInfo {
+                public_key: None,
+                mode_info: None,
+                sequence: 7,
+            }],
+            fee: Some(Fee {
+                amount: vec![Coin {
+                    denom: "stake".to_owned(),
+                    amount: "1000".to_owned(),
+                }],
+                gas_limit: 300000,
+                payer: "".to_owned(),
+                granter: "".to_owned(),
+            }),
+        };
+
+        proto_encode(&SignDoc {
+            body_bytes: proto_encode(&body).unwrap(),
+            auth_info_bytes: proto_encode(&auth_info).unwrap(),
+            chain_id: "test-1".to_owned(),
+            account_number: 3,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_msg_transfer_std_sign_doc() {
+        let msg = MsgTransfer {
+            source_port: "transfer".to_owned(),
+            source_channel: "channel-0".to_owned(),
+            token: Some(Coin {
+                denom: "ibc/ABCD".to_owned(),
+                amount: "10".to_owned(),
+            }),
+            sender: "cosmos1sender".to_owned(),
+            receiver: "cosmos1receiver".to_owned(),
+            timeout_height: Some(Height {
+                revision_number: 0,
+                revision_height: 100,
+            }),
+            timeout_timestamp: 0,
+        };
+
+        let std_sign_doc = to_std_sign_doc(&sign_doc(vec![msg.to_any().unwrap()])).unwrap();
+
+        assert_eq!(
+            String::from_utf8(std_sign_doc).unwrap(),
+            r#"{"account_number":"3","chain_id":"test-1","fee":{"amount":[{"amount":"1000","denom":"stake"}],"gas":"300000"},"memo":"memo","msgs":[{"type":"cosmos-sdk/MsgTransfer","value":{"receiver":"cosmos1receiver","sender":"cosmos1sender","source_channel":"channel-0","source_port":"transfer","timeout_height":{"revision_height":"100"},"token":{"amount":"10","denom":"ibc/ABCD"}}}],"sequence":"7"}"#
+        );
+    }
+
+    /// Golden vector of cosmjs (`@cosmjs/stargate` amino converters of ibc module)
+    #[test]
+    fn test_msg_transfer_golden_vector() {
+        let msg = MsgTransfer {
+            source_port: "testport".to_owned(),
+            source_channel: "testchannel".to_owned(),
+            token: Some(Coin {
+                denom: "utest".to_owned(),
+                amount: "1234".to_owned(),
+            }),
+            sender: "cosmos1pkptre7fdkl6gfrzlesjjvhxhlc3r4gmmk8rs6".to_owned(),
+            receiver: "cosmos10dyr9899g6t0pelew4nvf4j5c3jcgv0r73qga5".to_owned(),
+            timeout_height: Some(Height {
+                revision_number: 456,
+                revision_height: 123,
+            }),
+            timeout_timestamp: 0,
+        };
+
+        assert_eq!(
+            serde_json::to_string(&message(&msg.to_any().unwrap()).unwrap()).unwrap(),
+            r#"{"type":"cosmos-sdk/MsgTransfer","value":{"receiver":"cosmos10dyr9899g6t0pelew4nvf4j5c3jcgv0r73qga5","sender":"cosmos1pkptre7fdkl6gfrzlesjjvhxhlc3r4gmmk8rs6","source_channel":"testchannel","source_port":"testport","timeout_height":{"revision_height":"123","revision_number":"456"},"token":{"amount":"1234","denom":"utest"}}}"#
+        );
+
+        // Empty timeout height is encoded as an empty object and non-zero timestamp as a string
+        let msg = MsgTransfer {
+            timeout_height: None,
+            timeout_timestamp: 1_638_316_800_000_000_000,
+            ..msg
+        };
+
+        assert_eq!(
+            serde_json::to_string(&message(&msg.to_any().unwrap()).unwrap()).unwrap(),
+            r#"{"type":"cosmos-sdk/MsgTransfer","value":{"receiver":"cosmos10dyr9899g6t0pelew4nvf4j5c3jcgv0r73qga5","sender":"cosmos1pkptre7fdkl6gfrzlesjjvhxhlc3r4gmmk8rs6","source_channel":"testchannel","source_port":"testport","timeout_height":{},"timeout_timestamp":"1638316800000000000","token":{"amount":"1234","denom":"utest"}}}"#
+        );
+    }
+
+    #[test]
+    fn test_ibc_core_messages_are_not_supported() {
+        let messages = vec![
+            MsgUpdateClient::default().to_any().unwrap(),
+            MsgRecvPacket::default().to_any().unwrap(),
+            MsgAcknowledgement::default().to_any().unwrap(),
+            MsgConnectionOpenInit::default().to_any().unwrap(),
+            MsgChannelOpenInit::default().to_any().unwrap(),
+        ];
+
+        for msg in messages {
+            let err = to_std_sign_doc(&sign_doc(vec![msg.clone()])).unwrap_err();
+            assert!(
+                err.to_string().contains("does not support amino JSON"),
+                "{}: {}",
+                msg.type_url,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_unsupported_message() {
+        let msg = Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_owned(),
+            value: vec![],
+        };
+
+        assert!(to_std_sign_doc(&sign_doc(vec![msg])).is_err());
+    }
+}
@@ -1,18 +1,49 @@
-#[cfg(feature = "mnemonic-signer")]
+#[cfg(any(feature = "ledger-signer", feature = "mnemonic-signer"))]
 use std::collections::HashMap;
+#[cfg(feature = "offline-signer")]
+use std::path::Path;
+#[cfg(any(
+    feature = "keystore-signer",
+    feature = "offline-signer",
+    feature = "pkcs11-signer"
+))]
+use std::path::PathBuf;
+use std::{sync::Arc, time::Duration};
 
 use anyhow::{ensure, Result};
+use primitive_types::U256;
 use sealed::sealed;
-
-#[cfg(feature = "mnemonic-signer")]
-use crate::types::{ics::core::ics24_host::identifier::ChainId, public_key::PublicKeyAlgo};
+#[cfg(all(feature = "remote-signer", not(feature = "wasm"), feature = "non-wasm"))]
+use tonic::transport::{Certificate, ClientTlsConfig, Identity};
+#[cfg(feature = "remote-signer")]
+use url::Url;
+
+use crate::types::ics::core::ics24_host::identifier::ChainId;
+#[cfg(any(feature = "mnemonic-signer", feature = "pkcs11-signer"))]
+use crate::types::public_key::PublicKeyAlgo;
 use crate::{trait_util::Base, types::public_key::PublicKey};
 
 #[cfg(feature = "keplr-signer")]
 use super::keplr_signer::KeplrSigner as KeplrSignerImpl;
+#[cfg(feature = "keystore-signer")]
+use super::keystore::Keystore;
+#[cfg(feature = "ledger-signer")]
+use super::ledger_signer::{LedgerChainConfig, LedgerSigner as LedgerSignerImpl, LedgerTransport};
 #[cfg(feature = "mnemonic-signer")]
 use super::mnemonic_signer::{MnemonicSigner as MnemonicSignerImpl, MnemonicSignerConfig};
+use super::multisig_signer::MultisigSigner as MultisigSignerImpl;
+#[cfg(feature = "offline-signer")]
+use super::offline_signer::{OfflineSigner as OfflineSignerImpl, RequestHook};
+#[cfg(feature = "pkcs11-signer")]
+use super::pkcs11_signer::Pkcs11Signer as Pkcs11SignerImpl;
+#[cfg(feature = "remote-signer")]
+use super::remote_signer::RemoteSigner as RemoteSignerImpl;
+use super::signing_policy::{
+    ApprovalHook, PolicyRules, SigningIntent, SigningPolicy as SigningPolicyImpl,
+};
 use super::Signer;
+#[cfg(any(feature = "keystore-signer", feature = "pkcs11-signer"))]
+use super::Zeroizing;
 
 /// Configuration for signer
 #[sealed]
@@ -24,6 +55,27 @@ pub trait SignerConfig: Base {
     fn into_signer(self) -> Result<Self::Signer>;
 }
 
+#[cfg(feature = "offline-signer")]
+/// Default interval at which offline signer checks for responses
+const DEFAULT_OFFLINE_SIGNER_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[cfg(feature = "pkcs11-signer")]
+/// Default bech32 account prefix for PKCS#11 signer
+const DEFAULT_PKCS11_ACCOUNT_PREFIX: &str = "cosmos";
+
+#[cfg(feature = "remote-signer")]
+/// Default timeout for requests to remote signing service
+const DEFAULT_REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[sealed]
+impl SignerConfig for Arc<dyn Signer> {
+    type Signer = Self;
+
+    fn into_signer(self) -> Result<Self::Signer> {
+        Ok(self)
+    }
+}
+
 #[cfg_attr(feature = "doc", doc(cfg(feature = "keplr-signer")))]
 #[cfg(feature = "keplr-signer")]
 #[derive(Default)]
@@ -49,6 +101,23 @@ pub struct MnemonicSigner {
     config_map: HashMap<ChainId, MnemonicSignerConfig>,
 }
 
+#[cfg_attr(feature = "doc", doc(cfg(feature = "mnemonic-signer")))]
+#[cfg(feature = "mnemonic-signer")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Public details of a chain configuration of mnemonic signer (never contains mnemonic)
+pub struct ChainSignerDetails {
+    /// Chain ID
+    pub chain_id: ChainId,
+    /// Account address of signer
+    pub account_address: String,
+    /// HD path of signer
+    pub hd_path: String,
+    /// Bech32 account prefix of signer
+    pub account_prefix: String,
+    /// Algorithm used for address generation
+    pub algo: PublicKeyAlgo,
+}
+
 #[cfg(feature = "mnemonic-signer")]
 impl MnemonicSigner {
     /// Creates a new instance of mnemonic signer
@@ -102,6 +171,17 @@ impl MnemonicSigner {
         Ok(self)
     }
 
+    /// Removes configuration for a chain id from the mnemonic signer
+    pub fn remove_chain_config(&mut self, chain_id: &ChainId) -> Result<&mut Self> {
+        ensure!(
+            self.config_map.remove(chain_id).is_some(),
+            "Signer config for chain id {} does not exist",
+            chain_id
+        );
+
+        Ok(self)
+    }
+
     /// Checks if mnemonic signer has configuration for a chain id
     pub fn has_chain_config(&self, chain_id: &ChainId) -> bool {
         self.config_map.contains_key(chain_id)
@@ -117,6 +197,22 @@ impl MnemonicSigner {
             .collect()
     }
 
+    /// Returns details (excluding mnemonic) of configurations for all the chain ids
+    pub fn get_chain_configs(&self) -> Result<Vec<ChainSignerDetails>> {
+        self.config_map
+            .iter()
+            .map(|(chain_id, config)| -> Result<ChainSignerDetails> {
+                Ok(ChainSignerDetails {
+                    chain_id: chain_id.clone(),
+                    account_address: config.get_account_address()?,
+                    hd_path: config.get_hd_path().to_owned(),
+                    account_prefix: config.get_account_prefix().to_owned(),
+                    algo: config.get_algo(),
+                })
+            })
+            .collect()
+    }
+
     /// Computes public key for given signer details
     pub fn compute_public_key(
         mnemonic: &str,
@@ -140,6 +236,453 @@ impl SignerConfig for MnemonicSigner {
     }
 }
 
+#[cfg_attr(feature = "doc", doc(cfg(feature = "keystore-signer")))]
+#[cfg(feature = "keystore-signer")]
+#[derive(Clone)]
+/// Signer backend using mnemonics stored in a password encrypted keystore file
+pub struct KeystoreSigner {
+    path: PathBuf,
+    password: Zeroizing<String>,
+}
+
+#[cfg(feature = "keystore-signer")]
+impl KeystoreSigner {
+    /// Creates a new instance of keystore signer
+    pub fn new(path: impl Into<PathBuf>, password: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            password: Zeroizing::new(password.into()),
+        }
+    }
+}
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "keystore-signer")))]
+#[cfg(feature = "keystore-signer")]
+#[sealed]
+impl SignerConfig for KeystoreSigner {
+    type Signer = MnemonicSignerImpl;
+
+    fn into_signer(self) -> Result<Self::Signer> {
+        Keystore::read(&self.path)?
+            .decrypt(&self.password)?
+            .into_signer()
+    }
+}
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "ledger-signer")))]
+#[cfg(feature = "ledger-signer")]
+#[derive(Clone)]
+/// Signer backend using Cosmos app on a ledger device (sign docs are signed in amino JSON sign mode)
+pub struct LedgerSigner {
+    transport: Arc<dyn LedgerTransport>,
+    config_map: HashMap<ChainId, LedgerChainConfig>,
+}
+
+#[cfg(feature = "ledger-signer")]
+impl LedgerSigner {
+    /// Creates a new instance of ledger signer which exchanges APDUs with ledger device using given transport
+    pub fn new(transport: impl LedgerTransport + 'static) -> Self {
+        Self {
+            transport: Arc::new(transport),
+            config_map: Default::default(),
+        }
+    }
+
+    /// Adds configuration for a chain id to the ledger signer (HD path defaults to `m/44'/118'/0'/0/0` and account
+    /// prefix defaults to `cosmos`)
+    pub fn add_chain_config(
+        &mut self,
+        chain_id: ChainId,
+        hd_path: Option<&str>,
+        account_prefix: Option<&str>,
+    ) -> Result<&mut Self> {
+        ensure!(
+            !self.config_map.contains_key(&chain_id),
+            "Signer config for chain id {} already exists",
+            chain_id
+        );
+
+        self.config_map
+            .insert(chain_id, LedgerChainConfig::new(hd_path, account_prefix)?);
+
+        Ok(self)
+    }
+}
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "ledger-signer")))]
+#[cfg(feature = "ledger-signer")]
+#[sealed]
+impl SignerConfig for LedgerSigner {
+    type Signer = LedgerSignerImpl;
+
+    fn into_signer(self) -> Result<Self::Signer> {
+        Ok(LedgerSignerImpl::new(self.transport, self.config_map))
+    }
+}
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "offline-signer")))]
+#[cfg(feature = "offline-signer")]
+#[derive(Clone)]
+/// Signer backend which writes signing requests to a directory (to be signed on an offline machine) and waits for
+/// their responses
+pub struct OfflineSigner {
+    dir: PathBuf,
+    poll_interval: Duration,
+    timeout: Option<Duration>,
+    request_hook: Option<RequestHook>,
+}
+
+#[cfg(feature = "offline-signer")]
+impl OfflineSigner {
+    /// Creates a new instance of offline signer which exchanges requests and responses in given directory
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            poll_interval: DEFAULT_OFFLINE_SIGNER_POLL_INTERVAL,
+            timeout: None,
+            request_hook: None,
+        }
+    }
+
+    /// Sets the interval at which requests directory is checked for responses (default: 1 second)
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets the maximum duration to wait for a response (default: wait indefinitely)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets a hook which is called with the path of every request file written to requests directory
+    pub fn with_request_hook(
+        mut self,
+        request_hook: impl Fn(&Path) + Send + Sync + 'static,
+    ) -> Self {
+        self.request_hook = Some(Arc::new(request_hook));
+        self
+    }
+}
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "offline-signer")))]
+#[cfg(feature = "offline-signer")]
+#[sealed]
+impl SignerConfig for OfflineSigner {
+    type Signer = OfflineSignerImpl;
+
+    fn into_signer(self) -> Result<Self::Signer> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        Ok(OfflineSignerImpl::new(
+            self.dir,
+            self.poll_interval,
+            self.timeout,
+            self.request_hook,
+        ))
+    }
+}
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "pkcs11-signer")))]
+#[cfg(feature = "pkcs11-signer")]
+#[derive(Clone)]
+/// Signer backend using an EC key stored in an HSM (accessed using a PKCS#11 module)
+pub struct Pkcs11Signer {
+    module_path: PathBuf,
+    slot: u64,
+    key_label: String,
+    pin: Zeroizing<String>,
+    algo: PublicKeyAlgo,
+    account_prefix: String,
+}
+
+#[cfg(feature = "pkcs11-signer")]
+impl Pkcs11Signer {
+    /// Creates a new instance of PKCS#11 signer using the key pair with given label on the token in given slot
+    pub fn new(
+        module_path: impl Into<PathBuf>,
+        slot: u64,
+        key_label: impl Into<String>,
+        pin: impl Into<String>,
+    ) -> Self {
+        Self {
+            module_path: module_path.into(),
+            slot,
+            key_label: key_label.into(),
+            pin: Zeroizing::new(pin.into()),
+            algo: PublicKeyAlgo::Secp256k1,
+            account_prefix: DEFAULT_PKCS11_ACCOUNT_PREFIX.to_string(),
+        }
+    }
+
+    /// Sets the algorithm of key pair (default: `secp256k1`) [possible values: `secp256k1`, `secp256r1`]
+    pub fn with_algo(mut self, algo: PublicKeyAlgo) -> Self {
+        self.algo = algo;
+        self
+    }
+
+    /// Sets the bech32 account prefix (default: `cosmos`)
+    pub fn with_account_prefix(mut self, account_prefix: impl Into<String>) -> Self {
+        self.account_prefix = account_prefix.into();
+        self
+    }
+}
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "pkcs11-signer")))]
+#[cfg(feature = "pkcs11-signer")]
+#[sealed]
+impl SignerConfig for Pkcs11Signer {
+    type Signer = Pkcs11SignerImpl;
+
+    fn into_signer(self) -> Result<Self::Signer> {
+        Pkcs11SignerImpl::new(
+            &self.module_path,
+            self.slot,
+            self.key_label,
+            self.pin,
+            self.algo,
+            self.account_prefix,
+        )
+    }
+}
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "remote-signer")))]
+#[cfg(feature = "remote-signer")]
+#[derive(Debug, Clone)]
+/// Signer backend using an external signing service over gRPC (keys never leave the signing service)
+pub struct RemoteSigner {
+    url: Url,
+    timeout: Duration,
+    #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+    ca_certificate: Option<Vec<u8>>,
+    #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+    identity: Option<(Vec<u8>, Vec<u8>)>,
+    #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+    domain_name: Option<String>,
+}
+
+#[cfg(feature = "remote-signer")]
+impl RemoteSigner {
+    /// Creates a new instance of remote signer for signing service at given gRPC address (TLS is used when URL scheme
+    /// is `https`)
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            timeout: DEFAULT_REMOTE_SIGNER_TIMEOUT,
+            #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+            ca_certificate: None,
+            #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+            identity: None,
+            #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+            domain_name: None,
+        }
+    }
+
+    /// Sets timeout for requests to signing service (default: 30 seconds)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets PEM encoded CA certificate used to verify signing service's certificate (webpki roots are used by
+    /// default)
+    #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+    pub fn with_ca_certificate(mut self, ca_certificate: impl Into<Vec<u8>>) -> Self {
+        self.ca_certificate = Some(ca_certificate.into());
+        self
+    }
+
+    /// Sets PEM encoded client certificate and private key presented to signing service (required when signing
+    /// service uses mutual TLS)
+    #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+    pub fn with_identity(mut self, cert: impl Into<Vec<u8>>, key: impl Into<Vec<u8>>) -> Self {
+        self.identity = Some((cert.into(), key.into()));
+        self
+    }
+
+    /// Sets the domain name against which signing service's certificate is verified (host of URL is used by default)
+    #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+    pub fn with_domain_name(mut self, domain_name: impl Into<String>) -> Self {
+        self.domain_name = Some(domain_name.into());
+        self
+    }
+}
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "remote-signer")))]
+#[cfg(feature = "remote-signer")]
+#[sealed]
+impl SignerConfig for RemoteSigner {
+    type Signer = RemoteSignerImpl;
+
+    fn into_signer(self) -> Result<Self::Signer> {
+        let signer = RemoteSignerImpl::new(self.url, self.timeout);
+
+        #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+        let signer = {
+            let mut tls_config = ClientTlsConfig::new();
+
+            if let Some(ca_certificate) = self.ca_certificate {
+                tls_config = tls_config.ca_certificate(Certificate::from_pem(ca_certificate));
+            }
+
+            if let Some((cert, key)) = self.identity {
+                tls_config = tls_config.identity(Identity::from_pem(cert, key));
+            }
+
+            if let Some(domain_name) = self.domain_name {
+                tls_config = tls_config.domain_name(domain_name);
+            }
+
+            signer.with_tls_config(tls_config)
+        };
+
+        Ok(signer)
+    }
+}
+
+#[derive(Clone, Default)]
+/// Signer backend which collects signatures from multiple signers for a threshold multisig public key (cosmos sdk
+/// transactions are signed using the first signer)
+pub struct MultisigSigner {
+    threshold: u32,
+    signers: Vec<Arc<dyn Signer>>,
+}
+
+impl MultisigSigner {
+    /// Creates a new instance of multisig signer requiring signatures from `threshold` signers
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            threshold,
+            signers: Default::default(),
+        }
+    }
+
+    /// Adds a signer to the multisig signer (order of signers determines the order of public keys in multisig public
+    /// key)
+    pub fn add_signer<T>(&mut self, signer: T) -> Result<&mut Self>
+    where
+        T: SignerConfig,
+        T::Signer: 'static,
+    {
+        self.signers.push(Arc::new(signer.into_signer()?));
+        Ok(self)
+    }
+}
+
+#[sealed]
+impl SignerConfig for MultisigSigner {
+    type Signer = MultisigSignerImpl;
+
+    fn into_signer(self) -> Result<Self::Signer> {
+        ensure!(
+            self.threshold > 0,
+            "multisig threshold must be greater than zero"
+        );
+        ensure!(
+            self.threshold as usize <= self.signers.len(),
+            "multisig threshold ({}) cannot be greater than number of signers ({})",
+            self.threshold,
+            self.signers.len()
+        );
+
+        Ok(MultisigSignerImpl::new(self.threshold, self.signers))
+    }
+}
+
+#[derive(Clone)]
+/// Signer backend which decodes messages into human readable intents (e.g. mint of tokens, ICA delegation, etc.) and
+/// enforces signing rules on them before forwarding them to the wrapped signer
+pub struct SigningPolicy {
+    signer: Arc<dyn Signer>,
+    rules: PolicyRules,
+}
+
+impl SigningPolicy {
+    /// Creates a new instance of signing policy wrapping given signer (all messages are allowed by default)
+    pub fn new<T>(signer: T) -> Result<Self>
+    where
+        T: SignerConfig,
+        T::Signer: 'static,
+    {
+        Ok(Self {
+            signer: Arc::new(signer.into_signer()?),
+            rules: Default::default(),
+        })
+    }
+
+    /// Sets the maximum amount of tokens of given denom which can be moved in a single message (denoms of minted and
+    /// burnt tokens are denoms on solo machine and denoms of ICA tokens are denoms on IBC enabled chain)
+    pub fn with_denom_limit(mut self, denom: impl Into<String>, limit: U256) -> Self {
+        self.rules.denom_limits.insert(denom.into(), limit);
+        self
+    }
+
+    /// Sets the maximum amount of tokens of given denom which can be moved in last 24 hours
+    pub fn with_daily_cap(mut self, denom: impl Into<String>, cap: U256) -> Self {
+        self.rules.daily_caps.insert(denom.into(), cap);
+        self
+    }
+
+    /// Adds an address on IBC enabled chain to which tokens can be minted or sent from interchain account (when no
+    /// mint receiver is added, tokens can be minted or sent to any address)
+    pub fn with_allowed_mint_receiver(mut self, receiver: impl Into<String>) -> Self {
+        self.rules.allowed_mint_receivers.insert(receiver.into());
+        self
+    }
+
+    /// Adds an address on solo machine to which tokens can be burnt (when no burn receiver is added, tokens can be
+    /// burnt to any address)
+    pub fn with_allowed_burn_receiver(mut self, receiver: impl Into<String>) -> Self {
+        self.rules.allowed_burn_receivers.insert(receiver.into());
+        self
+    }
+
+    /// Sets the maximum number of messages moving tokens which can be signed in given duration
+    pub fn with_rate_limit(mut self, max_messages: u32, duration: Duration) -> Self {
+        self.rules.rate_limit = Some((max_messages, duration));
+        self
+    }
+
+    /// Sets a hook which is called with the intents of every message which satisfies all the other rules (returning
+    /// an error from hook rejects the message)
+    #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+    pub fn with_approval_hook(
+        mut self,
+        approval_hook: impl Fn(&ChainId, &[SigningIntent]) -> Result<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.rules.approval_hook = Some(Arc::new(approval_hook) as ApprovalHook);
+        self
+    }
+
+    /// Sets a hook which is called with the intents of every message which satisfies all the other rules (returning
+    /// an error from hook rejects the message)
+    #[cfg(feature = "wasm")]
+    pub fn with_approval_hook(
+        mut self,
+        approval_hook: impl Fn(&ChainId, &[SigningIntent]) -> Result<()> + 'static,
+    ) -> Self {
+        self.rules.approval_hook = Some(Arc::new(approval_hook) as ApprovalHook);
+        self
+    }
+}
+
+#[sealed]
+impl SignerConfig for SigningPolicy {
+    type Signer = SigningPolicyImpl;
+
+    fn into_signer(self) -> Result<Self::Signer> {
+        if let Some((max_messages, _)) = self.rules.rate_limit {
+            ensure!(
+                max_messages > 0,
+                "rate limit of signing policy must allow at least one message"
+            );
+        }
+
+        Ok(SigningPolicyImpl::new(self.signer, self.rules))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::signer::GetPublicKey;
@@ -199,6 +742,29 @@ mod tests {
         assert_eq!(signers[0].1, ACCOUNT_ADDRESS);
     }
 
+    #[test]
+    fn test_remove_chain_config() {
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        let mut config = MnemonicSigner::new();
+        config
+            .add_chain_config(chain_id.clone(), MNEMONIC, None, None, None)
+            .unwrap();
+
+        let chain_configs = config.get_chain_configs().unwrap();
+        assert_eq!(chain_configs.len(), 1);
+        assert_eq!(chain_configs[0].chain_id, chain_id);
+        assert_eq!(chain_configs[0].account_address, ACCOUNT_ADDRESS);
+        assert_eq!(chain_configs[0].hd_path, "m/44'/118'/0'/0/0");
+        assert_eq!(chain_configs[0].account_prefix, "cosmos");
+
+        assert!(config.remove_chain_config(&chain_id).is_ok());
+        assert!(!config.has_chain_config(&chain_id));
+        assert!(config.get_chain_configs().unwrap().is_empty());
+
+        assert!(config.remove_chain_config(&chain_id).is_err());
+    }
+
     #[test]
     fn test_cannot_add_same_config_twice() {
         let chain_id: ChainId = "test-1".parse().unwrap();
@@ -0,0 +1,536 @@
+//! Password encrypted keystore for mnemonics of signers
+use std::{
+    ffi::OsString,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+};
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use anyhow::{anyhow, ensure, Context, Result};
+use bip32::{Language, Mnemonic};
+use rand::{rngs::OsRng, RngCore};
+use scrypt::{scrypt, Params};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::types::{ics::core::ics24_host::identifier::ChainId, public_key::PublicKeyAlgo};
+
+use super::{builder::MnemonicSigner, mnemonic_signer::MnemonicSignerConfig};
+
+/// Current version of keystore format
+const KEYSTORE_VERSION: u32 = 1;
+
+const KDF: &str = "scrypt";
+const CIPHER: &str = "aes-256-gcm";
+
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// Password encrypted keystore containing mnemonics of signers for different chains
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    version: u32,
+    keys: Vec<KeystoreEntry>,
+    /// Parameters of key derivation function used when adding new keys
+    #[serde(skip)]
+    kdf_params: KdfParams,
+}
+
+/// Parameters of scrypt key derivation function
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Log2 of CPU/memory cost parameter
+    pub log_n: u8,
+    /// Block size parameter
+    pub r: u32,
+    /// Parallelization parameter
+    pub p: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// A key in keystore (only the mnemonic is encrypted, all the other details can be read without password)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreEntry {
+    /// Chain ID
+    pub chain_id: ChainId,
+    /// Account address of signer
+    pub address: String,
+    /// HD path of signer
+    pub hd_path: Option<String>,
+    /// Bech32 account prefix of signer
+    pub account_prefix: Option<String>,
+    /// Algorithm used for address generation
+    pub algo: Option<String>,
+    crypto: EncryptedMnemonic,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EncryptedMnemonic {
+    kdf: String,
+    kdf_params: KdfParams,
+    #[serde(with = "hex")]
+    salt: Vec<u8>,
+    cipher: String,
+    #[serde(with = "hex")]
+    nonce: Vec<u8>,
+    #[serde(with = "hex")]
+    ciphertext: Vec<u8>,
+}
+
+impl Default for Keystore {
+    fn default() -> Self {
+        Self {
+            version: KEYSTORE_VERSION,
+            keys: Vec::new(),
+            kdf_params: KdfParams::default(),
+        }
+    }
+}
+
+impl Keystore {
+    /// Creates a new empty keystore
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Reads keystore from given file
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+
+        Self::from_slice(&bytes).with_context(|| format!("invalid keystore {}", path.display()))
+    }
+
+    /// Reads keystore from given file if it exists, otherwise returns an empty keystore
+    pub fn read_or_new(path: impl AsRef<Path>) -> Result<Self> {
+        if path.as_ref().exists() {
+            Self::read(path)
+        } else {
+            Ok(Self::new())
+        }
+    }
+
+    /// Writes keystore to given file (only readable by current user on unix systems)
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        write_private_file(path, &self.to_vec()?)
+    }
+
+    /// Deserializes keystore from JSON bytes
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        let keystore: Self = serde_json::from_slice(bytes).context("failed to parse keystore")?;
+
+        ensure!(
+            keystore.version == KEYSTORE_VERSION,
+            "unsupported keystore version: {}",
+            keystore.version
+        );
+
+        Ok(keystore)
+    }
+
+    /// Serializes keystore to JSON bytes
+    pub fn to_vec(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec_pretty(self).context("failed to serialize keystore")
+    }
+
+    /// Sets parameters of key derivation function used when adding new keys
+    pub fn set_kdf_params(&mut self, kdf_params: KdfParams) -> &mut Self {
+        self.kdf_params = kdf_params;
+        self
+    }
+
+    /// Returns all the keys in keystore
+    pub fn keys(&self) -> &[KeystoreEntry] {
+        &self.keys
+    }
+
+    /// Checks if keystore has a key for given chain id
+    pub fn has_key(&self, chain_id: &ChainId) -> bool {
+        self.keys.iter().any(|key| &key.chain_id == chain_id)
+    }
+
+    /// Encrypts given mnemonic with password and adds it to keystore for given chain id
+    pub fn add_key(
+        &mut self,
+        chain_id: ChainId,
+        mnemonic: &str,
+        hd_path: Option<&str>,
+        account_prefix: Option<&str>,
+        algo: Option<PublicKeyAlgo>,
+        password: &str,
+    ) -> Result<&KeystoreEntry> {
+        ensure!(
+            !self.has_key(&chain_id),
+            "key for chain id {} already exists in keystore",
+            chain_id
+        );
+
+        // All the keys in keystore must be encrypted with the same password
+        if let Some(key) = self.keys.first() {
+            key.decrypt_mnemonic(password)
+                .context("password does not match the password of existing keys")?;
+        }
+
+        let address = MnemonicSignerConfig::new(mnemonic, hd_path, account_prefix, algo)?
+            .get_account_address()?;
+
+        let crypto = EncryptedMnemonic::encrypt(mnemonic, &chain_id, password, self.kdf_params)?;
+
+        self.keys.push(KeystoreEntry {
+            chain_id,
+            address,
+            hd_path: hd_path.map(ToOwned::to_owned),
+            account_prefix: account_prefix.map(ToOwned::to_owned),
+            algo: algo.map(|algo| algo.to_string()),
+            crypto,
+        });
+
+        Ok(self.keys.last().expect("key was just added"))
+    }
+
+    /// Removes key for given chain id from keystore and returns it
+    pub fn remove_key(&mut self, chain_id: &ChainId) -> Result<KeystoreEntry> {
+        let index = self
+            .keys
+            .iter()
+            .position(|key| &key.chain_id == chain_id)
+            .ok_or_else(|| anyhow!("key for chain id {} does not exist in keystore", chain_id))?;
+
+        Ok(self.keys.remove(index))
+    }
+
+    /// Decrypts all the keys in keystore with given password and returns a mnemonic signer configured with them
+    pub fn decrypt(&self, password: &str) -> Result<MnemonicSigner> {
+        let mut mnemonic_signer = MnemonicSigner::new();
+
+        for key in self.keys.iter() {
+            let mnemonic = key.decrypt_mnemonic(password)?;
+
+            mnemonic_signer
+                .add_chain_config(
+                    key.chain_id.clone(),
+                    &mnemonic,
+                    key.hd_path.as_deref(),
+                    key.account_prefix.as_deref(),
+                    key.algo
+                        .as_deref()
+                        .map(str::parse)
+                        .transpose()
+                        .with_context(|| format!("invalid algo for chain id: {}", key.chain_id))?,
+                )
+                .with_context(|| format!("invalid key for chain id: {}", key.chain_id))?;
+        }
+
+        Ok(mnemonic_signer)
+    }
+
+    /// Generates a new random mnemonic phrase (24 words)
+    pub fn generate_mnemonic() -> Zeroizing<String> {
+        Zeroizing::new(
+            Mnemonic::random(OsRng, Language::English)
+                .phrase()
+                .to_owned(),
+        )
+    }
+}
+
+impl KeystoreEntry {
+    /// Decrypts mnemonic of key with given password
+    pub fn decrypt_mnemonic(&self, password: &str) -> Result<Zeroizing<String>> {
+        self.crypto.decrypt(&self.chain_id, password)
+    }
+}
+
+impl EncryptedMnemonic {
+    fn encrypt(
+        mnemonic: &str,
+        chain_id: &ChainId,
+        password: &str,
+        kdf_params: KdfParams,
+    ) -> Result<Self> {
+        let mut salt = vec![0; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+
+        let mut nonce = vec![0; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+
+        let key = derive_key(password, &salt, kdf_params)?;
+        let cipher = Aes256Gcm::new_from_slice(key.as_ref()).context("invalid key length")?;
+
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: mnemonic.as_bytes(),
+                    aad: chain_id.to_string().as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow!("failed to encrypt mnemonic for chain id: {}", chain_id))?;
+
+        Ok(Self {
+            kdf: KDF.to_owned(),
+            kdf_params,
+            salt,
+            cipher: CIPHER.to_owned(),
+            nonce,
+            ciphertext,
+        })
+    }
+
+    fn decrypt(&self, chain_id: &ChainId, password: &str) -> Result<Zeroizing<String>> {
+        ensure!(
+            self.kdf == KDF,
+            "unsupported key derivation function: {}",
+            self.kdf
+        );
+        ensure!(self.cipher == CIPHER, "unsupported cipher: {}", self.cipher);
+        ensure!(self.nonce.len() == NONCE_LEN, "invalid nonce length");
+
+        let key = derive_key(password, &self.salt, self.kdf_params)?;
+        let cipher = Aes256Gcm::new_from_slice(key.as_ref()).context("invalid key length")?;
+
+        let mnemonic = cipher
+            .decrypt(
+                Nonce::from_slice(&self.nonce),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: chain_id.to_string().as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow!("invalid password for chain id: {}", chain_id))?;
+
+        String::from_utf8(mnemonic)
+            .map(Zeroizing::new)
+            .map_err(|_| anyhow!("invalid mnemonic for chain id: {}", chain_id))
+    }
+}
+
+fn derive_key(
+    password: &str,
+    salt: &[u8],
+    kdf_params: KdfParams,
+) -> Result<Zeroizing<[u8; KEY_LEN]>> {
+    let params = Params::new(kdf_params.log_n, kdf_params.r, kdf_params.p)
+        .map_err(|_| anyhow!("invalid key derivation function parameters"))?;
+
+    let mut key = Zeroizing::new([0; KEY_LEN]);
+    scrypt(password.as_bytes(), salt, &params, key.as_mut())
+        .map_err(|_| anyhow!("failed to derive key from password"))?;
+
+    Ok(key)
+}
+
+/// Atomically writes given contents to a file only readable by current user on unix systems
+///
+/// Contents are first written to a new temporary file (created with restricted permissions) in the same directory which
+/// is then renamed to given path, so that the file is never partially written or readable by other users.
+pub fn write_private_file(path: impl AsRef<Path>, contents: &[u8]) -> Result<()> {
+    let path = path.as_ref();
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| anyhow!("invalid file path: {}", path.display()))?;
+
+    let mut temp_file_name = OsString::from(".");
+    temp_file_name.push(file_name);
+    temp_file_name.push(format!(".{:016x}.tmp", OsRng.next_u64()));
+
+    let temp_path = path.with_file_name(temp_file_name);
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        options.mode(0o600);
+    }
+
+    let result = options
+        .open(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp_path, path));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    result.with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::signer::{GetPublicKey, SignerConfig};
+
+    use super::*;
+
+    const MNEMONIC: &str = "practice empty client sauce pistol work ticket casual romance appear army fault palace coyote fox super salute slim catch kite wrist three hedgehog sign";
+    const ACCOUNT_ADDRESS: &str = "cosmos1j2qpprh2xke7qjqzehfqgjdkfgddf9dm06dugw";
+    const PASSWORD: &str = "password";
+
+    fn test_keystore() -> Keystore {
+        let mut keystore = Keystore::new();
+        keystore.set_kdf_params(KdfParams {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        });
+        keystore
+    }
+
+    #[tokio::test]
+    async fn test_keystore_roundtrip() {
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        let mut keystore = test_keystore();
+        let key = keystore
+            .add_key(chain_id.clone(), MNEMONIC, None, None, None, PASSWORD)
+            .unwrap();
+        assert_eq!(key.address, ACCOUNT_ADDRESS);
+
+        // Should not allow adding key for same chain id twice
+        assert!(keystore
+            .add_key(chain_id.clone(), MNEMONIC, None, None, None, PASSWORD)
+            .is_err());
+
+        let bytes = keystore.to_vec().unwrap();
+        assert!(!String::from_utf8_lossy(&bytes).contains("practice"));
+
+        let keystore = Keystore::from_slice(&bytes).unwrap();
+        assert_eq!(keystore.keys().len(), 1);
+        assert!(keystore.has_key(&chain_id));
+
+        assert_eq!(
+            keystore.keys()[0]
+                .decrypt_mnemonic(PASSWORD)
+                .unwrap()
+                .as_str(),
+            MNEMONIC
+        );
+
+        let signer = keystore.decrypt(PASSWORD).unwrap().into_signer().unwrap();
+        assert_eq!(
+            signer.to_account_address(&chain_id).await.unwrap(),
+            ACCOUNT_ADDRESS
+        );
+    }
+
+    #[test]
+    fn test_keystore_invalid_password() {
+        let mut keystore = test_keystore();
+        keystore
+            .add_key(
+                "test-1".parse().unwrap(),
+                MNEMONIC,
+                None,
+                None,
+                None,
+                PASSWORD,
+            )
+            .unwrap();
+
+        assert!(keystore.decrypt("invalid").is_err());
+
+        // Should not allow adding key with a different password
+        assert!(keystore
+            .add_key(
+                "test-2".parse().unwrap(),
+                MNEMONIC,
+                None,
+                None,
+                None,
+                "invalid",
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_keystore_remove_key() {
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        let mut keystore = test_keystore();
+        keystore
+            .add_key(chain_id.clone(), MNEMONIC, None, None, None, PASSWORD)
+            .unwrap();
+
+        let key = keystore.remove_key(&chain_id).unwrap();
+        assert_eq!(key.chain_id, chain_id);
+        assert!(!keystore.has_key(&chain_id));
+
+        // Should not allow removing non-existing key
+        assert!(keystore.remove_key(&chain_id).is_err());
+    }
+
+    #[test]
+    fn test_keystore_tampered_chain_id() {
+        let mut keystore = test_keystore();
+        keystore
+            .add_key(
+                "test-1".parse().unwrap(),
+                MNEMONIC,
+                None,
+                None,
+                None,
+                PASSWORD,
+            )
+            .unwrap();
+
+        // Encrypted mnemonic of one chain cannot be moved to another chain
+        keystore.keys[0].chain_id = "test-2".parse().unwrap();
+        assert!(keystore.decrypt(PASSWORD).is_err());
+    }
+
+    #[test]
+    fn test_write_private_file() {
+        let dir = std::env::temp_dir().join(format!("stag-keystore-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join("keystore.json");
+
+        write_private_file(&path, b"first").unwrap();
+        write_private_file(&path, b"second").unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), b"second");
+        // No temporary files should be left behind
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // Writing to a path without a file name should fail
+        assert!(write_private_file(dir.join(".."), b"contents").is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_generate_mnemonic() {
+        let mnemonic = Keystore::generate_mnemonic();
+        assert_eq!(mnemonic.split_whitespace().count(), 24);
+        assert!(Mnemonic::new(mnemonic.as_str(), Language::English).is_ok());
+    }
+}
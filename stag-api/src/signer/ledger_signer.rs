@@ -0,0 +1,693 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use async_trait::async_trait;
+use cosmos_sdk_proto::cosmos::tx::signing::v1beta1::SignMode;
+use k256::ecdsa::{Signature, VerifyingKey};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    sync::Mutex,
+};
+
+use crate::{
+    trait_util::Base,
+    types::{ics::core::ics24_host::identifier::ChainId, public_key::PublicKey},
+};
+
+use super::{amino_json::to_std_sign_doc, GetPublicKey, Message, Signer};
+
+const DEFAULT_HD_PATH: &str = "m/44'/118'/0'/0/0";
+const DEFAULT_ACCOUNT_PREFIX: &str = "cosmos";
+
+/// APDU class of Cosmos ledger app
+const CLA: u8 = 0x55;
+/// Instruction for signing a message using secp256k1 key
+const INS_SIGN_SECP256K1: u8 = 0x02;
+/// Instruction for getting public key and address of secp256k1 key
+const INS_GET_ADDR_SECP256K1: u8 = 0x04;
+
+/// First chunk of a message (contains HD path)
+const P1_INIT: u8 = 0x00;
+/// Intermediate chunk of a message
+const P1_ADD: u8 = 0x01;
+/// Last chunk of a message
+const P1_LAST: u8 = 0x02;
+
+/// Message is a JSON document (amino JSON sign doc). Cosmos app parses the message before signing and rejects anything
+/// else, and its only other mode (`0x01`) accepts CBOR encoded `SIGN_MODE_TEXTUAL` envelopes, so protobuf encoded
+/// messages cannot be signed by it.
+const P2_JSON: u8 = 0x00;
+
+/// Maximum size of data in an APDU command
+const CHUNK_SIZE: usize = 250;
+
+/// Status word of a successful APDU response
+const SW_OK: u16 = 0x9000;
+
+/// Hardened bit of a BIP-32 path component
+const HARDENED: u32 = 0x8000_0000;
+
+/// Transport for exchanging APDUs with a ledger device (e.g., USB HID or TCP connection with Speculos emulator)
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+pub trait LedgerTransport: Base {
+    /// Sends an APDU command to ledger device and returns the response (response data followed by two bytes of status
+    /// word)
+    async fn exchange(&self, command: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Transport for ledger devices emulated by Speculos (APDUs are exchanged over TCP as length prefixed frames)
+pub struct TcpTransport {
+    /// Address of APDU server of emulator (e.g., `127.0.0.1:9999`)
+    addr: String,
+    /// Connection with emulator (connected lazily and reconnected after a failed exchange)
+    stream: Mutex<Option<TcpStream>>,
+}
+
+impl TcpTransport {
+    /// Creates a new TCP transport for APDU server at given address (e.g., `127.0.0.1:9999`)
+    pub fn new(addr: impl Into<String>) -> Self {
+        Self {
+            addr: addr.into(),
+            stream: Mutex::new(None),
+        }
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl LedgerTransport for TcpTransport {
+    async fn exchange(&self, command: &[u8]) -> Result<Vec<u8>> {
+        let mut stream = self.stream.lock().await;
+
+        if stream.is_none() {
+            *stream = Some(
+                TcpStream::connect(&self.addr)
+                    .await
+                    .with_context(|| format!("failed to connect to ledger at {}", self.addr))?,
+            );
+        }
+
+        let result = exchange_frame(stream.as_mut().expect("stream is connected"), command).await;
+
+        if result.is_err() {
+            *stream = None;
+        }
+
+        result
+    }
+}
+
+/// Writes APDU command as a length prefixed frame and reads the response (response frame is prefixed with length of
+/// data and followed by two bytes of status word)
+async fn exchange_frame(stream: &mut TcpStream, command: &[u8]) -> Result<Vec<u8>> {
+    let len = u32::try_from(command.len()).context("APDU command is too long")?;
+
+    stream.write_all(&len.to_be_bytes()).await?;
+    stream.write_all(command).await?;
+
+    let mut len = [0; 4];
+    stream
+        .read_exact(&mut len)
+        .await
+        .context("failed to read APDU response from ledger")?;
+
+    let mut response = vec![0; u32::from_be_bytes(len) as usize + 2];
+    stream
+        .read_exact(&mut response)
+        .await
+        .context("failed to read APDU response from ledger")?;
+
+    Ok(response)
+}
+
+/// Configuration of a chain for ledger signer
+#[derive(Debug, Clone)]
+pub struct LedgerChainConfig {
+    /// HD path of signer (`m/44'/<coin type>'/<account>'/<change>/<index>`)
+    path: [u32; 5],
+    /// Bech32 prefix
+    account_prefix: String,
+}
+
+impl LedgerChainConfig {
+    /// Creates a new ledger chain config
+    pub fn new(hd_path: Option<&str>, account_prefix: Option<&str>) -> Result<Self> {
+        Ok(Self {
+            path: parse_hd_path(hd_path.unwrap_or(DEFAULT_HD_PATH))?,
+            account_prefix: account_prefix.unwrap_or(DEFAULT_ACCOUNT_PREFIX).to_owned(),
+        })
+    }
+
+    /// Returns HD path serialized as expected by Cosmos ledger app
+    fn path_bytes(&self) -> Vec<u8> {
+        self.path
+            .iter()
+            .flat_map(|component| component.to_le_bytes())
+            .collect()
+    }
+}
+
+/// Parses HD path supported by Cosmos ledger app (first three components must be hardened and last two must not be)
+fn parse_hd_path(hd_path: &str) -> Result<[u32; 5]> {
+    let mut components = hd_path.split('/');
+
+    ensure!(
+        components.next() == Some("m"),
+        "HD path must start with `m`: {}",
+        hd_path
+    );
+
+    let components = components
+        .map(|component| match component.strip_suffix('\'') {
+            Some(index) => Ok(index.parse::<u32>()? | HARDENED),
+            None => Ok(component.parse::<u32>()?),
+        })
+        .collect::<Result<Vec<_>>>()
+        .with_context(|| format!("invalid HD path: {}", hd_path))?;
+
+    let path: [u32; 5] = components
+        .try_into()
+        .map_err(|_| anyhow!("HD path must have exactly five components: {}", hd_path))?;
+
+    ensure!(
+        path[..3].iter().all(|component| component & HARDENED != 0)
+            && path[3..].iter().all(|component| component & HARDENED == 0),
+        "ledger only supports HD paths of form `m/44'/<coin type>'/<account>'/<change>/<index>`: {}",
+        hd_path
+    );
+
+    Ok(path)
+}
+
+/// Signer implementation using Cosmos app on a ledger device (sign docs are signed in amino JSON sign mode as ledger
+/// cannot sign protobuf encoded sign docs; protobuf encoded solo machine sign bytes cannot be signed at all)
+pub struct LedgerSigner {
+    /// Transport for exchanging APDUs with ledger device
+    transport: Arc<dyn LedgerTransport>,
+    /// Chain configurations
+    config_map: HashMap<ChainId, LedgerChainConfig>,
+    /// Public keys fetched from ledger device (also serializes the exchanges so that chunks of different messages are
+    /// not interleaved)
+    public_keys: Mutex<HashMap<ChainId, PublicKey>>,
+}
+
+impl LedgerSigner {
+    /// Creates a new instance of ledger signer
+    pub fn new(
+        transport: Arc<dyn LedgerTransport>,
+        config_map: HashMap<ChainId, LedgerChainConfig>,
+    ) -> Self {
+        Self {
+            transport,
+            config_map,
+            public_keys: Default::default(),
+        }
+    }
+
+    fn get_config(&self, chain_id: &ChainId) -> Result<&LedgerChainConfig> {
+        self.config_map
+            .get(chain_id)
+            .ok_or_else(|| anyhow!("signer for chain id {} not found", chain_id))
+    }
+
+    /// Sends an APDU command to ledger device and returns response data after checking status word
+    async fn exchange(&self, ins: u8, p1: u8, p2: u8, data: &[u8]) -> Result<Vec<u8>> {
+        ensure!(data.len() <= u8::MAX as usize, "APDU data is too long");
+
+        let mut command = vec![CLA, ins, p1, p2, data.len() as u8];
+        command.extend_from_slice(data);
+
+        let mut response = self.transport.exchange(&command).await?;
+
+        ensure!(
+            response.len() >= 2,
+            "invalid APDU response from ledger: {}",
+            hex::encode(&response)
+        );
+
+        let status =
+            u16::from_be_bytes([response[response.len() - 2], response[response.len() - 1]]);
+        response.truncate(response.len() - 2);
+
+        if status != SW_OK {
+            bail!(
+                "ledger returned error: {} (0x{:04x})",
+                status_message(status),
+                status
+            );
+        }
+
+        Ok(response)
+    }
+
+    /// Fetches public key from ledger device and checks that the address computed by ledger matches it
+    async fn fetch_public_key(&self, config: &LedgerChainConfig) -> Result<PublicKey> {
+        let mut data = vec![config.account_prefix.len() as u8];
+        data.extend_from_slice(config.account_prefix.as_bytes());
+        data.extend_from_slice(&config.path_bytes());
+
+        let response = self
+            .exchange(INS_GET_ADDR_SECP256K1, 0, 0, &data)
+            .await
+            .context("failed to get public key from ledger")?;
+
+        ensure!(
+            response.len() > 33,
+            "invalid public key response from ledger"
+        );
+
+        let public_key = PublicKey::Secp256k1(
+            VerifyingKey::from_sec1_bytes(&response[..33])
+                .context("invalid public key returned by ledger")?,
+        );
+
+        let address =
+            std::str::from_utf8(&response[33..]).context("invalid address returned by ledger")?;
+
+        ensure!(
+            address == public_key.account_address(&config.account_prefix)?,
+            "address returned by ledger ({}) does not match its public key",
+            address
+        );
+
+        Ok(public_key)
+    }
+}
+
+/// Returns a human readable message for status words returned by Cosmos ledger app
+fn status_message(status: u16) -> &'static str {
+    match status {
+        0x6400 => "execution error",
+        0x6700 => "wrong length",
+        0x6982 => "empty buffer",
+        0x6983 => "output buffer too small",
+        0x6984 => "data is invalid",
+        0x6986 => "request rejected by user",
+        0x6a80 => "bad key handle",
+        0x6b00 => "invalid P1/P2",
+        0x6d00 => "instruction not supported",
+        0x6e00 | 0x6e01 => "Cosmos app is not open",
+        0x6f00 => "unknown error",
+        _ => "unexpected status",
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl GetPublicKey for LedgerSigner {
+    async fn get_public_key(&self, chain_id: &ChainId) -> Result<PublicKey> {
+        let config = self.get_config(chain_id)?;
+        let mut public_keys = self.public_keys.lock().await;
+
+        if let Some(public_key) = public_keys.get(chain_id) {
+            return Ok(public_key.clone());
+        }
+
+        let public_key = self.fetch_public_key(config).await?;
+        public_keys.insert(chain_id.clone(), public_key.clone());
+
+        Ok(public_key)
+    }
+
+    async fn to_account_address(&self, chain_id: &ChainId) -> Result<String> {
+        let config = self.get_config(chain_id)?;
+
+        self.get_public_key(chain_id)
+            .await?
+            .account_address(&config.account_prefix)
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl Signer for LedgerSigner {
+    /// Signs given message on ledger device (sign docs are converted to amino JSON and signed in JSON mode)
+    ///
+    /// Solo machine sign bytes are rejected instead of being sent to ledger: they are protobuf encoded, so Cosmos app
+    /// would either refuse them or, in textual mode, sign a CBOR envelope which solo machine client on chain cannot
+    /// verify.
+    async fn sign(
+        &self,
+        _request_id: Option<&str>,
+        chain_id: &ChainId,
+        message: Message<'_>,
+    ) -> Result<Vec<u8>> {
+        let config = self.get_config(chain_id)?;
+
+        let (p2, message) = match message {
+            Message::SignDoc(sign_doc) => (P2_JSON, to_std_sign_doc(sign_doc)?),
+            Message::SignBytes(_) | Message::PacketCommitment { .. } => bail!(
+                "solo machine sign bytes are protobuf encoded and cannot be signed by Cosmos ledger app (it only \
+                signs amino JSON and textual sign docs)"
+            ),
+        };
+
+        ensure!(!message.is_empty(), "cannot sign an empty message");
+
+        // Holding the lock ensures that chunks of different messages are not interleaved
+        let _public_keys = self.public_keys.lock().await;
+
+        self.exchange(INS_SIGN_SECP256K1, P1_INIT, p2, &config.path_bytes())
+            .await
+            .context("failed to initialize signing on ledger")?;
+
+        let chunks = message.chunks(CHUNK_SIZE).collect::<Vec<_>>();
+        let mut signature = Vec::new();
+
+        for (index, chunk) in chunks.iter().enumerate() {
+            let p1 = if index == chunks.len() - 1 {
+                P1_LAST
+            } else {
+                P1_ADD
+            };
+
+            signature = self
+                .exchange(INS_SIGN_SECP256K1, p1, p2, chunk)
+                .await
+                .context("failed to sign message on ledger")?;
+        }
+
+        let signature =
+            Signature::from_der(&signature).context("invalid signature returned by ledger")?;
+
+        Ok(signature
+            .normalize_s()
+            .unwrap_or(signature)
+            .as_ref()
+            .to_vec())
+    }
+
+    fn sign_mode(&self) -> SignMode {
+        SignMode::LegacyAminoJson
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex as StdMutex;
+
+    use cosmos_sdk_proto::{
+        cosmos::{
+            base::v1beta1::Coin,
+            tx::v1beta1::{AuthInfo, Fee, SignDoc, SignerInfo, TxBody},
+        },
+        ibc::applications::transfer::v1::MsgTransfer,
+    };
+    use k256::ecdsa::{
+        signature::{Signature as _, Signer as _, Verifier},
+        SigningKey,
+    };
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::types::proto_util::{proto_encode, AnyConvert};
+
+    /// Message is a CBOR encoded `SIGN_MODE_TEXTUAL` envelope
+    const P2_TEXTUAL: u8 = 0x01;
+
+    /// Emulated Cosmos ledger app (uses the same key for all the HD paths and, like the real app, parses the message
+    /// according to the P2 mode before signing it)
+    struct EmulatedApp {
+        signing_key: SigningKey,
+        reject: bool,
+        message: StdMutex<(u8, Vec<u8>)>,
+    }
+
+    impl EmulatedApp {
+        fn new(reject: bool) -> Self {
+            Self {
+                signing_key: SigningKey::from_bytes(&[7; 32]).unwrap(),
+                reject,
+                message: Default::default(),
+            }
+        }
+
+        fn handle(&self, command: &[u8]) -> Vec<u8> {
+            let (header, data) = command.split_at(5);
+            assert_eq!(header[0], CLA);
+            assert_eq!(header[4] as usize, data.len());
+
+            let mut response = match (header[1], header[2]) {
+                (INS_GET_ADDR_SECP256K1, _) => {
+                    let prefix_len = data[0] as usize;
+                    let prefix = std::str::from_utf8(&data[1..prefix_len + 1]).unwrap();
+                    assert_eq!(data.len(), prefix_len + 21);
+
+                    let public_key = PublicKey::Secp256k1(self.signing_key.verifying_key());
+
+                    let mut response = public_key.verifying_key().unwrap().to_bytes().to_vec();
+                    response
+                        .extend_from_slice(public_key.account_address(prefix).unwrap().as_bytes());
+                    response
+                }
+                (INS_SIGN_SECP256K1, _) if header[3] != P2_JSON && header[3] != P2_TEXTUAL => {
+                    return vec![0x6b, 0x00]
+                }
+                (INS_SIGN_SECP256K1, P1_INIT) => {
+                    assert_eq!(data.len(), 20);
+                    *self.message.lock().unwrap() = (header[3], Vec::new());
+                    vec![]
+                }
+                (INS_SIGN_SECP256K1, P1_ADD) => {
+                    self.message.lock().unwrap().1.extend_from_slice(data);
+                    vec![]
+                }
+                (INS_SIGN_SECP256K1, P1_LAST) => {
+                    let mut message = self.message.lock().unwrap();
+                    message.1.extend_from_slice(data);
+
+                    let (mode, ref message) = *message;
+
+                    let is_valid = match mode {
+                        P2_JSON => serde_json::from_slice::<serde_json::Value>(message)
+                            .map(|value| value.is_object())
+                            .unwrap_or(false),
+                        // Textual envelope is a CBOR map (major type 5)
+                        _ => message.first().map(|byte| byte >> 5 == 5).unwrap_or(false),
+                    };
+
+                    if !is_valid {
+                        return vec![0x69, 0x84];
+                    }
+
+                    if self.reject {
+                        return vec![0x69, 0x86];
+                    }
+
+                    let signature: Signature = self.signing_key.sign(message);
+                    signature.to_der().as_bytes().to_vec()
+                }
+                _ => return vec![0x6d, 0x00],
+            };
+
+            response.extend_from_slice(&SW_OK.to_be_bytes());
+            response
+        }
+    }
+
+    #[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+    #[cfg_attr(feature = "wasm", async_trait(?Send))]
+    impl LedgerTransport for EmulatedApp {
+        async fn exchange(&self, command: &[u8]) -> Result<Vec<u8>> {
+            Ok(self.handle(command))
+        }
+    }
+
+    fn ledger_signer(transport: Arc<dyn LedgerTransport>, chain_id: &ChainId) -> LedgerSigner {
+        let mut config_map = HashMap::new();
+        config_map.insert(
+            chain_id.clone(),
+            LedgerChainConfig::new(None, Some("stag")).unwrap(),
+        );
+
+        LedgerSigner::new(transport, config_map)
+    }
+
+    fn sign_doc() -> Vec<u8> {
+        let msg = MsgTransfer {
+            source_port: "transfer".to_owned(),
+            source_channel: "channel-0".to_owned(),
+            token: Some(Coin {
+                denom: "stake".to_owned(),
+                amount: "10".to_owned(),
+            }),
+            sender: "stag1sender".to_owned(),
+            receiver: "cosmos1receiver".to_owned(),
+            timeout_height: None,
+            timeout_timestamp: 0,
+        };
+
+        let body = TxBody {
+            messages: vec![msg.to_any().unwrap()],
+            // Long memo to make sure that the message is split into multiple chunks
+            memo: "m".repeat(600),
+            ..Default::default()
+        };
+
+        let auth_info = AuthInfo {
+            signer_infos: vec![SignerInfo {
+                public_key: None,
+                mode_info: None,
+                sequence: 1,
+            }],
+            fee: Some(Fee {
+                amount: vec![],
+                gas_limit: 200000,
+                payer: "".to_owned(),
+                granter: "".to_owned(),
+            }),
+        };
+
+        proto_encode(&SignDoc {
+            body_bytes: proto_encode(&body).unwrap(),
+            auth_info_bytes: proto_encode(&auth_info).unwrap(),
+            chain_id: "test-1".to_owned(),
+            account_number: 1,
+        })
+        .unwrap()
+    }
+
+    async fn check_signer(signer: &LedgerSigner, app: &EmulatedApp, chain_id: &ChainId) {
+        let public_key = signer.get_public_key(chain_id).await.unwrap();
+        assert_eq!(
+            public_key.to_string(),
+            PublicKey::Secp256k1(app.signing_key.verifying_key()).to_string()
+        );
+        assert_eq!(
+            signer.to_account_address(chain_id).await.unwrap(),
+            public_key.account_address("stag").unwrap()
+        );
+        assert_eq!(signer.sign_mode(), SignMode::LegacyAminoJson);
+
+        let verifying_key = public_key.verifying_key().unwrap();
+
+        let sign_doc = sign_doc();
+        let signature = signer
+            .sign(None, chain_id, Message::SignDoc(&sign_doc))
+            .await
+            .unwrap();
+        let signature = Signature::from_bytes(&signature).unwrap();
+        assert!(signature.normalize_s().is_none());
+        assert!(verifying_key
+            .verify(&to_std_sign_doc(&sign_doc).unwrap(), &signature)
+            .is_ok());
+
+        // Protobuf encoded solo machine sign bytes are never sent to ledger
+        let err = signer
+            .sign(None, chain_id, Message::SignBytes(b"\x08\x01\x10\x02"))
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("cannot be signed by Cosmos ledger app"));
+
+        assert!(signer
+            .get_public_key(&"unknown-1".parse().unwrap())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ledger_signer() {
+        let chain_id: ChainId = "test-1".parse().unwrap();
+        let app = Arc::new(EmulatedApp::new(false));
+
+        let signer = ledger_signer(app.clone(), &chain_id);
+        check_signer(&signer, &app, &chain_id).await;
+
+        let rejecting_app = Arc::new(EmulatedApp::new(true));
+        let signer = ledger_signer(rejecting_app, &chain_id);
+
+        let err = signer
+            .sign(None, &chain_id, Message::SignDoc(&sign_doc()))
+            .await
+            .unwrap_err();
+        assert!(format!("{:?}", err).contains("rejected by user"));
+    }
+
+    #[tokio::test]
+    async fn test_emulated_app_rejects_invalid_payload() {
+        let chain_id: ChainId = "test-1".parse().unwrap();
+        let signer = ledger_signer(Arc::new(EmulatedApp::new(false)), &chain_id);
+        let path = signer.get_config(&chain_id).unwrap().path_bytes();
+
+        let sign = |p2: u8, payload: &'static [u8]| {
+            let signer = &signer;
+            let path = &path;
+
+            async move {
+                signer
+                    .exchange(INS_SIGN_SECP256K1, P1_INIT, p2, path)
+                    .await?;
+                signer
+                    .exchange(INS_SIGN_SECP256K1, P1_LAST, p2, payload)
+                    .await
+            }
+        };
+
+        // Protobuf payload is neither valid JSON nor a textual envelope
+        let err = sign(P2_JSON, b"\x08\x01\x10\x02").await.unwrap_err();
+        assert!(err.to_string().contains("data is invalid"));
+        let err = sign(P2_TEXTUAL, b"\x08\x01\x10\x02").await.unwrap_err();
+        assert!(err.to_string().contains("data is invalid"));
+
+        // JSON payload is not valid in textual mode and vice versa
+        assert!(sign(P2_TEXTUAL, br#"{"a":"b"}"#).await.is_err());
+        assert!(sign(P2_JSON, b"\xa1\x01\x80").await.is_err());
+
+        assert!(sign(P2_JSON, br#"{"a":"b"}"#).await.is_ok());
+        assert!(sign(P2_TEXTUAL, b"\xa1\x01\x80").await.is_ok());
+
+        let err = sign(0x02, br#"{"a":"b"}"#).await.unwrap_err();
+        assert!(err.to_string().contains("invalid P1/P2"));
+    }
+
+    #[tokio::test]
+    async fn test_tcp_transport() {
+        let chain_id: ChainId = "test-1".parse().unwrap();
+        let app = Arc::new(EmulatedApp::new(false));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // Speculos compatible APDU server
+        let server_app = app.clone();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+
+            loop {
+                let mut len = [0; 4];
+                if stream.read_exact(&mut len).await.is_err() {
+                    break;
+                }
+
+                let mut command = vec![0; u32::from_be_bytes(len) as usize];
+                stream.read_exact(&mut command).await.unwrap();
+
+                let response = server_app.handle(&command);
+                let data_len = (response.len() - 2) as u32;
+
+                stream.write_all(&data_len.to_be_bytes()).await.unwrap();
+                stream.write_all(&response).await.unwrap();
+            }
+        });
+
+        let signer = ledger_signer(Arc::new(TcpTransport::new(addr.to_string())), &chain_id);
+        check_signer(&signer, &app, &chain_id).await;
+    }
+
+    #[test]
+    fn test_parse_hd_path() {
+        assert_eq!(
+            parse_hd_path("m/44'/118'/1'/0/2").unwrap(),
+            [44 | HARDENED, 118 | HARDENED, 1 | HARDENED, 0, 2]
+        );
+
+        assert!(parse_hd_path("44'/118'/0'/0/0").is_err());
+        assert!(parse_hd_path("m/44'/118'/0'/0").is_err());
+        assert!(parse_hd_path("m/44'/118'/0'/0'/0'").is_err());
+        assert!(parse_hd_path("m/44'/118'/x'/0/0").is_err());
+    }
+}
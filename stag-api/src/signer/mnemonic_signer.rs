@@ -1,18 +1,22 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, str::FromStr, sync::Arc};
 
 use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
 use bip32::{DerivationPath, ExtendedPrivateKey, Language, Mnemonic};
+use ed25519_dalek::{Keypair as Ed25519Keypair, SecretKey as Ed25519SecretKey};
 use k256::ecdsa::{Signature, SigningKey};
+use p256::ecdsa::SigningKey as Secp256r1SigningKey;
 
 use crate::types::{
     ics::core::ics24_host::identifier::ChainId,
     public_key::{PublicKey, PublicKeyAlgo},
 };
 
-use super::{GetPublicKey, Message, Signer};
+use super::{slip10, GetPublicKey, Message, Signer};
 
 const DEFAULT_HD_PATH: &str = "m/44'/118'/0'/0/0";
+/// Default HD path for ed25519 keys (ed25519 only supports hardened derivation)
+const DEFAULT_ED25519_HD_PATH: &str = "m/44'/118'/0'/0'/0'";
 const DEFAULT_ACCOUNT_PREFIX: &str = "cosmos";
 const DEFAULT_ADDRESS_ALGO: &str = "secp256k1";
 
@@ -58,11 +62,16 @@ impl MnemonicSignerConfig {
         let mnemonic =
             Mnemonic::new(mnemonic, Language::English).map_err(|_| anyhow!("invalid mnemonic"))?;
 
-        let hd_path = hd_path.unwrap_or(DEFAULT_HD_PATH).to_string();
-        let account_prefix = account_prefix.unwrap_or(DEFAULT_ACCOUNT_PREFIX).to_string();
-
         let algo = algo.unwrap_or_else(|| DEFAULT_ADDRESS_ALGO.parse().unwrap());
 
+        let hd_path = hd_path
+            .unwrap_or(match algo {
+                PublicKeyAlgo::Ed25519 => DEFAULT_ED25519_HD_PATH,
+                _ => DEFAULT_HD_PATH,
+            })
+            .to_string();
+        let account_prefix = account_prefix.unwrap_or(DEFAULT_ACCOUNT_PREFIX).to_string();
+
         Ok(MnemonicSignerConfig {
             mnemonic,
             hd_path,
@@ -71,33 +80,73 @@ impl MnemonicSignerConfig {
         })
     }
 
-    /// Returns the signing key
+    /// Returns the HD path
+    fn get_derivation_path(&self) -> Result<DerivationPath> {
+        DerivationPath::from_str(&self.hd_path).context("invalid HD path")
+    }
+
+    /// Returns the signing key (secp256k1)
     fn get_signing_key(&self) -> Result<SigningKey> {
         let seed = self.mnemonic.to_seed("");
-        let hd_path = DerivationPath::from_str(&self.hd_path).context("invalid HD path")?;
+        let hd_path = self.get_derivation_path()?;
         let private_key =
             ExtendedPrivateKey::<SigningKey>::derive_from_path(seed.as_bytes(), &hd_path).unwrap();
 
         Ok(private_key.into())
     }
 
+    /// Returns the ed25519 keypair
+    fn get_ed25519_keypair(&self) -> Result<Ed25519Keypair> {
+        let seed = self.mnemonic.to_seed("");
+        let secret_key_bytes =
+            slip10::derive_ed25519(seed.as_bytes(), &self.get_derivation_path()?)?;
+
+        let secret = Ed25519SecretKey::from_bytes(&secret_key_bytes)?;
+        let public = (&secret).into();
+
+        Ok(Ed25519Keypair { secret, public })
+    }
+
+    /// Returns the secp256r1 signing key
+    fn get_secp256r1_signing_key(&self) -> Result<Secp256r1SigningKey> {
+        let seed = self.mnemonic.to_seed("");
+        let secret_key = slip10::derive_secp256r1(seed.as_bytes(), &self.get_derivation_path()?)?;
+
+        Ok(secret_key.into())
+    }
+
     /// Returns the public key
     pub(crate) fn get_public_key(&self) -> Result<PublicKey> {
-        let signing_key = self.get_signing_key()?;
-        let verifying_key = signing_key.verifying_key();
-
         match self.algo {
-            PublicKeyAlgo::Secp256k1 => Ok(PublicKey::Secp256k1(verifying_key)),
+            PublicKeyAlgo::Secp256k1 => Ok(PublicKey::Secp256k1(
+                self.get_signing_key()?.verifying_key(),
+            )),
             #[cfg(feature = "ethermint")]
-            PublicKeyAlgo::EthSecp256k1 => Ok(PublicKey::EthSecp256k1(verifying_key)),
+            PublicKeyAlgo::EthSecp256k1 => Ok(PublicKey::EthSecp256k1(
+                self.get_signing_key()?.verifying_key(),
+            )),
+            PublicKeyAlgo::Ed25519 => Ok(PublicKey::Ed25519(self.get_ed25519_keypair()?.public)),
+            PublicKeyAlgo::Secp256r1 => Ok(PublicKey::Secp256r1(
+                self.get_secp256r1_signing_key()?.verifying_key(),
+            )),
         }
     }
 
     /// Returns account address prefix
-    fn get_account_prefix(&self) -> &str {
+    pub(crate) fn get_account_prefix(&self) -> &str {
         &self.account_prefix
     }
 
+    /// Returns HD path
+    pub(crate) fn get_hd_path(&self) -> &str {
+        &self.hd_path
+    }
+
+    /// Returns algorithm used for address generation
+    pub(crate) fn get_algo(&self) -> PublicKeyAlgo {
+        self.algo
+    }
+
     /// Returns the account address
     pub(crate) fn get_account_address(&self) -> Result<String> {
         self.get_public_key()?
@@ -119,6 +168,12 @@ impl MnemonicSigner {
     }
 }
 
+impl From<MnemonicSigner> for Arc<dyn Signer> {
+    fn from(signer: MnemonicSigner) -> Self {
+        Arc::new(signer)
+    }
+}
+
 #[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
 #[cfg_attr(feature = "wasm", async_trait(?Send))]
 impl GetPublicKey for MnemonicSigner {
@@ -142,20 +197,52 @@ impl Signer for MnemonicSigner {
     ) -> Result<Vec<u8>> {
         let config = self.get_config(chain_id)?;
 
-        let signing_key = config.get_signing_key()?;
+        let signature = match config.algo {
+            PublicKeyAlgo::Secp256k1 => {
+                let signature: Signature = <SigningKey as k256::ecdsa::signature::Signer<
+                    k256::ecdsa::Signature,
+                >>::sign(
+                    &config.get_signing_key()?, message.as_ref()
+                );
 
-        let signature: Signature = match config.algo {
-            PublicKeyAlgo::Secp256k1 => <SigningKey as k256::ecdsa::signature::Signer<
-                k256::ecdsa::Signature,
-            >>::sign(&signing_key, message.as_ref()),
+                signature.as_ref().to_vec()
+            }
             #[cfg(feature = "ethermint")]
-            PublicKeyAlgo::EthSecp256k1 => <SigningKey as k256::ecdsa::signature::Signer<
-                k256::ecdsa::recoverable::Signature,
-            >>::sign(&signing_key, message.as_ref())
-            .into(),
+            PublicKeyAlgo::EthSecp256k1 => {
+                let signature: Signature =
+                    <SigningKey as k256::ecdsa::signature::Signer<
+                        k256::ecdsa::recoverable::Signature,
+                    >>::sign(&config.get_signing_key()?, message.as_ref())
+                    .into();
+
+                signature.as_ref().to_vec()
+            }
+            PublicKeyAlgo::Ed25519 => {
+                let signature = <Ed25519Keypair as ed25519_dalek::Signer<
+                    ed25519_dalek::Signature,
+                >>::sign(
+                    &config.get_ed25519_keypair()?, message.as_ref()
+                );
+
+                signature.to_bytes().to_vec()
+            }
+            PublicKeyAlgo::Secp256r1 => {
+                let signature = <Secp256r1SigningKey as p256::ecdsa::signature::Signer<
+                    p256::ecdsa::Signature,
+                >>::sign(
+                    &config.get_secp256r1_signing_key()?, message.as_ref()
+                );
+
+                // Cosmos SDK rejects secp256r1 signatures which are not in "low S" form
+                signature
+                    .normalize_s()
+                    .unwrap_or(signature)
+                    .as_ref()
+                    .to_vec()
+            }
         };
 
-        Ok(signature.as_ref().to_vec())
+        Ok(signature)
     }
 }
 
@@ -270,4 +357,77 @@ mod tests {
             ]
         );
     }
+
+    #[tokio::test]
+    async fn test_mnemonic_signer_ed25519() {
+        use ed25519_dalek::Verifier;
+
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        let config =
+            MnemonicSignerConfig::new(MNEMONIC, None, None, Some(PublicKeyAlgo::Ed25519)).unwrap();
+        assert_eq!(config.get_hd_path(), DEFAULT_ED25519_HD_PATH);
+
+        let mut config_map = HashMap::with_capacity(1);
+        config_map.insert(chain_id.clone(), config);
+
+        let signer = MnemonicSigner::new(config_map);
+
+        let public_key = match signer.get_public_key(&chain_id).await.unwrap() {
+            PublicKey::Ed25519(public_key) => public_key,
+            _ => panic!("expected ed25519 public key"),
+        };
+
+        let signature = signer
+            .sign(None, &chain_id, Message::SignBytes(b"test-message"))
+            .await
+            .unwrap();
+
+        assert!(public_key
+            .verify(
+                b"test-message",
+                &ed25519_dalek::Signature::from_bytes(&signature).unwrap()
+            )
+            .is_ok());
+
+        // Ed25519 keys cannot be derived using non-hardened HD path
+        let config = MnemonicSignerConfig::new(
+            MNEMONIC,
+            Some(DEFAULT_HD_PATH),
+            None,
+            Some(PublicKeyAlgo::Ed25519),
+        )
+        .unwrap();
+        assert!(config.get_public_key().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mnemonic_signer_secp256r1() {
+        use p256::ecdsa::signature::{Signature as _, Verifier};
+
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        let config =
+            MnemonicSignerConfig::new(MNEMONIC, None, None, Some(PublicKeyAlgo::Secp256r1))
+                .unwrap();
+
+        let mut config_map = HashMap::with_capacity(1);
+        config_map.insert(chain_id.clone(), config);
+
+        let signer = MnemonicSigner::new(config_map);
+
+        let public_key = match signer.get_public_key(&chain_id).await.unwrap() {
+            PublicKey::Secp256r1(public_key) => public_key,
+            _ => panic!("expected secp256r1 public key"),
+        };
+
+        let signature = signer
+            .sign(None, &chain_id, Message::SignBytes(b"test-message"))
+            .await
+            .unwrap();
+        let signature = p256::ecdsa::Signature::from_bytes(&signature).unwrap();
+
+        assert!(signature.normalize_s().is_none());
+        assert!(public_key.verify(b"test-message", &signature).is_ok());
+    }
 }
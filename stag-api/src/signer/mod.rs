@@ -1,22 +1,66 @@
 //! Transaction signers used by Stag API
+#[cfg(feature = "ledger-signer")]
+mod amino_json;
 mod builder;
 #[cfg(feature = "keplr-signer")]
 mod keplr_signer;
+#[cfg_attr(feature = "doc", doc(cfg(feature = "keystore-signer")))]
+#[cfg(feature = "keystore-signer")]
+pub mod keystore;
+#[cfg(feature = "ledger-signer")]
+mod ledger_signer;
 #[cfg(feature = "mnemonic-signer")]
 mod mnemonic_signer;
+mod multisig_signer;
+#[cfg(feature = "offline-signer")]
+mod offline_signer;
+#[cfg(feature = "pkcs11-signer")]
+mod pkcs11_signer;
+#[cfg(feature = "remote-signer")]
+mod remote_signer;
 mod signer_traits;
+mod signing_policy;
+#[cfg(feature = "mnemonic-signer")]
+mod slip10;
 
 pub use self::{
-    builder::SignerConfig,
+    builder::{MultisigSigner, SignerConfig, SigningPolicy},
     signer_traits::{GetPublicKey, Message, Signer},
+    signing_policy::{ApprovalHook, SigningIntent},
 };
 
 #[cfg(feature = "mnemonic-signer")]
-pub use self::builder::MnemonicSigner;
+pub use self::builder::{ChainSignerDetails, MnemonicSigner};
 
 #[cfg(feature = "keplr-signer")]
 pub use self::builder::KeplrSigner;
 
+#[cfg(feature = "keystore-signer")]
+pub use self::builder::KeystoreSigner;
+#[cfg(any(feature = "keystore-signer", feature = "pkcs11-signer"))]
+pub use zeroize::Zeroizing;
+
+#[cfg(feature = "ledger-signer")]
+pub use self::{
+    builder::LedgerSigner,
+    ledger_signer::{LedgerTransport, TcpTransport},
+};
+
+#[cfg(feature = "offline-signer")]
+pub use self::{
+    builder::OfflineSigner,
+    offline_signer::{
+        OfflineRequest, OfflineRequestKind, OfflineResponse, OfflineResponseKind, PublicKeyDetails,
+        RequestHook,
+    },
+};
+
+#[cfg(feature = "pkcs11-signer")]
+pub use self::builder::Pkcs11Signer;
+
+#[cfg(feature = "remote-signer")]
+pub use self::builder::RemoteSigner;
+
 #[derive(Clone)]
 /// A no-op signer
 pub struct NoopSigner;
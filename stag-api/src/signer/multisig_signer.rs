@@ -0,0 +1,306 @@
+use std::sync::Arc;
+
+use anyhow::{bail, ensure, Result};
+use async_trait::async_trait;
+use cosmos_sdk_proto::cosmos::{
+    crypto::multisig::v1beta1::CompactBitArray,
+    tx::signing::v1beta1::signature_descriptor::{
+        data::{Multi as MultiSignatureData, Sum as SignatureDataInner},
+        Data as SignatureData,
+    },
+};
+
+use crate::types::{ics::core::ics24_host::identifier::ChainId, public_key::PublicKey};
+
+use super::{GetPublicKey, Message, Signer};
+
+#[derive(Clone)]
+/// Signer implementation which collects signatures from multiple signers for a threshold multisig public key
+pub struct MultisigSigner {
+    threshold: u32,
+    signers: Vec<Arc<dyn Signer>>,
+}
+
+impl MultisigSigner {
+    /// Creates a new instance of multisig signer (`signers` must not be empty)
+    pub fn new(threshold: u32, signers: Vec<Arc<dyn Signer>>) -> Self {
+        Self { threshold, signers }
+    }
+
+    /// Returns the signer used for signing cosmos sdk transactions (multisig public keys can only sign solo machine
+    /// sign bytes)
+    fn transaction_signer(&self) -> &Arc<dyn Signer> {
+        &self.signers[0]
+    }
+
+    /// Collects signatures for solo machine sign bytes from signers (in order) until threshold is reached
+    async fn sign_bytes(
+        &self,
+        request_id: Option<&str>,
+        chain_id: &ChainId,
+        message: Message<'_>,
+    ) -> Result<SignatureData> {
+        let threshold = self.threshold as usize;
+        let mut bitarray = new_bitarray(self.signers.len());
+        let mut signatures = Vec::with_capacity(threshold);
+        let mut errors = Vec::new();
+
+        for (index, signer) in self.signers.iter().enumerate() {
+            if signatures.len() == threshold {
+                break;
+            }
+
+            match signer.sign_data(request_id, chain_id, message).await {
+                Ok(signature_data) => {
+                    set_bit(&mut bitarray, index);
+                    signatures.push(signature_data);
+                }
+                Err(err) => errors.push(format!("signer {}: {}", index, err)),
+            }
+        }
+
+        ensure!(
+            signatures.len() == threshold,
+            "collected only {} of {} required signatures for multisig: [{}]",
+            signatures.len(),
+            threshold,
+            errors.join("; ")
+        );
+
+        Ok(SignatureData {
+            sum: Some(SignatureDataInner::Multi(MultiSignatureData {
+                bitarray: Some(bitarray),
+                signatures,
+            })),
+        })
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl GetPublicKey for MultisigSigner {
+    async fn get_public_key(&self, chain_id: &ChainId) -> Result<PublicKey> {
+        let mut public_keys = Vec::with_capacity(self.signers.len());
+
+        for signer in self.signers.iter() {
+            public_keys.push(signer.get_public_key(chain_id).await?);
+        }
+
+        PublicKey::new_multisig(self.threshold, public_keys)
+    }
+
+    async fn to_account_address(&self, chain_id: &ChainId) -> Result<String> {
+        self.transaction_signer().to_account_address(chain_id).await
+    }
+
+    async fn get_transaction_public_key(&self, chain_id: &ChainId) -> Result<PublicKey> {
+        self.transaction_signer()
+            .get_transaction_public_key(chain_id)
+            .await
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl Signer for MultisigSigner {
+    async fn sign(
+        &self,
+        request_id: Option<&str>,
+        chain_id: &ChainId,
+        message: Message<'_>,
+    ) -> Result<Vec<u8>> {
+        match message {
+            Message::SignDoc(_) => {
+                self.transaction_signer()
+                    .sign(request_id, chain_id, message)
+                    .await
+            }
+            Message::SignBytes(_) | Message::PacketCommitment { .. } => {
+                bail!("multisig signer can only sign solo machine sign bytes as signature data")
+            }
+        }
+    }
+
+    async fn sign_data(
+        &self,
+        request_id: Option<&str>,
+        chain_id: &ChainId,
+        message: Message<'_>,
+    ) -> Result<SignatureData> {
+        match message {
+            Message::SignDoc(_) => {
+                self.transaction_signer()
+                    .sign_data(request_id, chain_id, message)
+                    .await
+            }
+            Message::SignBytes(_) | Message::PacketCommitment { .. } => {
+                self.sign_bytes(request_id, chain_id, message).await
+            }
+        }
+    }
+}
+
+/// Creates a new compact bit array with given number of bits (all unset)
+fn new_bitarray(bits: usize) -> CompactBitArray {
+    CompactBitArray {
+        extra_bits_stored: (bits % 8) as u32,
+        elems: vec![0; bits.div_ceil(8)],
+    }
+}
+
+/// Sets bit at given index in compact bit array (most significant bit first)
+fn set_bit(bitarray: &mut CompactBitArray, index: usize) {
+    bitarray.elems[index / 8] |= 1 << (7 - (index % 8));
+}
+
+#[cfg(all(test, feature = "mnemonic-signer"))]
+mod tests {
+    use std::collections::HashMap;
+
+    use k256::ecdsa::{signature::Verifier, Signature};
+
+    use crate::signer::mnemonic_signer::{MnemonicSigner, MnemonicSignerConfig};
+
+    use super::*;
+
+    const MNEMONIC: &str = "practice empty client sauce pistol work ticket casual romance appear army fault palace coyote fox super salute slim catch kite wrist three hedgehog sign";
+    const ACCOUNT_ADDRESS: &str = "cosmos1j2qpprh2xke7qjqzehfqgjdkfgddf9dm06dugw";
+
+    fn mnemonic_signer(hd_path: &str, chain_id: &str) -> Arc<dyn Signer> {
+        let mut config_map = HashMap::new();
+        config_map.insert(
+            chain_id.parse().unwrap(),
+            MnemonicSignerConfig::new(MNEMONIC, Some(hd_path), None, None).unwrap(),
+        );
+
+        Arc::new(MnemonicSigner::new(config_map))
+    }
+
+    fn multisig_signer(threshold: u32, chain_ids: [&str; 3]) -> MultisigSigner {
+        MultisigSigner::new(
+            threshold,
+            vec![
+                mnemonic_signer("m/44'/118'/0'/0/0", chain_ids[0]),
+                mnemonic_signer("m/44'/118'/0'/0/1", chain_ids[1]),
+                mnemonic_signer("m/44'/118'/0'/0/2", chain_ids[2]),
+            ],
+        )
+    }
+
+    fn single_signature(signature_data: &SignatureData) -> Signature {
+        match signature_data.sum {
+            Some(SignatureDataInner::Single(ref single)) => {
+                Signature::try_from(single.signature.as_slice()).unwrap()
+            }
+            _ => panic!("expected single signature data"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_multisig_public_key() {
+        let signer = multisig_signer(2, ["test-1", "test-1", "test-1"]);
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        let public_key = signer.get_public_key(&chain_id).await.unwrap();
+
+        match public_key {
+            PublicKey::Multisig(ref multisig) => {
+                assert_eq!(multisig.threshold(), 2);
+                assert_eq!(multisig.public_keys().len(), 3);
+            }
+            _ => panic!("expected multisig public key"),
+        }
+
+        // Transactions are signed by first signer
+        assert_eq!(
+            signer.to_account_address(&chain_id).await.unwrap(),
+            ACCOUNT_ADDRESS
+        );
+        assert!(matches!(
+            signer.get_transaction_public_key(&chain_id).await.unwrap(),
+            PublicKey::Secp256k1(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_multisig_sign_bytes() {
+        // Second signer does not have config for chain, so signatures are collected from first and third signers
+        let signer = multisig_signer(2, ["test-1", "test-2", "test-1"]);
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        let signature_data = signer
+            .sign_data(None, &chain_id, Message::SignBytes(b"sign bytes"))
+            .await
+            .unwrap();
+
+        // Multi signature data can be verified using multisig public key of all the signers
+        let public_key = multisig_signer(2, ["test-1", "test-1", "test-1"])
+            .get_public_key(&chain_id)
+            .await
+            .unwrap();
+        assert!(public_key
+            .verify_signature_data(b"sign bytes", &signature_data)
+            .is_ok());
+        assert!(public_key
+            .verify_signature_data(b"other bytes", &signature_data)
+            .is_err());
+
+        let multi = match signature_data.sum {
+            Some(SignatureDataInner::Multi(multi)) => multi,
+            _ => panic!("expected multi signature data"),
+        };
+
+        assert_eq!(
+            multi.bitarray,
+            Some(CompactBitArray {
+                extra_bits_stored: 3,
+                elems: vec![0b1010_0000],
+            })
+        );
+        assert_eq!(multi.signatures.len(), 2);
+
+        for (signature_data, hd_path) in multi
+            .signatures
+            .iter()
+            .zip(["m/44'/118'/0'/0/0", "m/44'/118'/0'/0/2"])
+        {
+            let public_key = mnemonic_signer(hd_path, "test-1")
+                .get_public_key(&chain_id)
+                .await
+                .unwrap();
+
+            assert!(public_key
+                .verifying_key()
+                .unwrap()
+                .verify(b"sign bytes", &single_signature(signature_data))
+                .is_ok());
+        }
+
+        assert!(signer
+            .sign(None, &chain_id, Message::SignBytes(b"sign bytes"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_multisig_threshold_not_reached() {
+        let signer = multisig_signer(2, ["test-1", "test-2", "test-2"]);
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        assert!(signer
+            .sign_data(None, &chain_id, Message::SignBytes(b"sign bytes"))
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_bitarray() {
+        let mut bitarray = new_bitarray(9);
+        assert_eq!(bitarray.extra_bits_stored, 1);
+        assert_eq!(bitarray.elems, vec![0, 0]);
+
+        set_bit(&mut bitarray, 0);
+        set_bit(&mut bitarray, 8);
+        assert_eq!(bitarray.elems, vec![0b1000_0000, 0b1000_0000]);
+    }
+}
@@ -0,0 +1,660 @@
+//! Signing workflow for signers on offline (air-gapped) machines. Signing requests are written to a directory on the
+//! online machine as JSON files which are carried to the offline machine, signed there and the responses are carried
+//! back to the same directory.
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use async_trait::async_trait;
+use cosmos_sdk_proto::{
+    cosmos::tx::signing::v1beta1::signature_descriptor::{
+        data::Sum as SignatureDataInner, Data as SignatureData,
+    },
+    ibc::core::channel::v1::Packet,
+};
+use prost::Message as _;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::types::{
+    ics::core::ics24_host::identifier::ChainId, proto_util::proto_encode, public_key::PublicKey,
+};
+
+use super::{GetPublicKey, Message, Signer};
+
+/// Name of the file (in requests directory) which caches public keys received from offline signer
+const PUBLIC_KEYS_FILE: &str = "public-keys.json";
+
+/// Hook which is called with the path of every request file written by offline signer
+pub type RequestHook = Arc<dyn Fn(&Path) + Send + Sync>;
+
+/// Request for offline signer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineRequest {
+    /// Unique ID of request (derived from chain id and contents of request)
+    pub id: String,
+    /// Request ID of operation for which the request was generated
+    pub request_id: Option<String>,
+    /// Chain ID for which the request was generated
+    pub chain_id: ChainId,
+    /// Contents of request
+    #[serde(flatten)]
+    pub kind: OfflineRequestKind,
+}
+
+/// Contents of request for offline signer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum OfflineRequestKind {
+    /// Request for public key and account address of signer
+    PublicKey,
+    /// Request for signing a message
+    Sign {
+        /// Type of message (`sign-bytes` or `sign-doc`)
+        message_type: String,
+        /// Hex encoded message
+        #[serde(with = "hex::serde")]
+        message: Vec<u8>,
+        /// Hex encoded packet (only for packet commitment sign bytes)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        packet: Option<String>,
+    },
+}
+
+/// Response of offline signer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineResponse {
+    /// ID of request for which this response was generated
+    pub id: String,
+    /// Contents of response
+    #[serde(flatten)]
+    pub kind: OfflineResponseKind,
+}
+
+/// Contents of response of offline signer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum OfflineResponseKind {
+    /// Public key and account address of signer
+    PublicKey(Box<PublicKeyDetails>),
+    /// Signature of message
+    Signature {
+        /// Hex encoded signature data (`cosmos.tx.signing.v1beta1.SignatureDescriptor.Data`)
+        #[serde(with = "hex::serde")]
+        signature_data: Vec<u8>,
+    },
+}
+
+/// Public key details of offline signer for a chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicKeyDetails {
+    /// Public key of signer
+    pub public_key: PublicKey,
+    /// Public key used for signing cosmos sdk transactions
+    pub transaction_public_key: PublicKey,
+    /// Account address of signer
+    pub account_address: String,
+}
+
+impl OfflineRequest {
+    /// Creates a new request for offline signer
+    pub fn new(
+        request_id: Option<&str>,
+        chain_id: &ChainId,
+        kind: OfflineRequestKind,
+    ) -> Result<Self> {
+        let hash = Sha256::digest(serde_json::to_vec(&(chain_id, &kind))?);
+
+        Ok(Self {
+            id: hex::encode(hash),
+            request_id: request_id.map(ToOwned::to_owned),
+            chain_id: chain_id.clone(),
+            kind,
+        })
+    }
+
+    /// Creates a new request for signing given message
+    pub fn sign(
+        request_id: Option<&str>,
+        chain_id: &ChainId,
+        message: Message<'_>,
+    ) -> Result<Self> {
+        let packet = match message {
+            Message::PacketCommitment { packet, .. } => Some(hex::encode(proto_encode(packet)?)),
+            Message::SignBytes(_) | Message::SignDoc(_) => None,
+        };
+
+        Self::new(
+            request_id,
+            chain_id,
+            OfflineRequestKind::Sign {
+                message_type: message.message_type().to_owned(),
+                message: message.as_ref().to_vec(),
+                packet,
+            },
+        )
+    }
+
+    /// Reads request from given file
+    pub async fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("failed to read request file: {}", path.display()))?;
+
+        let request: Self = serde_json::from_slice(&bytes).context("invalid request file")?;
+        let expected = Self::new(None, &request.chain_id, request.kind.clone())?;
+
+        ensure!(
+            request.id == expected.id,
+            "request id does not match contents of request"
+        );
+
+        Ok(request)
+    }
+
+    /// Responds to request using given signer (on offline machine)
+    pub async fn respond(&self, signer: &impl Signer) -> Result<OfflineResponse> {
+        let kind = match self.kind {
+            OfflineRequestKind::PublicKey => {
+                OfflineResponseKind::PublicKey(Box::new(PublicKeyDetails {
+                    public_key: signer.get_public_key(&self.chain_id).await?,
+                    transaction_public_key: signer
+                        .get_transaction_public_key(&self.chain_id)
+                        .await?,
+                    account_address: signer.to_account_address(&self.chain_id).await?,
+                }))
+            }
+            OfflineRequestKind::Sign {
+                ref message_type,
+                ref message,
+                ref packet,
+            } => {
+                let packet = packet
+                    .as_ref()
+                    .map(|packet| -> Result<Packet> {
+                        Ok(Packet::decode(hex::decode(packet)?.as_slice())?)
+                    })
+                    .transpose()
+                    .context("invalid packet in request")?;
+
+                let message = match (message_type.as_str(), packet.as_ref()) {
+                    ("sign-bytes", Some(packet)) => Message::PacketCommitment {
+                        sign_bytes: message,
+                        packet,
+                    },
+                    ("sign-bytes", None) => Message::SignBytes(message),
+                    ("sign-doc", None) => Message::SignDoc(message),
+                    _ => bail!("invalid message type in request: {}", message_type),
+                };
+
+                let signature_data = signer
+                    .sign_data(self.request_id.as_deref(), &self.chain_id, message)
+                    .await?;
+
+                OfflineResponseKind::Signature {
+                    signature_data: proto_encode(&signature_data)?,
+                }
+            }
+        };
+
+        Ok(OfflineResponse {
+            id: self.id.clone(),
+            kind,
+        })
+    }
+}
+
+impl OfflineResponse {
+    /// Reads response from given file
+    pub async fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("failed to read response file: {}", path.display()))?;
+
+        serde_json::from_slice(&bytes).context("invalid response file")
+    }
+
+    /// Writes response to given file
+    pub async fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        write_json(path.as_ref(), self).await
+    }
+
+    /// Submits response to pending request in given requests directory (on online machine), which resumes the
+    /// operation waiting for it. Signatures are verified against the public key of offline signer for the chain (which
+    /// must have been submitted earlier) before the response is accepted.
+    pub async fn submit(&self, dir: impl AsRef<Path>) -> Result<()> {
+        let dir = dir.as_ref();
+        let request = OfflineRequest::read(request_path(dir, &self.id))
+            .await
+            .with_context(|| format!("no pending request found with id: {}", self.id))?;
+
+        match (&request.kind, &self.kind) {
+            (OfflineRequestKind::PublicKey, OfflineResponseKind::PublicKey(_)) => {}
+            (
+                OfflineRequestKind::Sign {
+                    message_type,
+                    message,
+                    ..
+                },
+                OfflineResponseKind::Signature { signature_data },
+            ) => {
+                let signature_data = SignatureData::decode(signature_data.as_slice())
+                    .context("invalid signature data in response")?;
+
+                let details = read_public_keys(dir)
+                    .await?
+                    .remove(&request.chain_id)
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "public key of offline signer for chain {} is not known (response to public key request must be submitted first)",
+                            request.chain_id
+                        )
+                    })?;
+
+                // Cosmos SDK transactions are signed using transaction public key
+                let public_key = match message_type.as_str() {
+                    "sign-doc" => &details.transaction_public_key,
+                    _ => &details.public_key,
+                };
+
+                public_key
+                    .verify_signature_data(message, &signature_data)
+                    .with_context(|| {
+                        format!(
+                            "signature in response does not match public key of offline signer for chain {}",
+                            request.chain_id
+                        )
+                    })?;
+            }
+            _ => bail!("type of response does not match type of request"),
+        }
+
+        self.write(response_path(dir, &self.id)).await
+    }
+}
+
+/// Signer implementation which writes signing requests to a directory and waits for responses generated by a signer
+/// on an offline machine
+pub struct OfflineSigner {
+    dir: PathBuf,
+    poll_interval: Duration,
+    timeout: Option<Duration>,
+    request_hook: Option<RequestHook>,
+    public_keys: Mutex<HashMap<ChainId, PublicKeyDetails>>,
+}
+
+impl OfflineSigner {
+    /// Creates a new instance of offline signer
+    pub fn new(
+        dir: PathBuf,
+        poll_interval: Duration,
+        timeout: Option<Duration>,
+        request_hook: Option<RequestHook>,
+    ) -> Self {
+        Self {
+            dir,
+            poll_interval,
+            timeout,
+            request_hook,
+            public_keys: Default::default(),
+        }
+    }
+
+    /// Writes request to requests directory and waits for its response
+    async fn request(&self, request: OfflineRequest) -> Result<OfflineResponseKind> {
+        let request_path = request_path(&self.dir, &request.id);
+        let response_path = response_path(&self.dir, &request.id);
+
+        if !exists(&response_path).await? {
+            write_json(&request_path, &request).await?;
+
+            if let Some(ref request_hook) = self.request_hook {
+                request_hook(&request_path);
+            }
+
+            let start = Instant::now();
+
+            while !exists(&response_path).await? {
+                if let Some(timeout) = self.timeout {
+                    ensure!(
+                        start.elapsed() < timeout,
+                        "timed out waiting for response of offline signer for request: {}",
+                        request_path.display()
+                    );
+                }
+
+                tokio::time::sleep(self.poll_interval).await;
+            }
+        }
+
+        let response = OfflineResponse::read(&response_path).await?;
+
+        ensure!(
+            response.id == request.id,
+            "id of response does not match id of request"
+        );
+
+        let _ = tokio::fs::remove_file(&request_path).await;
+        let _ = tokio::fs::remove_file(&response_path).await;
+
+        Ok(response.kind)
+    }
+
+    /// Returns public key details of offline signer for given chain (public key is requested from offline signer
+    /// only once and cached in requests directory)
+    async fn get_public_key_details(&self, chain_id: &ChainId) -> Result<PublicKeyDetails> {
+        if let Some(details) = self.cached_public_keys().get(chain_id) {
+            return Ok(details.clone());
+        }
+
+        let mut public_keys = read_public_keys(&self.dir).await?;
+
+        let details = match public_keys.get(chain_id) {
+            Some(details) => details.clone(),
+            None => {
+                let request = OfflineRequest::new(None, chain_id, OfflineRequestKind::PublicKey)?;
+
+                let details = match self.request(request).await? {
+                    OfflineResponseKind::PublicKey(details) => *details,
+                    OfflineResponseKind::Signature { .. } => {
+                        bail!("expected public key in response of offline signer")
+                    }
+                };
+
+                public_keys.insert(chain_id.clone(), details.clone());
+                write_json(&self.dir.join(PUBLIC_KEYS_FILE), &public_keys).await?;
+
+                details
+            }
+        };
+
+        self.cached_public_keys()
+            .insert(chain_id.clone(), details.clone());
+
+        Ok(details)
+    }
+
+    fn cached_public_keys(&self) -> std::sync::MutexGuard<'_, HashMap<ChainId, PublicKeyDetails>> {
+        self.public_keys
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+#[async_trait]
+impl GetPublicKey for OfflineSigner {
+    async fn get_public_key(&self, chain_id: &ChainId) -> Result<PublicKey> {
+        Ok(self.get_public_key_details(chain_id).await?.public_key)
+    }
+
+    async fn to_account_address(&self, chain_id: &ChainId) -> Result<String> {
+        Ok(self.get_public_key_details(chain_id).await?.account_address)
+    }
+
+    async fn get_transaction_public_key(&self, chain_id: &ChainId) -> Result<PublicKey> {
+        Ok(self
+            .get_public_key_details(chain_id)
+            .await?
+            .transaction_public_key)
+    }
+}
+
+#[async_trait]
+impl Signer for OfflineSigner {
+    async fn sign(
+        &self,
+        request_id: Option<&str>,
+        chain_id: &ChainId,
+        message: Message<'_>,
+    ) -> Result<Vec<u8>> {
+        let signature_data = self.sign_data(request_id, chain_id, message).await?;
+
+        match signature_data.sum {
+            Some(SignatureDataInner::Single(single)) => Ok(single.signature),
+            _ => Err(anyhow!(
+                "offline signer returned multi signature data for {}",
+                message.message_type()
+            )),
+        }
+    }
+
+    async fn sign_data(
+        &self,
+        request_id: Option<&str>,
+        chain_id: &ChainId,
+        message: Message<'_>,
+    ) -> Result<SignatureData> {
+        let request = OfflineRequest::sign(request_id, chain_id, message)?;
+
+        match self.request(request).await? {
+            OfflineResponseKind::Signature { signature_data } => {
+                SignatureData::decode(signature_data.as_slice())
+                    .context("invalid signature data in response of offline signer")
+            }
+            OfflineResponseKind::PublicKey(_) => {
+                bail!("expected signature in response of offline signer")
+            }
+        }
+    }
+}
+
+/// Returns path of request file with given id in given directory
+fn request_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{}.request.json", id))
+}
+
+/// Returns path of response file with given id in given directory
+fn response_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{}.response.json", id))
+}
+
+/// Reads public keys of offline signer (for all the chains) cached in given requests directory
+async fn read_public_keys(dir: &Path) -> Result<HashMap<ChainId, PublicKeyDetails>> {
+    let public_keys_path = dir.join(PUBLIC_KEYS_FILE);
+
+    if !exists(&public_keys_path).await? {
+        return Ok(HashMap::new());
+    }
+
+    serde_json::from_slice(&tokio::fs::read(&public_keys_path).await?)
+        .context("invalid public keys file")
+}
+
+/// Returns true if given file exists
+async fn exists(path: &Path) -> Result<bool> {
+    match tokio::fs::metadata(path).await {
+        Ok(_) => Ok(true),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Writes given value as JSON to given file (atomically, so that readers never see a partially written file)
+async fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let temp_path = path.with_extension("tmp");
+
+    tokio::fs::write(&temp_path, serde_json::to_vec_pretty(value)?)
+        .await
+        .with_context(|| format!("failed to write file: {}", temp_path.display()))?;
+    tokio::fs::rename(&temp_path, path)
+        .await
+        .with_context(|| format!("failed to write file: {}", path.display()))
+}
+
+#[cfg(all(test, feature = "mnemonic-signer"))]
+mod tests {
+    use crate::signer::{MnemonicSigner, SignerConfig};
+
+    use super::*;
+
+    const MNEMONIC: &str = "practice empty client sauce pistol work ticket casual romance appear army fault palace coyote fox super salute slim catch kite wrist three hedgehog sign";
+    const ACCOUNT_ADDRESS: &str = "cosmos1j2qpprh2xke7qjqzehfqgjdkfgddf9dm06dugw";
+
+    /// Responds to given number of requests written to requests directory (simulates carrying requests to offline
+    /// machine and responses back)
+    async fn respond(dir: &Path, signer: &impl Signer, count: usize) {
+        let mut responded = std::collections::HashSet::new();
+
+        while responded.len() < count {
+            let mut entries = tokio::fs::read_dir(dir).await.unwrap();
+
+            while let Some(entry) = entries.next_entry().await.unwrap() {
+                let path = entry.path();
+
+                if !path.to_string_lossy().ends_with(".request.json") {
+                    continue;
+                }
+
+                // Request file may be removed by offline signer after its response is consumed
+                if let Ok(request) = OfflineRequest::read(&path).await {
+                    if !responded.contains(&request.id) {
+                        let response = request.respond(signer).await.unwrap();
+                        response.submit(dir).await.unwrap();
+                        responded.insert(request.id);
+                    }
+                }
+            }
+
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_offline_signer() {
+        let dir = std::env::temp_dir().join(format!("stag-offline-signer-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        let mut config = MnemonicSigner::new();
+        config
+            .add_chain_config(chain_id.clone(), MNEMONIC, None, None, None)
+            .unwrap();
+        let mnemonic_signer = config.into_signer().unwrap();
+
+        let signer = OfflineSigner::new(
+            dir.clone(),
+            Duration::from_millis(10),
+            Some(Duration::from_secs(10)),
+            None,
+        );
+
+        let (result, _) = tokio::join!(
+            async {
+                let account_address = signer.to_account_address(&chain_id).await?;
+                let signature = signer
+                    .sign(None, &chain_id, Message::SignDoc(b"sign doc"))
+                    .await?;
+
+                Ok::<_, anyhow::Error>((account_address, signature))
+            },
+            respond(&dir, &mnemonic_signer, 2)
+        );
+
+        let (account_address, signature) = result.unwrap();
+
+        assert_eq!(account_address, ACCOUNT_ADDRESS);
+        assert_eq!(
+            signature,
+            mnemonic_signer
+                .sign(None, &chain_id, Message::SignDoc(b"sign doc"))
+                .await
+                .unwrap()
+        );
+
+        // Public keys are cached in requests directory
+        let signer = OfflineSigner::new(
+            dir.clone(),
+            Duration::from_millis(10),
+            Some(Duration::from_millis(50)),
+            None,
+        );
+        assert_eq!(
+            signer.to_account_address(&chain_id).await.unwrap(),
+            ACCOUNT_ADDRESS
+        );
+
+        // Requests time out when no response is submitted
+        assert!(signer
+            .sign(None, &chain_id, Message::SignBytes(b"sign bytes"))
+            .await
+            .is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_submit_verifies_signature() {
+        let dir =
+            std::env::temp_dir().join(format!("stag-offline-signer-submit-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        let mut config = MnemonicSigner::new();
+        config
+            .add_chain_config(chain_id.clone(), MNEMONIC, None, None, None)
+            .unwrap();
+        let mnemonic_signer = config.into_signer().unwrap();
+
+        let mut config = MnemonicSigner::new();
+        config
+            .add_chain_config(
+                chain_id.clone(),
+                MNEMONIC,
+                Some("m/44'/118'/0'/0/1"),
+                None,
+                None,
+            )
+            .unwrap();
+        let other_signer = config.into_signer().unwrap();
+
+        let request =
+            OfflineRequest::sign(None, &chain_id, Message::SignBytes(b"sign bytes")).unwrap();
+        write_json(&request_path(&dir, &request.id), &request)
+            .await
+            .unwrap();
+
+        // Signatures cannot be verified before public key of offline signer is known
+        let response = request.respond(&mnemonic_signer).await.unwrap();
+        let err = response.submit(&dir).await.unwrap_err();
+        assert!(err.to_string().contains("is not known"));
+
+        let public_key_request =
+            OfflineRequest::new(None, &chain_id, OfflineRequestKind::PublicKey).unwrap();
+        let details = match public_key_request
+            .respond(&mnemonic_signer)
+            .await
+            .unwrap()
+            .kind
+        {
+            OfflineResponseKind::PublicKey(details) => *details,
+            OfflineResponseKind::Signature { .. } => unreachable!(),
+        };
+        write_json(
+            &dir.join(PUBLIC_KEYS_FILE),
+            &HashMap::from([(chain_id.clone(), details)]),
+        )
+        .await
+        .unwrap();
+
+        // Signature generated using another key is rejected
+        let response = request.respond(&other_signer).await.unwrap();
+        assert!(response.submit(&dir).await.is_err());
+        assert!(!exists(&response_path(&dir, &request.id)).await.unwrap());
+
+        let response = request.respond(&mnemonic_signer).await.unwrap();
+        response.submit(&dir).await.unwrap();
+        assert!(exists(&response_path(&dir, &request.id)).await.unwrap());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,530 @@
+use std::{
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use async_trait::async_trait;
+use cryptoki::{
+    context::{CInitializeArgs, Pkcs11},
+    error::{Error as Pkcs11Error, RvError},
+    mechanism::{Mechanism, MechanismType},
+    object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle},
+    session::{Session, UserType},
+    slot::Slot,
+};
+use k256::ecdsa::{signature::Signature as _, VerifyingKey};
+use p256::ecdsa::VerifyingKey as Secp256r1VerifyingKey;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::types::{
+    ics::core::ics24_host::identifier::ChainId,
+    public_key::{PublicKey, PublicKeyAlgo},
+};
+
+use super::{GetPublicKey, Message, Signer};
+
+/// DER encoded object identifier of secp256k1 curve (1.3.132.0.10)
+const SECP256K1_OID: &[u8] = &[0x06, 0x05, 0x2B, 0x81, 0x04, 0x00, 0x0A];
+/// DER encoded object identifier of secp256r1 curve (1.2.840.10045.3.1.7)
+const SECP256R1_OID: &[u8] = &[0x06, 0x08, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07];
+
+#[derive(Clone)]
+/// Signer implementation using an EC key stored in an HSM (accessed using a PKCS#11 module). The same key is used for
+/// all the chains.
+pub struct Pkcs11Signer {
+    /// Token containing the key
+    token: Pkcs11Token,
+    /// Bech32 account prefix
+    account_prefix: String,
+    /// Public key of signer
+    public_key: PublicKey,
+}
+
+#[derive(Clone)]
+/// Key pair on a PKCS#11 token
+struct Pkcs11Token {
+    /// PKCS#11 context
+    pkcs11: Pkcs11,
+    /// Slot containing the token
+    slot: Slot,
+    /// Label of key pair on token
+    key_label: String,
+    /// User PIN of token
+    pin: Zeroizing<String>,
+    /// Logged in session reused by all the signing requests (PKCS#11 sessions must not be used concurrently, so signing
+    /// requests are serialized)
+    session: Arc<Mutex<Option<SigningSession>>>,
+}
+
+/// Logged in session with token along with the handle of private key of signer
+struct SigningSession {
+    session: Session,
+    private_key: ObjectHandle,
+}
+
+impl Pkcs11Signer {
+    /// Creates a new instance of PKCS#11 signer (loads PKCS#11 module and fetches public key of signer from token)
+    pub fn new(
+        module_path: &Path,
+        slot: u64,
+        key_label: String,
+        pin: Zeroizing<String>,
+        algo: PublicKeyAlgo,
+        account_prefix: String,
+    ) -> Result<Self> {
+        let mut pkcs11 = Pkcs11::new(module_path)
+            .with_context(|| format!("failed to load PKCS#11 module: {}", module_path.display()))?;
+
+        pkcs11
+            .initialize(CInitializeArgs::OsThreads)
+            .context("failed to initialize PKCS#11 module")?;
+
+        let slot = pkcs11
+            .get_slots_with_token()
+            .context("failed to get PKCS#11 slots")?
+            .into_iter()
+            .find(|s| s.id() == slot)
+            .ok_or_else(|| anyhow!("no PKCS#11 token found in slot {}", slot))?;
+
+        ensure!(
+            pkcs11
+                .get_mechanism_list(slot)
+                .context("failed to get supported mechanisms of PKCS#11 token")?
+                .contains(&MechanismType::ECDSA),
+            "PKCS#11 token in slot {} does not support ECDSA mechanism",
+            slot.id()
+        );
+
+        let token = Pkcs11Token {
+            pkcs11,
+            slot,
+            key_label,
+            pin,
+            session: Default::default(),
+        };
+
+        let session = token.open_session()?;
+        let public_key = token.fetch_public_key(&session, algo)?;
+        let private_key = token.find_key(&session, ObjectClass::PRIVATE_KEY)?;
+
+        *token.lock_session() = Some(SigningSession {
+            session,
+            private_key,
+        });
+
+        Ok(Self {
+            token,
+            account_prefix,
+            public_key,
+        })
+    }
+}
+
+impl Pkcs11Token {
+    /// Opens a new session with token and logs in as user
+    fn open_session(&self) -> Result<Session> {
+        let session = self
+            .pkcs11
+            .open_ro_session(self.slot)
+            .context("failed to open PKCS#11 session")?;
+
+        match session.login(UserType::User, Some(&self.pin)) {
+            // Login state is shared by all the sessions of an application
+            Ok(()) | Err(Pkcs11Error::Pkcs11(RvError::UserAlreadyLoggedIn)) => Ok(session),
+            Err(err) => Err(err).context("failed to login to PKCS#11 token"),
+        }
+    }
+
+    /// Finds the EC key of given class with configured label
+    fn find_key(&self, session: &Session, class: ObjectClass) -> Result<ObjectHandle> {
+        let mut keys = session
+            .find_objects(&[
+                Attribute::Class(class),
+                Attribute::KeyType(KeyType::EC),
+                Attribute::Label(self.key_label.as_bytes().to_vec()),
+            ])
+            .context("failed to find keys on PKCS#11 token")?;
+
+        ensure!(
+            keys.len() == 1,
+            "expected exactly one EC {} with label `{}` on PKCS#11 token, found {}",
+            if class == ObjectClass::PRIVATE_KEY {
+                "private key"
+            } else {
+                "public key"
+            },
+            self.key_label,
+            keys.len()
+        );
+
+        Ok(keys.remove(0))
+    }
+
+    /// Fetches public key of signer from token and checks that it uses the curve of given algorithm
+    fn fetch_public_key(&self, session: &Session, algo: PublicKeyAlgo) -> Result<PublicKey> {
+        let expected_oid = match algo {
+            PublicKeyAlgo::Secp256k1 => SECP256K1_OID,
+            PublicKeyAlgo::Secp256r1 => SECP256R1_OID,
+            _ => bail!("PKCS#11 signer only supports `secp256k1` and `secp256r1` algorithms"),
+        };
+
+        let key = self.find_key(session, ObjectClass::PUBLIC_KEY)?;
+
+        let mut ec_params = None;
+        let mut ec_point = None;
+
+        for attribute in session
+            .get_attributes(key, &[AttributeType::EcParams, AttributeType::EcPoint])
+            .context("failed to get attributes of public key from PKCS#11 token")?
+        {
+            match attribute {
+                Attribute::EcParams(value) => ec_params = Some(value),
+                Attribute::EcPoint(value) => ec_point = Some(value),
+                _ => {}
+            }
+        }
+
+        let ec_params =
+            ec_params.ok_or_else(|| anyhow!("public key on PKCS#11 token has no EC params"))?;
+        let ec_point =
+            ec_point.ok_or_else(|| anyhow!("public key on PKCS#11 token has no EC point"))?;
+
+        ensure!(
+            ec_params == expected_oid,
+            "curve of key with label `{}` on PKCS#11 token does not match algorithm {}",
+            self.key_label,
+            algo
+        );
+
+        let public_key = match algo {
+            PublicKeyAlgo::Secp256k1 => PublicKey::Secp256k1(
+                parse_ec_point(&ec_point, |bytes| VerifyingKey::from_sec1_bytes(bytes).ok())
+                    .context("invalid secp256k1 public key on PKCS#11 token")?,
+            ),
+            _ => PublicKey::Secp256r1(
+                parse_ec_point(&ec_point, |bytes| {
+                    Secp256r1VerifyingKey::from_sec1_bytes(bytes).ok()
+                })
+                .context("invalid secp256r1 public key on PKCS#11 token")?,
+            ),
+        };
+
+        Ok(public_key)
+    }
+
+    /// Signs SHA-256 digest of message using private key on token (returns signature in "low S" form)
+    fn sign_message(&self, algo: PublicKeyAlgo, message: &[u8]) -> Result<Vec<u8>> {
+        let digest = Sha256::digest(message);
+        let mut session = self.lock_session();
+
+        let signature = match self.sign_digest(&mut session, &digest) {
+            Err(err) if is_session_error(&err) => {
+                // Session was closed or logged out by token (e.g., when token was removed and inserted again), so the
+                // digest is signed again in a new session
+                *session = None;
+                self.sign_digest(&mut session, &digest)?
+            }
+            result => result?,
+        };
+
+        match algo {
+            PublicKeyAlgo::Secp256r1 => {
+                let signature = p256::ecdsa::Signature::from_bytes(&signature)
+                    .context("invalid signature returned by PKCS#11 token")?;
+
+                Ok(signature
+                    .normalize_s()
+                    .unwrap_or(signature)
+                    .as_ref()
+                    .to_vec())
+            }
+            _ => {
+                let signature = k256::ecdsa::Signature::from_bytes(&signature)
+                    .context("invalid signature returned by PKCS#11 token")?;
+
+                Ok(signature
+                    .normalize_s()
+                    .unwrap_or(signature)
+                    .as_ref()
+                    .to_vec())
+            }
+        }
+    }
+}
+
+impl Pkcs11Token {
+    /// Signs given digest in cached session (opens a new session when there is no cached session)
+    fn sign_digest(&self, session: &mut Option<SigningSession>, digest: &[u8]) -> Result<Vec<u8>> {
+        let session = match session {
+            Some(session) => session,
+            None => {
+                let new_session = self.open_session()?;
+                let private_key = self.find_key(&new_session, ObjectClass::PRIVATE_KEY)?;
+
+                session.insert(SigningSession {
+                    session: new_session,
+                    private_key,
+                })
+            }
+        };
+
+        session
+            .session
+            .sign(&Mechanism::Ecdsa, session.private_key, digest)
+            .context("failed to sign message using PKCS#11 token")
+    }
+
+    fn lock_session(&self) -> MutexGuard<'_, Option<SigningSession>> {
+        self.session.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// Returns true if given error is caused by an invalid session (which can be fixed by opening a new session)
+fn is_session_error(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<Pkcs11Error>(),
+        Some(Pkcs11Error::Pkcs11(
+            RvError::SessionClosed | RvError::SessionHandleInvalid | RvError::UserNotLoggedIn
+        ))
+    )
+}
+
+/// Parses EC point returned by PKCS#11 module (PKCS#11 specifies a DER encoded octet string but some modules return
+/// raw SEC1 bytes)
+fn parse_ec_point<T>(ec_point: &[u8], parse: impl Fn(&[u8]) -> Option<T>) -> Result<T> {
+    if let Some(key) = parse(ec_point) {
+        return Ok(key);
+    }
+
+    match ec_point {
+        [0x04, len, bytes @ ..] if *len as usize == bytes.len() => {
+            parse(bytes).ok_or_else(|| anyhow!("invalid SEC1 bytes of EC point"))
+        }
+        _ => bail!("invalid encoding of EC point"),
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl GetPublicKey for Pkcs11Signer {
+    async fn get_public_key(&self, _chain_id: &ChainId) -> Result<PublicKey> {
+        Ok(self.public_key.clone())
+    }
+
+    async fn to_account_address(&self, _chain_id: &ChainId) -> Result<String> {
+        self.public_key.account_address(&self.account_prefix)
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl Signer for Pkcs11Signer {
+    async fn sign(
+        &self,
+        _request_id: Option<&str>,
+        _chain_id: &ChainId,
+        message: Message<'_>,
+    ) -> Result<Vec<u8>> {
+        let algo = self
+            .public_key
+            .algo()
+            .ok_or_else(|| anyhow!("PKCS#11 signer does not support multisig public keys"))?;
+
+        let token = self.token.clone();
+        let message = message.as_ref().to_vec();
+
+        // PKCS#11 calls block the current thread until the token responds
+        tokio::task::spawn_blocking(move || token.sign_message(algo, &message))
+            .await
+            .context("PKCS#11 signing task failed")?
+    }
+}
+
+/// Tests use SoftHSM as PKCS#11 module and are ignored by default. To run them locally:
+///
+/// ```shell
+/// mkdir -p /tmp/softhsm/tokens
+/// echo "directories.tokendir = /tmp/softhsm/tokens" > /tmp/softhsm/softhsm2.conf
+/// SOFTHSM2_CONF=/tmp/softhsm/softhsm2.conf PKCS11_MODULE=/usr/lib/softhsm/libsofthsm2.so \
+///     cargo test -p stag-api --features pkcs11-signer pkcs11 -- --ignored
+/// ```
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use k256::ecdsa::signature::Verifier;
+
+    use super::*;
+
+    const SO_PIN: &str = "so-pin";
+    const USER_PIN: &str = "user-pin";
+
+    fn module_path() -> PathBuf {
+        std::env::var("PKCS11_MODULE")
+            .expect(
+                "`PKCS11_MODULE` environment variable must be set to the path of SoftHSM module",
+            )
+            .into()
+    }
+
+    /// Initializes a new token in a free slot, generates an EC key pair on it and returns the slot ID of token
+    fn setup_token(token_label: &str, key_label: &str, oid: &[u8]) -> u64 {
+        let mut pkcs11 = Pkcs11::new(module_path()).unwrap();
+        pkcs11.initialize(CInitializeArgs::OsThreads).unwrap();
+
+        let free_slot = pkcs11
+            .get_slots_with_token()
+            .unwrap()
+            .into_iter()
+            .find(|slot| !pkcs11.get_token_info(*slot).unwrap().token_initialized())
+            .unwrap();
+        pkcs11.init_token(free_slot, SO_PIN, token_label).unwrap();
+
+        // SoftHSM reassigns slot ID of a token after initialization
+        let slot = pkcs11
+            .get_slots_with_initialized_token()
+            .unwrap()
+            .into_iter()
+            .find(|slot| pkcs11.get_token_info(*slot).unwrap().label().trim() == token_label)
+            .unwrap();
+
+        let session = pkcs11.open_rw_session(slot).unwrap();
+        session.login(UserType::So, Some(SO_PIN)).unwrap();
+        session.init_pin(USER_PIN).unwrap();
+        session.logout().unwrap();
+        session.login(UserType::User, Some(USER_PIN)).unwrap();
+
+        session
+            .generate_key_pair(
+                &Mechanism::EccKeyPairGen,
+                &[
+                    Attribute::Token(true),
+                    Attribute::Verify(true),
+                    Attribute::EcParams(oid.to_vec()),
+                    Attribute::Label(key_label.as_bytes().to_vec()),
+                ],
+                &[
+                    Attribute::Token(true),
+                    Attribute::Private(true),
+                    Attribute::Sensitive(true),
+                    Attribute::Sign(true),
+                    Attribute::Label(key_label.as_bytes().to_vec()),
+                ],
+            )
+            .unwrap();
+
+        slot.id()
+    }
+
+    #[tokio::test]
+    #[ignore = "requires SoftHSM"]
+    async fn test_pkcs11_signer() {
+        let chain_id: ChainId = "test-1".parse().unwrap();
+        let label = format!("stag-{}", std::process::id());
+
+        // PKCS#11 module is finalized when the last context is dropped, so only one context is used at a time
+        for algo in [PublicKeyAlgo::Secp256k1, PublicKeyAlgo::Secp256r1] {
+            let token_label = format!("{}-{}", label, algo);
+            let oid = match algo {
+                PublicKeyAlgo::Secp256k1 => SECP256K1_OID,
+                _ => SECP256R1_OID,
+            };
+
+            let slot = setup_token(&token_label, "signer", oid);
+
+            // Signer should not be created with invalid PIN, key label or algorithm
+            assert!(Pkcs11Signer::new(
+                &module_path(),
+                slot,
+                "signer".to_string(),
+                Zeroizing::new("invalid".to_string()),
+                algo,
+                "cosmos".to_string(),
+            )
+            .is_err());
+            assert!(Pkcs11Signer::new(
+                &module_path(),
+                slot,
+                "invalid".to_string(),
+                Zeroizing::new(USER_PIN.to_string()),
+                algo,
+                "cosmos".to_string(),
+            )
+            .is_err());
+            assert!(Pkcs11Signer::new(
+                &module_path(),
+                slot,
+                "signer".to_string(),
+                Zeroizing::new(USER_PIN.to_string()),
+                PublicKeyAlgo::Ed25519,
+                "cosmos".to_string(),
+            )
+            .is_err());
+
+            let signer = Pkcs11Signer::new(
+                &module_path(),
+                slot,
+                "signer".to_string(),
+                Zeroizing::new(USER_PIN.to_string()),
+                algo,
+                "cosmos".to_string(),
+            )
+            .unwrap();
+
+            let public_key = signer.get_public_key(&chain_id).await.unwrap();
+            assert_eq!(public_key.algo(), Some(algo));
+            assert_eq!(
+                signer.to_account_address(&chain_id).await.unwrap(),
+                public_key.account_address("cosmos").unwrap()
+            );
+
+            let signature = signer
+                .sign(None, &chain_id, Message::SignBytes(b"test-message"))
+                .await
+                .unwrap();
+
+            match public_key {
+                PublicKey::Secp256k1(ref key) => {
+                    let signature = k256::ecdsa::Signature::from_bytes(&signature).unwrap();
+                    assert!(signature.normalize_s().is_none());
+                    assert!(key.verify(b"test-message", &signature).is_ok());
+                }
+                PublicKey::Secp256r1(ref key) => {
+                    let signature = p256::ecdsa::Signature::from_bytes(&signature).unwrap();
+                    assert!(signature.normalize_s().is_none());
+                    assert!(key.verify(b"test-message", &signature).is_ok());
+                }
+                _ => unreachable!(),
+            }
+
+            // Logged in session is reused by subsequent signing requests
+            assert!(signer.token.lock_session().is_some());
+            assert!(signer
+                .sign(None, &chain_id, Message::SignBytes(b"test-message-2"))
+                .await
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn test_parse_ec_point() {
+        let public_key = "02A1633CAFCC01EBFB6D78E39F687A1F0995C62FC95F51EAD10A02EE0BE551B5DC";
+        let raw = hex::decode(public_key).unwrap();
+
+        let mut der = vec![0x04, raw.len() as u8];
+        der.extend_from_slice(&raw);
+
+        for ec_point in [raw, der] {
+            let key = parse_ec_point(&ec_point, |bytes| VerifyingKey::from_sec1_bytes(bytes).ok())
+                .unwrap();
+            assert_eq!(hex::encode_upper(key.to_bytes()), public_key);
+        }
+
+        assert!(parse_ec_point(&[0x04, 0x01, 0x02], |bytes| {
+            VerifyingKey::from_sec1_bytes(bytes).ok()
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,480 @@
+#[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{ensure, Context, Result};
+use async_trait::async_trait;
+#[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+use tokio::sync::OnceCell;
+#[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+#[cfg(feature = "wasm")]
+use tonic_web_wasm_client::Client;
+use url::Url;
+
+use crate::types::{
+    ics::core::ics24_host::identifier::ChainId,
+    proto::stag::signer::v1::{
+        remote_signer_client::RemoteSignerClient, GetPublicKeyRequest, GetPublicKeyResponse,
+        MessageType, SignRequest,
+    },
+    public_key::PublicKey,
+};
+
+use super::{GetPublicKey, Message, Signer};
+
+#[derive(Debug, Clone)]
+/// Signer implementation which forwards all the signing requests to an external signing service over gRPC
+pub struct RemoteSigner {
+    /// gRPC address of signing service
+    url: Url,
+    /// Timeout for requests to signing service
+    #[cfg_attr(feature = "wasm", allow(dead_code))]
+    timeout: Duration,
+    /// Channel to signing service (connected on first request and shared by all the subsequent requests and clones of
+    /// signer; lost connections are re-established by the channel itself)
+    #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+    channel: Arc<OnceCell<Channel>>,
+    /// TLS configuration used when signing service URL uses `https` scheme (webpki roots are trusted by default)
+    #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+    tls_config: Option<ClientTlsConfig>,
+}
+
+impl RemoteSigner {
+    /// Creates a new instance of remote signer
+    pub fn new(url: Url, timeout: Duration) -> Self {
+        Self {
+            url,
+            timeout,
+            #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+            channel: Default::default(),
+            #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+            tls_config: None,
+        }
+    }
+
+    /// Sets TLS configuration used when signing service URL uses `https` scheme
+    #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+    pub fn with_tls_config(mut self, tls_config: ClientTlsConfig) -> Self {
+        self.tls_config = Some(tls_config);
+        self
+    }
+
+    /// Fetches public key details of signer for given chain id from signing service
+    async fn get_public_key_response(&self, chain_id: &ChainId) -> Result<GetPublicKeyResponse> {
+        let response = self
+            .get_client()
+            .await?
+            .get_public_key(GetPublicKeyRequest {
+                chain_id: chain_id.to_string(),
+            })
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to get public key from remote signer for chain id: {}",
+                    chain_id
+                )
+            })?
+            .into_inner();
+
+        Ok(response)
+    }
+
+    #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+    async fn get_client(&self) -> Result<RemoteSignerClient<Channel>> {
+        let channel =
+            self.channel
+                .get_or_try_init(|| async {
+                    let mut endpoint = Endpoint::from_shared(self.url.to_string())
+                        .context("invalid remote signer url")?
+                        .timeout(self.timeout);
+
+                    if self.url.scheme() == "https" {
+                        endpoint = endpoint
+                            .tls_config(self.tls_config.clone().unwrap_or_default())
+                            .context("invalid remote signer tls configuration")?;
+                    }
+
+                    endpoint.connect().await.with_context(|| {
+                        format!("failed to connect to remote signer at {}", self.url)
+                    })
+                })
+                .await?;
+
+        Ok(RemoteSignerClient::new(channel.clone()))
+    }
+
+    #[cfg(feature = "wasm")]
+    async fn get_client(&self) -> Result<RemoteSignerClient<Client>> {
+        let mut url = self.url.to_string();
+
+        if url.ends_with('/') {
+            url.pop();
+        }
+
+        Ok(RemoteSignerClient::new(Client::new(url)))
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl GetPublicKey for RemoteSigner {
+    async fn get_public_key(&self, chain_id: &ChainId) -> Result<PublicKey> {
+        let response = self.get_public_key_response(chain_id).await?;
+        PublicKey::new(response.public_key, response.algo.parse()?)
+    }
+
+    async fn to_account_address(&self, chain_id: &ChainId) -> Result<String> {
+        let response = self.get_public_key_response(chain_id).await?;
+
+        ensure!(
+            !response.account_address.is_empty(),
+            "remote signer returned an empty account address for chain id: {}",
+            chain_id
+        );
+
+        Ok(response.account_address)
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl Signer for RemoteSigner {
+    async fn sign(
+        &self,
+        request_id: Option<&str>,
+        chain_id: &ChainId,
+        message: Message<'_>,
+    ) -> Result<Vec<u8>> {
+        let message_type = match message {
+            Message::SignBytes(_) | Message::PacketCommitment { .. } => MessageType::SignBytes,
+            Message::SignDoc(_) => MessageType::SignDoc,
+        };
+
+        let response = self
+            .get_client()
+            .await?
+            .sign(SignRequest {
+                request_id: request_id.map(ToOwned::to_owned),
+                chain_id: chain_id.to_string(),
+                message_type: message_type.into(),
+                message: message.as_ref().to_vec(),
+            })
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to sign {} using remote signer for chain id: {}",
+                    message.message_type(),
+                    chain_id
+                )
+            })?
+            .into_inner();
+
+        ensure!(
+            !response.signature.is_empty(),
+            "remote signer returned an empty signature for chain id: {}",
+            chain_id
+        );
+
+        Ok(response.signature)
+    }
+}
+
+#[cfg(all(test, feature = "mnemonic-signer"))]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use tokio::net::TcpListener;
+    use tokio_stream::{wrappers::TcpListenerStream, StreamExt};
+    use tonic::transport::{Certificate, Identity, ServerTlsConfig};
+    use tonic::{Request, Response, Status};
+
+    use crate::{
+        signer::{
+            mnemonic_signer::{MnemonicSigner, MnemonicSignerConfig},
+            RemoteSigner as RemoteSignerConfig, SignerConfig,
+        },
+        types::proto::stag::signer::v1::{
+            remote_signer_server::{self, RemoteSignerServer},
+            SignResponse,
+        },
+    };
+
+    use super::*;
+
+    const MNEMONIC: &str = "practice empty client sauce pistol work ticket casual romance appear army fault palace coyote fox super salute slim catch kite wrist three hedgehog sign";
+    const ACCOUNT_ADDRESS: &str = "cosmos1j2qpprh2xke7qjqzehfqgjdkfgddf9dm06dugw";
+
+    const CA_CERTIFICATE: &[u8] = include_bytes!("../../tests/data/tls/ca.pem");
+    const SERVER_CERTIFICATE: &[u8] = include_bytes!("../../tests/data/tls/server.pem");
+    const SERVER_KEY: &[u8] = include_bytes!("../../tests/data/tls/server.key");
+    const CLIENT_CERTIFICATE: &[u8] = include_bytes!("../../tests/data/tls/client.pem");
+    const CLIENT_KEY: &[u8] = include_bytes!("../../tests/data/tls/client.key");
+
+    /// Reference implementation of signing service (backed by mnemonic signer)
+    struct MockSigningService {
+        signer: MnemonicSigner,
+    }
+
+    #[tonic::async_trait]
+    impl remote_signer_server::RemoteSigner for MockSigningService {
+        async fn get_public_key(
+            &self,
+            request: Request<GetPublicKeyRequest>,
+        ) -> Result<Response<GetPublicKeyResponse>, Status> {
+            let chain_id: ChainId = request
+                .into_inner()
+                .chain_id
+                .parse()
+                .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+
+            let public_key = self
+                .signer
+                .get_public_key(&chain_id)
+                .await
+                .map_err(|err| Status::not_found(err.to_string()))?;
+
+            let account_address = self
+                .signer
+                .to_account_address(&chain_id)
+                .await
+                .map_err(|err| Status::not_found(err.to_string()))?;
+
+            Ok(Response::new(GetPublicKeyResponse {
+                public_key: public_key.to_string(),
+                algo: public_key
+                    .algo()
+                    .map(|algo| algo.to_string())
+                    .unwrap_or_default(),
+                account_address,
+            }))
+        }
+
+        async fn sign(
+            &self,
+            request: Request<SignRequest>,
+        ) -> Result<Response<SignResponse>, Status> {
+            let request = request.into_inner();
+
+            let chain_id: ChainId = request
+                .chain_id
+                .parse()
+                .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+
+            let message = match MessageType::from_i32(request.message_type) {
+                Some(MessageType::SignBytes) => Message::SignBytes(&request.message),
+                Some(MessageType::SignDoc) => Message::SignDoc(&request.message),
+                _ => return Err(Status::invalid_argument("invalid message type")),
+            };
+
+            let signature = self
+                .signer
+                .sign(request.request_id.as_deref(), &chain_id, message)
+                .await
+                .map_err(|err| Status::not_found(err.to_string()))?;
+
+            Ok(Response::new(SignResponse { signature }))
+        }
+    }
+
+    fn mnemonic_signer() -> MnemonicSigner {
+        let mut config_map = HashMap::new();
+        config_map.insert(
+            "test-1".parse().unwrap(),
+            MnemonicSignerConfig::new(MNEMONIC, None, None, None).unwrap(),
+        );
+
+        MnemonicSigner::new(config_map)
+    }
+
+    /// Starts signing service and returns its URL along with the number of accepted connections
+    async fn start_signing_service() -> (Url, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let connections = Arc::new(AtomicUsize::new(0));
+        let accepted = connections.clone();
+
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(RemoteSignerServer::new(MockSigningService {
+                    signer: mnemonic_signer(),
+                }))
+                .serve_with_incoming(TcpListenerStream::new(listener).map(move |connection| {
+                    accepted.fetch_add(1, Ordering::AcqRel);
+                    connection
+                })),
+        );
+
+        (format!("http://{}", addr).parse().unwrap(), connections)
+    }
+
+    /// Starts signing service over TLS (certificate is issued for `localhost`) which requires client certificates signed
+    /// by test CA when `mutual` is true
+    async fn start_tls_signing_service(mutual: bool) -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let mut tls_config =
+            ServerTlsConfig::new().identity(Identity::from_pem(SERVER_CERTIFICATE, SERVER_KEY));
+
+        if mutual {
+            tls_config = tls_config.client_ca_root(Certificate::from_pem(CA_CERTIFICATE));
+        }
+
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .tls_config(tls_config)
+                .unwrap()
+                .add_service(RemoteSignerServer::new(MockSigningService {
+                    signer: mnemonic_signer(),
+                }))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+
+        format!("https://{}", addr).parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_tls() {
+        let url = start_tls_signing_service(false).await;
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        let signer = RemoteSignerConfig::new(url.clone())
+            .with_ca_certificate(CA_CERTIFICATE)
+            .with_domain_name("localhost")
+            .into_signer()
+            .unwrap();
+        assert_eq!(
+            signer.to_account_address(&chain_id).await.unwrap(),
+            ACCOUNT_ADDRESS
+        );
+
+        // Server's certificate is not trusted without test CA
+        let signer = RemoteSignerConfig::new(url)
+            .with_domain_name("localhost")
+            .into_signer()
+            .unwrap();
+        assert!(signer.to_account_address(&chain_id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_mutual_tls() {
+        let url = start_tls_signing_service(true).await;
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        let signer = RemoteSignerConfig::new(url.clone())
+            .with_ca_certificate(CA_CERTIFICATE)
+            .with_identity(CLIENT_CERTIFICATE, CLIENT_KEY)
+            .with_domain_name("localhost")
+            .into_signer()
+            .unwrap();
+        assert!(signer
+            .sign(None, &chain_id, Message::SignBytes(b"test"))
+            .await
+            .is_ok());
+
+        // Clients without a certificate are rejected
+        let signer = RemoteSignerConfig::new(url)
+            .with_ca_certificate(CA_CERTIFICATE)
+            .with_domain_name("localhost")
+            .into_signer()
+            .unwrap();
+        assert!(signer
+            .sign(None, &chain_id, Message::SignBytes(b"test"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_remote_get_public_key() {
+        let (url, _) = start_signing_service().await;
+        let signer = RemoteSigner::new(url, Duration::from_secs(5));
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        let public_key = signer.get_public_key(&chain_id).await.unwrap();
+        let expected = mnemonic_signer().get_public_key(&chain_id).await.unwrap();
+        assert_eq!(public_key.to_string(), expected.to_string());
+        assert_eq!(public_key.algo(), expected.algo());
+
+        let account_address = signer.to_account_address(&chain_id).await.unwrap();
+        assert_eq!(account_address, ACCOUNT_ADDRESS);
+    }
+
+    #[tokio::test]
+    async fn test_remote_sign() {
+        let (url, _) = start_signing_service().await;
+        let signer = RemoteSigner::new(url, Duration::from_secs(5));
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        for message in [Message::SignBytes(b"test"), Message::SignDoc(b"test")] {
+            let expected = mnemonic_signer()
+                .sign(
+                    Some("request"),
+                    &chain_id,
+                    Message::SignBytes(message.as_ref()),
+                )
+                .await
+                .unwrap();
+
+            let signature = signer
+                .sign(Some("request"), &chain_id, message)
+                .await
+                .unwrap();
+
+            assert_eq!(signature, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_reuses_connection() {
+        let (url, connections) = start_signing_service().await;
+        let signer = RemoteSigner::new(url, Duration::from_secs(5));
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        signer.get_public_key(&chain_id).await.unwrap();
+        signer
+            .sign(None, &chain_id, Message::SignBytes(b"test"))
+            .await
+            .unwrap();
+        signer
+            .clone()
+            .sign(None, &chain_id, Message::SignDoc(b"test"))
+            .await
+            .unwrap();
+
+        assert_eq!(connections.load(Ordering::Acquire), 1);
+    }
+
+    #[tokio::test]
+    async fn test_remote_unknown_chain() {
+        let (url, _) = start_signing_service().await;
+        let signer = RemoteSigner::new(url, Duration::from_secs(5));
+        let chain_id: ChainId = "test-2".parse().unwrap();
+
+        assert!(signer.get_public_key(&chain_id).await.is_err());
+        assert!(signer
+            .sign(None, &chain_id, Message::SignBytes(b"test"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_unavailable() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        drop(listener);
+
+        let signer = RemoteSigner::new(url, Duration::from_secs(5));
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        assert!(signer
+            .sign(None, &chain_id, Message::SignBytes(b"test"))
+            .await
+            .is_err());
+    }
+}
@@ -1,5 +1,17 @@
+use std::sync::Arc;
+
 use anyhow::Result;
 use async_trait::async_trait;
+use cosmos_sdk_proto::{
+    cosmos::tx::signing::v1beta1::{
+        signature_descriptor::{
+            data::{Single as SingleSignatureData, Sum as SignatureDataInner},
+            Data as SignatureData,
+        },
+        SignMode,
+    },
+    ibc::core::channel::v1::Packet,
+};
 
 use crate::{
     trait_util::Base,
@@ -15,22 +27,36 @@ pub trait GetPublicKey: Base {
 
     /// Returns accounts address for this signer for given prefix
     async fn to_account_address(&self, chain_id: &ChainId) -> Result<String>;
+
+    /// Returns public key used for signing cosmos sdk transactions (same as public key of signer by default, but
+    /// differs for signers whose public key cannot sign transactions, e.g., multisig)
+    async fn get_transaction_public_key(&self, chain_id: &ChainId) -> Result<PublicKey> {
+        self.get_public_key(chain_id).await
+    }
 }
 
 /// Type of message given to a signer
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum Message<'a> {
     /// [cosmos_sdk_proto::ibc::lightclients::solomachine::v2::SignBytes]
     SignBytes(&'a [u8]),
     /// [cosmos_sdk_proto::cosmos::tx::v1beta1::SignDoc]
     SignDoc(&'a [u8]),
+    /// [cosmos_sdk_proto::ibc::lightclients::solomachine::v2::SignBytes] of a packet commitment along with the
+    /// committed packet (so that signers can inspect packet data before signing)
+    PacketCommitment {
+        /// Encoded sign bytes
+        sign_bytes: &'a [u8],
+        /// Packet committed in sign bytes
+        packet: &'a Packet,
+    },
 }
 
 impl<'a> Message<'a> {
     /// Returns the message type of current message
     pub fn message_type(&self) -> &'static str {
         match self {
-            Self::SignBytes(_) => "sign-bytes",
+            Self::SignBytes(_) | Self::PacketCommitment { .. } => "sign-bytes",
             Self::SignDoc(_) => "sign-doc",
         }
     }
@@ -41,6 +67,7 @@ impl AsRef<[u8]> for Message<'_> {
         match self {
             Self::SignBytes(bytes) => bytes,
             Self::SignDoc(bytes) => bytes,
+            Self::PacketCommitment { sign_bytes, .. } => sign_bytes,
         }
     }
 }
@@ -56,6 +83,78 @@ pub trait Signer: GetPublicKey {
         chain_id: &ChainId,
         message: Message<'_>,
     ) -> Result<Vec<u8>>;
+
+    /// Signs the given message and returns signature data corresponding to public key of signer (single signature
+    /// data by default)
+    async fn sign_data(
+        &self,
+        request_id: Option<&str>,
+        chain_id: &ChainId,
+        message: Message<'_>,
+    ) -> Result<SignatureData> {
+        let signature = self.sign(request_id, chain_id, message).await?;
+
+        Ok(SignatureData {
+            sum: Some(SignatureDataInner::Single(SingleSignatureData {
+                signature,
+                mode: SignMode::Unspecified.into(),
+            })),
+        })
+    }
+
+    /// Returns the sign mode in which signer signs [Message::SignDoc] of cosmos sdk transactions (`SIGN_MODE_DIRECT` by
+    /// default, signers which cannot sign protobuf encoded sign docs convert them before signing)
+    fn sign_mode(&self) -> SignMode {
+        SignMode::Direct
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl<T> GetPublicKey for Arc<T>
+where
+    T: GetPublicKey + ?Sized,
+{
+    async fn get_public_key(&self, chain_id: &ChainId) -> Result<PublicKey> {
+        (**self).get_public_key(chain_id).await
+    }
+
+    async fn to_account_address(&self, chain_id: &ChainId) -> Result<String> {
+        (**self).to_account_address(chain_id).await
+    }
+
+    async fn get_transaction_public_key(&self, chain_id: &ChainId) -> Result<PublicKey> {
+        (**self).get_transaction_public_key(chain_id).await
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl<T> Signer for Arc<T>
+where
+    T: Signer + ?Sized,
+{
+    async fn sign(
+        &self,
+        request_id: Option<&str>,
+        chain_id: &ChainId,
+        message: Message<'_>,
+    ) -> Result<Vec<u8>> {
+        (**self).sign(request_id, chain_id, message).await
+    }
+
+    async fn sign_data(
+        &self,
+        request_id: Option<&str>,
+        chain_id: &ChainId,
+        message: Message<'_>,
+    ) -> Result<SignatureData> {
+        (**self).sign_data(request_id, chain_id, message).await
+    }
+
+    fn sign_mode(&self) -> SignMode {
+        (**self).sign_mode()
+    }
 }
 
 #[cfg(test)]
@@ -69,6 +168,13 @@ mod tests {
 
         let message = Message::SignDoc(b"test");
         assert_eq!(message.message_type(), "sign-doc");
+
+        let packet = Packet::default();
+        let message = Message::PacketCommitment {
+            sign_bytes: b"test",
+            packet: &packet,
+        };
+        assert_eq!(message.message_type(), "sign-bytes");
     }
 
     #[tokio::test]
@@ -78,5 +184,12 @@ mod tests {
 
         let message = Message::SignDoc(b"test");
         assert_eq!(message.as_ref(), b"test");
+
+        let packet = Packet::default();
+        let message = Message::PacketCommitment {
+            sign_bytes: b"test",
+            packet: &packet,
+        };
+        assert_eq!(message.as_ref(), b"test");
     }
 }
@@ -0,0 +1,883 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use cosmos_sdk_proto::{
+    cosmos::{
+        bank::v1beta1::MsgSend,
+        staking::v1beta1::{MsgDelegate, MsgUndelegate},
+        tx::{
+            signing::v1beta1::{signature_descriptor::Data as SignatureData, SignMode},
+            v1beta1::{SignDoc, TxBody},
+        },
+    },
+    ibc::{
+        applications::{interchain_accounts::v1::CosmosTx, transfer::v1::MsgTransfer},
+        core::channel::v1::Packet,
+        lightclients::solomachine::v2::{DataType, PacketCommitmentData, SignBytes},
+    },
+};
+use primitive_types::U256;
+use prost::Message as _;
+use prost_types::Any;
+
+use crate::{
+    time_util::now_utc,
+    transaction_builder::{ica::InterchainAccountPacketData, transfer::TokenTransferPacketData},
+    types::{
+        cosmos::{bank::msg_send, staking::msg_delegate, staking::msg_undelegate},
+        ics::{
+            applications::transfer::msg_transfer,
+            core::{
+                ics04_channel::packet::IPacket,
+                ics24_host::{
+                    identifier::{ChainId, PortId},
+                    path::{DenomTrace, PacketCommitmentPath},
+                },
+            },
+        },
+        proto_util::AnyConvert,
+        public_key::PublicKey,
+    },
+};
+
+use super::{GetPublicKey, Message, Signer};
+
+/// Hook which is called with intents decoded from a message before signing it (returning an error rejects the
+/// message)
+#[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+pub type ApprovalHook = Arc<dyn Fn(&ChainId, &[SigningIntent]) -> Result<()> + Send + Sync>;
+
+/// Hook which is called with intents decoded from a message before signing it (returning an error rejects the
+/// message)
+#[cfg(feature = "wasm")]
+pub type ApprovalHook = Arc<dyn Fn(&ChainId, &[SigningIntent]) -> Result<()>>;
+
+/// Human readable intent decoded from a message given to signer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SigningIntent {
+    /// Mint tokens on IBC enabled chain
+    Mint {
+        /// Denom of tokens on solo machine
+        denom: String,
+        /// Amount of tokens
+        amount: U256,
+        /// Receiver of tokens on IBC enabled chain
+        receiver: String,
+    },
+    /// Burn tokens on IBC enabled chain
+    Burn {
+        /// Denom of tokens on solo machine (or IBC denom if it is not one of the denoms in signing policy)
+        denom: String,
+        /// Amount of tokens
+        amount: U256,
+        /// Receiver of tokens on solo machine
+        receiver: String,
+    },
+    /// Send tokens from interchain account
+    IcaSend {
+        /// Denom of tokens on IBC enabled chain
+        denom: String,
+        /// Amount of tokens
+        amount: U256,
+        /// Receiver of tokens on IBC enabled chain
+        receiver: String,
+    },
+    /// Delegate tokens from interchain account
+    IcaDelegate {
+        /// Denom of tokens on IBC enabled chain
+        denom: String,
+        /// Amount of tokens
+        amount: U256,
+        /// Address of validator
+        validator: String,
+    },
+    /// Undelegate tokens to interchain account
+    IcaUndelegate {
+        /// Denom of tokens on IBC enabled chain
+        denom: String,
+        /// Amount of tokens
+        amount: U256,
+        /// Address of validator
+        validator: String,
+    },
+    /// Any other message (type URL of cosmos sdk message or data type of solo machine sign bytes)
+    Other(String),
+}
+
+impl SigningIntent {
+    /// Returns denom and amount of tokens moved by this intent
+    pub fn tokens(&self) -> Option<(&str, U256)> {
+        match self {
+            Self::Mint { denom, amount, .. }
+            | Self::Burn { denom, amount, .. }
+            | Self::IcaSend { denom, amount, .. }
+            | Self::IcaDelegate { denom, amount, .. }
+            | Self::IcaUndelegate { denom, amount, .. } => Some((denom, *amount)),
+            Self::Other(_) => None,
+        }
+    }
+
+    /// Returns receiver of tokens moved by this intent (`None` for delegations and other intents)
+    pub fn receiver(&self) -> Option<&str> {
+        match self {
+            Self::Mint { receiver, .. }
+            | Self::Burn { receiver, .. }
+            | Self::IcaSend { receiver, .. } => Some(receiver),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SigningIntent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mint {
+                denom,
+                amount,
+                receiver,
+            } => write!(f, "mint {} {} to {}", amount, denom, receiver),
+            Self::Burn {
+                denom,
+                amount,
+                receiver,
+            } => write!(f, "burn {} {} to {}", amount, denom, receiver),
+            Self::IcaSend {
+                denom,
+                amount,
+                receiver,
+            } => write!(f, "ICA send {} {} to {}", amount, denom, receiver),
+            Self::IcaDelegate {
+                denom,
+                amount,
+                validator,
+            } => write!(f, "ICA delegate {} {} to {}", amount, denom, validator),
+            Self::IcaUndelegate {
+                denom,
+                amount,
+                validator,
+            } => write!(f, "ICA undelegate {} {} from {}", amount, denom, validator),
+            Self::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// Rules enforced by signing policy
+#[derive(Clone, Default)]
+pub(crate) struct PolicyRules {
+    /// Maximum amount of tokens of a denom which can be moved in a single message
+    pub denom_limits: HashMap<String, U256>,
+    /// Maximum amount of tokens of a denom which can be moved in 24 hours
+    pub daily_caps: HashMap<String, U256>,
+    /// Receivers on IBC enabled chain to which tokens can be minted or sent from interchain account (any receiver is
+    /// allowed when empty)
+    pub allowed_mint_receivers: HashSet<String>,
+    /// Receivers on solo machine to which tokens can be burnt (any receiver is allowed when empty)
+    pub allowed_burn_receivers: HashSet<String>,
+    /// Maximum number of messages moving tokens which can be signed in given duration
+    pub rate_limit: Option<(u32, Duration)>,
+    /// Hook for approving messages after all the rules are satisfied
+    pub approval_hook: Option<ApprovalHook>,
+}
+
+/// Messages moving tokens signed in last 24 hours (or rate limit duration, if it is longer)
+#[derive(Default)]
+struct Usage {
+    next_id: u64,
+    records: VecDeque<UsageRecord>,
+}
+
+struct UsageRecord {
+    id: u64,
+    time: DateTime<Utc>,
+    tokens: HashMap<String, U256>,
+}
+
+/// Signer implementation which decodes messages into human readable intents and enforces signing rules on them
+/// before forwarding them to wrapped signer
+pub struct SigningPolicy {
+    signer: Arc<dyn Signer>,
+    rules: PolicyRules,
+    usage: Mutex<Usage>,
+}
+
+impl SigningPolicy {
+    /// Creates a new instance of signing policy
+    pub(crate) fn new(signer: Arc<dyn Signer>, rules: PolicyRules) -> Self {
+        Self {
+            signer,
+            rules,
+            usage: Default::default(),
+        }
+    }
+
+    /// Decodes given message into human readable intents
+    pub fn decode_intents(&self, message: Message<'_>) -> Result<Vec<SigningIntent>> {
+        match message {
+            Message::SignDoc(sign_doc) => self.decode_sign_doc(sign_doc),
+            Message::SignBytes(sign_bytes) => decode_sign_bytes(sign_bytes),
+            Message::PacketCommitment { sign_bytes, packet } => {
+                decode_packet_commitment(sign_bytes, packet)
+            }
+        }
+    }
+
+    fn decode_sign_doc(&self, sign_doc: &[u8]) -> Result<Vec<SigningIntent>> {
+        let sign_doc = SignDoc::decode(sign_doc).context("invalid sign doc")?;
+        let tx_body =
+            TxBody::decode(sign_doc.body_bytes.as_slice()).context("invalid transaction body")?;
+
+        tx_body
+            .messages
+            .iter()
+            .map(|message| match message.type_url.as_str() {
+                msg_transfer::TYPE_URL => {
+                    let message = MsgTransfer::from_any(message)?;
+                    let token = message.token.context("missing token in transfer message")?;
+
+                    Ok(SigningIntent::Burn {
+                        denom: self.resolve_ibc_denom(
+                            &message.source_port,
+                            &message.source_channel,
+                            token.denom,
+                        ),
+                        amount: parse_amount(&token.amount)?,
+                        receiver: message.receiver,
+                    })
+                }
+                // Packets received on IBC enabled chain are checked when their commitments are signed
+                type_url => Ok(SigningIntent::Other(type_url.to_owned())),
+            })
+            .collect()
+    }
+
+    /// Resolves IBC denom of tokens transferred over given port and channel of IBC enabled chain to one of the
+    /// denoms in rules (returns IBC denom as is if none of the denoms match)
+    fn resolve_ibc_denom(&self, port_id: &str, channel_id: &str, ibc_denom: String) -> String {
+        let (port_id, channel_id) = match (port_id.parse(), channel_id.parse()) {
+            (Ok(port_id), Ok(channel_id)) => (port_id, channel_id),
+            _ => return ibc_denom,
+        };
+
+        self.rules
+            .denom_limits
+            .keys()
+            .chain(self.rules.daily_caps.keys())
+            .find(|denom| {
+                denom.parse().is_ok_and(|denom| {
+                    DenomTrace::new(&port_id, &channel_id, &denom).to_ibc_denom() == ibc_denom
+                })
+            })
+            .cloned()
+            .unwrap_or(ibc_denom)
+    }
+
+    /// Enforces signing rules on given message and records the tokens moved by it (returns the ID of usage record
+    /// which should be released if signing fails)
+    fn enforce(&self, chain_id: &ChainId, message: Message<'_>) -> Result<Option<u64>> {
+        let intents = self.decode_intents(message)?;
+
+        let mut tokens: HashMap<String, U256> = HashMap::new();
+
+        for intent in intents.iter() {
+            if let Some(receiver) = intent.receiver() {
+                let allowed_receivers = match intent {
+                    SigningIntent::Burn { .. } => &self.rules.allowed_burn_receivers,
+                    _ => &self.rules.allowed_mint_receivers,
+                };
+
+                ensure!(
+                    allowed_receivers.is_empty() || allowed_receivers.contains(receiver),
+                    "signing policy rejected `{}`: receiver {} is not allowed",
+                    intent,
+                    receiver
+                );
+            }
+
+            if let Some((denom, amount)) = intent.tokens() {
+                let total = tokens.entry(denom.to_owned()).or_default();
+                *total = total
+                    .checked_add(amount)
+                    .ok_or_else(|| anyhow!("token amount overflow"))?;
+            }
+        }
+
+        for (denom, amount) in tokens.iter() {
+            if let Some(limit) = self.rules.denom_limits.get(denom) {
+                ensure!(
+                    amount <= limit,
+                    "signing policy rejected message moving {} {}: exceeds limit of {} {} per message",
+                    amount,
+                    denom,
+                    limit,
+                    denom
+                );
+            }
+        }
+
+        if let Some(ref approval_hook) = self.rules.approval_hook {
+            approval_hook(chain_id, &intents).context("signing policy approval hook rejected")?;
+        }
+
+        if tokens.is_empty() {
+            return Ok(None);
+        }
+
+        self.record_usage(tokens).map(Some)
+    }
+
+    /// Checks rate limit and daily caps and records given tokens as moved
+    fn record_usage(&self, tokens: HashMap<String, U256>) -> Result<u64> {
+        let now = now_utc();
+        let day_start = now - ChronoDuration::days(1);
+
+        let mut usage = self.usage.lock().unwrap_or_else(|err| err.into_inner());
+
+        let retention_start = match self.rules.rate_limit {
+            Some((_, duration)) => day_start.min(now - ChronoDuration::from_std(duration)?),
+            None => day_start,
+        };
+
+        while matches!(usage.records.front(), Some(record) if record.time <= retention_start) {
+            usage.records.pop_front();
+        }
+
+        if let Some((max_messages, duration)) = self.rules.rate_limit {
+            let window_start = now - ChronoDuration::from_std(duration)?;
+            let signed = usage
+                .records
+                .iter()
+                .filter(|record| record.time > window_start)
+                .count();
+
+            ensure!(
+                signed < max_messages as usize,
+                "signing policy rejected message: rate limit of {} messages per {:?} reached",
+                max_messages,
+                duration
+            );
+        }
+
+        for (denom, amount) in tokens.iter() {
+            if let Some(cap) = self.rules.daily_caps.get(denom) {
+                let mut total = *amount;
+
+                for record in usage
+                    .records
+                    .iter()
+                    .filter(|record| record.time > day_start)
+                {
+                    if let Some(moved) = record.tokens.get(denom) {
+                        total = total.saturating_add(*moved);
+                    }
+                }
+
+                ensure!(
+                    total <= *cap,
+                    "signing policy rejected message moving {} {}: exceeds daily cap of {} {}",
+                    amount,
+                    denom,
+                    cap,
+                    denom
+                );
+            }
+        }
+
+        let id = usage.next_id;
+        usage.next_id += 1;
+        usage.records.push_back(UsageRecord {
+            id,
+            time: now,
+            tokens,
+        });
+
+        Ok(id)
+    }
+
+    /// Removes usage record of a message which could not be signed
+    fn release_usage(&self, id: Option<u64>) {
+        if let Some(id) = id {
+            let mut usage = self.usage.lock().unwrap_or_else(|err| err.into_inner());
+            usage.records.retain(|record| record.id != id);
+        }
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl GetPublicKey for SigningPolicy {
+    async fn get_public_key(&self, chain_id: &ChainId) -> Result<PublicKey> {
+        self.signer.get_public_key(chain_id).await
+    }
+
+    async fn to_account_address(&self, chain_id: &ChainId) -> Result<String> {
+        self.signer.to_account_address(chain_id).await
+    }
+
+    async fn get_transaction_public_key(&self, chain_id: &ChainId) -> Result<PublicKey> {
+        self.signer.get_transaction_public_key(chain_id).await
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl Signer for SigningPolicy {
+    async fn sign(
+        &self,
+        request_id: Option<&str>,
+        chain_id: &ChainId,
+        message: Message<'_>,
+    ) -> Result<Vec<u8>> {
+        let usage_id = self.enforce(chain_id, message)?;
+        let result = self.signer.sign(request_id, chain_id, message).await;
+
+        if result.is_err() {
+            self.release_usage(usage_id);
+        }
+
+        result
+    }
+
+    async fn sign_data(
+        &self,
+        request_id: Option<&str>,
+        chain_id: &ChainId,
+        message: Message<'_>,
+    ) -> Result<SignatureData> {
+        let usage_id = self.enforce(chain_id, message)?;
+        let result = self.signer.sign_data(request_id, chain_id, message).await;
+
+        if result.is_err() {
+            self.release_usage(usage_id);
+        }
+
+        result
+    }
+
+    fn sign_mode(&self) -> SignMode {
+        self.signer.sign_mode()
+    }
+}
+
+fn decode_sign_bytes(sign_bytes: &[u8]) -> Result<Vec<SigningIntent>> {
+    let sign_bytes = SignBytes::decode(sign_bytes).context("invalid sign bytes")?;
+    let data_type = DataType::from_i32(sign_bytes.data_type)
+        .ok_or_else(|| anyhow!("invalid data type in sign bytes: {}", sign_bytes.data_type))?;
+
+    ensure!(
+        data_type != DataType::PacketCommitment,
+        "signing policy rejected packet commitment: committed packet is not provided"
+    );
+
+    Ok(vec![SigningIntent::Other(format!("{:?}", data_type))])
+}
+
+/// Decodes intents from given packet after verifying that it matches the commitment in sign bytes
+fn decode_packet_commitment(sign_bytes: &[u8], packet: &Packet) -> Result<Vec<SigningIntent>> {
+    let sign_bytes = SignBytes::decode(sign_bytes).context("invalid sign bytes")?;
+
+    ensure!(
+        sign_bytes.data_type == i32::from(DataType::PacketCommitment),
+        "sign bytes do not contain a packet commitment"
+    );
+
+    let commitment_data = PacketCommitmentData::decode(sign_bytes.data.as_slice())
+        .context("invalid packet commitment data")?;
+
+    let mut commitment_path = PacketCommitmentPath::new(
+        &packet.source_port.parse()?,
+        &packet.source_channel.parse()?,
+        packet.sequence,
+    );
+    commitment_path.apply_prefix(&"ibc".parse().unwrap());
+
+    ensure!(
+        commitment_data.path == commitment_path.into_bytes()
+            && commitment_data.commitment == packet.commitment_bytes()?,
+        "packet does not match the commitment in sign bytes"
+    );
+
+    decode_packet(packet)
+}
+
+fn decode_packet(packet: &Packet) -> Result<Vec<SigningIntent>> {
+    if packet.destination_port == PortId::transfer().to_string() {
+        let packet_data: TokenTransferPacketData =
+            serde_json::from_slice(&packet.data).context("invalid token transfer packet data")?;
+
+        Ok(vec![SigningIntent::Mint {
+            denom: packet_data.denom,
+            amount: parse_amount(&packet_data.amount)?,
+            receiver: packet_data.receiver,
+        }])
+    } else if packet.destination_port == PortId::ica_host().to_string() {
+        let packet_data: InterchainAccountPacketData = serde_json::from_slice(&packet.data)
+            .context("invalid interchain account packet data")?;
+        let cosmos_tx = CosmosTx::decode(packet_data.data.as_slice())
+            .context("invalid interchain account transaction")?;
+
+        let mut intents = Vec::with_capacity(cosmos_tx.messages.len());
+
+        for message in cosmos_tx.messages.iter() {
+            decode_ica_message(message, &mut intents)?;
+        }
+
+        Ok(intents)
+    } else {
+        Ok(vec![SigningIntent::Other(format!(
+            "packet to port {}",
+            packet.destination_port
+        ))])
+    }
+}
+
+fn decode_ica_message(message: &Any, intents: &mut Vec<SigningIntent>) -> Result<()> {
+    match message.type_url.as_str() {
+        msg_send::TYPE_URL => {
+            let message = MsgSend::from_any(message)?;
+
+            for coin in message.amount {
+                intents.push(SigningIntent::IcaSend {
+                    amount: parse_amount(&coin.amount)?,
+                    denom: coin.denom,
+                    receiver: message.to_address.clone(),
+                });
+            }
+        }
+        msg_delegate::TYPE_URL => {
+            let message = MsgDelegate::from_any(message)?;
+            let coin = message
+                .amount
+                .context("missing amount in delegate message")?;
+
+            intents.push(SigningIntent::IcaDelegate {
+                amount: parse_amount(&coin.amount)?,
+                denom: coin.denom,
+                validator: message.validator_address,
+            });
+        }
+        msg_undelegate::TYPE_URL => {
+            let message = MsgUndelegate::from_any(message)?;
+            let coin = message
+                .amount
+                .context("missing amount in undelegate message")?;
+
+            intents.push(SigningIntent::IcaUndelegate {
+                amount: parse_amount(&coin.amount)?,
+                denom: coin.denom,
+                validator: message.validator_address,
+            });
+        }
+        type_url => intents.push(SigningIntent::Other(type_url.to_owned())),
+    }
+
+    Ok(())
+}
+
+fn parse_amount(amount: &str) -> Result<U256> {
+    U256::from_dec_str(amount).map_err(|err| anyhow!("invalid token amount {}: {:?}", amount, err))
+}
+
+#[cfg(all(test, feature = "mnemonic-signer"))]
+mod tests {
+    use cosmos_sdk_proto::{cosmos::base::v1beta1::Coin, ibc::core::client::v1::Height};
+
+    use crate::{
+        signer::{MnemonicSigner, SignerConfig, SigningPolicy as SigningPolicyConfig},
+        types::{
+            ics::core::ics02_client::height::IHeight,
+            proto_util::{proto_encode, AnyConvert},
+        },
+    };
+
+    use super::*;
+
+    const MNEMONIC: &str = "practice empty client sauce pistol work ticket casual romance appear army fault palace coyote fox super salute slim catch kite wrist three hedgehog sign";
+    const RECEIVER: &str = "cosmos1j2qpprh2xke7qjqzehfqgjdkfgddf9dm06dugw";
+    const SOLO_MACHINE_RECEIVER: &str = "solo-machine-address";
+
+    fn policy_config() -> SigningPolicyConfig {
+        let mut signer = MnemonicSigner::new();
+        signer
+            .add_chain_config("test-1".parse().unwrap(), MNEMONIC, None, None, None)
+            .unwrap();
+
+        SigningPolicyConfig::new(signer).unwrap()
+    }
+
+    fn packet(destination_port: &str, data: Vec<u8>) -> Packet {
+        Packet {
+            sequence: 1,
+            source_port: destination_port.to_owned(),
+            source_channel: "channel-0".to_owned(),
+            destination_port: destination_port.to_owned(),
+            destination_channel: "channel-1".to_owned(),
+            data,
+            timeout_height: Some(Height::new(0, 100)),
+            timeout_timestamp: 0,
+        }
+    }
+
+    fn mint_packet(amount: u64, receiver: &str) -> Packet {
+        let packet_data = TokenTransferPacketData {
+            denom: "gld".to_owned(),
+            amount: amount.to_string(),
+            sender: RECEIVER.to_owned(),
+            receiver: receiver.to_owned(),
+        };
+
+        packet("transfer", serde_json::to_vec(&packet_data).unwrap())
+    }
+
+    fn commitment_sign_bytes(packet: &Packet) -> Vec<u8> {
+        let mut commitment_path = PacketCommitmentPath::new(
+            &packet.source_port.parse().unwrap(),
+            &packet.source_channel.parse().unwrap(),
+            packet.sequence,
+        );
+        commitment_path.apply_prefix(&"ibc".parse().unwrap());
+
+        let data = PacketCommitmentData {
+            path: commitment_path.into_bytes(),
+            commitment: packet.commitment_bytes().unwrap(),
+        };
+
+        proto_encode(&SignBytes {
+            sequence: 1,
+            timestamp: 0,
+            diversifier: "stag".to_owned(),
+            data_type: DataType::PacketCommitment.into(),
+            data: proto_encode(&data).unwrap(),
+        })
+        .unwrap()
+    }
+
+    async fn sign_mint(
+        policy: &SigningPolicy,
+        chain_id: &str,
+        amount: u64,
+        receiver: &str,
+    ) -> Result<()> {
+        let packet = mint_packet(amount, receiver);
+        let sign_bytes = commitment_sign_bytes(&packet);
+
+        policy
+            .sign_data(
+                None,
+                &chain_id.parse().unwrap(),
+                Message::PacketCommitment {
+                    sign_bytes: &sign_bytes,
+                    packet: &packet,
+                },
+            )
+            .await
+            .map(|_| ())
+    }
+
+    fn burn_sign_doc(amount: u64, receiver: &str) -> Vec<u8> {
+        let ibc_denom = DenomTrace::new(
+            &PortId::transfer(),
+            &"channel-1".parse().unwrap(),
+            &"gld".parse().unwrap(),
+        )
+        .to_ibc_denom();
+
+        let message = MsgTransfer {
+            source_port: "transfer".to_owned(),
+            source_channel: "channel-1".to_owned(),
+            token: Some(Coin {
+                denom: ibc_denom,
+                amount: amount.to_string(),
+            }),
+            sender: RECEIVER.to_owned(),
+            receiver: receiver.to_owned(),
+            timeout_height: Some(Height::new(0, 100)),
+            timeout_timestamp: 0,
+        };
+
+        proto_encode(&SignDoc {
+            body_bytes: proto_encode(&TxBody {
+                messages: vec![message.to_any().unwrap()],
+                ..Default::default()
+            })
+            .unwrap(),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    async fn sign_burn(policy: &SigningPolicy, amount: u64, receiver: &str) -> Result<()> {
+        let sign_doc = burn_sign_doc(amount, receiver);
+
+        policy
+            .sign_data(
+                None,
+                &"test-1".parse().unwrap(),
+                Message::SignDoc(&sign_doc),
+            )
+            .await
+            .map(|_| ())
+    }
+
+    #[tokio::test]
+    async fn test_denom_limit_and_allowed_receivers() {
+        let policy = policy_config()
+            .with_denom_limit("gld", 100.into())
+            .with_allowed_mint_receiver(RECEIVER)
+            .into_signer()
+            .unwrap();
+
+        assert!(sign_mint(&policy, "test-1", 100, RECEIVER).await.is_ok());
+        assert!(sign_mint(&policy, "test-1", 101, RECEIVER).await.is_err());
+        assert!(sign_mint(&policy, "test-1", 100, "cosmos1other")
+            .await
+            .is_err());
+
+        // Mint receivers do not restrict receivers of burnt tokens
+        assert!(sign_burn(&policy, 100, SOLO_MACHINE_RECEIVER).await.is_ok());
+
+        let policy = policy_config()
+            .with_allowed_burn_receiver(SOLO_MACHINE_RECEIVER)
+            .into_signer()
+            .unwrap();
+
+        assert!(sign_burn(&policy, 100, SOLO_MACHINE_RECEIVER).await.is_ok());
+        assert!(sign_burn(&policy, 100, "other-address").await.is_err());
+        assert!(sign_mint(&policy, "test-1", 100, RECEIVER).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_packet_commitment_must_match_packet() {
+        let policy = policy_config().into_signer().unwrap();
+        let chain_id: ChainId = "test-1".parse().unwrap();
+
+        let packet = mint_packet(100, RECEIVER);
+        let sign_bytes = commitment_sign_bytes(&packet);
+
+        assert!(policy
+            .sign_data(None, &chain_id, Message::SignBytes(&sign_bytes))
+            .await
+            .is_err());
+
+        let other_packet = mint_packet(1, RECEIVER);
+
+        assert!(policy
+            .sign_data(
+                None,
+                &chain_id,
+                Message::PacketCommitment {
+                    sign_bytes: &sign_bytes,
+                    packet: &other_packet,
+                },
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_daily_cap_and_rate_limit() {
+        let policy = policy_config()
+            .with_daily_cap("gld", 150.into())
+            .into_signer()
+            .unwrap();
+
+        // Usage is released when wrapped signer fails to sign
+        assert!(sign_mint(&policy, "test-2", 100, RECEIVER).await.is_err());
+        assert!(sign_mint(&policy, "test-1", 100, RECEIVER).await.is_ok());
+        assert!(sign_mint(&policy, "test-1", 100, RECEIVER).await.is_err());
+        assert!(sign_mint(&policy, "test-1", 50, RECEIVER).await.is_ok());
+
+        let policy = policy_config()
+            .with_rate_limit(2, Duration::from_secs(3600))
+            .into_signer()
+            .unwrap();
+
+        assert!(sign_mint(&policy, "test-1", 1, RECEIVER).await.is_ok());
+        assert!(sign_mint(&policy, "test-1", 1, RECEIVER).await.is_ok());
+        assert!(sign_mint(&policy, "test-1", 1, RECEIVER).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_decode_burn() {
+        let policy = policy_config()
+            .with_denom_limit("gld", 100.into())
+            .into_signer()
+            .unwrap();
+
+        let sign_doc = burn_sign_doc(100, SOLO_MACHINE_RECEIVER);
+
+        let intents = policy.decode_intents(Message::SignDoc(&sign_doc)).unwrap();
+
+        assert_eq!(
+            intents,
+            vec![SigningIntent::Burn {
+                denom: "gld".to_owned(),
+                amount: 100.into(),
+                receiver: SOLO_MACHINE_RECEIVER.to_owned(),
+            }]
+        );
+        assert_eq!(
+            intents[0].to_string(),
+            "burn 100 gld to solo-machine-address"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_decode_ica_and_approval_hook() {
+        let policy = policy_config()
+            .with_approval_hook(|_, intents| {
+                ensure!(
+                    !intents
+                        .iter()
+                        .any(|intent| matches!(intent, SigningIntent::IcaDelegate { .. })),
+                    "delegations are not approved"
+                );
+                Ok(())
+            })
+            .into_signer()
+            .unwrap();
+
+        let message = MsgDelegate {
+            delegator_address: RECEIVER.to_owned(),
+            validator_address: "cosmosvaloper1validator".to_owned(),
+            amount: Some(Coin {
+                denom: "stake".to_owned(),
+                amount: "10".to_owned(),
+            }),
+        };
+
+        let packet_data = InterchainAccountPacketData {
+            ty: 1,
+            data: proto_encode(&CosmosTx {
+                messages: vec![message.to_any().unwrap()],
+            })
+            .unwrap(),
+            memo: String::new(),
+        };
+        let packet = packet("icahost", serde_json::to_vec(&packet_data).unwrap());
+        let sign_bytes = commitment_sign_bytes(&packet);
+        let message = Message::PacketCommitment {
+            sign_bytes: &sign_bytes,
+            packet: &packet,
+        };
+
+        assert_eq!(
+            policy.decode_intents(message).unwrap(),
+            vec![SigningIntent::IcaDelegate {
+                denom: "stake".to_owned(),
+                amount: 10.into(),
+                validator: "cosmosvaloper1validator".to_owned(),
+            }]
+        );
+        assert!(policy
+            .sign_data(None, &"test-1".parse().unwrap(), message)
+            .await
+            .is_err());
+    }
+}
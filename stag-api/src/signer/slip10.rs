@@ -0,0 +1,159 @@
+//! Hierarchical key derivation for ed25519 and secp256r1 (NIST P-256) keys as specified in
+//! [SLIP-0010](https://github.com/satoshilabs/slips/blob/master/slip-0010.md) (BIP-32 only specifies secp256k1)
+use anyhow::{ensure, Result};
+use bip32::{ChildNumber, DerivationPath};
+use hmac::{Hmac, Mac};
+use p256::{
+    elliptic_curve::{
+        ff::{Field, PrimeField},
+        sec1::ToEncodedPoint,
+    },
+    FieldBytes, Scalar, SecretKey,
+};
+use sha2::Sha512;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// Derives ed25519 secret key bytes from seed for given derivation path (only hardened derivation is supported)
+pub fn derive_ed25519(seed: &[u8], path: &DerivationPath) -> Result<[u8; 32]> {
+    let (mut key, mut chain_code) = split(hmac_sha512(b"ed25519 seed", &[seed]));
+
+    for child_number in path.iter() {
+        ensure!(
+            child_number.is_hardened(),
+            "ed25519 only supports hardened derivation (all the components of HD path must be hardened)"
+        );
+
+        (key, chain_code) = split(hmac_sha512(
+            &chain_code,
+            &[&[0], &key, &child_number.0.to_be_bytes()],
+        ));
+    }
+
+    Ok(key)
+}
+
+/// Derives secp256r1 secret key from seed for given derivation path
+pub fn derive_secp256r1(seed: &[u8], path: &DerivationPath) -> Result<SecretKey> {
+    let mut hash = hmac_sha512(b"Nist256p1 seed", &[seed]);
+
+    let (mut key, mut chain_code) = loop {
+        let (key, chain_code) = split(hash);
+
+        match parse_scalar(&key) {
+            Some(scalar) if !bool::from(scalar.is_zero()) => break (scalar, chain_code),
+            _ => hash = hmac_sha512(b"Nist256p1 seed", &[&hash]),
+        }
+    };
+
+    for child_number in path.iter() {
+        (key, chain_code) = derive_secp256r1_child(&key, &chain_code, child_number)?;
+    }
+
+    Ok(SecretKey::from_be_bytes(&key.to_repr())?)
+}
+
+/// Derives child secret key and chain code from parent secret key and chain code
+fn derive_secp256r1_child(
+    key: &Scalar,
+    chain_code: &[u8; 32],
+    child_number: ChildNumber,
+) -> Result<(Scalar, [u8; 32])> {
+    let index = child_number.0.to_be_bytes();
+
+    let mut hash = if child_number.is_hardened() {
+        hmac_sha512(chain_code, &[&[0], &key.to_repr(), &index])
+    } else {
+        let public_key = SecretKey::from_be_bytes(&key.to_repr())?
+            .public_key()
+            .to_encoded_point(true);
+
+        hmac_sha512(chain_code, &[public_key.as_bytes(), &index])
+    };
+
+    loop {
+        let (tweak, child_chain_code) = split(hash);
+
+        if let Some(tweak) = parse_scalar(&tweak) {
+            let child_key = tweak + key;
+
+            if !bool::from(child_key.is_zero()) {
+                return Ok((child_key, child_chain_code));
+            }
+        }
+
+        hash = hmac_sha512(chain_code, &[&[1], &child_chain_code, &index]);
+    }
+}
+
+/// Parses big endian bytes as a scalar (returns `None` if the value is not less than curve order)
+fn parse_scalar(bytes: &[u8; 32]) -> Option<Scalar> {
+    Scalar::from_repr(FieldBytes::clone_from_slice(bytes)).into()
+}
+
+/// Computes HMAC-SHA512 of concatenation of given data using given key
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC can take key of any size");
+
+    for data in data {
+        mac.update(data);
+    }
+
+    mac.finalize().into_bytes().into()
+}
+
+/// Splits HMAC-SHA512 output into key and chain code
+fn split(hash: [u8; 64]) -> ([u8; 32], [u8; 32]) {
+    let mut key = [0; 32];
+    let mut chain_code = [0; 32];
+
+    key.copy_from_slice(&hash[..32]);
+    chain_code.copy_from_slice(&hash[32..]);
+
+    (key, chain_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test vector 1 from SLIP-0010
+    const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+    #[test]
+    fn test_derive_ed25519() {
+        let seed = hex::decode(SEED).unwrap();
+
+        assert_eq!(
+            hex::encode(derive_ed25519(&seed, &"m".parse().unwrap()).unwrap()),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+        assert_eq!(
+            hex::encode(derive_ed25519(&seed, &"m/0'".parse().unwrap()).unwrap()),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+        assert!(derive_ed25519(&seed, &"m/0".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_derive_secp256r1() {
+        let seed = hex::decode(SEED).unwrap();
+
+        assert_eq!(
+            hex::encode(
+                derive_secp256r1(&seed, &"m".parse().unwrap())
+                    .unwrap()
+                    .to_be_bytes()
+            ),
+            "612091aaa12e22dd2abef664f8a01a82cae99ad7441b7ef8110424915c268bc2"
+        );
+        assert_eq!(
+            hex::encode(
+                derive_secp256r1(&seed, &"m/0'".parse().unwrap())
+                    .unwrap()
+                    .to_be_bytes()
+            ),
+            "6939694369114c67917a182c59ddb8cafc3004e63ca5d3b84403ba8613debc0c"
+        );
+    }
+}
@@ -1,19 +1,26 @@
+use std::future::Future;
+
 use anyhow::Result;
+use cosmos_sdk_proto::ibc::lightclients::solomachine::v2::{Misbehaviour, SignBytes};
 use primitive_types::U256;
 use rust_decimal::Decimal;
 
 use crate::{
-    event::NoopEventHandler,
+    event::{Event, EventHandler, NoopEventHandler},
     service::{
-        add_chain, close_channel, connect, create_ica_channel, create_transfer_channel,
-        get_all_chains, get_balance, get_chain, get_history, get_ibc_balance, get_ibc_denom,
-        get_ica_address, get_public_keys, ica, transfer, update_signer,
+        add_chain, check_endpoints, close_channel, connect, create_ica_channel,
+        create_transfer_channel, detect_misbehaviour, freeze_client, get_all_chains, get_balance,
+        get_chain, get_events, get_history, get_ibc_balance, get_ibc_denom, get_ica_address,
+        get_public_keys, ica, replay_events, rotate_key, submit_misbehaviour, transfer,
+        update_signer, KeyRotationReport,
     },
-    signer::{NoopSigner, Signer, SignerConfig},
+    signer::{GetPublicKey, NoopSigner, Signer, SignerConfig},
     storage::{NoopStorage, Storage, TransactionProvider},
     tendermint::{JsonRpcClient, NoopRpcClient},
+    time_util::now_utc,
     types::{
-        chain_state::{ChainConfig, ChainKey, ChainState},
+        chain_state::{ChainConfig, ChainKey, ChainState, EndpointHealth},
+        event_record::{EventQuery, EventRecord},
         ics::core::ics24_host::identifier::{ChainId, Identifier, PortId},
         operation::Operation,
         public_key::PublicKey,
@@ -106,6 +113,41 @@ where
     ) -> Result<Vec<Operation>> {
         get_history(&self.context, chain_id, limit, offset).await
     }
+
+    /// Gets persisted events selected by given query (in increasing order of ID; events are persisted only when
+    /// `StorageEventHandler` is used with the same database as the one used for storage)
+    pub async fn get_events(
+        &self,
+        query: &EventQuery,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Vec<EventRecord>> {
+        get_events(&self.context, query, limit, offset).await
+    }
+
+    /// Re-delivers persisted events selected by given query to given event handler (e.g., a webhook event handler
+    /// after an outage) and returns the ID of last replayed event
+    pub async fn replay_events<H>(
+        &self,
+        query: &EventQuery,
+        event_handler: &H,
+    ) -> Result<Option<i64>>
+    where
+        H: EventHandler + ?Sized,
+    {
+        replay_events(&self.context, query, event_handler).await
+    }
+
+    /// Checks given signed bytes against signature history of solo machine and returns misbehaviour if data
+    /// conflicting with them was already signed at the same sequence
+    pub async fn detect_misbehaviour(
+        &self,
+        chain_id: &ChainId,
+        sign_bytes: SignBytes,
+        signature: Vec<u8>,
+    ) -> Result<Option<Misbehaviour>> {
+        detect_misbehaviour(&self.context, chain_id, sign_bytes, signature).await
+    }
 }
 
 impl<C> Stag<C>
@@ -135,6 +177,18 @@ where
     }
 }
 
+impl<C> Stag<C>
+where
+    C: StagContext,
+    C::Storage: Storage,
+    C::RpcClient: JsonRpcClient,
+{
+    /// Checks health of all the RPC and gRPC endpoints of given chain
+    pub async fn check_chain_endpoints(&self, chain_id: &ChainId) -> Result<Vec<EndpointHealth>> {
+        check_endpoints(&self.context, chain_id).await
+    }
+}
+
 impl<C> Stag<C>
 where
     C: StagContext + WithTransaction,
@@ -156,7 +210,14 @@ where
         memo: String,
         force: bool,
     ) -> Result<()> {
-        connect(&self.context, chain_id, request_id, memo, force).await
+        track_operation(
+            &self.context,
+            "connect",
+            chain_id.clone(),
+            request_id.clone(),
+            connect(&self.context, chain_id, request_id, memo, force),
+        )
+        .await
     }
 
     /// Creates a new transfer channel
@@ -166,7 +227,14 @@ where
         request_id: Option<String>,
         memo: String,
     ) -> Result<()> {
-        create_transfer_channel(&self.context, chain_id, request_id, memo).await
+        track_operation(
+            &self.context,
+            "create_transfer_channel",
+            chain_id.clone(),
+            request_id.clone(),
+            create_transfer_channel(&self.context, chain_id, request_id, memo),
+        )
+        .await
     }
 
     /// Creates a new ICA (Interchain Accounts) channel
@@ -176,7 +244,14 @@ where
         request_id: Option<String>,
         memo: String,
     ) -> Result<()> {
-        create_ica_channel(&self.context, chain_id, request_id, memo).await
+        track_operation(
+            &self.context,
+            "create_ica_channel",
+            chain_id.clone(),
+            request_id.clone(),
+            create_ica_channel(&self.context, chain_id, request_id, memo),
+        )
+        .await
     }
 
     /// Closes the channel with given port id
@@ -187,7 +262,14 @@ where
         request_id: Option<String>,
         memo: String,
     ) -> Result<()> {
-        close_channel(&self.context, chain_id, port_id, request_id, memo).await
+        track_operation(
+            &self.context,
+            "close_channel",
+            chain_id.clone(),
+            request_id.clone(),
+            close_channel(&self.context, chain_id, port_id, request_id, memo),
+        )
+        .await
     }
 
     /// Updates signer for future IBC transactions
@@ -198,7 +280,61 @@ where
         new_public_key: PublicKey,
         memo: String,
     ) -> Result<()> {
-        update_signer(&self.context, chain_id, request_id, new_public_key, memo).await
+        track_operation(
+            &self.context,
+            "update_signer",
+            chain_id.clone(),
+            request_id.clone(),
+            update_signer(&self.context, chain_id, request_id, new_public_key, memo),
+        )
+        .await
+    }
+
+    /// Rotates signer's public key on all the connected chains using public keys of given new signer (returns a report
+    /// with rotation status of each chain, local signer must be switched to new signer only for updated chains)
+    pub async fn rotate_key(
+        &self,
+        new_signer: &impl GetPublicKey,
+        request_id: Option<String>,
+        memo: String,
+    ) -> Result<KeyRotationReport> {
+        rotate_key(&self.context, new_signer, request_id, memo).await
+    }
+
+    /// Submits misbehaviour to given chain (which freezes solo machine client)
+    pub async fn submit_misbehaviour(
+        &self,
+        chain_id: ChainId,
+        request_id: Option<String>,
+        misbehaviour: Misbehaviour,
+        memo: String,
+    ) -> Result<String> {
+        track_operation(
+            &self.context,
+            "submit_misbehaviour",
+            chain_id.clone(),
+            request_id.clone(),
+            submit_misbehaviour(&self.context, chain_id, request_id, misbehaviour, memo),
+        )
+        .await
+    }
+
+    /// Freezes solo machine client on given chain (should only be used in emergencies as a frozen client cannot be
+    /// used anymore)
+    pub async fn freeze_client(
+        &self,
+        chain_id: ChainId,
+        request_id: Option<String>,
+        memo: String,
+    ) -> Result<String> {
+        track_operation(
+            &self.context,
+            "freeze_client",
+            chain_id.clone(),
+            request_id.clone(),
+            freeze_client(&self.context, chain_id, request_id, memo),
+        )
+        .await
     }
 
     /// Mints tokens on given chain
@@ -211,14 +347,20 @@ where
         receiver: Option<String>,
         memo: String,
     ) -> Result<String> {
-        transfer::mint_tokens(
+        track_operation(
             &self.context,
-            chain_id,
-            request_id,
-            amount,
-            denom,
-            receiver,
-            memo,
+            "mint",
+            chain_id.clone(),
+            request_id.clone(),
+            transfer::mint_tokens(
+                &self.context,
+                chain_id,
+                request_id,
+                amount,
+                denom,
+                receiver,
+                memo,
+            ),
         )
         .await
     }
@@ -232,7 +374,14 @@ where
         denom: Identifier,
         memo: String,
     ) -> Result<String> {
-        transfer::burn_tokens(&self.context, chain_id, request_id, amount, denom, memo).await
+        track_operation(
+            &self.context,
+            "burn",
+            chain_id.clone(),
+            request_id.clone(),
+            transfer::burn_tokens(&self.context, chain_id, request_id, amount, denom, memo),
+        )
+        .await
     }
 
     /// Send tokens from ICA (Interchain Account) on host chain
@@ -245,14 +394,20 @@ where
         denom: Identifier,
         memo: String,
     ) -> Result<String> {
-        ica::bank::send(
+        track_operation(
             &self.context,
-            chain_id,
-            request_id,
-            to_address,
-            amount,
-            denom,
-            memo,
+            "ica_send",
+            chain_id.clone(),
+            request_id.clone(),
+            ica::bank::send(
+                &self.context,
+                chain_id,
+                request_id,
+                to_address,
+                amount,
+                denom,
+                memo,
+            ),
         )
         .await
     }
@@ -267,14 +422,20 @@ where
         denom: Identifier,
         memo: String,
     ) -> Result<String> {
-        ica::staking::delegate(
+        track_operation(
             &self.context,
-            chain_id,
-            request_id,
-            validator_address,
-            amount,
-            denom,
-            memo,
+            "ica_delegate",
+            chain_id.clone(),
+            request_id.clone(),
+            ica::staking::delegate(
+                &self.context,
+                chain_id,
+                request_id,
+                validator_address,
+                amount,
+                denom,
+                memo,
+            ),
         )
         .await
     }
@@ -289,14 +450,20 @@ where
         denom: Identifier,
         memo: String,
     ) -> Result<String> {
-        ica::staking::undelegate(
+        track_operation(
             &self.context,
-            chain_id,
-            request_id,
-            validator_address,
-            amount,
-            denom,
-            memo,
+            "ica_undelegate",
+            chain_id.clone(),
+            request_id.clone(),
+            ica::staking::undelegate(
+                &self.context,
+                chain_id,
+                request_id,
+                validator_address,
+                amount,
+                denom,
+                memo,
+            ),
         )
         .await
     }
@@ -313,3 +480,31 @@ where
         storage.delete().await
     }
 }
+
+/// Awaits given operation and emits an `OperationFinished` event with its outcome and duration
+async fn track_operation<C, T>(
+    context: &C,
+    operation: &str,
+    chain_id: ChainId,
+    request_id: Option<String>,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T>
+where
+    C: StagContext,
+{
+    let started_at = now_utc();
+    let result = future.await;
+    let duration_ms = (now_utc() - started_at).num_milliseconds().max(0) as u64;
+
+    context
+        .handle_event(Event::OperationFinished {
+            chain_id,
+            request_id,
+            operation: operation.to_owned(),
+            duration_ms,
+            error: result.as_ref().err().map(|err| format!("{:#}", err)),
+        })
+        .await?;
+
+    result
+}
@@ -7,7 +7,7 @@ use crate::trait_util::Base;
 #[cfg(feature = "indexed-db-storage")]
 use super::indexed_db_storage::IndexedDbStorage;
 #[cfg(any(feature = "sqlite-storage", feature = "postgres-storage"))]
-use super::sql_db_storage::SqlDbStorage;
+use super::sql_db_storage::{SharedSqlDbStorage, SqlDbStorage};
 use super::TransactionProvider;
 
 #[cfg_attr(feature = "doc", doc(cfg(feature = "indexed-db-storage")))]
@@ -35,9 +35,9 @@ impl IndexedDb {
 #[cfg_attr(feature = "doc", doc(cfg(feature = "sqlite-storage")))]
 #[cfg(feature = "sqlite-storage")]
 #[derive(Debug, Clone, PartialEq, Eq)]
-/// Storage backend using SQLite database
+/// Storage backend using SQLite database (all the clones of a config share the same connection pool)
 pub struct Sqlite {
-    uri: String,
+    storage: SharedSqlDbStorage,
 }
 
 #[cfg(feature = "sqlite-storage")]
@@ -45,21 +45,27 @@ impl Sqlite {
     /// Creates a new instance of Sqlite
     pub fn new(uri: &str) -> Self {
         Self {
-            uri: uri.to_string(),
+            storage: SharedSqlDbStorage::new(uri.to_string()),
         }
     }
 
+    /// Returns the storage shared by all the clones of this config
+    #[cfg(feature = "webhook-event-handler")]
+    pub(crate) fn shared_storage(&self) -> SharedSqlDbStorage {
+        self.storage.clone()
+    }
+
     async fn get_sqlite_storage(self) -> Result<SqlDbStorage> {
-        SqlDbStorage::new(self.uri).await
+        self.storage.get().await.cloned()
     }
 }
 
 #[cfg_attr(feature = "doc", doc(cfg(feature = "postgres-storage")))]
 #[cfg(feature = "postgres-storage")]
 #[derive(Debug, Clone, PartialEq, Eq)]
-/// Storage backend using Postgres database
+/// Storage backend using Postgres database (all the clones of a config share the same connection pool)
 pub struct Postgres {
-    uri: String,
+    storage: SharedSqlDbStorage,
 }
 
 #[cfg(feature = "postgres-storage")]
@@ -67,12 +73,18 @@ impl Postgres {
     /// Creates a new instance of Postgres
     pub fn new(uri: &str) -> Self {
         Self {
-            uri: uri.to_string(),
+            storage: SharedSqlDbStorage::new(uri.to_string()),
         }
     }
 
+    /// Returns the storage shared by all the clones of this config
+    #[cfg(feature = "webhook-event-handler")]
+    pub(crate) fn shared_storage(&self) -> SharedSqlDbStorage {
+        self.storage.clone()
+    }
+
     async fn get_postgres_storage(self) -> Result<SqlDbStorage> {
-        SqlDbStorage::new(self.uri).await
+        self.storage.get().await.cloned()
     }
 }
 
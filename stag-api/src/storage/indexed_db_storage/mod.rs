@@ -6,5 +6,7 @@ pub use self::{storage::IndexedDbStorage, transaction::IndexedDbTransaction};
 
 const CHAIN_STATE_STORE_NAME: &str = "chain_state";
 const CHAIN_KEY_STORE_NAME: &str = "chain_key";
+const EVENTS_STORE_NAME: &str = "events";
 const IBC_DATA_STORE_NAME: &str = "ibc_data";
+const LIGHT_CLIENT_ATTACK_EVIDENCE_STORE_NAME: &str = "light_client_attack_evidence";
 const OPERATIONS_STORE_NAME: &str = "operations";
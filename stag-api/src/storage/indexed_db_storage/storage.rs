@@ -4,25 +4,31 @@ use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use cosmos_sdk_proto::ibc::{
     core::{channel::v1::Channel, client::v1::Height, connection::v1::ConnectionEnd},
-    lightclients::tendermint::v1::{
-        ClientState as TendermintClientState, ConsensusState as TendermintConsensusState,
+    lightclients::{
+        solomachine::v2::SignatureAndData,
+        tendermint::v1::{
+            ClientState as TendermintClientState, ConsensusState as TendermintConsensusState,
+        },
     },
 };
 use rexie::{Index, ObjectStore, Rexie, TransactionMode};
 use tendermint::node::Id as NodeId;
 
 use crate::{
+    event::Event,
     storage::{Storage, Transaction, TransactionProvider},
+    tendermint::LightClientAttackEvidence,
     types::{
         chain_state::{ChainConfig, ChainKey, ChainState},
+        event_record::{EventQuery, EventRecord},
         ics::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
         operation::{Operation, OperationType},
     },
 };
 
 use super::{
-    IndexedDbTransaction, CHAIN_KEY_STORE_NAME, CHAIN_STATE_STORE_NAME, IBC_DATA_STORE_NAME,
-    OPERATIONS_STORE_NAME,
+    IndexedDbTransaction, CHAIN_KEY_STORE_NAME, CHAIN_STATE_STORE_NAME, EVENTS_STORE_NAME,
+    IBC_DATA_STORE_NAME, LIGHT_CLIENT_ATTACK_EVIDENCE_STORE_NAME, OPERATIONS_STORE_NAME,
 };
 
 #[derive(Clone)]
@@ -33,7 +39,7 @@ pub struct IndexedDbStorage {
 impl IndexedDbStorage {
     pub async fn new(name: &str) -> Result<Self> {
         let rexie = Rexie::builder(name)
-            .version(1)
+            .version(3)
             .add_object_store(ObjectStore::new(CHAIN_STATE_STORE_NAME).key_path("id"))
             .add_object_store(
                 ObjectStore::new(CHAIN_KEY_STORE_NAME)
@@ -48,6 +54,17 @@ impl IndexedDbStorage {
                     .auto_increment(true)
                     .add_index(Index::new("chain_id", "chain_id")),
             )
+            .add_object_store(
+                ObjectStore::new(EVENTS_STORE_NAME)
+                    .key_path("id")
+                    .auto_increment(true),
+            )
+            .add_object_store(
+                ObjectStore::new(LIGHT_CLIENT_ATTACK_EVIDENCE_STORE_NAME)
+                    .key_path("id")
+                    .auto_increment(true)
+                    .add_index(Index::new("chain_id", "chain_id")),
+            )
             .build()
             .await
             .map_err(|err| anyhow!("error when opening indexed db: {}", err))?;
@@ -71,6 +88,14 @@ impl IndexedDbStorage {
                 "get_chain_keys" => (CHAIN_KEY_STORE_NAME, false),
                 "add_operation" => (OPERATIONS_STORE_NAME, true),
                 "get_operations" => (OPERATIONS_STORE_NAME, false),
+                "add_event" => (EVENTS_STORE_NAME, true),
+                "get_events" => (EVENTS_STORE_NAME, false),
+                "add_light_client_attack_evidence" => {
+                    (LIGHT_CLIENT_ATTACK_EVIDENCE_STORE_NAME, true)
+                }
+                "get_light_client_attack_evidence" => {
+                    (LIGHT_CLIENT_ATTACK_EVIDENCE_STORE_NAME, false)
+                }
                 "add_tendermint_client_state" => (IBC_DATA_STORE_NAME, true),
                 "get_tendermint_client_state" => (IBC_DATA_STORE_NAME, false),
                 "add_tendermint_consensus_state" => (IBC_DATA_STORE_NAME, true),
@@ -84,6 +109,8 @@ impl IndexedDbStorage {
                 "add_ica_address" => (IBC_DATA_STORE_NAME, true),
                 "get_ica_address" => (IBC_DATA_STORE_NAME, false),
                 "update_ica_address" => (IBC_DATA_STORE_NAME, true),
+                "add_signature_and_data" => (IBC_DATA_STORE_NAME, true),
+                "get_signature_and_data" => (IBC_DATA_STORE_NAME, false),
                 _ => return Err(anyhow!("unknown access point: {}", access_point)),
             };
 
@@ -237,6 +264,60 @@ impl Storage for IndexedDbStorage {
         Ok(result)
     }
 
+    async fn add_event(&self, event: &Event) -> Result<i64> {
+        let transaction = self.get_transaction(&["add_event"])?;
+
+        let result = transaction.add_event(event).await?;
+
+        transaction.done().await?;
+
+        Ok(result)
+    }
+
+    async fn get_events(
+        &self,
+        query: &EventQuery,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Vec<EventRecord>> {
+        let transaction = self.get_transaction(&["get_events"])?;
+
+        let result = transaction.get_events(query, limit, offset).await?;
+
+        transaction.done().await?;
+
+        Ok(result)
+    }
+
+    async fn add_light_client_attack_evidence(
+        &self,
+        chain_id: &ChainId,
+        evidence: &LightClientAttackEvidence,
+    ) -> Result<()> {
+        let transaction = self.get_transaction(&["add_light_client_attack_evidence"])?;
+
+        transaction
+            .add_light_client_attack_evidence(chain_id, evidence)
+            .await?;
+
+        transaction.done().await
+    }
+
+    async fn get_light_client_attack_evidence(
+        &self,
+        chain_id: &ChainId,
+    ) -> Result<Option<LightClientAttackEvidence>> {
+        let transaction = self.get_transaction(&["get_light_client_attack_evidence"])?;
+
+        let result = transaction
+            .get_light_client_attack_evidence(chain_id)
+            .await?;
+
+        transaction.done().await?;
+
+        Ok(result)
+    }
+
     async fn add_tendermint_client_state(
         &self,
         client_id: &ClientId,
@@ -421,6 +502,37 @@ impl Storage for IndexedDbStorage {
         transaction.done().await
     }
 
+    async fn add_signature_and_data(
+        &self,
+        chain_id: &ChainId,
+        sequence: u64,
+        signature_and_data: &SignatureAndData,
+    ) -> Result<()> {
+        let transaction = self.get_transaction(&["add_signature_and_data"])?;
+
+        transaction
+            .add_signature_and_data(chain_id, sequence, signature_and_data)
+            .await?;
+
+        transaction.done().await
+    }
+
+    async fn get_signature_and_data(
+        &self,
+        chain_id: &ChainId,
+        sequence: u64,
+    ) -> Result<Option<SignatureAndData>> {
+        let transaction = self.get_transaction(&["get_signature_and_data"])?;
+
+        let result = transaction
+            .get_signature_and_data(chain_id, sequence)
+            .await?;
+
+        transaction.done().await?;
+
+        Ok(result)
+    }
+
     async fn delete(self) -> Result<()> {
         let name = self.rexie.name();
 
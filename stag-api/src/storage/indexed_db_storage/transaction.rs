@@ -2,8 +2,11 @@ use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
 use cosmos_sdk_proto::ibc::{
     core::{channel::v1::Channel, client::v1::Height, connection::v1::ConnectionEnd},
-    lightclients::tendermint::v1::{
-        ClientState as TendermintClientState, ConsensusState as TendermintConsensusState,
+    lightclients::{
+        solomachine::v2::SignatureAndData,
+        tendermint::v1::{
+            ClientState as TendermintClientState, ConsensusState as TendermintConsensusState,
+        },
     },
 };
 use prost::Message;
@@ -11,16 +14,19 @@ use rexie::{Direction, KeyRange, Transaction as RexieTransaction};
 use tendermint::node::Id as NodeId;
 
 use crate::{
+    event::Event,
     storage::{Storage, Transaction},
+    tendermint::LightClientAttackEvidence,
     time_util::now_utc,
     types::{
         chain_state::{ChainConfig, ChainKey, ChainState},
+        event_record::{EventQuery, EventRecord},
         ibc_data::IbcData,
         ics::core::ics24_host::{
             identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
             path::{
                 ChannelPath, ClientStatePath, ConnectionPath, ConsensusStatePath,
-                InterchainAccountAddressPath,
+                InterchainAccountAddressPath, SoloMachineSignaturePath,
             },
         },
         operation::{Operation, OperationType},
@@ -29,8 +35,12 @@ use crate::{
 };
 
 use super::{
-    types::{ChainKeyRequest, OperationRequest},
-    CHAIN_KEY_STORE_NAME, CHAIN_STATE_STORE_NAME, IBC_DATA_STORE_NAME, OPERATIONS_STORE_NAME,
+    types::{
+        ChainKeyRequest, EventRequest, LightClientAttackEvidenceRecord,
+        LightClientAttackEvidenceRequest, OperationRequest,
+    },
+    CHAIN_KEY_STORE_NAME, CHAIN_STATE_STORE_NAME, EVENTS_STORE_NAME, IBC_DATA_STORE_NAME,
+    LIGHT_CLIENT_ATTACK_EVIDENCE_STORE_NAME, OPERATIONS_STORE_NAME,
 };
 
 pub struct IndexedDbTransaction {
@@ -386,6 +396,175 @@ impl Storage for IndexedDbTransaction {
             .collect()
     }
 
+    async fn add_event(&self, event: &Event) -> Result<i64> {
+        let event_request = EventRequest {
+            event_type: event.event_type(),
+            chain_id: event.chain_id(),
+            request_id: event.request_id(),
+            event,
+            created_at: now_utc(),
+        };
+
+        let store = self
+            .transaction
+            .store(EVENTS_STORE_NAME)
+            .map_err(|err| anyhow!("error when getting events object store: {}", err))?;
+
+        let id = store
+            .add(
+                &serde_wasm_bindgen::to_value(&event_request)
+                    .map_err(|err| anyhow!("error when serializing event: {}", err))?,
+                None,
+            )
+            .await
+            .map_err(|err| anyhow!("error when adding value in events object store: {}", err))?;
+
+        serde_wasm_bindgen::from_value(id)
+            .map_err(|err| anyhow!("error when deserializing event id: {}", err))
+    }
+
+    async fn get_events(
+        &self,
+        query: &EventQuery,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Vec<EventRecord>> {
+        let store = self
+            .transaction
+            .store(EVENTS_STORE_NAME)
+            .map_err(|err| anyhow!("error when getting events object store: {}", err))?;
+
+        let key_range = query
+            .from_id
+            .map(|from_id| {
+                let js_from_id = serde_wasm_bindgen::to_value(&from_id)
+                    .map_err(|err| anyhow!("error when serializing from_id: {}", err))?;
+
+                KeyRange::lower_bound(&js_from_id, false)
+                    .map_err(|err| anyhow!("unable to generate keyrange: {}", err))
+            })
+            .transpose()?;
+
+        let pairs = store
+            .get_all(key_range.as_ref(), None, None, Some(Direction::Next))
+            .await
+            .map_err(|err| anyhow!("error when getting all events: {}", err))?;
+
+        // Other filters are applied in memory because events object store only has an index on ID
+        let events = pairs
+            .into_iter()
+            .map(|(_, js_value)| {
+                serde_wasm_bindgen::from_value(js_value)
+                    .map_err(|err| anyhow!("error when deserializing event: {}", err))
+            })
+            .filter(|event: &Result<EventRecord>| {
+                event.as_ref().map_or(true, |event| query.matches(event))
+            })
+            .skip(offset.unwrap_or_default() as usize)
+            .take(limit.map_or(usize::MAX, |limit| limit as usize));
+
+        events.collect()
+    }
+
+    async fn add_light_client_attack_evidence(
+        &self,
+        chain_id: &ChainId,
+        evidence: &LightClientAttackEvidence,
+    ) -> Result<()> {
+        let evidence_request = LightClientAttackEvidenceRequest {
+            chain_id,
+            evidence,
+            created_at: now_utc(),
+        };
+
+        let store = self
+            .transaction
+            .store(LIGHT_CLIENT_ATTACK_EVIDENCE_STORE_NAME)
+            .map_err(|err| {
+                anyhow!(
+                    "error when getting light_client_attack_evidence object store: {}",
+                    err
+                )
+            })?;
+
+        store
+            .add(
+                &serde_wasm_bindgen::to_value(&evidence_request).map_err(|err| {
+                    anyhow!(
+                        "error when serializing light client attack evidence: {}",
+                        err
+                    )
+                })?,
+                None,
+            )
+            .await
+            .map_err(|err| {
+                anyhow!(
+                    "error when adding value in light_client_attack_evidence object store: {}",
+                    err
+                )
+            })?;
+
+        Ok(())
+    }
+
+    async fn get_light_client_attack_evidence(
+        &self,
+        chain_id: &ChainId,
+    ) -> Result<Option<LightClientAttackEvidence>> {
+        let store = self
+            .transaction
+            .store(LIGHT_CLIENT_ATTACK_EVIDENCE_STORE_NAME)
+            .map_err(|err| {
+                anyhow!(
+                    "error when getting light_client_attack_evidence object store: {}",
+                    err
+                )
+            })?;
+
+        let index = store.index("chain_id").map_err(|err| {
+            anyhow!(
+                "error when getting index from light_client_attack_evidence object store: {}",
+                err
+            )
+        })?;
+
+        let js_chain_id = serde_wasm_bindgen::to_value(&chain_id)
+            .map_err(|err| anyhow!("error when serializing chain_id: {}", err))?;
+
+        index
+            .get_all(
+                Some(
+                    &KeyRange::only(&js_chain_id)
+                        .map_err(|err| anyhow!("unable to generate keyrange: {}", err))?,
+                ),
+                Some(1),
+                None,
+                Some(Direction::Next),
+            )
+            .await
+            .map_err(|err| {
+                anyhow!(
+                    "error when getting light client attack evidence for chain id [{}]: {}",
+                    chain_id,
+                    err
+                )
+            })?
+            .into_iter()
+            .next()
+            .map(|(_, js_value)| {
+                serde_wasm_bindgen::from_value::<LightClientAttackEvidenceRecord>(js_value)
+                    .map(|record| record.evidence)
+                    .map_err(|err| {
+                        anyhow!(
+                            "error when deserializing light client attack evidence: {}",
+                            err
+                        )
+                    })
+            })
+            .transpose()
+    }
+
     async fn add_tendermint_client_state(
         &self,
         client_id: &ClientId,
@@ -575,6 +754,37 @@ impl Storage for IndexedDbTransaction {
         self.update_ibc_data(&ibc_data).await
     }
 
+    async fn add_signature_and_data(
+        &self,
+        chain_id: &ChainId,
+        sequence: u64,
+        signature_and_data: &SignatureAndData,
+    ) -> Result<()> {
+        let ibc_data = IbcData {
+            path: SoloMachineSignaturePath::new(chain_id, sequence).into(),
+            data: proto_encode(signature_and_data)?,
+        };
+
+        self.add_ibc_data(&ibc_data).await
+    }
+
+    async fn get_signature_and_data(
+        &self,
+        chain_id: &ChainId,
+        sequence: u64,
+    ) -> Result<Option<SignatureAndData>> {
+        let path: String = SoloMachineSignaturePath::new(chain_id, sequence).into();
+
+        let ibc_data: Option<IbcData> = self.get_ibc_data(&path).await?;
+
+        match ibc_data {
+            None => Ok(None),
+            Some(ibc_data) => SignatureAndData::decode(ibc_data.data.as_slice())
+                .context("error when deserializing signature and data")
+                .map(Some),
+        }
+    }
+
     async fn delete(self) -> Result<()> {
         Err(anyhow!("cannot delete the storage from a transaction"))
     }
@@ -1,9 +1,13 @@
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::types::{
-    ics::core::ics24_host::identifier::{ChainId, PortId},
-    operation::OperationType,
+use crate::{
+    event::Event,
+    tendermint::LightClientAttackEvidence,
+    types::{
+        ics::core::ics24_host::identifier::{ChainId, PortId},
+        operation::OperationType,
+    },
 };
 
 /// Signer's public key entry for an IBC enabled chain
@@ -32,3 +36,33 @@ pub struct OperationRequest<'a> {
     /// Time at which this operation was created
     pub created_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Serialize)]
+pub struct EventRequest<'a> {
+    /// Type of event
+    pub event_type: &'a str,
+    /// Chain ID associated with the event
+    pub chain_id: Option<&'a ChainId>,
+    /// Request ID associated with the event
+    pub request_id: Option<&'a str>,
+    /// Event
+    pub event: &'a Event,
+    /// Time at which this event was stored
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LightClientAttackEvidenceRequest<'a> {
+    /// Chain ID of attacked chain
+    pub chain_id: &'a ChainId,
+    /// Evidence of light client attack
+    pub evidence: &'a LightClientAttackEvidence,
+    /// Time at which this evidence was stored
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LightClientAttackEvidenceRecord {
+    /// Evidence of light client attack
+    pub evidence: LightClientAttackEvidence,
+}
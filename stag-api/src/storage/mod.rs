@@ -12,6 +12,11 @@ pub use self::builder::IndexedDb;
 pub use self::builder::Postgres;
 #[cfg(feature = "sqlite-storage")]
 pub use self::builder::Sqlite;
+#[cfg(all(
+    feature = "webhook-event-handler",
+    any(feature = "sqlite-storage", feature = "postgres-storage")
+))]
+pub(crate) use self::sql_db_storage::{SharedSqlDbStorage, SqlDbStorage};
 pub use self::{
     builder::StorageConfig,
     storage_traits::{Storage, Transaction, TransactionProvider},
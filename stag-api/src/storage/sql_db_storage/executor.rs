@@ -4,26 +4,36 @@ use anyhow::{bail, ensure, Context, Result};
 use chrono::Utc;
 use cosmos_sdk_proto::ibc::{
     core::{channel::v1::Channel, client::v1::Height, connection::v1::ConnectionEnd},
-    lightclients::tendermint::v1::{
-        ClientState as TendermintClientState, ConsensusState as TendermintConsensusState,
+    lightclients::{
+        solomachine::v2::SignatureAndData,
+        tendermint::v1::{
+            ClientState as TendermintClientState, ConsensusState as TendermintConsensusState,
+        },
     },
 };
 use prost::Message;
-use sqlx::{types::Json, Executor};
+use sqlx::{types::Json, Executor, Row};
 use tendermint::node::Id as NodeId;
 
-use crate::types::{
-    chain_state::{ChainConfig, ChainKey, ChainState},
-    ibc_data::IbcData,
-    ics::core::ics24_host::{
-        identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
-        path::{
-            ChannelPath, ClientStatePath, ConnectionPath, ConsensusStatePath,
-            InterchainAccountAddressPath,
+#[cfg(feature = "webhook-event-handler")]
+use crate::event::OutboxEntry;
+use crate::{
+    event::Event,
+    tendermint::LightClientAttackEvidence,
+    types::{
+        chain_state::{ChainConfig, ChainKey, ChainState},
+        event_record::{EventQuery, EventRecord},
+        ibc_data::IbcData,
+        ics::core::ics24_host::{
+            identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
+            path::{
+                ChannelPath, ClientStatePath, ConnectionPath, ConsensusStatePath,
+                InterchainAccountAddressPath, SoloMachineSignaturePath,
+            },
         },
+        operation::{Operation, OperationType},
+        proto_util::proto_encode,
     },
-    operation::{Operation, OperationType},
-    proto_util::proto_encode,
 };
 
 use super::{Db, DbRow};
@@ -65,6 +75,7 @@ pub async fn get_chain_state<'e>(
     row.map(TryFrom::try_from).transpose()
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(chain_id = %chain_state.id)))]
 pub async fn update_chain_state<'e>(
     executor: impl Executor<'e, Database = Db>,
     chain_state: &ChainState,
@@ -177,6 +188,10 @@ pub async fn get_operations<'e>(
 }
 
 #[allow(clippy::too_many_arguments)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_id, request_id = request_id))
+)]
 pub async fn add_operation<'e>(
     executor: impl Executor<'e, Database = Db>,
     request_id: Option<&str>,
@@ -206,6 +221,133 @@ pub async fn add_operation<'e>(
     Ok(())
 }
 
+pub async fn add_event<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    event: &Event,
+) -> Result<i64> {
+    let row: DbRow = sqlx::query(
+        "INSERT INTO events (event_type, chain_id, request_id, event) VALUES ($1, $2, $3, $4) RETURNING id",
+    )
+    .bind(event.event_type())
+    .bind(event.chain_id().map(ToString::to_string))
+    .bind(event.request_id())
+    .bind(Json(event))
+    .fetch_one(executor)
+    .await
+    .context("unable to add event to database")?;
+
+    row.try_get("id").context("unable to get id of event")
+}
+
+pub async fn get_events<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    query: &EventQuery,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<EventRecord>> {
+    let mut conditions = Vec::new();
+    let mut placeholders = 1..;
+
+    if query.from_id.is_some() {
+        conditions.push(format!("id >= ${}", placeholders.next().unwrap()));
+    }
+
+    if query.chain_id.is_some() {
+        conditions.push(format!("chain_id = ${}", placeholders.next().unwrap()));
+    }
+
+    if !query.event_types.is_empty() {
+        let event_types = query
+            .event_types
+            .iter()
+            .map(|_| format!("${}", placeholders.next().unwrap()))
+            .collect::<Vec<_>>();
+
+        conditions.push(format!("event_type IN ({})", event_types.join(", ")));
+    }
+
+    if query.request_id.is_some() {
+        conditions.push(format!("request_id = ${}", placeholders.next().unwrap()));
+    }
+
+    let mut sql = "SELECT * FROM events".to_owned();
+
+    if !conditions.is_empty() {
+        write!(sql, " WHERE {}", conditions.join(" AND "))?;
+    }
+
+    sql.push_str(" ORDER BY id");
+
+    push_limit_offset(&mut sql, limit, offset)?;
+
+    let mut sql_query = sqlx::query(&sql);
+
+    if let Some(from_id) = query.from_id {
+        sql_query = sql_query.bind(from_id);
+    }
+
+    if let Some(ref chain_id) = query.chain_id {
+        sql_query = sql_query.bind(chain_id.to_string());
+    }
+
+    for event_type in query.event_types.iter() {
+        sql_query = sql_query.bind(event_type);
+    }
+
+    if let Some(ref request_id) = query.request_id {
+        sql_query = sql_query.bind(request_id);
+    }
+
+    let raw: Vec<DbRow> = sql_query
+        .fetch_all(executor)
+        .await
+        .context("unable to query events from database")?;
+
+    raw.into_iter().map(TryFrom::try_from).collect()
+}
+
+pub async fn add_light_client_attack_evidence<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    evidence: &LightClientAttackEvidence,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "INSERT INTO light_client_attack_evidence (chain_id, evidence) VALUES ($1, $2)",
+    )
+    .bind(chain_id.to_string())
+    .bind(Json(evidence))
+    .execute(executor)
+    .await
+    .context("unable to add light client attack evidence")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when adding light client attack evidence"
+    );
+
+    Ok(())
+}
+
+pub async fn get_light_client_attack_evidence<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<Option<LightClientAttackEvidence>> {
+    let row: Option<DbRow> = sqlx::query(
+        "SELECT evidence FROM light_client_attack_evidence WHERE chain_id = $1 ORDER BY id LIMIT 1",
+    )
+    .bind(chain_id.to_string())
+    .fetch_optional(executor)
+    .await
+    .context("unable to query light client attack evidence from database")?;
+
+    row.map(|row| {
+        let evidence: Json<LightClientAttackEvidence> = row.try_get("evidence")?;
+        Ok(evidence.0)
+    })
+    .transpose()
+}
+
 pub async fn add_tendermint_client_state<'e>(
     executor: impl Executor<'e, Database = Db>,
     client_id: &ClientId,
@@ -342,6 +484,162 @@ pub async fn update_ica_address<'e>(
     update_ibc_data(executor, path, data).await
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(chain_id = %chain_id)))]
+pub async fn add_signature_and_data<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    sequence: u64,
+    signature_and_data: &SignatureAndData,
+) -> Result<()> {
+    let path: String = SoloMachineSignaturePath::new(chain_id, sequence).into();
+    let data = proto_encode(signature_and_data)?;
+
+    add_ibc_data(executor, path, data).await
+}
+
+pub async fn get_signature_and_data<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    sequence: u64,
+) -> Result<Option<SignatureAndData>> {
+    let path: String = SoloMachineSignaturePath::new(chain_id, sequence).into();
+    get_ibc_data(executor, &path).await
+}
+
+#[cfg(feature = "webhook-event-handler")]
+pub async fn add_outbox_entry<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    url: &str,
+    payload: &str,
+    next_attempt_at: i64,
+) -> Result<i64> {
+    let row: DbRow = sqlx::query(
+        "INSERT INTO webhook_outbox (url, payload, next_attempt_at) VALUES ($1, $2, $3) RETURNING id",
+    )
+    .bind(url)
+    .bind(payload)
+    .bind(next_attempt_at)
+    .fetch_one(executor)
+    .await
+    .context("unable to add webhook outbox entry in database")?;
+
+    row.try_get("id")
+        .context("unable to get id of webhook outbox entry")
+}
+
+#[cfg(feature = "webhook-event-handler")]
+pub async fn get_due_outbox_entries<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    now: i64,
+    max_attempts: u32,
+    limit: u32,
+) -> Result<Vec<OutboxEntry>> {
+    let raw: Vec<DbRow> = sqlx::query(
+        "SELECT * FROM webhook_outbox WHERE next_attempt_at <= $1 AND attempts < $2 ORDER BY id LIMIT $3",
+    )
+    .bind(now)
+    .bind(i64::from(max_attempts))
+    .bind(i64::from(limit))
+    .fetch_all(executor)
+    .await
+    .context("unable to query webhook outbox entries from database")?;
+
+    raw.into_iter().map(TryInto::try_into).collect()
+}
+
+#[cfg(feature = "webhook-event-handler")]
+pub async fn get_next_outbox_attempt_at<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    max_attempts: u32,
+) -> Result<Option<i64>> {
+    let row: DbRow = sqlx::query(
+        "SELECT MIN(next_attempt_at) AS next_attempt_at FROM webhook_outbox WHERE attempts < $1",
+    )
+    .bind(i64::from(max_attempts))
+    .fetch_one(executor)
+    .await
+    .context("unable to query next attempt time of webhook outbox entries from database")?;
+
+    row.try_get("next_attempt_at")
+        .context("unable to get next attempt time of webhook outbox entries")
+}
+
+#[cfg(feature = "webhook-event-handler")]
+pub async fn reschedule_outbox_entry<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+    error: &str,
+    next_attempt_at: i64,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "UPDATE webhook_outbox SET attempts = attempts + 1, last_error = $1, next_attempt_at = $2 WHERE id = $3",
+    )
+    .bind(error)
+    .bind(next_attempt_at)
+    .bind(id)
+    .execute(executor)
+    .await
+    .context("unable to update webhook outbox entry in database")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when updating webhook outbox entry"
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "webhook-event-handler")]
+pub async fn delete_outbox_entry<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+) -> Result<()> {
+    sqlx::query("DELETE FROM webhook_outbox WHERE id = $1")
+        .bind(id)
+        .execute(executor)
+        .await
+        .context("unable to delete webhook outbox entry from database")?;
+
+    Ok(())
+}
+
+#[cfg(all(test, feature = "webhook-event-handler"))]
+pub async fn get_webhook_dead_letters<'e>(
+    executor: impl Executor<'e, Database = Db>,
+) -> Result<Vec<OutboxEntry>> {
+    let raw: Vec<DbRow> = sqlx::query("SELECT * FROM webhook_dead_letters ORDER BY id")
+        .fetch_all(executor)
+        .await
+        .context("unable to query webhook dead letters from database")?;
+
+    raw.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Moves exhausted webhook deliveries to `webhook_dead_letters` table (must be called within a transaction)
+#[cfg(feature = "webhook-event-handler")]
+pub async fn move_exhausted_outbox_entries(
+    transaction: &mut sqlx::Transaction<'_, Db>,
+    max_attempts: u32,
+) -> Result<u64> {
+    sqlx::query(
+        "INSERT INTO webhook_dead_letters (id, url, payload, attempts, last_error, created_at) SELECT id, url, payload, attempts, last_error, created_at FROM webhook_outbox WHERE attempts >= $1",
+    )
+    .bind(i64::from(max_attempts))
+    .execute(&mut *transaction)
+    .await
+    .context("unable to add webhook dead letters in database")?;
+
+    let rows_affected = sqlx::query("DELETE FROM webhook_outbox WHERE attempts >= $1")
+        .bind(i64::from(max_attempts))
+        .execute(&mut *transaction)
+        .await
+        .context("unable to delete exhausted webhook outbox entries from database")?
+        .rows_affected();
+
+    Ok(rows_affected)
+}
+
 async fn add_ibc_data<'e>(
     executor: impl Executor<'e, Database = Db>,
     path: String,
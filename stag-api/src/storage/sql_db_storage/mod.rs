@@ -3,7 +3,10 @@ mod storage;
 mod transaction;
 mod types;
 
-pub use self::{storage::SqlDbStorage, transaction::SqlDbTransaction};
+pub use self::{
+    storage::{SharedSqlDbStorage, SqlDbStorage},
+    transaction::SqlDbTransaction,
+};
 
 #[cfg(feature = "postgres-storage")]
 use sqlx::{postgres::PgRow as DbRow, Postgres as Db};
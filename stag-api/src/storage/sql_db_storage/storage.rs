@@ -1,9 +1,14 @@
+use std::{fmt, sync::Arc};
+
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use cosmos_sdk_proto::ibc::{
     core::{channel::v1::Channel, client::v1::Height, connection::v1::ConnectionEnd},
-    lightclients::tendermint::v1::{
-        ClientState as TendermintClientState, ConsensusState as TendermintConsensusState,
+    lightclients::{
+        solomachine::v2::SignatureAndData,
+        tendermint::v1::{
+            ClientState as TendermintClientState, ConsensusState as TendermintConsensusState,
+        },
     },
 };
 use sqlx::{
@@ -11,11 +16,17 @@ use sqlx::{
     Pool,
 };
 use tendermint::node::Id as NodeId;
+use tokio::sync::OnceCell;
 
+#[cfg(feature = "webhook-event-handler")]
+use crate::event::OutboxEntry;
 use crate::{
+    event::Event,
     storage::{Storage, TransactionProvider},
+    tendermint::LightClientAttackEvidence,
     types::{
         chain_state::{ChainConfig, ChainKey, ChainState},
+        event_record::{EventQuery, EventRecord},
         ics::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
         operation::{Operation, OperationType},
     },
@@ -29,11 +40,52 @@ const MIGRATOR: Migrator = sqlx::migrate!("./migrations/sqlite");
 #[cfg(feature = "postgres-storage")]
 const MIGRATOR: Migrator = sqlx::migrate!("./migrations/postgres");
 
+#[derive(Clone)]
 pub struct SqlDbStorage {
     uri: String,
     pool: Pool<Db>,
 }
 
+/// SQL database storage which is connected lazily and shared by all of its clones (so that different components,
+/// e.g., event handlers, use the same connection pool, which is required for in-memory databases)
+#[derive(Clone)]
+pub struct SharedSqlDbStorage {
+    uri: String,
+    storage: Arc<OnceCell<SqlDbStorage>>,
+}
+
+impl SharedSqlDbStorage {
+    pub fn new(uri: String) -> Self {
+        Self {
+            uri,
+            storage: Default::default(),
+        }
+    }
+
+    /// Returns the storage (connects to database and runs migrations when called for the first time)
+    pub async fn get(&self) -> Result<&SqlDbStorage> {
+        self.storage
+            .get_or_try_init(|| SqlDbStorage::new(self.uri.clone()))
+            .await
+    }
+}
+
+impl fmt::Debug for SharedSqlDbStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedSqlDbStorage")
+            .field("uri", &self.uri)
+            .finish()
+    }
+}
+
+impl PartialEq for SharedSqlDbStorage {
+    fn eq(&self, other: &Self) -> bool {
+        self.uri == other.uri
+    }
+}
+
+impl Eq for SharedSqlDbStorage {}
+
 impl SqlDbStorage {
     pub async fn new(uri: String) -> Result<Self> {
         if !Db::database_exists(&uri).await? {
@@ -50,6 +102,77 @@ impl SqlDbStorage {
     }
 }
 
+#[cfg(feature = "webhook-event-handler")]
+impl SqlDbStorage {
+    /// Adds a webhook delivery to outbox and returns its ID
+    pub(crate) async fn add_outbox_entry(
+        &self,
+        url: &str,
+        payload: &str,
+        next_attempt_at: i64,
+    ) -> Result<i64> {
+        executor::add_outbox_entry(&self.pool, url, payload, next_attempt_at).await
+    }
+
+    /// Returns webhook deliveries which are due at `now` (in milliseconds since unix epoch)
+    pub(crate) async fn get_due_outbox_entries(
+        &self,
+        now: i64,
+        max_attempts: u32,
+        limit: u32,
+    ) -> Result<Vec<OutboxEntry>> {
+        executor::get_due_outbox_entries(&self.pool, now, max_attempts, limit).await
+    }
+
+    /// Returns the earliest next attempt time of pending webhook deliveries
+    pub(crate) async fn get_next_outbox_attempt_at(
+        &self,
+        max_attempts: u32,
+    ) -> Result<Option<i64>> {
+        executor::get_next_outbox_attempt_at(&self.pool, max_attempts).await
+    }
+
+    /// Records a failed attempt of webhook delivery and schedules the next attempt
+    pub(crate) async fn reschedule_outbox_entry(
+        &self,
+        id: i64,
+        error: &str,
+        next_attempt_at: i64,
+    ) -> Result<()> {
+        executor::reschedule_outbox_entry(&self.pool, id, error, next_attempt_at).await
+    }
+
+    /// Removes a delivered webhook delivery from outbox
+    pub(crate) async fn delete_outbox_entry(&self, id: i64) -> Result<()> {
+        executor::delete_outbox_entry(&self.pool, id).await
+    }
+
+    /// Returns webhook deliveries which were not delivered within the maximum number of attempts
+    #[cfg(test)]
+    pub(crate) async fn get_webhook_dead_letters(&self) -> Result<Vec<OutboxEntry>> {
+        executor::get_webhook_dead_letters(&self.pool).await
+    }
+
+    /// Moves webhook deliveries with at least `max_attempts` failed attempts from outbox to `webhook_dead_letters`
+    /// table and returns the number of moved deliveries
+    pub(crate) async fn move_exhausted_outbox_entries(&self, max_attempts: u32) -> Result<u64> {
+        let mut transaction = self
+            .pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let moved = executor::move_exhausted_outbox_entries(&mut transaction, max_attempts).await?;
+
+        transaction
+            .commit()
+            .await
+            .context("unable to commit database transaction")?;
+
+        Ok(moved)
+    }
+}
+
 #[async_trait]
 impl TransactionProvider for SqlDbStorage {
     type Transaction = SqlDbTransaction;
@@ -134,6 +257,34 @@ impl Storage for SqlDbStorage {
         executor::get_operations(&self.pool, chain_id, limit, offset).await
     }
 
+    async fn add_event(&self, event: &Event) -> Result<i64> {
+        executor::add_event(&self.pool, event).await
+    }
+
+    async fn get_events(
+        &self,
+        query: &EventQuery,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Vec<EventRecord>> {
+        executor::get_events(&self.pool, query, limit, offset).await
+    }
+
+    async fn add_light_client_attack_evidence(
+        &self,
+        chain_id: &ChainId,
+        evidence: &LightClientAttackEvidence,
+    ) -> Result<()> {
+        executor::add_light_client_attack_evidence(&self.pool, chain_id, evidence).await
+    }
+
+    async fn get_light_client_attack_evidence(
+        &self,
+        chain_id: &ChainId,
+    ) -> Result<Option<LightClientAttackEvidence>> {
+        executor::get_light_client_attack_evidence(&self.pool, chain_id).await
+    }
+
     async fn add_tendermint_client_state(
         &self,
         client_id: &ClientId,
@@ -239,6 +390,23 @@ impl Storage for SqlDbStorage {
         executor::update_ica_address(&self.pool, connection_id, port_id, address).await
     }
 
+    async fn add_signature_and_data(
+        &self,
+        chain_id: &ChainId,
+        sequence: u64,
+        signature_and_data: &SignatureAndData,
+    ) -> Result<()> {
+        executor::add_signature_and_data(&self.pool, chain_id, sequence, signature_and_data).await
+    }
+
+    async fn get_signature_and_data(
+        &self,
+        chain_id: &ChainId,
+        sequence: u64,
+    ) -> Result<Option<SignatureAndData>> {
+        executor::get_signature_and_data(&self.pool, chain_id, sequence).await
+    }
+
     async fn delete(self) -> Result<()> {
         self.pool.close().await;
 
@@ -253,6 +421,7 @@ mod tests {
     use std::time::Duration;
 
     use primitive_types::U256;
+    use tendermint::Hash;
 
     use crate::types::{chain_state::Fee, ics::core::ics24_host::identifier::Identifier};
 
@@ -274,6 +443,9 @@ mod tests {
         let chain_config = ChainConfig {
             grpc_addr: "http://0.0.0.0:9090".parse().unwrap(),
             rpc_addr: "http://0.0.0.0:26657".parse().unwrap(),
+            fallback_grpc_addrs: Vec::new(),
+            fallback_rpc_addrs: Vec::new(),
+            witness_rpc_addrs: Vec::new(),
             fee: Fee {
                 amount: "1000".parse().unwrap(),
                 denom: "atom".parse().unwrap(),
@@ -287,6 +459,7 @@ mod tests {
             trusted_height: 1,
             trusted_hash: [0; 32],
             packet_timeout_height_offset: 10,
+            retry_policy: Default::default(),
         };
 
         // Add a new chain state
@@ -829,4 +1002,221 @@ mod tests {
 
         assert_eq!(updated_ica_address, address_2);
     }
+
+    #[tokio::test]
+    async fn test_signature_and_data() {
+        let storage = SqlDbStorage::new(URI.to_owned()).await.unwrap();
+
+        let chain_id: ChainId = "test-1".parse().unwrap();
+        let signature_and_data = SignatureAndData {
+            signature: vec![1, 2, 3],
+            data_type: 1,
+            data: vec![4, 5, 6],
+            timestamp: 1,
+        };
+
+        // Add signature and data for a chain id and sequence
+        assert!(storage
+            .add_signature_and_data(&chain_id, 1, &signature_and_data)
+            .await
+            .is_ok());
+
+        // Should not allow adding signature and data again for same chain id and sequence
+        assert!(storage
+            .add_signature_and_data(&chain_id, 1, &signature_and_data)
+            .await
+            .is_err());
+
+        // Should not return signature and data for invalid sequence
+        let stored = storage.get_signature_and_data(&chain_id, 2).await;
+        assert!(stored.is_ok(), "error: {:?}", stored.unwrap_err());
+        assert!(stored.unwrap().is_none());
+
+        // Should not return signature and data for invalid chain id
+        let stored = storage
+            .get_signature_and_data(&"test-2".parse().unwrap(), 1)
+            .await;
+        assert!(stored.is_ok(), "error: {:?}", stored.unwrap_err());
+        assert!(stored.unwrap().is_none());
+
+        // Should return signature and data for valid chain id and sequence
+        let stored = storage.get_signature_and_data(&chain_id, 1).await;
+        assert!(stored.is_ok(), "error: {:?}", stored.unwrap_err());
+        let stored = stored.unwrap();
+
+        assert!(stored.is_some());
+        assert_eq!(stored.unwrap(), signature_and_data);
+    }
+
+    #[cfg(feature = "webhook-event-handler")]
+    #[tokio::test]
+    async fn test_webhook_outbox() {
+        let storage = SqlDbStorage::new(URI.to_owned()).await.unwrap();
+
+        let first = storage
+            .add_outbox_entry("http://localhost/hook", "{}", 10)
+            .await
+            .unwrap();
+        let second = storage
+            .add_outbox_entry("http://localhost/hook", "{}", 20)
+            .await
+            .unwrap();
+
+        let due = storage.get_due_outbox_entries(15, 3, 10).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, first);
+        assert_eq!(due[0].attempts, 0);
+
+        assert_eq!(
+            storage.get_next_outbox_attempt_at(3).await.unwrap(),
+            Some(10)
+        );
+
+        storage
+            .reschedule_outbox_entry(first, "error", 30)
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.get_next_outbox_attempt_at(3).await.unwrap(),
+            Some(20)
+        );
+
+        // Entries with exhausted attempts should not be returned
+        let due = storage.get_due_outbox_entries(30, 1, 10).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, second);
+
+        storage.delete_outbox_entry(second).await.unwrap();
+        assert_eq!(storage.get_next_outbox_attempt_at(1).await.unwrap(), None);
+
+        let due = storage.get_due_outbox_entries(30, 3, 10).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].attempts, 1);
+
+        // Exhausted entries are moved to dead letters
+        assert_eq!(storage.move_exhausted_outbox_entries(2).await.unwrap(), 0);
+        assert_eq!(storage.move_exhausted_outbox_entries(1).await.unwrap(), 1);
+        assert!(storage
+            .get_due_outbox_entries(30, 3, 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let dead_letters = storage.get_webhook_dead_letters().await.unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].id, first);
+        assert_eq!(dead_letters[0].attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_events() {
+        let storage = SqlDbStorage::new(URI.to_owned()).await.unwrap();
+
+        let chain_id: ChainId = "test-1".parse().unwrap();
+        let tokens_minted = |request_id: &str| Event::TokensMinted {
+            chain_id: chain_id.clone(),
+            request_id: Some(request_id.to_owned()),
+            to_address: "address".to_owned(),
+            amount: U256::from(100),
+            denom: "gld".parse().unwrap(),
+            transaction_hash: "transaction-hash".to_owned(),
+        };
+
+        let first = storage
+            .add_event(&tokens_minted("request-1"))
+            .await
+            .unwrap();
+        storage
+            .add_event(&Event::ChainAdded {
+                chain_id: "test-2".parse().unwrap(),
+            })
+            .await
+            .unwrap();
+        let third = storage
+            .add_event(&tokens_minted("request-2"))
+            .await
+            .unwrap();
+
+        // Events are sorted by insertion order
+        let events = storage
+            .get_events(&EventQuery::default(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].id, first);
+        assert_eq!(events[2].id, third);
+
+        let query = EventQuery {
+            chain_id: Some(chain_id.clone()),
+            event_types: vec!["TokensMinted".to_owned(), "ChainAdded".to_owned()],
+            ..Default::default()
+        };
+        let events = storage.get_events(&query, Some(1), Some(1)).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, third);
+
+        let query = EventQuery {
+            from_id: Some(first + 1),
+            request_id: Some("request-1".to_owned()),
+            ..Default::default()
+        };
+        assert!(storage
+            .get_events(&query, None, None)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let query = EventQuery {
+            request_id: Some("request-1".to_owned()),
+            ..Default::default()
+        };
+        let events = storage.get_events(&query, None, None).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(
+            matches!(events[0].event, Event::TokensMinted { ref request_id, .. } if request_id.as_deref() == Some("request-1"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_light_client_attack_evidence() {
+        let storage = SqlDbStorage::new(URI.to_owned()).await.unwrap();
+
+        let chain_id: ChainId = "test-1".parse().unwrap();
+        let evidence = |height| LightClientAttackEvidence {
+            height,
+            primary: NodeId::new([1; 20]),
+            primary_header_hash: Hash::Sha256([1; 32]),
+            witness: "http://witness:26657".parse().unwrap(),
+            witness_header_hash: Hash::Sha256([2; 32]),
+        };
+
+        assert!(storage
+            .get_light_client_attack_evidence(&chain_id)
+            .await
+            .unwrap()
+            .is_none());
+
+        storage
+            .add_light_client_attack_evidence(&chain_id, &evidence(10))
+            .await
+            .unwrap();
+        storage
+            .add_light_client_attack_evidence(&chain_id, &evidence(20))
+            .await
+            .unwrap();
+
+        // The first recorded evidence is returned
+        assert_eq!(
+            storage
+                .get_light_client_attack_evidence(&chain_id)
+                .await
+                .unwrap(),
+            Some(evidence(10))
+        );
+        assert!(storage
+            .get_light_client_attack_evidence(&"test-2".parse().unwrap())
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
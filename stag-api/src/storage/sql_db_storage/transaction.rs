@@ -2,17 +2,23 @@ use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
 use cosmos_sdk_proto::ibc::{
     core::{channel::v1::Channel, client::v1::Height, connection::v1::ConnectionEnd},
-    lightclients::tendermint::v1::{
-        ClientState as TendermintClientState, ConsensusState as TendermintConsensusState,
+    lightclients::{
+        solomachine::v2::SignatureAndData,
+        tendermint::v1::{
+            ClientState as TendermintClientState, ConsensusState as TendermintConsensusState,
+        },
     },
 };
 use tendermint::node::Id as NodeId;
 use tokio::sync::Mutex;
 
 use crate::{
+    event::Event,
     storage::{Storage, Transaction},
+    tendermint::LightClientAttackEvidence,
     types::{
         chain_state::{ChainConfig, ChainKey, ChainState},
+        event_record::{EventQuery, EventRecord},
         ics::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
         operation::{Operation, OperationType},
     },
@@ -34,6 +40,7 @@ impl SqlDbTransaction {
 
 #[async_trait]
 impl Transaction for SqlDbTransaction {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "commit", skip_all))]
     async fn done(self) -> Result<()> {
         self.transaction
             .into_inner()
@@ -128,6 +135,42 @@ impl Storage for SqlDbTransaction {
         executor::get_operations(&mut *transaction, chain_id, limit, offset).await
     }
 
+    async fn add_event(&self, event: &Event) -> Result<i64> {
+        let mut transaction = self.transaction.lock().await;
+
+        executor::add_event(&mut *transaction, event).await
+    }
+
+    async fn get_events(
+        &self,
+        query: &EventQuery,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Vec<EventRecord>> {
+        let mut transaction = self.transaction.lock().await;
+
+        executor::get_events(&mut *transaction, query, limit, offset).await
+    }
+
+    async fn add_light_client_attack_evidence(
+        &self,
+        chain_id: &ChainId,
+        evidence: &LightClientAttackEvidence,
+    ) -> Result<()> {
+        let mut transaction = self.transaction.lock().await;
+
+        executor::add_light_client_attack_evidence(&mut *transaction, chain_id, evidence).await
+    }
+
+    async fn get_light_client_attack_evidence(
+        &self,
+        chain_id: &ChainId,
+    ) -> Result<Option<LightClientAttackEvidence>> {
+        let mut transaction = self.transaction.lock().await;
+
+        executor::get_light_client_attack_evidence(&mut *transaction, chain_id).await
+    }
+
     async fn add_tendermint_client_state(
         &self,
         client_id: &ClientId,
@@ -264,6 +307,28 @@ impl Storage for SqlDbTransaction {
         executor::update_ica_address(&mut *transaction, connection_id, port_id, address).await
     }
 
+    async fn add_signature_and_data(
+        &self,
+        chain_id: &ChainId,
+        sequence: u64,
+        signature_and_data: &SignatureAndData,
+    ) -> Result<()> {
+        let mut transaction = self.transaction.lock().await;
+
+        executor::add_signature_and_data(&mut *transaction, chain_id, sequence, signature_and_data)
+            .await
+    }
+
+    async fn get_signature_and_data(
+        &self,
+        chain_id: &ChainId,
+        sequence: u64,
+    ) -> Result<Option<SignatureAndData>> {
+        let mut transaction = self.transaction.lock().await;
+
+        executor::get_signature_and_data(&mut *transaction, chain_id, sequence).await
+    }
+
     async fn delete(self) -> Result<()> {
         Err(anyhow!("cannot delete storage from a transaction"))
     }
@@ -303,6 +368,9 @@ mod tests {
         let chain_config = ChainConfig {
             grpc_addr: "http://0.0.0.0:9090".parse().unwrap(),
             rpc_addr: "http://0.0.0.0:26657".parse().unwrap(),
+            fallback_grpc_addrs: Vec::new(),
+            fallback_rpc_addrs: Vec::new(),
+            witness_rpc_addrs: Vec::new(),
             fee: Fee {
                 amount: "1000".parse().unwrap(),
                 denom: "atom".parse().unwrap(),
@@ -316,6 +384,7 @@ mod tests {
             trusted_height: 1,
             trusted_hash: [0; 32],
             packet_timeout_height_offset: 10,
+            retry_policy: Default::default(),
         };
 
         // Add a new chain state
@@ -2,10 +2,16 @@ use anyhow::Error;
 use chrono::{DateTime, Utc};
 use sqlx::{types::Json, Row};
 
-use crate::types::{
-    chain_state::{ChainConfig, ChainKey, ChainState, ConnectionDetails},
-    ibc_data::IbcData,
-    operation::{Operation, OperationType},
+#[cfg(feature = "webhook-event-handler")]
+use crate::event::OutboxEntry;
+use crate::{
+    event::Event,
+    types::{
+        chain_state::{ChainConfig, ChainKey, ChainState, ConnectionDetails},
+        event_record::EventRecord,
+        ibc_data::IbcData,
+        operation::{Operation, OperationType},
+    },
 };
 
 use super::DbRow;
@@ -34,6 +40,41 @@ impl TryFrom<DbRow> for Operation {
     }
 }
 
+impl TryFrom<DbRow> for EventRecord {
+    type Error = Error;
+
+    fn try_from(row: DbRow) -> Result<Self, Self::Error> {
+        let id: i64 = row.try_get("id")?;
+        let event: Json<Event> = row.try_get("event")?;
+        let created_at: DateTime<Utc> = row.try_get("created_at")?;
+
+        Ok(Self {
+            id,
+            event: event.0,
+            created_at,
+        })
+    }
+}
+
+#[cfg(feature = "webhook-event-handler")]
+impl TryFrom<DbRow> for OutboxEntry {
+    type Error = Error;
+
+    fn try_from(row: DbRow) -> Result<Self, Self::Error> {
+        let id: i64 = row.try_get("id")?;
+        let url: String = row.try_get("url")?;
+        let payload: String = row.try_get("payload")?;
+        let attempts: i64 = row.try_get("attempts")?;
+
+        Ok(Self {
+            id,
+            url: url.parse()?,
+            payload,
+            attempts: attempts.try_into()?,
+        })
+    }
+}
+
 impl TryFrom<DbRow> for ChainState {
     type Error = Error;
 
@@ -2,16 +2,22 @@ use anyhow::Result;
 use async_trait::async_trait;
 use cosmos_sdk_proto::ibc::{
     core::{channel::v1::Channel, client::v1::Height, connection::v1::ConnectionEnd},
-    lightclients::tendermint::v1::{
-        ClientState as TendermintClientState, ConsensusState as TendermintConsensusState,
+    lightclients::{
+        solomachine::v2::SignatureAndData,
+        tendermint::v1::{
+            ClientState as TendermintClientState, ConsensusState as TendermintConsensusState,
+        },
     },
 };
 use tendermint::node::Id as NodeId;
 
 use crate::{
+    event::Event,
+    tendermint::LightClientAttackEvidence,
     trait_util::Base,
     types::{
         chain_state::{ChainConfig, ChainKey, ChainState},
+        event_record::{EventQuery, EventRecord},
         ics::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
         operation::{Operation, OperationType},
     },
@@ -90,6 +96,30 @@ pub trait Storage: Base {
         offset: Option<u32>,
     ) -> Result<Vec<Operation>>;
 
+    /// Adds an event to the storage and returns its ID
+    async fn add_event(&self, event: &Event) -> Result<i64>;
+
+    /// Gets all the events selected by given query from the storage (in increasing order of ID)
+    async fn get_events(
+        &self,
+        query: &EventQuery,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Vec<EventRecord>>;
+
+    /// Adds evidence of a light client attack on given chain to the storage
+    async fn add_light_client_attack_evidence(
+        &self,
+        chain_id: &ChainId,
+        evidence: &LightClientAttackEvidence,
+    ) -> Result<()>;
+
+    /// Gets the first recorded evidence of a light client attack on given chain from the storage (if any)
+    async fn get_light_client_attack_evidence(
+        &self,
+        chain_id: &ChainId,
+    ) -> Result<Option<LightClientAttackEvidence>>;
+
     /// Adds tendermint client state to the storage
     async fn add_tendermint_client_state(
         &self,
@@ -181,6 +211,21 @@ pub trait Storage: Base {
         address: &str,
     ) -> Result<()>;
 
+    /// Adds signature and data signed by solo machine for given chain at given sequence to the storage
+    async fn add_signature_and_data(
+        &self,
+        chain_id: &ChainId,
+        sequence: u64,
+        signature_and_data: &SignatureAndData,
+    ) -> Result<()>;
+
+    /// Gets signature and data signed by solo machine for given chain at given sequence from the storage
+    async fn get_signature_and_data(
+        &self,
+        chain_id: &ChainId,
+        sequence: u64,
+    ) -> Result<Option<SignatureAndData>>;
+
     /// Delete the storage (should only be used for testing)
     async fn delete(self) -> Result<()>;
 }
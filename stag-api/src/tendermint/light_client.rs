@@ -1,10 +1,13 @@
 // Some part of this file is taken from the original project. https://github.com/informalsystems/tendermint-rs
 // Copyright © 2020 Informal Systems
 
-use std::{sync::Arc, time::Duration};
+use std::{fmt, sync::Arc, time::Duration};
 
 use anyhow::{anyhow, bail, Context, Result};
-use tendermint::{block::Height, trust_threshold::TrustThresholdFraction, Hash, Time};
+use serde::{Deserialize, Serialize};
+use tendermint::{
+    block::Height, node::Id as NodeId, trust_threshold::TrustThresholdFraction, Hash, Time,
+};
 use tendermint_light_client::{
     components::{
         scheduler::basic_bisecting_schedule,
@@ -18,16 +21,61 @@ use tendermint_light_client::{
 use tokio::sync::Mutex;
 use url::Url;
 
-use crate::{time_util::now_utc, types::chain_state::ChainState};
+use crate::{
+    endpoint_util::with_failover,
+    time_util::now_utc,
+    types::chain_state::{ChainState, RetryPolicy},
+};
 
 use super::tendermint_client::TendermintClient;
 
-/// Tendermint light client
+/// Evidence of a light client attack, i.e., a witness returned a header conflicting with a header verified from
+/// primary
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientAttackEvidence {
+    /// Height of conflicting headers
+    pub height: u64,
+    /// Node ID of primary which provided the verified header
+    pub primary: NodeId,
+    /// Hash of header verified from primary
+    pub primary_header_hash: Hash,
+    /// Address of witness which provided the conflicting header
+    pub witness: Url,
+    /// Hash of conflicting header provided by witness
+    pub witness_header_hash: Hash,
+}
+
+impl fmt::Display for LightClientAttackEvidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "light client attack detected at height [{}]: header hash [{}] of primary [{}] does not match header hash [{}] of witness [{}]",
+            self.height,
+            self.primary_header_hash,
+            self.primary,
+            self.witness_header_hash,
+            self.witness
+        )
+    }
+}
+
+impl std::error::Error for LightClientAttackEvidence {}
+
+/// Tendermint light client (fetches light blocks from primary RPC address of chain, failing over to fallback RPC
+/// addresses, and cross-checks verified light blocks against witness RPC addresses)
+///
+/// Once a light client attack is detected, light client refuses to verify any more blocks and returns
+/// [`LightClientAttackEvidence`] as error. Callers are expected to persist the evidence and pass it back when creating
+/// new light clients for the same chain so that the refusal survives restarts.
 pub struct LightClient<T>
 where
     T: TendermintClient,
 {
     url: Url,
+    fallbacks: Vec<Url>,
+    witnesses: Vec<Url>,
+    retry_policy: RetryPolicy,
+    evidence: Arc<Mutex<Option<LightClientAttackEvidence>>>,
     state: Arc<Mutex<State>>,
     tendermint_client: T,
     verifier: ProdVerifier,
@@ -38,8 +86,16 @@ impl<T> LightClient<T>
 where
     T: TendermintClient,
 {
-    /// Creates a new tendermint light client
-    pub async fn new(url: Url, tendermint_client: T, chain_state: &ChainState) -> Result<Self> {
+    /// Creates a new tendermint light client (fails with given evidence of an earlier light client attack, if any)
+    pub async fn new(
+        tendermint_client: T,
+        chain_state: &ChainState,
+        evidence: Option<LightClientAttackEvidence>,
+    ) -> Result<Self> {
+        if let Some(evidence) = evidence {
+            return Err(evidence.into());
+        }
+
         let options = Options {
             trust_threshold: TrustThresholdFraction::new(
                 *chain_state.config.trust_level.numer(),
@@ -51,7 +107,11 @@ where
         };
 
         let this = LightClient {
-            url,
+            url: chain_state.config.rpc_addr.clone(),
+            fallbacks: chain_state.config.fallback_rpc_addrs.clone(),
+            witnesses: chain_state.config.witness_addrs(),
+            retry_policy: chain_state.config.retry_policy.clone(),
+            evidence: Default::default(),
             state: Arc::new(Mutex::new(State::new(MemoryStore::new()))),
             tendermint_client,
             verifier: Default::default(),
@@ -69,38 +129,128 @@ where
 
     /// Runs the light client verification process until the latest block
     pub async fn verify_to_highest(&self) -> Result<LightBlock> {
-        let target_block = self.tendermint_client.light_block(&self.url, None).await?;
-        let target_height = target_block.height();
+        self.verify_to_target(None).await
+    }
+
+    /// Runs the light client verification process until the block at given height
+    pub async fn verify_to_height(&self, height: u64) -> Result<LightBlock> {
+        self.verify_to_target(Some(height.try_into()?)).await
+    }
+
+    async fn verify_to_target(&self, height: Option<u32>) -> Result<LightBlock> {
+        self.ensure_no_attack().await?;
+
+        let target_height = match height {
+            Some(height) => height.into(),
+            None => self.fetch_light_block(None).await?.height(),
+        };
 
         let mut state = self.state.lock().await;
 
         // Let's first look in the store to see whether
         // we have already successfully verified this block.
-        if let Some(light_block) = state.light_store.get_trusted_or_verified(target_height) {
-            return Ok(light_block);
+        let light_block = match state.light_store.get_trusted_or_verified(target_height) {
+            Some(light_block) => light_block,
+            None => {
+                // Get the highest trusted state
+                let highest = state
+                    .light_store
+                    .highest_trusted_or_verified()
+                    .context("no initial trusted state in light client")?;
+
+                if target_height >= highest.height() {
+                    // Perform forward verification with bisection
+                    self.verify_forward(target_height, &mut state).await
+                } else {
+                    // Perform sequential backward verification
+                    self.verify_backward(target_height, &mut state).await
+                }?
+            }
+        };
+
+        // Cross-check the verified light block with witnesses before using it
+        if let Some(evidence) = self.cross_check(&light_block).await? {
+            state.light_store.update(&light_block, Status::Failed);
+            *self.evidence.lock().await = Some(evidence.clone());
+
+            return Err(evidence.into());
         }
 
-        // Get the highest trusted state
-        let highest = state
-            .light_store
-            .highest_trusted_or_verified()
-            .context("no initial trusted state in light client")?;
-
-        if target_height >= highest.height() {
-            // Perform forward verification with bisection
-            self.verify_forward(target_height, &mut state).await
-        } else {
-            // Perform sequential backward verification
-            self.verify_backward(target_height, &mut state).await
+        Ok(light_block)
+    }
+
+    /// Returns an error if a light client attack was detected earlier (so that the divergent chain is never used)
+    async fn ensure_no_attack(&self) -> Result<()> {
+        match *self.evidence.lock().await {
+            Some(ref evidence) => Err(evidence.clone().into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Compares header of given verified light block with the headers returned by witnesses at the same height and
+    /// returns evidence of light client attack if any of the headers conflict. Witnesses which are unavailable or have
+    /// not reached the given height are skipped, but verification fails when none of the witnesses responded (so that
+    /// blocking the traffic to witnesses does not disable cross-checking).
+    async fn cross_check(
+        &self,
+        light_block: &LightBlock,
+    ) -> Result<Option<LightClientAttackEvidence>> {
+        let height = light_block.height().value();
+        let header_hash = light_block.signed_header.header.hash();
+
+        let mut responses = 0;
+        let mut last_error = None;
+
+        for witness in self.witnesses.iter() {
+            let witness_header_hash = match self
+                .tendermint_client
+                .with_retry_policy(&self.retry_policy)
+                .commit(witness, Some(height.try_into()?))
+                .await
+            {
+                Ok(response) => response.signed_header.header.hash(),
+                Err(err) => {
+                    last_error = Some(err.context(format!("witness {} did not respond", witness)));
+                    continue;
+                }
+            };
+
+            if witness_header_hash != header_hash {
+                return Ok(Some(LightClientAttackEvidence {
+                    height,
+                    primary: light_block.provider,
+                    primary_header_hash: header_hash,
+                    witness: witness.clone(),
+                    witness_header_hash,
+                }));
+            }
+
+            responses += 1;
+        }
+
+        match last_error {
+            Some(err) if responses == 0 => Err(err.context(format!(
+                "failed to cross-check light block at height {}: none of the witnesses responded",
+                height
+            ))),
+            _ => Ok(None),
         }
     }
 
+    /// Fetches light block at given height (or latest light block if height is `None`) from primary RPC address,
+    /// failing over to fallback RPC addresses
+    async fn fetch_light_block(&self, height: Option<u32>) -> Result<LightBlock> {
+        with_failover(
+            std::iter::once(&self.url).chain(self.fallbacks.iter()),
+            &self.retry_policy,
+            |url| self.tendermint_client.light_block(url, height),
+        )
+        .await
+    }
+
     /// Adds a block to trusted state
     async fn trust_block(&self, trusted_height: u32, trusted_hash: [u8; 32]) -> Result<()> {
-        let trusted_block = self
-            .tendermint_client
-            .light_block(&self.url, Some(trusted_height))
-            .await?;
+        let trusted_block = self.fetch_light_block(Some(trusted_height)).await?;
 
         if trusted_block.height() != trusted_height.into() {
             bail!(
@@ -318,8 +468,7 @@ where
         }
 
         let block = self
-            .tendermint_client
-            .light_block(&self.url, Some(height.value().try_into()?))
+            .fetch_light_block(Some(height.value().try_into()?))
             .await?;
 
         state.light_store.insert(block.clone(), Status::Unverified);
@@ -396,3 +545,157 @@ fn now() -> Result<Time> {
     )
     .map_err(|_| anyhow!("time overflow"))
 }
+
+#[cfg(test)]
+mod tests {
+    use tendermint::block::Header;
+
+    use crate::tendermint::test_util::{
+        chain_state, header, mock_client, unavailable_witness_client, NODE_ID, PRIMARY, WITNESS,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_verify_with_agreeing_witness() {
+        let header = header("0000000000000000");
+        let chain_state = chain_state(&header);
+
+        let light_client = LightClient::new(
+            mock_client(header.clone(), header.clone()),
+            &chain_state,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let light_block = light_client.verify_to_height(5).await.unwrap();
+        assert_eq!(light_block.height().value(), 5);
+        assert_eq!(
+            light_block.signed_header.header.hash().as_bytes(),
+            chain_state.config.trusted_hash
+        );
+
+        assert!(light_client.verify_to_highest().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_verify_with_conflicting_witness() {
+        let header = header("0000000000000000");
+        let conflicting_header = self::header("0100000000000000");
+        let chain_state = chain_state(&header);
+
+        let light_client = LightClient::new(
+            mock_client(header.clone(), conflicting_header.clone()),
+            &chain_state,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let err = light_client.verify_to_height(5).await.unwrap_err();
+        let evidence = err.downcast_ref::<LightClientAttackEvidence>().unwrap();
+
+        let conflicting_header: Header = serde_json::from_value(conflicting_header).unwrap();
+        assert_eq!(evidence.height, 5);
+        assert_eq!(evidence.primary, NODE_ID.parse().unwrap());
+        assert_eq!(
+            evidence.primary_header_hash.as_bytes(),
+            chain_state.config.trusted_hash
+        );
+        assert_eq!(evidence.witness.as_str(), "http://witness:26657/");
+        assert_eq!(evidence.witness_header_hash, conflicting_header.hash());
+
+        // Light client refuses to verify any more blocks once an attack is detected
+        let err = light_client.verify_to_highest().await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<LightClientAttackEvidence>(),
+            Some(evidence)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_with_unavailable_witness() {
+        let header = header("0000000000000000");
+        let mut chain_state = chain_state(&header);
+
+        let light_client = LightClient::new(
+            unavailable_witness_client(header.clone()),
+            &chain_state,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let err = light_client.verify_to_height(5).await.unwrap_err();
+        assert!(err.downcast_ref::<LightClientAttackEvidence>().is_none());
+        assert!(err.to_string().contains("none of the witnesses responded"));
+
+        // Block is verified once any of the witnesses responds
+        chain_state
+            .config
+            .witness_rpc_addrs
+            .push("http://other-witness:26657".parse().unwrap());
+
+        let light_client = LightClient::new(
+            unavailable_witness_client(header.clone()),
+            &chain_state,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(light_client.verify_to_height(5).await.is_ok());
+
+        // Blocks are not cross-checked when no witness is configured
+        chain_state.config.witness_rpc_addrs.clear();
+
+        let light_client = LightClient::new(unavailable_witness_client(header), &chain_state, None)
+            .await
+            .unwrap();
+        assert!(light_client.verify_to_height(5).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_witnesses_exclude_primary_and_fallbacks() {
+        let header = header("0000000000000000");
+        let mut chain_state = chain_state(&header);
+
+        let fallback: Url = "http://fallback:26657".parse().unwrap();
+        chain_state.config.fallback_rpc_addrs = vec![fallback.clone()];
+        chain_state.config.witness_rpc_addrs =
+            vec![WITNESS.parse().unwrap(), fallback, PRIMARY.parse().unwrap()];
+
+        let light_client =
+            LightClient::new(mock_client(header.clone(), header), &chain_state, None)
+                .await
+                .unwrap();
+        assert_eq!(light_client.witnesses, vec![WITNESS.parse().unwrap()]);
+    }
+
+    #[tokio::test]
+    async fn test_new_with_recorded_evidence() {
+        let header = header("0000000000000000");
+        let chain_state = chain_state(&header);
+        let evidence = LightClientAttackEvidence {
+            height: 5,
+            primary: NODE_ID.parse().unwrap(),
+            primary_header_hash: Hash::Sha256([1; 32]),
+            witness: WITNESS.parse().unwrap(),
+            witness_header_hash: Hash::Sha256([2; 32]),
+        };
+
+        let tendermint_client = mock_client(header.clone(), header);
+
+        let err = LightClient::new(&tendermint_client, &chain_state, Some(evidence.clone()))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.downcast_ref::<LightClientAttackEvidence>(),
+            Some(&evidence)
+        );
+
+        // No block is fetched from a chain with recorded evidence
+        assert_eq!(tendermint_client.request_count("commit"), 0);
+    }
+}
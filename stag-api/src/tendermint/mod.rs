@@ -2,5 +2,11 @@
 mod light_client;
 mod rpc_client;
 mod tendermint_client;
+#[cfg(test)]
+pub(crate) mod test_util;
 
-pub use self::{light_client::LightClient, rpc_client::*, tendermint_client::*};
+pub use self::{
+    light_client::{LightClient, LightClientAttackEvidence},
+    rpc_client::*,
+    tendermint_client::*,
+};
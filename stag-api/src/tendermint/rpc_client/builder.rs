@@ -3,8 +3,12 @@ use sealed::sealed;
 
 use crate::trait_util::Base;
 
+#[cfg(test)]
+use super::mock_client::MockRpcClient;
 #[cfg(feature = "reqwest-client")]
 use super::reqwest_client::ReqwestClient as ReqwestClientImpl;
+#[cfg(feature = "websocket-client")]
+use super::websocket_client::WebSocketClient as WebSocketClientImpl;
 use super::JsonRpcClient;
 
 #[cfg_attr(feature = "doc", doc(cfg(feature = "reqwest-client")))]
@@ -13,6 +17,12 @@ use super::JsonRpcClient;
 /// Json RPC backend using `reqwest`
 pub struct ReqwestClient;
 
+#[cfg_attr(feature = "doc", doc(cfg(feature = "websocket-client")))]
+#[cfg(feature = "websocket-client")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// Json RPC backend using a persistent WebSocket connection (supports event subscriptions)
+pub struct WebSocketClient;
+
 #[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
 #[cfg_attr(feature = "wasm", async_trait(?Send))]
 #[sealed]
@@ -37,3 +47,27 @@ impl JsonRpcConfig for ReqwestClient {
         ReqwestClientImpl::default()
     }
 }
+
+#[cfg_attr(feature = "doc", doc(cfg(feature = "websocket-client")))]
+#[cfg(feature = "websocket-client")]
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+#[sealed]
+impl JsonRpcConfig for WebSocketClient {
+    type Client = WebSocketClientImpl;
+
+    fn into_client(self) -> Self::Client {
+        WebSocketClientImpl::default()
+    }
+}
+
+#[cfg(test)]
+#[async_trait]
+#[sealed]
+impl JsonRpcConfig for MockRpcClient {
+    type Client = Self;
+
+    fn into_client(self) -> Self::Client {
+        self
+    }
+}
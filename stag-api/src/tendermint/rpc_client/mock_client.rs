@@ -0,0 +1,88 @@
+//! Mock JSON RPC client used in tests
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::trait_util::Base;
+
+use super::JsonRpcClient;
+
+type Handler = dyn Fn(&Url, &str, &Value) -> Result<Value> + Send + Sync;
+
+/// JSON RPC client which responds to requests using given handler (called with URL, method and params of request)
+/// and records all the requests (clones share the handler and the recorded requests)
+#[derive(Clone)]
+pub struct MockRpcClient {
+    handler: Arc<Handler>,
+    requests: Arc<Mutex<Vec<(Url, String)>>>,
+}
+
+impl MockRpcClient {
+    /// Creates a new mock client which responds to requests using given handler
+    pub fn new(
+        handler: impl Fn(&Url, &str, &Value) -> Result<Value> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            handler: Arc::new(handler),
+            requests: Default::default(),
+        }
+    }
+
+    /// Returns the number of requests sent for given method
+    pub fn request_count(&self, method: &str) -> usize {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, request_method)| request_method == method)
+            .count()
+    }
+
+    /// Returns the URLs to which requests for given method were sent (in order)
+    pub fn request_urls(&self, method: &str) -> Vec<Url> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, request_method)| request_method == method)
+            .map(|(url, _)| url.clone())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for MockRpcClient {
+    fn get_next_id(&self) -> u32 {
+        0
+    }
+
+    async fn send_request<REQ>(&self, url: &Url, request: REQ) -> Result<Value>
+    where
+        REQ: Serialize + Base,
+    {
+        let request = serde_json::to_value(request)?;
+
+        let method = request
+            .get("method")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("method not found in request: {}", request))?
+            .to_owned();
+
+        self.requests
+            .lock()
+            .unwrap()
+            .push((url.clone(), method.clone()));
+
+        let result = (self.handler)(url, &method, &request["params"])?;
+
+        Ok(json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "result": result,
+        }))
+    }
+}
@@ -1,12 +1,29 @@
 //! JSON RPC client
 mod builder;
+#[cfg(test)]
+mod mock_client;
 #[cfg(feature = "reqwest-client")]
 mod reqwest_client;
+mod retry_client;
 mod rpc_client_traits;
+#[cfg(feature = "websocket-client")]
+mod subscription;
+#[cfg(feature = "websocket-client")]
+mod websocket_client;
 
 #[cfg(feature = "reqwest-client")]
 pub use self::builder::ReqwestClient;
-pub use self::{builder::JsonRpcConfig, rpc_client_traits::JsonRpcClient};
+#[cfg(test)]
+pub(crate) use self::mock_client::MockRpcClient;
+pub use self::{
+    builder::JsonRpcConfig, retry_client::RetryClient, rpc_client_traits::JsonRpcClient,
+};
+#[cfg(feature = "websocket-client")]
+pub use self::{
+    builder::WebSocketClient,
+    subscription::{Subscription, SubscriptionClient},
+    websocket_client::WebSocketError,
+};
 
 #[derive(Clone)]
 /// A no-op JSON RPC client
@@ -41,14 +41,13 @@ impl JsonRpcClient for ReqwestClient {
             .await
             .context("failed to send post request")?;
 
-        if !response.status().is_success() {
-            return Err(anyhow!(
-                "Unexpected response status code: {}",
-                response.status()
-            ));
-        }
-
-        let response = response.text().await?;
+        let status = response.status();
+
+        let response = response
+            .error_for_status()
+            .with_context(|| anyhow!("Unexpected response status code: {}", status))?
+            .text()
+            .await?;
 
         Ok(serde_json::from_str(&response)?)
     }
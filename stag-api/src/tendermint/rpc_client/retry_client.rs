@@ -0,0 +1,75 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::Value;
+use url::Url;
+
+use crate::{
+    endpoint_util::with_failover_if,
+    retry_util::{is_connect_error, is_retriable},
+    trait_util::Base,
+    types::chain_state::RetryPolicy,
+};
+
+use super::JsonRpcClient;
+
+/// JSON RPC client which retries transient failures of underlying client based on a retry policy and fails over to
+/// fallback URLs when the requested URL is unavailable
+pub struct RetryClient<'a, C> {
+    client: &'a C,
+    retry_policy: &'a RetryPolicy,
+    fallback_urls: &'a [Url],
+}
+
+impl<'a, C> RetryClient<'a, C> {
+    /// Creates a new instance of retry client
+    pub fn new(client: &'a C, retry_policy: &'a RetryPolicy, fallback_urls: &'a [Url]) -> Self {
+        Self {
+            client,
+            retry_policy,
+            fallback_urls,
+        }
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl<C> JsonRpcClient for RetryClient<'_, C>
+where
+    C: JsonRpcClient,
+{
+    fn get_next_id(&self) -> u32 {
+        self.client.get_next_id()
+    }
+
+    async fn send_request<REQ>(&self, url: &Url, request: REQ) -> Result<serde_json::Value>
+    where
+        REQ: Serialize + Base,
+    {
+        let request = serde_json::to_value(request)?;
+
+        // Broadcasts are not idempotent (a broadcast which timed out may still be committed), so they are re-sent only
+        // when the node could not be reached
+        let should_retry = if is_broadcast(&request) {
+            is_connect_error
+        } else {
+            is_retriable
+        };
+
+        with_failover_if(
+            std::iter::once(url).chain(self.fallback_urls),
+            self.retry_policy,
+            should_retry,
+            |url| self.client.send_request(url, request.clone()),
+        )
+        .await
+    }
+}
+
+/// Returns true if given JSON RPC request broadcasts a transaction
+fn is_broadcast(request: &Value) -> bool {
+    request
+        .get("method")
+        .and_then(Value::as_str)
+        .is_some_and(|method| method.starts_with("broadcast_tx"))
+}
@@ -0,0 +1,60 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures_util::Stream;
+use tendermint_rpc::{
+    event::Event,
+    query::{EventType, Query},
+};
+use tokio::sync::mpsc::UnboundedReceiver;
+use url::Url;
+
+use super::JsonRpcClient;
+
+/// Stream of events received for a subscription (ends when the underlying connection is closed and cannot be
+/// restored; events emitted while reconnecting are not received)
+pub struct Subscription {
+    query: String,
+    receiver: UnboundedReceiver<Result<Event>>,
+}
+
+impl Subscription {
+    pub(crate) fn new(query: String, receiver: UnboundedReceiver<Result<Event>>) -> Self {
+        Self { query, receiver }
+    }
+
+    /// Returns the query of subscription
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+}
+
+impl Stream for Subscription {
+    type Item = Result<Event>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+/// Trait that must be implemented by all the JSON RPC backends which support event subscriptions
+pub trait SubscriptionClient: JsonRpcClient {
+    /// Subscribes to events matching given query on the given url
+    async fn subscribe(&self, url: &Url, query: Query) -> Result<Subscription>;
+
+    /// Subscribes to new block events on the given url
+    async fn subscribe_new_blocks(&self, url: &Url) -> Result<Subscription> {
+        self.subscribe(url, EventType::NewBlock.into()).await
+    }
+
+    /// Subscribes to transaction events on the given url
+    async fn subscribe_txs(&self, url: &Url) -> Result<Subscription> {
+        self.subscribe(url, EventType::Tx.into()).await
+    }
+}
@@ -0,0 +1,601 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex as StdMutex,
+    },
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tendermint_rpc::{event::Event, query::Query};
+use tokio::{
+    sync::{mpsc, oneshot, Mutex},
+    time::timeout,
+};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream};
+use url::Url;
+
+use crate::{time_util::sleep, trait_util::Base};
+
+use super::{JsonRpcClient, Subscription, SubscriptionClient};
+
+/// Maximum duration to wait for a response of a json rpc request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Backoff after the first failed attempt to reconnect a lost connection with subscribers
+const RECONNECT_MIN_BACKOFF: Duration = Duration::from_millis(500);
+/// Maximum backoff between attempts to reconnect a lost connection with subscribers
+const RECONNECT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+type PendingRequests = Arc<StdMutex<HashMap<String, oneshot::Sender<Value>>>>;
+type Subscriptions = Arc<StdMutex<HashMap<String, Vec<mpsc::UnboundedSender<Result<Event>>>>>>;
+type WebSocketStream = tokio_tungstenite::WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Errors which can occur on an established websocket connection
+#[derive(Debug)]
+pub enum WebSocketError {
+    /// Websocket connection was closed before receiving response
+    ConnectionClosed(Url),
+    /// Timed out while waiting for response
+    Timeout(Url),
+}
+
+impl std::fmt::Display for WebSocketError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ConnectionClosed(url) => write!(f, "websocket connection to {} closed", url),
+            Self::Timeout(url) => write!(f, "timed out while waiting for response from {}", url),
+        }
+    }
+}
+
+impl std::error::Error for WebSocketError {}
+
+#[derive(Default, Clone)]
+pub struct WebSocketClient {
+    connections: Arc<Mutex<HashMap<Url, Connection>>>,
+    id: Arc<AtomicU32>,
+}
+
+impl WebSocketClient {
+    /// Returns an open connection to given url (opens a new connection if there is no open connection)
+    async fn connection(&self, url: &Url) -> Result<Connection> {
+        let mut connections = self.connections.lock().await;
+
+        if let Some(connection) = connections.get(url) {
+            if !connection.is_closed() {
+                return Ok(connection.clone());
+            }
+        }
+
+        let connection = Connection::connect(url).await?;
+        connections.insert(url.clone(), connection.clone());
+
+        Ok(connection)
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl JsonRpcClient for WebSocketClient {
+    fn get_next_id(&self) -> u32 {
+        self.id.fetch_add(1, Ordering::AcqRel)
+    }
+
+    async fn send_request<REQ>(&self, url: &Url, request: REQ) -> Result<Value>
+    where
+        REQ: Serialize + Base,
+    {
+        let request = serde_json::to_value(request)?;
+        let id = request
+            .get("id")
+            .context("json rpc request does not contain an id")?
+            .to_string();
+
+        let connection = self.connection(url).await?;
+        let (sender, receiver) = oneshot::channel();
+
+        lock(&connection.pending).insert(id.clone(), sender);
+
+        if connection
+            .sender
+            .send(Message::Text(serde_json::to_string(&request)?))
+            .is_err()
+        {
+            lock(&connection.pending).remove(&id);
+            bail!(WebSocketError::ConnectionClosed(url.clone()));
+        }
+
+        match timeout(REQUEST_TIMEOUT, receiver).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Err(WebSocketError::ConnectionClosed(url.clone()).into()),
+            Err(_) => {
+                lock(&connection.pending).remove(&id);
+                Err(WebSocketError::Timeout(url.clone()).into())
+            }
+        }
+    }
+}
+
+#[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
+#[cfg_attr(feature = "wasm", async_trait(?Send))]
+impl SubscriptionClient for WebSocketClient {
+    async fn subscribe(&self, url: &Url, query: Query) -> Result<Subscription> {
+        let query = query.to_string();
+
+        let connection = self.connection(url).await?;
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        let is_subscribed = {
+            let mut subscriptions = lock(&connection.subscriptions);
+            let is_subscribed = subscriptions.contains_key(&query);
+            subscriptions.entry(query.clone()).or_default().push(sender);
+            is_subscribed
+        };
+
+        if !is_subscribed {
+            if let Err(err) = self
+                .send::<_, Value>(url, "subscribe", json!({ "query": query }))
+                .await
+            {
+                lock(&connection.subscriptions).remove(&query);
+                return Err(err.context(format!("failed to subscribe to `{}`", query)));
+            }
+        }
+
+        Ok(Subscription::new(query, receiver))
+    }
+}
+
+/// A persistent websocket connection which multiplexes json rpc requests and subscriptions
+#[derive(Clone)]
+struct Connection {
+    sender: mpsc::UnboundedSender<Message>,
+    pending: PendingRequests,
+    subscriptions: Subscriptions,
+}
+
+impl Connection {
+    async fn connect(url: &Url) -> Result<Self> {
+        let websocket_url = websocket_url(url)?;
+
+        let (stream, _) = connect_async(&websocket_url)
+            .await
+            .with_context(|| format!("failed to connect to {}", websocket_url))?;
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        let pending = PendingRequests::default();
+        let subscriptions = Subscriptions::default();
+
+        tokio::spawn(run(
+            websocket_url,
+            stream,
+            receiver,
+            pending.clone(),
+            subscriptions.clone(),
+        ));
+
+        Ok(Self {
+            sender,
+            pending,
+            subscriptions,
+        })
+    }
+
+    fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+}
+
+/// Drives a websocket connection: sends queued messages and routes received messages. When the connection is lost
+/// while there are still subscribers, it reconnects and restores their subscriptions (requests sent in the meantime
+/// are queued and sent after reconnecting).
+async fn run(
+    websocket_url: Url,
+    mut stream: WebSocketStream,
+    mut receiver: mpsc::UnboundedReceiver<Message>,
+    pending: PendingRequests,
+    subscriptions: Subscriptions,
+) {
+    loop {
+        let client_dropped = serve(stream, &mut receiver, &pending, &subscriptions).await;
+
+        // Dropping the senders notifies pending requests that the connection is closed (their responses can never be
+        // received on a new connection)
+        lock(&pending).clear();
+
+        if client_dropped {
+            break;
+        }
+
+        match reconnect(&websocket_url, &subscriptions).await {
+            Some(new_stream) => stream = new_stream,
+            None => break,
+        }
+    }
+
+    // Dropping all the senders notifies subscriptions that the connection is closed
+    lock(&subscriptions).clear();
+}
+
+/// Serves given websocket stream until it is closed. Returns true if the client (i.e., sender of queued messages) was
+/// dropped.
+async fn serve(
+    stream: WebSocketStream,
+    receiver: &mut mpsc::UnboundedReceiver<Message>,
+    pending: &PendingRequests,
+    subscriptions: &Subscriptions,
+) -> bool {
+    let (mut sink, mut stream) = stream.split();
+
+    loop {
+        tokio::select! {
+            message = receiver.recv() => match message {
+                Some(message) => {
+                    if sink.send(message).await.is_err() {
+                        return false;
+                    }
+                }
+                None => return true,
+            },
+            message = stream.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    if let Some(query) = handle_message(&text, pending, subscriptions) {
+                        let unsubscribe = json!({
+                            "jsonrpc": "2.0",
+                            "id": "unsubscribe",
+                            "method": "unsubscribe",
+                            "params": { "query": query },
+                        });
+
+                        if sink.send(Message::Text(unsubscribe.to_string())).await.is_err() {
+                            return false;
+                        }
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return false,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/// Reconnects to given websocket url (with exponential backoff between attempts) and subscribes to all the queries
+/// which still have subscribers. Returns `None` (without reconnecting) once there are no more subscribers.
+async fn reconnect(websocket_url: &Url, subscriptions: &Subscriptions) -> Option<WebSocketStream> {
+    let mut backoff = RECONNECT_MIN_BACKOFF;
+
+    loop {
+        let queries = subscribed_queries(subscriptions);
+
+        if queries.is_empty() {
+            return None;
+        }
+
+        if let Ok((mut stream, _)) = connect_async(websocket_url).await {
+            if resubscribe(&mut stream, &queries).await.is_ok() {
+                return Some(stream);
+            }
+        }
+
+        sleep(backoff).await;
+        backoff = std::cmp::min(backoff * 2, RECONNECT_MAX_BACKOFF);
+    }
+}
+
+/// Sends subscription requests for given queries on a new websocket connection (their responses are ignored)
+async fn resubscribe(stream: &mut WebSocketStream, queries: &[String]) -> Result<()> {
+    for query in queries {
+        let subscribe = json!({
+            "jsonrpc": "2.0",
+            "id": "resubscribe",
+            "method": "subscribe",
+            "params": { "query": query },
+        });
+
+        stream.send(Message::Text(subscribe.to_string())).await?;
+    }
+
+    Ok(())
+}
+
+/// Removes subscribers which dropped their subscriptions and returns the queries which still have subscribers
+fn subscribed_queries(subscriptions: &Subscriptions) -> Vec<String> {
+    let mut subscriptions = lock(subscriptions);
+
+    subscriptions.retain(|_, senders| {
+        senders.retain(|sender| !sender.is_closed());
+        !senders.is_empty()
+    });
+
+    subscriptions.keys().cloned().collect()
+}
+
+/// Routes a message received on websocket connection to a pending request or subscriptions. Returns the query of
+/// subscription if there are no more subscribers for it (so that it can be unsubscribed).
+fn handle_message(
+    text: &str,
+    pending: &PendingRequests,
+    subscriptions: &Subscriptions,
+) -> Option<String> {
+    let response: Value = serde_json::from_str(text).ok()?;
+
+    if let Some(id) = response.get("id") {
+        if let Some(sender) = lock(pending).remove(&id.to_string()) {
+            let _ = sender.send(response);
+            return None;
+        }
+
+        // Responses to the requests sent internally by connection are ignored
+        if matches!(id.as_str(), Some("resubscribe" | "unsubscribe")) {
+            return None;
+        }
+    }
+
+    let query = response.pointer("/result/query").and_then(Value::as_str)?;
+    let event =
+        serde_json::from_value::<Event>(response["result"].clone()).map_err(|err| err.to_string());
+
+    let mut subscriptions = lock(subscriptions);
+    let senders = subscriptions.get_mut(query)?;
+
+    senders.retain(|sender| {
+        sender
+            .send(
+                event
+                    .clone()
+                    .map_err(|err| anyhow!("failed to deserialize event: {}", err)),
+            )
+            .is_ok()
+    });
+
+    if senders.is_empty() {
+        subscriptions.remove(query);
+        return Some(query.to_owned());
+    }
+
+    None
+}
+
+/// Converts given RPC url to websocket url of tendermint (e.g., `http://localhost:26657` to
+/// `ws://localhost:26657/websocket`)
+fn websocket_url(url: &Url) -> Result<Url> {
+    let scheme = match url.scheme() {
+        "http" | "ws" => "ws",
+        "https" | "wss" => "wss",
+        scheme => bail!("unsupported scheme for websocket connection: {}", scheme),
+    };
+
+    let mut websocket_url = url.clone();
+    websocket_url
+        .set_scheme(scheme)
+        .map_err(|_| anyhow!("invalid websocket url: {}", url))?;
+
+    if !websocket_url.path().ends_with("/websocket") {
+        websocket_url
+            .path_segments_mut()
+            .map_err(|_| anyhow!("invalid websocket url: {}", url))?
+            .pop_if_empty()
+            .push("websocket");
+    }
+
+    Ok(websocket_url)
+}
+
+fn lock<T>(mutex: &StdMutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::accept_async;
+
+    use super::*;
+
+    /// Starts a websocket server which responds to all the requests with their params and sends an event after
+    /// subscription (closes the first connection after sending the event when `drop_first_connection` is true) and
+    /// returns its URL along with the number of received subscription requests
+    async fn start_server(drop_first_connection: bool) -> (Url, Arc<AtomicU32>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let subscribe_count = Arc::new(AtomicU32::new(0));
+        let server_subscribe_count = subscribe_count.clone();
+
+        tokio::spawn(async move {
+            let mut drop_connection = drop_first_connection;
+
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let stream = accept_async(stream).await.unwrap();
+
+                serve_connection(stream, drop_connection, &server_subscribe_count).await;
+                drop_connection = false;
+            }
+        });
+
+        (format!("http://{}", addr).parse().unwrap(), subscribe_count)
+    }
+
+    async fn serve_connection(
+        mut stream: tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>,
+        drop_after_event: bool,
+        subscribe_count: &AtomicU32,
+    ) {
+        while let Some(Ok(Message::Text(text))) = stream.next().await {
+            let request: Value = serde_json::from_str(&text).unwrap();
+
+            let response = json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": request["params"],
+            });
+            stream
+                .send(Message::Text(response.to_string()))
+                .await
+                .unwrap();
+
+            if request["method"] == "subscribe" {
+                let event = json!({
+                    "jsonrpc": "2.0",
+                    "id": format!("{}#event", request["id"]),
+                    "result": {
+                        "query": request["params"]["query"],
+                        "data": { "type": "tendermint/event/Tx", "value": {
+                            "TxResult": { "height": "1", "tx": "", "result": { "events": [] } },
+                        } },
+                        "events": null,
+                    },
+                });
+                stream.send(Message::Text(event.to_string())).await.unwrap();
+                subscribe_count.fetch_add(1, Ordering::AcqRel);
+
+                if drop_after_event {
+                    return;
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_websocket_client() {
+        let (url, _) = start_server(false).await;
+        let client = WebSocketClient::default();
+
+        let response: Value = client
+            .send(&url, "echo", json!({ "value": 1 }))
+            .await
+            .unwrap();
+        assert_eq!(response, json!({ "value": 1 }));
+
+        let mut subscription = client.subscribe_txs(&url).await.unwrap();
+        assert_eq!(subscription.query(), "tm.event = 'Tx'");
+
+        let event = subscription.next().await.unwrap().unwrap();
+        assert_eq!(event.query, "tm.event = 'Tx'");
+        assert!(matches!(
+            event.event_type(),
+            Some(tendermint_rpc::query::EventType::Tx)
+        ));
+
+        // Requests after subscription should reuse the same connection
+        let response: Value = client
+            .send(&url, "echo", json!({ "value": 2 }))
+            .await
+            .unwrap();
+        assert_eq!(response, json!({ "value": 2 }));
+    }
+
+    #[tokio::test]
+    async fn test_websocket_client_restores_subscriptions_after_reconnect() {
+        let (url, subscribe_count) = start_server(true).await;
+        let client = WebSocketClient::default();
+
+        let mut subscription = client.subscribe_txs(&url).await.unwrap();
+
+        let event = subscription.next().await.unwrap().unwrap();
+        assert_eq!(event.query, "tm.event = 'Tx'");
+
+        // First connection is closed by the server after sending the event, so the next event can only be received
+        // after the client reconnects and subscribes again
+        let event = timeout(Duration::from_secs(10), subscription.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.query, "tm.event = 'Tx'");
+        assert_eq!(subscribe_count.load(Ordering::Acquire), 2);
+
+        let response: Value = client
+            .send(&url, "echo", json!({ "value": 1 }))
+            .await
+            .unwrap();
+        assert_eq!(response, json!({ "value": 1 }));
+    }
+
+    #[test]
+    fn test_websocket_url() {
+        let url: Url = "http://localhost:26657".parse().unwrap();
+        assert_eq!(
+            websocket_url(&url).unwrap().as_str(),
+            "ws://localhost:26657/websocket"
+        );
+
+        let url: Url = "https://rpc.example.com/tendermint/".parse().unwrap();
+        assert_eq!(
+            websocket_url(&url).unwrap().as_str(),
+            "wss://rpc.example.com/tendermint/websocket"
+        );
+
+        let url: Url = "ws://localhost:26657/websocket".parse().unwrap();
+        assert_eq!(websocket_url(&url).unwrap(), url);
+
+        let url: Url = "ftp://localhost:26657".parse().unwrap();
+        assert!(websocket_url(&url).is_err());
+    }
+
+    #[test]
+    fn test_handle_response() {
+        let pending = PendingRequests::default();
+        let subscriptions = Subscriptions::default();
+
+        let (sender, mut receiver) = oneshot::channel();
+        lock(&pending).insert("1".to_owned(), sender);
+
+        let unsubscribe = handle_message(
+            r#"{"jsonrpc":"2.0","id":1,"result":{}}"#,
+            &pending,
+            &subscriptions,
+        );
+
+        assert!(unsubscribe.is_none());
+        assert!(lock(&pending).is_empty());
+        assert_eq!(receiver.try_recv().unwrap()["result"], json!({}));
+    }
+
+    #[test]
+    fn test_handle_event() {
+        let pending = PendingRequests::default();
+        let subscriptions = Subscriptions::default();
+
+        let query = "tm.event = 'NewBlock'";
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        lock(&subscriptions).insert(query.to_owned(), vec![sender]);
+
+        let event = json!({
+            "jsonrpc": "2.0",
+            "id": "1#event",
+            "result": {
+                "query": query,
+                "data": { "type": "tendermint/event/NewBlock", "value": {
+                    "block": null,
+                    "result_begin_block": null,
+                    "result_end_block": null,
+                } },
+                "events": null,
+            },
+        });
+
+        let unsubscribe = handle_message(&event.to_string(), &pending, &subscriptions);
+        assert!(unsubscribe.is_none());
+
+        let event = receiver.try_recv().unwrap().unwrap();
+        assert_eq!(event.query, query);
+
+        // Should return the query for unsubscribing when there are no more subscribers
+        drop(receiver);
+        let unsubscribe = handle_message(
+            &json!({ "jsonrpc": "2.0", "id": "1#event", "result": { "query": query } }).to_string(),
+            &pending,
+            &subscriptions,
+        );
+
+        assert_eq!(unsubscribe.as_deref(), Some(query));
+        assert!(lock(&subscriptions).is_empty());
+    }
+}
@@ -1,16 +1,64 @@
-use anyhow::Result;
+use anyhow::{ensure, Context, Result};
 use async_trait::async_trait;
+use chrono::Duration as ChronoDuration;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
+use sha2::{Digest, Sha256};
+use tendermint::abci::transaction::Hash;
 use tendermint_light_client::types::{LightBlock, ValidatorSet};
-use tendermint_rpc::endpoint::{broadcast, commit, status, validators};
+use tendermint_rpc::endpoint::{
+    broadcast::{self, tx_commit::TxResult},
+    commit, status, tx, validators,
+};
 use url::Url;
 
-use crate::{tendermint::rpc_client::JsonRpcClient, types::proto_util::proto_encode};
+use crate::{
+    retry_util::is_connect_error,
+    tendermint::rpc_client::{JsonRpcClient, RetryClient},
+    time_util::{now_utc, sleep},
+    types::{
+        chain_state::{ChainConfig, RetryPolicy},
+        proto_util::proto_encode,
+    },
+};
 
 #[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
 #[cfg_attr(feature = "wasm", async_trait(?Send))]
 /// Helper trait to send a transaction to a tendermint node (auto-implemented for json rpc client)
 pub trait TendermintClient: JsonRpcClient {
+    /// Returns a client which retries transient failures of current client based on given retry policy
+    fn with_retry_policy<'a>(&'a self, retry_policy: &'a RetryPolicy) -> RetryClient<'a, Self>
+    where
+        Self: Sized,
+    {
+        RetryClient::new(self, retry_policy, &[])
+    }
+
+    /// Returns a client which retries transient failures of current client based on retry policy of given chain and
+    /// fails over to fallback RPC addresses of chain when an RPC address is unavailable
+    fn for_chain<'a>(&'a self, chain_config: &'a ChainConfig) -> RetryClient<'a, Self>
+    where
+        Self: Sized,
+    {
+        RetryClient::new(
+            self,
+            &chain_config.retry_policy,
+            &chain_config.fallback_rpc_addrs,
+        )
+    }
+
+    /// Checks if tendermint node at given URL is healthy (i.e., reachable and not catching up)
+    async fn health_check(&self, url: &Url) -> Result<()> {
+        let status = self.status(url).await?;
+
+        ensure!(
+            !status.sync_info.catching_up,
+            "tendermint node at {} is catching up",
+            url
+        );
+
+        Ok(())
+    }
+
     /// Sends status request to tendermint node
     async fn status(&self, url: &Url) -> Result<status::Response> {
         self.send(url, "status", status::Request).await
@@ -32,6 +80,66 @@ pub trait TendermintClient: JsonRpcClient {
         .await
     }
 
+    /// Broadcasts given transaction to chain and waits for it to be committed
+    ///
+    /// Transaction is re-sent (to fallback RPC addresses of chain) only when the node could not be reached. When the
+    /// outcome of broadcast is unknown (e.g., node timed out while waiting for commit after the transaction reached its
+    /// mempool), the transaction is looked up by its hash until RPC timeout of chain elapses so that a transaction which
+    /// is committed later is not reported as failed.
+    async fn broadcast_tx_to_chain(
+        &self,
+        chain_config: &ChainConfig,
+        transaction: TxRaw,
+    ) -> Result<broadcast::tx_commit::Response>
+    where
+        Self: Sized,
+    {
+        let hash = Hash::new(Sha256::digest(proto_encode(&transaction)?).into());
+        let rpc_client = self.for_chain(chain_config);
+
+        match rpc_client
+            .broadcast_tx(&chain_config.rpc_addr, transaction)
+            .await
+        {
+            Ok(response) => Ok(response),
+            Err(err) if is_connect_error(&err) => Err(err),
+            Err(err) => {
+                let response = poll_tx(&rpc_client, chain_config, hash)
+                    .await
+                    .map_err(|_| {
+                        err.context(format!(
+                            "transaction {} was not found on chain after failed broadcast",
+                            hash
+                        ))
+                    })?;
+
+                Ok(broadcast::tx_commit::Response {
+                    check_tx: Default::default(),
+                    deliver_tx: TxResult {
+                        code: response.tx_result.code,
+                        data: Some(response.tx_result.data),
+                        log: response.tx_result.log,
+                        info: response.tx_result.info,
+                        gas_wanted: response.tx_result.gas_wanted,
+                        gas_used: response.tx_result.gas_used,
+                        events: response.tx_result.events,
+                        codespace: response.tx_result.codespace,
+                        ..Default::default()
+                    },
+                    hash: response.hash,
+                    height: response.height,
+                })
+            }
+        }
+    }
+
+    /// Sends tx request to tendermint node (to find a committed transaction by its hash)
+    async fn tx(&self, url: &Url, hash: Hash) -> Result<tx::Response> {
+        self.send(url, "tx", tx::Request::new(hash, false))
+            .await
+            .with_context(|| format!("failed to find transaction {}", hash))
+    }
+
     /// Sends commit request to tendermint node
     async fn commit(&self, url: &Url, height: Option<u32>) -> Result<commit::Response> {
         let height = height.map(Into::into);
@@ -112,3 +220,230 @@ pub trait TendermintClient: JsonRpcClient {
 #[cfg_attr(all(not(feature = "wasm"), feature = "non-wasm"), async_trait)]
 #[cfg_attr(feature = "wasm", async_trait(?Send))]
 impl<C> TendermintClient for C where C: JsonRpcClient {}
+
+/// Looks up transaction with given hash until it is found or RPC timeout of chain elapses (waiting between attempts
+/// based on retry policy of chain)
+async fn poll_tx<C>(rpc_client: &C, chain_config: &ChainConfig, hash: Hash) -> Result<tx::Response>
+where
+    C: TendermintClient,
+{
+    let deadline = now_utc() + ChronoDuration::from_std(chain_config.rpc_timeout)?;
+    let mut attempt = 1;
+
+    loop {
+        match rpc_client.tx(&chain_config.rpc_addr, hash).await {
+            Ok(response) => return Ok(response),
+            Err(err) => {
+                let remaining = (deadline - now_utc()).to_std().unwrap_or_default();
+
+                if remaining.is_zero() {
+                    return Err(err);
+                }
+
+                sleep(chain_config.retry_policy.backoff(attempt).min(remaining)).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Error as IoError, ErrorKind},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use anyhow::Error;
+    use serde_json::{json, Value};
+    use tonic::Status;
+
+    use crate::tendermint::MockRpcClient;
+
+    use super::*;
+
+    fn test_transaction() -> TxRaw {
+        TxRaw {
+            body_bytes: b"body".to_vec(),
+            auth_info_bytes: b"auth info".to_vec(),
+            signatures: vec![b"signature".to_vec()],
+        }
+    }
+
+    fn tx_hash(transaction: &TxRaw) -> String {
+        hex::encode_upper(Sha256::digest(proto_encode(transaction).unwrap()))
+    }
+
+    fn tx_response(hash: &str, code: u32) -> Value {
+        json!({
+            "hash": hash,
+            "height": "10",
+            "index": 0,
+            "tx_result": {
+                "code": code,
+                "data": null,
+                "log": "",
+                "info": "",
+                "gas_wanted": "100",
+                "gas_used": "50",
+                "events": [],
+                "codespace": "",
+            },
+            "tx": "",
+        })
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_tx_to_chain_finds_committed_tx() {
+        let transaction = test_transaction();
+        let hash = tx_hash(&transaction);
+
+        let expected_hash = hash.clone();
+        let client = MockRpcClient::new(move |_, method, params| match method {
+            // Node timed out while waiting for commit but the transaction was committed afterwards
+            "broadcast_tx_commit" => Err(Status::unavailable("timed out waiting for tx").into()),
+            "tx" => Ok(tx_response(&expected_hash, 0)),
+            _ => Err(anyhow::anyhow!("unexpected request: {} {}", method, params)),
+        });
+
+        let chain_config =
+            ChainConfig::for_test("http://broadcast-committed:26657", "http://0.0.0.0:9090");
+
+        let response = client
+            .broadcast_tx_to_chain(&chain_config, transaction)
+            .await
+            .unwrap();
+
+        assert!(response.deliver_tx.code.is_ok());
+        assert_eq!(response.hash.to_string(), hash);
+        assert_eq!(response.height.value(), 10);
+
+        // Transaction should not be re-sent after a failure which is not a connection failure
+        assert_eq!(client.request_count("broadcast_tx_commit"), 1);
+        assert_eq!(client.request_count("tx"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_tx_to_chain_reports_failed_tx() {
+        let transaction = test_transaction();
+        let hash = tx_hash(&transaction);
+
+        let client = MockRpcClient::new(move |_, method, _| match method {
+            "broadcast_tx_commit" => Err(Status::deadline_exceeded("timed out").into()),
+            _ => Ok(tx_response(&hash, 5)),
+        });
+
+        let chain_config =
+            ChainConfig::for_test("http://broadcast-failed:26657", "http://0.0.0.0:9090");
+
+        let response = client
+            .broadcast_tx_to_chain(&chain_config, transaction)
+            .await
+            .unwrap();
+
+        // Committed transaction is returned as is (caller checks the response code)
+        assert!(response.deliver_tx.code.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_tx_to_chain_tx_not_found() {
+        let client = MockRpcClient::new(|_, method, _| match method {
+            "broadcast_tx_commit" => Err(Status::unavailable("timed out waiting for tx").into()),
+            _ => Err(anyhow::anyhow!("tx not found")),
+        });
+
+        let mut chain_config =
+            ChainConfig::for_test("http://broadcast-not-found:26657", "http://0.0.0.0:9090");
+        chain_config.rpc_timeout = Duration::from_millis(50);
+
+        let err = client
+            .broadcast_tx_to_chain(&chain_config, test_transaction())
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("was not found on chain"));
+        assert_eq!(client.request_count("broadcast_tx_commit"), 1);
+
+        // Transaction is looked up until RPC timeout elapses (not only for the attempts of retry policy)
+        assert!(client.request_count("tx") > chain_config.retry_policy.max_attempts as usize);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_tx_to_chain_waits_for_pending_tx() {
+        let transaction = test_transaction();
+        let hash = tx_hash(&transaction);
+
+        let lookups = Arc::new(AtomicUsize::new(0));
+        let client = MockRpcClient::new({
+            let lookups = lookups.clone();
+
+            move |_, method, _| match method {
+                "broadcast_tx_commit" => {
+                    Err(Status::unavailable("timed out waiting for tx").into())
+                }
+                // Transaction is still in mempool for the first few lookups
+                "tx" if lookups.fetch_add(1, Ordering::SeqCst) < 4 => {
+                    Err(anyhow::anyhow!("tx not found"))
+                }
+                _ => Ok(tx_response(&hash, 0)),
+            }
+        });
+
+        let chain_config =
+            ChainConfig::for_test("http://broadcast-pending:26657", "http://0.0.0.0:9090");
+
+        let response = client
+            .broadcast_tx_to_chain(&chain_config, transaction)
+            .await
+            .unwrap();
+
+        assert!(response.deliver_tx.code.is_ok());
+        assert_eq!(client.request_count("tx"), 5);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_tx_to_chain_fails_over_on_connect_error() {
+        let transaction = test_transaction();
+        let hash = tx_hash(&transaction);
+
+        let client =
+            MockRpcClient::new(
+                move |url, method, _| match (url.host_str().unwrap(), method) {
+                    ("broadcast-unreachable", "broadcast_tx_commit") => {
+                        Err(Error::from(IoError::from(ErrorKind::ConnectionRefused)))
+                    }
+                    (_, "broadcast_tx_commit") => Ok(json!({
+                        "check_tx": {},
+                        "deliver_tx": {},
+                        "hash": hash,
+                        "height": "10",
+                    })),
+                    _ => Err(anyhow::anyhow!("unexpected request")),
+                },
+            );
+
+        let mut chain_config =
+            ChainConfig::for_test("http://broadcast-unreachable:26657", "http://0.0.0.0:9090");
+        chain_config.fallback_rpc_addrs = vec!["http://broadcast-fallback:26657".parse().unwrap()];
+
+        let response = client
+            .broadcast_tx_to_chain(&chain_config, transaction)
+            .await
+            .unwrap();
+
+        assert!(response.deliver_tx.code.is_ok());
+        assert_eq!(client.request_count("tx"), 0);
+        assert_eq!(
+            client
+                .request_urls("broadcast_tx_commit")
+                .last()
+                .unwrap()
+                .as_str(),
+            "http://broadcast-fallback:26657/"
+        );
+    }
+}
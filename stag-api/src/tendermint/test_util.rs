@@ -0,0 +1,166 @@
+//! Fixtures of a tendermint chain (with a primary and a witness) used in tests
+use anyhow::{anyhow, Result};
+use chrono::{Duration as ChronoDuration, SecondsFormat, Utc};
+use serde_json::{json, Value};
+use tendermint::{block::Header, validator::Info as ValidatorInfo};
+use tendermint_light_client::types::ValidatorSet;
+
+use crate::types::chain_state::{ChainConfig, ChainState};
+
+use super::MockRpcClient;
+
+pub const PRIMARY: &str = "http://primary:26657";
+pub const WITNESS: &str = "http://witness:26657";
+pub const NODE_ID: &str = "79fdd516b5477fd637b019cc6a129912a7f13bd6";
+
+fn validator() -> Value {
+    json!({
+        "address": "CCD08E1A253A3A53929EA9FB71C3C9018E9D2678",
+        "pub_key": {
+            "type": "tendermint/PubKeyEd25519",
+            "value": "BJh7fTq/5bLf0XXKGixr3icDBQtkvaJRnkfsJgVLsnI="
+        },
+        "voting_power": "10",
+        "proposer_priority": "0"
+    })
+}
+
+/// Returns a header at height 5 (headers with different app hashes conflict with each other)
+pub fn header(app_hash: &str) -> Value {
+    let validator: ValidatorInfo = serde_json::from_value(validator()).unwrap();
+    let validators_hash = ValidatorSet::without_proposer(vec![validator])
+        .hash()
+        .to_string();
+    let time =
+        (Utc::now() - ChronoDuration::seconds(10)).to_rfc3339_opts(SecondsFormat::Nanos, true);
+
+    json!({
+        "app_hash": app_hash,
+        "chain_id": "test-1",
+        "consensus_hash": "048091BC7DDC283F77BFBF91D73C44DA58C3DF8A9CBC867405D8B7F3DAADA22F",
+        "data_hash": "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855",
+        "evidence_hash": "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855",
+        "height": "5",
+        "last_block_id": {
+            "hash": "91FD98892CE6313DE7A5C8574C71C2F44CF86AC8140A1D7CB8606E81BADD5872",
+            "parts": {
+                "hash": "2DCCD7515860671A67FEFB621D3775BF4F780A71BFE7B2AD589519A1EDC4ABA0",
+                "total": 1
+            }
+        },
+        "last_commit_hash": "BF6EA2D96423A568C5937A7DABF6E22B68FA516BE0EB5A26CF1DAB173A18A754",
+        "last_results_hash": "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855",
+        "next_validators_hash": validators_hash,
+        "proposer_address": "CCD08E1A253A3A53929EA9FB71C3C9018E9D2678",
+        "time": time,
+        "validators_hash": validators_hash,
+        "version": {
+            "app": "1",
+            "block": "11"
+        }
+    })
+}
+
+fn commit(header: &Value) -> Value {
+    json!({
+        "canonical": true,
+        "signed_header": {
+            "commit": {
+                "block_id": {
+                    "hash": "A33CD4A336E3452CFCDB105BD52FD6AEFE905A5B4F62176D6FA138F89B04A206",
+                    "parts": {
+                        "hash": "D598CFB5B50C8E616E845F12A8BC6FACA82D6015E7B176B03C54A64077F4B4D4",
+                        "total": 1
+                    }
+                },
+                "height": "5",
+                "round": 0,
+                "signatures": []
+            },
+            "header": header
+        }
+    })
+}
+
+fn status() -> Value {
+    json!({
+        "node_info": {
+            "channels": "40202122233038606100",
+            "id": NODE_ID,
+            "listen_addr": "tcp://0.0.0.0:26656",
+            "moniker": "node",
+            "network": "test-1",
+            "other": {
+                "rpc_address": "tcp://0.0.0.0:26657",
+                "tx_index": "on"
+            },
+            "protocol_version": {
+                "app": "1",
+                "block": "11",
+                "p2p": "8"
+            },
+            "version": "v0.34.20"
+        },
+        "sync_info": {
+            "catching_up": false,
+            "earliest_app_hash": "",
+            "earliest_block_hash": "F4FA302B25AF6113B997EB3870189A29177A593184F0AE0AC08DB4D1BE1BE568",
+            "earliest_block_height": "1",
+            "earliest_block_time": "2022-07-21T22:06:01.373424724Z",
+            "latest_app_hash": "0600000000000000",
+            "latest_block_hash": "A48DA0925AA7EDD5C1E07AE9D7CDC4A30F5541B2F555048B50EECB818164B297",
+            "latest_block_height": "5",
+            "latest_block_time": "2022-07-21T22:06:25.282998322Z"
+        },
+        "validator_info": validator()
+    })
+}
+
+/// Returns a mock client for a chain where primary returns `primary_header` and witness returns `witness_header`
+pub fn mock_client(primary_header: Value, witness_header: Value) -> MockRpcClient {
+    let witness_commit = commit(&witness_header);
+    client(primary_header, move || Ok(witness_commit.clone()))
+}
+
+/// Returns a mock client for a chain where primary returns `primary_header` and witness is unavailable
+pub fn unavailable_witness_client(primary_header: Value) -> MockRpcClient {
+    client(primary_header, || Err(anyhow!("witness is unavailable")))
+}
+
+fn client(
+    primary_header: Value,
+    witness_commit: impl Fn() -> Result<Value> + Send + Sync + 'static,
+) -> MockRpcClient {
+    MockRpcClient::new(move |url, method, _| match method {
+        "commit" if url.as_str().starts_with(WITNESS) => witness_commit(),
+        "commit" => Ok(commit(&primary_header)),
+        "validators" => Ok(json!({
+            "block_height": "5",
+            "validators": [validator()],
+            "count": "1",
+            "total": "1"
+        })),
+        "status" => Ok(status()),
+        _ => Err(anyhow!("unexpected method: {}", method)),
+    })
+}
+
+pub fn chain_state(trusted_header: &Value) -> ChainState {
+    let trusted_header: Header = serde_json::from_value(trusted_header.clone()).unwrap();
+
+    let mut config = ChainConfig::for_test(PRIMARY, "http://primary:9090");
+    config.witness_rpc_addrs = vec![WITNESS.parse().unwrap()];
+    config.trusted_height = 5;
+    config.trusted_hash = trusted_header.hash().as_bytes().try_into().unwrap();
+
+    ChainState {
+        id: "test-1".parse().unwrap(),
+        node_id: NODE_ID.parse().unwrap(),
+        config,
+        consensus_timestamp: Utc::now(),
+        sequence: 1,
+        connection_details: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
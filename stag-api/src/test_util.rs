@@ -0,0 +1,154 @@
+//! Fixtures shared by unit tests of services and transaction builders (a context backed by an in-memory sqlite
+//! storage, a mnemonic signer, a mock JSON RPC client and an event handler recording all the events)
+use std::sync::Arc;
+
+use cosmos_sdk_proto::cosmos::auth::v1beta1::{
+    query_server::{Query, QueryServer},
+    BaseAccount, QueryAccountRequest, QueryAccountResponse, QueryAccountsRequest,
+    QueryAccountsResponse, QueryParamsRequest, QueryParamsResponse,
+};
+use tendermint::node::Id as NodeId;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{transport::server::Router, Request, Response, Status};
+use url::Url;
+
+use crate::{
+    event::{EventHandler, RecordingEventHandler},
+    signer::{MnemonicSigner, SignerConfig},
+    stag::{StagBuilder, StagContext},
+    storage::{Sqlite, Storage, StorageConfig},
+    tendermint::MockRpcClient,
+    types::{
+        chain_state::{ChainConfig, ChainState, ConnectionDetails},
+        proto_util::AnyConvert,
+    },
+};
+
+/// Context used in tests (storage type can be replaced with the transaction type of sqlite storage)
+pub type TestContext<T = <Sqlite as StorageConfig>::Storage> =
+    StagBuilder<<MnemonicSigner as SignerConfig>::Signer, T, MockRpcClient, Arc<dyn EventHandler>>;
+
+pub const CHAIN_ID: &str = "test-1";
+pub const MNEMONIC: &str = "practice empty client sauce pistol work ticket casual romance appear army fault palace coyote fox super salute slim catch kite wrist three hedgehog sign";
+
+/// Returns a context which uses given RPC client (with a mnemonic signer for [`CHAIN_ID`]) and the event handler
+/// recording its events
+pub async fn test_context(rpc_client: MockRpcClient) -> (TestContext, RecordingEventHandler) {
+    let mut signer = MnemonicSigner::new();
+    signer
+        .add_chain_config(CHAIN_ID.parse().unwrap(), MNEMONIC, None, None, None)
+        .unwrap();
+
+    let event_handler = RecordingEventHandler::default();
+
+    let context = StagBuilder::default()
+        .with_storage(Sqlite::new("sqlite::memory:"))
+        .await
+        .unwrap()
+        .with_signer(signer)
+        .unwrap()
+        .with_rpc_client(rpc_client)
+        .with_event_handler(Arc::new(event_handler.clone()) as Arc<dyn EventHandler>);
+
+    (context, event_handler)
+}
+
+/// Adds a chain connected over IBC (with given configuration) to the storage of context and returns its state
+pub async fn add_connected_chain<C>(context: &C, config: ChainConfig) -> ChainState
+where
+    C: StagContext,
+    C::Storage: Storage,
+{
+    let chain_id = CHAIN_ID.parse().unwrap();
+
+    context
+        .storage()
+        .add_chain_state(chain_id, NodeId::new([1; 20]), config)
+        .await
+        .unwrap();
+
+    let mut chain_state = context
+        .storage()
+        .get_chain_state(&CHAIN_ID.parse().unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+
+    chain_state.connection_details = Some(ConnectionDetails {
+        solo_machine_client_id: "07-tendermint-0".parse().unwrap(),
+        tendermint_client_id: "06-solomachine-0".parse().unwrap(),
+        solo_machine_connection_id: "connection-0".parse().unwrap(),
+        tendermint_connection_id: "connection-1".parse().unwrap(),
+        channels: Default::default(),
+    });
+
+    context
+        .storage()
+        .update_chain_state(&chain_state)
+        .await
+        .unwrap();
+
+    chain_state
+}
+
+/// Auth query service which returns the same account details for all the addresses
+pub struct MockAuthService {
+    account_number: u64,
+    sequence: u64,
+}
+
+#[tonic::async_trait]
+impl Query for MockAuthService {
+    async fn accounts(
+        &self,
+        _: Request<QueryAccountsRequest>,
+    ) -> Result<Response<QueryAccountsResponse>, Status> {
+        Err(Status::unimplemented("accounts"))
+    }
+
+    async fn account(
+        &self,
+        request: Request<QueryAccountRequest>,
+    ) -> Result<Response<QueryAccountResponse>, Status> {
+        let account = BaseAccount {
+            address: request.into_inner().address,
+            pub_key: None,
+            account_number: self.account_number,
+            sequence: self.sequence,
+        };
+
+        Ok(Response::new(QueryAccountResponse {
+            account: Some(
+                account
+                    .to_any()
+                    .map_err(|err| Status::internal(err.to_string()))?,
+            ),
+        }))
+    }
+
+    async fn params(
+        &self,
+        _: Request<QueryParamsRequest>,
+    ) -> Result<Response<QueryParamsResponse>, Status> {
+        Err(Status::unimplemented("params"))
+    }
+}
+
+/// Serves given gRPC services on a random local port and returns its address
+pub async fn serve(router: Router) -> Url {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(router.serve_with_incoming(TcpListenerStream::new(listener)));
+
+    format!("http://{}", addr).parse().unwrap()
+}
+
+/// Returns a mock auth query service (used when building transactions) returning given account details
+pub fn auth_service(account_number: u64, sequence: u64) -> QueryServer<MockAuthService> {
+    QueryServer::new(MockAuthService {
+        account_number,
+        sequence,
+    })
+}
@@ -1,4 +1,6 @@
 //! Time related utilities.
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
 #[cfg(feature = "wasm")]
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
@@ -32,3 +34,15 @@ pub fn now_utc() -> DateTime<Utc> {
 
     DateTime::<Utc>::from_utc(naive_date_time, Utc)
 }
+
+#[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
+/// Waits until given duration has elapsed
+pub async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await
+}
+
+#[cfg(feature = "wasm")]
+/// Waits until given duration has elapsed
+pub async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await
+}
@@ -1,9 +1,38 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 
+use crate::{
+    event::{Event, EventHandler},
+    stag::StagContext,
+    types::ics::core::ics24_host::identifier::ChainId,
+};
+
 pub fn to_u64_timestamp(timestamp: DateTime<Utc>) -> Result<u64> {
     timestamp
         .timestamp()
         .try_into()
         .context("unable to convert unix timestamp to u64")
 }
+
+/// Emits `SignerFailed` event if signing failed (the result is returned as is)
+pub async fn report_signer_failure<C, T>(
+    context: &C,
+    chain_id: &ChainId,
+    request_id: Option<&str>,
+    result: Result<T>,
+) -> Result<T>
+where
+    C: StagContext,
+{
+    if let Err(ref err) = result {
+        context
+            .handle_event(Event::SignerFailed {
+                chain_id: chain_id.clone(),
+                request_id: request_id.map(ToOwned::to_owned),
+                error: format!("{:#}", err),
+            })
+            .await?;
+    }
+
+    result
+}
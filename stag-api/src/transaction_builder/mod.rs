@@ -2,7 +2,7 @@
 mod common;
 mod msg;
 mod proofs;
-mod signing;
+pub(crate) mod signing;
 mod tx;
 
-pub use self::msg::*;
+pub use self::{msg::*, signing::is_conflicting};
@@ -27,6 +27,10 @@ use crate::{
 
 #[allow(clippy::too_many_arguments)]
 /// Creates a message for opening (init) a channel on IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_channel_open_init<C>(
     context: &C,
     chain_state: &ChainState,
@@ -62,6 +66,10 @@ where
 
 #[allow(clippy::too_many_arguments)]
 /// Creates a message for opening (init) a channel on IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_channel_open_try<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -117,6 +125,10 @@ where
 
 #[allow(clippy::too_many_arguments)]
 /// Creates a message for acknowledging a channel on IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_channel_open_ack<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -160,6 +172,10 @@ where
 
 #[allow(clippy::too_many_arguments)]
 /// Creates a message for confirming a channel on IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_channel_open_confirm<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -200,6 +216,10 @@ where
 }
 
 /// Creates a message for initiating closing of channel
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_channel_close_init<C>(
     context: &C,
     chain_state: &ChainState,
@@ -223,6 +243,10 @@ where
 
 /// Creates a message for confirming closing on channel
 #[allow(clippy::too_many_arguments)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_channel_close_confirm<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -26,6 +26,7 @@ use tonic_web_wasm_client::Client;
 use url::Url;
 
 use crate::{
+    endpoint_util::with_failover,
     signer::{GetPublicKey, Signer},
     stag::StagContext,
     tendermint::{LightClient, TendermintClient},
@@ -41,6 +42,10 @@ use crate::{
 };
 
 /// Creates a message for creating a solo machine client on IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_create_solo_machine_client<C>(
     context: &C,
     chain_state: &ChainState,
@@ -82,6 +87,7 @@ where
 }
 
 /// Creates tendermint client on solo machine
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(chain_id = %chain_state.id)))]
 pub async fn msg_create_tendermint_client<T>(
     chain_state: &ChainState,
     light_client: &LightClient<T>,
@@ -118,16 +124,23 @@ where
 }
 
 async fn get_unbonding_period(chain_state: &ChainState) -> Result<Duration> {
-    let mut query_client = get_staking_query_client(chain_state.config.grpc_addr.clone()).await?;
-
-    query_client
-        .params(QueryParamsRequest::default())
-        .await?
-        .into_inner()
-        .params
-        .context("staking params are empty")?
-        .unbonding_time
-        .context("missing unbonding period in staking params")
+    with_failover(
+        chain_state.config.grpc_addrs(),
+        &chain_state.config.retry_policy,
+        |grpc_addr| async move {
+            let mut query_client = get_staking_query_client(grpc_addr.clone()).await?;
+
+            Ok(query_client
+                .params(QueryParamsRequest::default())
+                .await?
+                .into_inner())
+        },
+    )
+    .await?
+    .params
+    .context("staking params are empty")?
+    .unbonding_time
+    .context("missing unbonding period in staking params")
 }
 
 async fn get_latest_header<T>(light_client: &LightClient<T>) -> Result<Header>
@@ -15,6 +15,7 @@ use crate::{
     signer::{GetPublicKey, Signer},
     stag::StagContext,
     storage::{Storage, Transaction},
+    tendermint::TendermintClient,
     transaction_builder::{
         proofs::{get_client_proof, get_connection_proof, get_consensus_proof},
         tx::build,
@@ -30,6 +31,10 @@ use crate::{
 };
 
 /// Creates a message for opening a connection on IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_connection_open_init<C>(
     context: &C,
     chain_state: &ChainState,
@@ -64,6 +69,10 @@ where
 
 #[allow(clippy::too_many_arguments)]
 /// Creates a message for acknowledginging a connection open on IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_connection_open_ack<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -77,6 +86,7 @@ where
     C: StagContext,
     C::Signer: Signer,
     C::Storage: Transaction,
+    C::RpcClient: TendermintClient,
 {
     let tendermint_client_state = context
         .storage()
@@ -17,6 +17,10 @@ use crate::{
 
 /// Creates and signs a `MsgRecvPacket` transaction.
 #[allow(clippy::too_many_arguments)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_send<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -1,3 +1,5 @@
 pub mod bank;
 pub mod staking;
 mod submit;
+
+pub(crate) use self::submit::InterchainAccountPacketData;
@@ -4,11 +4,12 @@ use cosmos_sdk_proto::{
     ibc::applications::interchain_accounts::v1::{CosmosTx, Type},
 };
 use prost_types::Any;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     signer::Signer,
     stag::StagContext,
+    storage::Storage,
     tendermint::TendermintClient,
     transaction_builder::msg_receive_packet,
     types::{
@@ -17,7 +18,7 @@ use crate::{
     },
 };
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct InterchainAccountPacketData {
     #[serde(rename = "type")]
     pub ty: i32,
@@ -25,6 +26,10 @@ pub struct InterchainAccountPacketData {
     pub memo: String,
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_submit<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -36,6 +41,7 @@ pub async fn msg_submit<C>(
 where
     C: StagContext,
     C::Signer: Signer,
+    C::Storage: Storage,
     C::RpcClient: TendermintClient,
 {
     let cosmos_tx = CosmosTx { messages };
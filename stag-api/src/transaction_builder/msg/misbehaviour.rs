@@ -0,0 +1,128 @@
+use anyhow::{anyhow, Result};
+use cosmos_sdk_proto::{
+    cosmos::tx::v1beta1::TxRaw,
+    ibc::{
+        core::client::v1::MsgSubmitMisbehaviour,
+        lightclients::solomachine::v2::{
+            DataType, Misbehaviour, NextSequenceRecvData, SignBytes, SignatureAndData,
+        },
+    },
+};
+
+use crate::{
+    signer::{GetPublicKey, Signer},
+    stag::StagContext,
+    transaction_builder::{common::to_u64_timestamp, signing::sign_unrecorded, tx::build},
+    types::{
+        chain_state::ChainState,
+        proto_util::{proto_encode, AnyConvert},
+    },
+};
+
+/// Path used in the data signed for freezing solo machine client (it is never used in any proof)
+const FREEZE_PATH: &str = "stag/freeze";
+
+/// Creates a message for submitting solo machine misbehaviour on IBC enabled chain (which freezes the solo machine
+/// client)
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
+pub async fn msg_submit_misbehaviour<C>(
+    context: &C,
+    chain_state: &ChainState,
+    misbehaviour: Misbehaviour,
+    memo: String,
+    request_id: Option<&str>,
+) -> Result<TxRaw>
+where
+    C: StagContext,
+    C::Signer: Signer,
+{
+    let message = MsgSubmitMisbehaviour {
+        client_id: misbehaviour.client_id.clone(),
+        misbehaviour: Some(misbehaviour.to_any()?),
+        signer: context.signer().to_account_address(&chain_state.id).await?,
+    };
+
+    build(context, chain_state, &[message], memo, request_id).await
+}
+
+/// Creates solo machine misbehaviour from two signatures at the same sequence
+pub fn misbehaviour(
+    chain_state: &ChainState,
+    sequence: u64,
+    signature_one: SignatureAndData,
+    signature_two: SignatureAndData,
+) -> Result<Misbehaviour> {
+    let connection_details = chain_state.connection_details.as_ref().ok_or_else(|| {
+        anyhow!(
+            "connection details not found for chain with id {}",
+            chain_state.id
+        )
+    })?;
+
+    Ok(Misbehaviour {
+        client_id: connection_details.tendermint_client_id.to_string(),
+        sequence,
+        signature_one: Some(signature_one),
+        signature_two: Some(signature_two),
+    })
+}
+
+/// Creates solo machine misbehaviour for freezing solo machine client on IBC enabled chain (should only be used in
+/// emergencies, e.g., when signer's key is compromised)
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
+pub async fn freeze_misbehaviour<C>(
+    context: &C,
+    chain_state: &ChainState,
+    request_id: Option<&str>,
+) -> Result<Misbehaviour>
+where
+    C: StagContext,
+    C::Signer: Signer,
+{
+    let sequence = chain_state.sequence.into();
+
+    let signature_one = sign_freeze_data(context, chain_state, 0, request_id).await?;
+    let signature_two = sign_freeze_data(context, chain_state, 1, request_id).await?;
+
+    misbehaviour(chain_state, sequence, signature_one, signature_two)
+}
+
+async fn sign_freeze_data<C>(
+    context: &C,
+    chain_state: &ChainState,
+    next_seq_recv: u64,
+    request_id: Option<&str>,
+) -> Result<SignatureAndData>
+where
+    C: StagContext,
+    C::Signer: Signer,
+{
+    let data = proto_encode(&NextSequenceRecvData {
+        path: FREEZE_PATH.as_bytes().to_vec(),
+        next_seq_recv,
+    })?;
+
+    let sign_bytes = SignBytes {
+        sequence: chain_state.sequence.into(),
+        timestamp: to_u64_timestamp(chain_state.consensus_timestamp)?,
+        diversifier: chain_state.config.diversifier.to_owned(),
+        data_type: DataType::NextSequenceRecv.into(),
+        data: data.clone(),
+    };
+
+    let timestamp = sign_bytes.timestamp;
+    let signature = sign_unrecorded(context, request_id, &chain_state.id, sign_bytes, None).await?;
+
+    Ok(SignatureAndData {
+        signature,
+        data_type: DataType::NextSequenceRecv.into(),
+        data,
+        timestamp,
+    })
+}
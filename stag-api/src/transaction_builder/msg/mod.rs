@@ -1,9 +1,10 @@
 mod connect;
 /// ICA (Interchain accounts) transactions
 pub mod ica;
+mod misbehaviour;
 mod packet;
 /// IBC transfer transactions
 pub mod transfer;
 mod update_signer;
 
-pub use self::{connect::*, packet::*, update_signer::*};
+pub use self::{connect::*, misbehaviour::*, packet::*, update_signer::*};
@@ -12,6 +12,7 @@ use serde_json::json;
 use crate::{
     signer::{GetPublicKey, Signer},
     stag::StagContext,
+    storage::Storage,
     tendermint::TendermintClient,
     transaction_builder::{
         proofs::{get_packet_acknowledgement_proof, get_packet_commitment_proof},
@@ -27,6 +28,10 @@ use crate::{
 };
 
 /// Creates a message for sending a packet on IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_receive_packet<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -38,6 +43,7 @@ pub async fn msg_receive_packet<C>(
 where
     C: StagContext,
     C::Signer: Signer,
+    C::Storage: Storage,
     C::RpcClient: TendermintClient,
 {
     let connection_details = chain_state
@@ -114,6 +120,10 @@ where
 }
 
 /// Creates a message for acknowledging a packet on IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_acknowledgement<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -125,6 +135,7 @@ pub async fn msg_acknowledgement<C>(
 where
     C: StagContext,
     C::Signer: Signer,
+    C::Storage: Storage,
 {
     let proof_height = Height::new(0, chain_state.sequence.into());
     let acknowledgement = serde_json::to_vec(&json!({ "result": [1u8] }))?;
@@ -159,6 +170,7 @@ where
 {
     let response = context
         .rpc_client()
+        .for_chain(&chain_state.config)
         .status(&chain_state.config.rpc_addr)
         .await?;
 
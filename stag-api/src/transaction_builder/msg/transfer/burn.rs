@@ -19,6 +19,10 @@ use crate::{
 };
 
 /// Creates a message (`MsgTransfer`) for burning tokens on IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_burn<C>(
     context: &C,
     chain_state: &ChainState,
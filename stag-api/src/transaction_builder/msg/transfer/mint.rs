@@ -1,11 +1,12 @@
 use anyhow::Result;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
 use primitive_types::U256;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     signer::{GetPublicKey, Signer},
     stag::StagContext,
+    storage::Storage,
     tendermint::TendermintClient,
     transaction_builder::msg::packet::msg_receive_packet,
     types::{
@@ -14,8 +15,8 @@ use crate::{
     },
 };
 
-#[derive(Debug, Serialize)]
-struct TokenTransferPacketData {
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TokenTransferPacketData {
     pub denom: String,
     // Ideally `amount` should be `U256` but `ibc-go` uses `protojson` which encodes `uint256` into `string`. So, using
     // `String` here to keep consistent wire format.
@@ -25,6 +26,10 @@ struct TokenTransferPacketData {
 }
 
 /// Creates and signs a `MsgRecvPacket` transaction.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_mint<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -37,6 +42,7 @@ pub async fn msg_mint<C>(
 where
     C: StagContext,
     C::Signer: Signer,
+    C::Storage: Storage,
     C::RpcClient: TendermintClient,
 {
     let packet_data = TokenTransferPacketData {
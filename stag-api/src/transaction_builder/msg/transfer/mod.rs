@@ -2,3 +2,5 @@ mod burn;
 mod mint;
 
 pub use self::{burn::msg_burn, mint::msg_mint};
+
+pub(crate) use self::mint::TokenTransferPacketData;
@@ -10,11 +10,16 @@ use cosmos_sdk_proto::{
 use crate::{
     signer::{GetPublicKey, Signer},
     stag::StagContext,
+    storage::Storage,
     transaction_builder::{common::to_u64_timestamp, proofs::get_header_proof, tx::build},
     types::{chain_state::ChainState, proto_util::AnyConvert, public_key::PublicKey},
 };
 
 /// Creates a message for updating solo machine client on IBC enabled chain
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn msg_update_solo_machine_client<C>(
     context: &C,
     chain_state: &mut ChainState,
@@ -25,6 +30,7 @@ pub async fn msg_update_solo_machine_client<C>(
 where
     C: StagContext,
     C::Signer: Signer,
+    C::Storage: Storage,
 {
     if chain_state.connection_details.is_none() {
         bail!(
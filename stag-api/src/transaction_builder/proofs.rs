@@ -1,9 +1,12 @@
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, ensure, Context, Result};
 use cosmos_sdk_proto::ibc::{
-    core::channel::v1::Packet,
-    lightclients::solomachine::v2::{
-        ChannelStateData, ClientStateData, ConnectionStateData, ConsensusStateData, DataType,
-        HeaderData, PacketAcknowledgementData, PacketCommitmentData, SignBytes,
+    core::{channel::v1::Packet, client::v1::Height},
+    lightclients::{
+        solomachine::v2::{
+            ChannelStateData, ClientStateData, ConnectionStateData, ConsensusStateData, DataType,
+            HeaderData, PacketAcknowledgementData, PacketCommitmentData, SignBytes,
+        },
+        tendermint::v1::ConsensusState as TendermintConsensusState,
     },
 };
 use prost_types::Any;
@@ -12,18 +15,22 @@ use crate::{
     signer::Signer,
     stag::StagContext,
     storage::{Storage, Transaction},
+    tendermint::{LightClient, TendermintClient},
     types::{
         chain_state::ChainState,
-        ics::core::{
-            ics02_client::height::IHeight,
-            ics04_channel::packet::IPacket,
-            ics24_host::{
-                identifier::{ChannelId, ClientId, ConnectionId, PortId},
-                path::{
-                    ChannelPath, ClientStatePath, ConnectionPath, ConsensusStatePath,
-                    PacketAcknowledgementPath, PacketCommitmentPath,
+        ics::{
+            core::{
+                ics02_client::height::IHeight,
+                ics04_channel::packet::IPacket,
+                ics24_host::{
+                    identifier::{ChannelId, ClientId, ConnectionId, PortId},
+                    path::{
+                        ChannelPath, ClientStatePath, ConnectionPath, ConsensusStatePath,
+                        PacketAcknowledgementPath, PacketCommitmentPath,
+                    },
                 },
             },
+            lightclients::tendermint::consensus_state::IConsensusState,
         },
         proto_util::{proto_encode, AnyConvert},
     },
@@ -34,6 +41,10 @@ use super::{
     signing::{sign, timestamped_sign},
 };
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn get_packet_acknowledgement_proof<C>(
     context: &C,
     chain_state: &ChainState,
@@ -45,6 +56,7 @@ pub async fn get_packet_acknowledgement_proof<C>(
 where
     C: StagContext,
     C::Signer: Signer,
+    C::Storage: Storage,
 {
     let connection_details = chain_state.connection_details.as_ref().ok_or_else(|| {
         anyhow!(
@@ -80,9 +92,13 @@ where
         data: acknowledgement_data_bytes,
     };
 
-    timestamped_sign(context, chain_state, sign_bytes, request_id).await
+    timestamped_sign(context, chain_state, sign_bytes, None, request_id).await
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn get_packet_commitment_proof<C>(
     context: &C,
     chain_state: &ChainState,
@@ -93,6 +109,7 @@ pub async fn get_packet_commitment_proof<C>(
 where
     C: StagContext,
     C::Signer: Signer,
+    C::Storage: Storage,
 {
     let commitment_bytes = packet.commitment_bytes()?;
 
@@ -130,9 +147,13 @@ where
         data: packet_commitment_data_bytes,
     };
 
-    timestamped_sign(context, chain_state, sign_bytes, request_id).await
+    timestamped_sign(context, chain_state, sign_bytes, Some(packet), request_id).await
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn get_channel_proof<C>(
     context: &C,
     chain_state: &ChainState,
@@ -175,9 +196,13 @@ where
         data: channel_state_data_bytes,
     };
 
-    timestamped_sign(context, chain_state, sign_bytes, request_id).await
+    timestamped_sign(context, chain_state, sign_bytes, None, request_id).await
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn get_connection_proof<C>(
     context: &C,
     chain_state: &ChainState,
@@ -213,9 +238,13 @@ where
         data: connection_state_data_bytes,
     };
 
-    timestamped_sign(context, chain_state, sign_bytes, request_id).await
+    timestamped_sign(context, chain_state, sign_bytes, None, request_id).await
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn get_client_proof<C>(
     context: &C,
     chain_state: &ChainState,
@@ -226,13 +255,34 @@ where
     C: StagContext,
     C::Signer: Signer,
     C::Storage: Storage,
+    C::RpcClient: TendermintClient,
 {
     let client_state = context
         .storage()
         .get_tendermint_client_state(client_id)
         .await?
-        .ok_or_else(|| anyhow!("client with id {} not found", client_id))?
-        .to_any()?;
+        .ok_or_else(|| anyhow!("client with id {} not found", client_id))?;
+
+    let height = client_state
+        .latest_height
+        .clone()
+        .context("client state does not contain latest height")?;
+
+    let consensus_state = context
+        .storage()
+        .get_tendermint_consensus_state(client_id, &height)
+        .await?
+        .ok_or_else(|| {
+            anyhow!(
+                "consensus state with id {} and height {} not found",
+                client_id,
+                height.to_string(),
+            )
+        })?;
+
+    verify_consensus_state(context, chain_state, &height, &consensus_state).await?;
+
+    let client_state = client_state.to_any()?;
 
     let mut client_state_path = ClientStatePath::new(client_id);
     client_state_path.apply_prefix(&"ibc".parse().unwrap());
@@ -252,9 +302,13 @@ where
         data: client_state_data_bytes,
     };
 
-    timestamped_sign(context, chain_state, sign_bytes, request_id).await
+    timestamped_sign(context, chain_state, sign_bytes, None, request_id).await
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn get_consensus_proof<C>(
     context: &C,
     chain_state: &ChainState,
@@ -265,6 +319,7 @@ where
     C: StagContext,
     C::Signer: Signer,
     C::Storage: Transaction,
+    C::RpcClient: TendermintClient,
 {
     let client_state = context
         .storage()
@@ -286,8 +341,11 @@ where
                 client_id,
                 height.to_string(),
             )
-        })?
-        .to_any()?;
+        })?;
+
+    verify_consensus_state(context, chain_state, &height, &consensus_state).await?;
+
+    let consensus_state = consensus_state.to_any()?;
 
     let mut consensus_state_path = ConsensusStatePath::new(client_id, &height);
     consensus_state_path.apply_prefix(&"ibc".parse().unwrap());
@@ -307,9 +365,13 @@ where
         data: consensus_state_data_bytes,
     };
 
-    timestamped_sign(context, chain_state, sign_bytes, request_id).await
+    timestamped_sign(context, chain_state, sign_bytes, None, request_id).await
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn get_header_proof<C>(
     context: &C,
     chain_state: &ChainState,
@@ -320,6 +382,7 @@ pub async fn get_header_proof<C>(
 where
     C: StagContext,
     C::Signer: Signer,
+    C::Storage: Storage,
 {
     let header_data = HeaderData {
         new_pub_key: Some(new_public_key),
@@ -336,5 +399,186 @@ where
         data: header_data_bytes,
     };
 
-    sign(context, request_id, &chain_state.id, sign_bytes).await
+    sign(context, request_id, &chain_state.id, sign_bytes, None).await
+}
+
+/// Verifies the header from which given tendermint consensus state was created using light client (which also
+/// cross-checks it against witnesses) so that a proof is never signed for a header only vouched for by the primary
+async fn verify_consensus_state<C>(
+    context: &C,
+    chain_state: &ChainState,
+    height: &Height,
+    consensus_state: &TendermintConsensusState,
+) -> Result<()>
+where
+    C: StagContext,
+    C::Storage: Storage,
+    C::RpcClient: TendermintClient,
+{
+    let evidence = context
+        .storage()
+        .get_light_client_attack_evidence(&chain_state.id)
+        .await?;
+    let light_client = LightClient::new(context.rpc_client(), chain_state, evidence).await?;
+
+    let light_block = light_client
+        .verify_to_height(height.revision_height)
+        .await?;
+
+    ensure!(
+        &TendermintConsensusState::from_block_header(light_block.signed_header.header)
+            == consensus_state,
+        "consensus state at height {} does not match verified header of chain {}",
+        height.to_string(),
+        chain_state.id
+    );
+
+    Ok(())
+}
+
+#[cfg(all(test, feature = "mnemonic-signer", feature = "sqlite-storage"))]
+mod tests {
+    use cosmos_sdk_proto::ibc::lightclients::tendermint::v1::ClientState as TendermintClientState;
+    use tendermint::block::Header;
+
+    use crate::{
+        stag::WithTransaction,
+        storage::{Sqlite, StorageConfig, TransactionProvider},
+        tendermint::{
+            test_util::{chain_state, header, mock_client, NODE_ID, WITNESS},
+            LightClientAttackEvidence, MockRpcClient,
+        },
+        test_util::{test_context, TestContext},
+        types::ics::core::ics02_client::client_type::ClientType,
+    };
+
+    use super::*;
+
+    type TransactionContext =
+        TestContext<<<Sqlite as StorageConfig>::Storage as TransactionProvider>::Transaction>;
+
+    /// Returns a context with a tendermint client whose consensus state was created from `stored_header`
+    async fn setup(
+        rpc_client: MockRpcClient,
+        stored_header: &serde_json::Value,
+    ) -> (TransactionContext, ClientId) {
+        let (context, _) = test_context(rpc_client).await;
+
+        let client_id = ClientId::generate(ClientType::Tendermint);
+        let height = Height::new(1, 5);
+        let stored_header: Header = serde_json::from_value(stored_header.clone()).unwrap();
+
+        context
+            .storage()
+            .add_tendermint_client_state(
+                &client_id,
+                &TendermintClientState {
+                    chain_id: "test-1".to_owned(),
+                    latest_height: Some(height.clone()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        context
+            .storage()
+            .add_tendermint_consensus_state(
+                &client_id,
+                &height,
+                &TendermintConsensusState::from_block_header(stored_header),
+            )
+            .await
+            .unwrap();
+
+        (context.with_transaction().await.unwrap(), client_id)
+    }
+
+    #[tokio::test]
+    async fn test_consensus_proof_with_agreeing_witness() {
+        let header = header("0000000000000000");
+        let mut chain_state = chain_state(&header);
+        let (context, client_id) =
+            setup(mock_client(header.clone(), header.clone()), &header).await;
+
+        assert!(
+            get_consensus_proof(&context, &chain_state, &client_id, None)
+                .await
+                .is_ok()
+        );
+
+        chain_state.sequence += 1;
+
+        assert!(get_client_proof(&context, &chain_state, &client_id, None)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_proofs_with_conflicting_witness() {
+        let header = header("0000000000000000");
+        let chain_state = chain_state(&header);
+        let (context, client_id) = setup(
+            mock_client(header.clone(), self::header("0100000000000000")),
+            &header,
+        )
+        .await;
+
+        let err = get_consensus_proof(&context, &chain_state, &client_id, None)
+            .await
+            .unwrap_err();
+        assert!(err.downcast_ref::<LightClientAttackEvidence>().is_some());
+
+        let err = get_client_proof(&context, &chain_state, &client_id, None)
+            .await
+            .unwrap_err();
+        assert!(err.downcast_ref::<LightClientAttackEvidence>().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_consensus_proof_with_unverified_consensus_state() {
+        let header = header("0000000000000000");
+        let chain_state = chain_state(&header);
+        let (context, client_id) = setup(
+            mock_client(header.clone(), header.clone()),
+            &self::header("0100000000000000"),
+        )
+        .await;
+
+        let err = get_consensus_proof(&context, &chain_state, &client_id, None)
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("does not match verified header of chain test-1"));
+    }
+
+    #[tokio::test]
+    async fn test_consensus_proof_with_recorded_evidence() {
+        let header = header("0000000000000000");
+        let chain_state = chain_state(&header);
+        let rpc_client = mock_client(header.clone(), header.clone());
+        let (context, client_id) = setup(rpc_client.clone(), &header).await;
+
+        let evidence = LightClientAttackEvidence {
+            height: 5,
+            primary: NODE_ID.parse().unwrap(),
+            primary_header_hash: tendermint::Hash::Sha256([1; 32]),
+            witness: WITNESS.parse().unwrap(),
+            witness_header_hash: tendermint::Hash::Sha256([2; 32]),
+        };
+        context
+            .storage()
+            .add_light_client_attack_evidence(&chain_state.id, &evidence)
+            .await
+            .unwrap();
+
+        let err = get_consensus_proof(&context, &chain_state, &client_id, None)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<LightClientAttackEvidence>(),
+            Some(&evidence)
+        );
+        assert_eq!(rpc_client.request_count("commit"), 0);
+    }
 }
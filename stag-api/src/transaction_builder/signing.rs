@@ -1,38 +1,39 @@
-use anyhow::Result;
-use cosmos_sdk_proto::{
-    cosmos::tx::signing::v1beta1::{
-        signature_descriptor::{
-            data::{Single as SingleSignatureData, Sum as SignatureDataInner},
-            Data as SignatureData,
-        },
-        SignMode,
-    },
-    ibc::lightclients::solomachine::v2::{SignBytes, TimestampedSignatureData},
+use anyhow::{bail, Result};
+use cosmos_sdk_proto::ibc::{
+    core::channel::v1::Packet,
+    lightclients::solomachine::v2::{SignBytes, SignatureAndData, TimestampedSignatureData},
 };
 
 use crate::{
     signer::{Message, Signer},
     stag::StagContext,
+    storage::Storage,
     types::{
         chain_state::ChainState, ics::core::ics24_host::identifier::ChainId,
         proto_util::proto_encode,
     },
 };
 
-use super::common::to_u64_timestamp;
+use super::common::{report_signer_failure, to_u64_timestamp};
 
-/// Signs a transaction with timestamp information
+/// Signs a transaction with timestamp information (`packet` must be provided when signing packet commitments)
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn timestamped_sign<C>(
     context: &C,
     chain_state: &ChainState,
     sign_bytes: SignBytes,
+    packet: Option<&Packet>,
     request_id: Option<&str>,
 ) -> Result<Vec<u8>>
 where
     C: StagContext,
     C::Signer: Signer,
+    C::Storage: Storage,
 {
-    let signature_data = sign(context, request_id, &chain_state.id, sign_bytes).await?;
+    let signature_data = sign(context, request_id, &chain_state.id, sign_bytes, packet).await?;
 
     let timestamped_signature_data = TimestampedSignatureData {
         signature_data,
@@ -42,29 +43,232 @@ where
     proto_encode(&timestamped_signature_data)
 }
 
-/// Signs a transaction
+/// Signs a transaction and records the signed data in signature history of solo machine. Refuses to sign if data
+/// conflicting with given data was already signed at the same sequence (as it'll allow anyone to freeze solo machine
+/// client on IBC enabled chain).
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_id, request_id = request_id))
+)]
 pub async fn sign<C>(
     context: &C,
     request_id: Option<&str>,
     chain_id: &ChainId,
     sign_bytes: SignBytes,
+    packet: Option<&Packet>,
 ) -> Result<Vec<u8>>
 where
     C: StagContext,
     C::Signer: Signer,
+    C::Storage: Storage,
 {
-    let sign_bytes = proto_encode(&sign_bytes)?;
-    let signature = context
-        .signer()
-        .sign(request_id, chain_id, Message::SignBytes(&sign_bytes))
+    let recorded = context
+        .storage()
+        .get_signature_and_data(chain_id, sign_bytes.sequence)
         .await?;
 
-    let signature_data = SignatureData {
-        sum: Some(SignatureDataInner::Single(SingleSignatureData {
-            signature,
-            mode: SignMode::Unspecified.into(),
-        })),
+    if let Some(ref recorded) = recorded {
+        if is_conflicting(recorded, &sign_bytes) {
+            bail!(
+                "refusing to sign data conflicting with already signed data at sequence {} for chain {}",
+                sign_bytes.sequence,
+                chain_id
+            );
+        }
+    }
+
+    let sequence = sign_bytes.sequence;
+    let signature_and_data = SignatureAndData {
+        signature: Vec::new(),
+        data_type: sign_bytes.data_type,
+        data: sign_bytes.data.clone(),
+        timestamp: sign_bytes.timestamp,
     };
 
+    let signature = sign_unrecorded(context, request_id, chain_id, sign_bytes, packet).await?;
+
+    if recorded.is_none() {
+        context
+            .storage()
+            .add_signature_and_data(
+                chain_id,
+                sequence,
+                &SignatureAndData {
+                    signature: signature.clone(),
+                    ..signature_and_data
+                },
+            )
+            .await?;
+    }
+
+    Ok(signature)
+}
+
+/// Returns true if given sign bytes conflict with given signature and data signed at the same sequence
+pub fn is_conflicting(signature_and_data: &SignatureAndData, sign_bytes: &SignBytes) -> bool {
+    signature_and_data.data_type != sign_bytes.data_type
+        || signature_and_data.data != sign_bytes.data
+        || signature_and_data.timestamp != sign_bytes.timestamp
+}
+
+/// Signs a transaction without recording the signed data in signature history of solo machine (should only be used
+/// for signing data which is never used in proofs, e.g., for freezing solo machine client)
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_id, request_id = request_id))
+)]
+pub async fn sign_unrecorded<C>(
+    context: &C,
+    request_id: Option<&str>,
+    chain_id: &ChainId,
+    sign_bytes: SignBytes,
+    packet: Option<&Packet>,
+) -> Result<Vec<u8>>
+where
+    C: StagContext,
+    C::Signer: Signer,
+{
+    let sign_bytes = proto_encode(&sign_bytes)?;
+    let message = match packet {
+        Some(packet) => Message::PacketCommitment {
+            sign_bytes: &sign_bytes,
+            packet,
+        },
+        None => Message::SignBytes(&sign_bytes),
+    };
+
+    let result = context
+        .signer()
+        .sign_data(request_id, chain_id, message)
+        .await;
+    let signature_data = report_signer_failure(context, chain_id, request_id, result).await?;
+
     proto_encode(&signature_data)
 }
+
+#[cfg(all(test, feature = "mnemonic-signer", feature = "sqlite-storage"))]
+mod tests {
+    use anyhow::anyhow;
+    use cosmos_sdk_proto::ibc::lightclients::solomachine::v2::DataType;
+
+    use crate::{
+        tendermint::MockRpcClient,
+        test_util::{test_context, CHAIN_ID},
+    };
+
+    use super::*;
+
+    fn sign_bytes(sequence: u64, data: &[u8]) -> SignBytes {
+        SignBytes {
+            sequence,
+            timestamp: 100,
+            diversifier: "stag".to_owned(),
+            data_type: DataType::PacketCommitment.into(),
+            data: data.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_is_conflicting() {
+        let signature_and_data = SignatureAndData {
+            signature: b"signature".to_vec(),
+            data_type: DataType::PacketCommitment.into(),
+            data: b"data".to_vec(),
+            timestamp: 100,
+        };
+
+        assert!(!is_conflicting(
+            &signature_and_data,
+            &sign_bytes(1, b"data")
+        ));
+        assert!(is_conflicting(
+            &signature_and_data,
+            &sign_bytes(1, b"other data")
+        ));
+        assert!(is_conflicting(
+            &signature_and_data,
+            &SignBytes {
+                timestamp: 101,
+                ..sign_bytes(1, b"data")
+            }
+        ));
+        assert!(is_conflicting(
+            &signature_and_data,
+            &SignBytes {
+                data_type: DataType::PacketAcknowledgement.into(),
+                ..sign_bytes(1, b"data")
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_refuses_conflicting_data() {
+        let (context, _) = test_context(MockRpcClient::new(|_, method, _| {
+            Err(anyhow!("unexpected request: {}", method))
+        }))
+        .await;
+        let chain_id: ChainId = CHAIN_ID.parse().unwrap();
+
+        let signature = sign(&context, None, &chain_id, sign_bytes(1, b"data"), None)
+            .await
+            .unwrap();
+
+        let recorded = context
+            .storage()
+            .get_signature_and_data(&chain_id, 1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(recorded.signature, signature);
+        assert_eq!(recorded.data, b"data");
+
+        // Signing the same data again at the same sequence is allowed
+        assert_eq!(
+            sign(&context, None, &chain_id, sign_bytes(1, b"data"), None)
+                .await
+                .unwrap(),
+            signature
+        );
+
+        let err = sign(
+            &context,
+            None,
+            &chain_id,
+            sign_bytes(1, b"other data"),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().starts_with(
+            "refusing to sign data conflicting with already signed data at sequence 1"
+        ));
+
+        // Data at other sequences is not affected by signature history
+        assert!(sign(
+            &context,
+            None,
+            &chain_id,
+            sign_bytes(2, b"other data"),
+            None
+        )
+        .await
+        .is_ok());
+
+        // Unrecorded signatures are neither checked against nor added to signature history
+        assert!(
+            sign_unrecorded(&context, None, &chain_id, sign_bytes(1, b"freeze"), None)
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            context
+                .storage()
+                .get_signature_and_data(&chain_id, 1)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+            b"data"
+        );
+    }
+}
@@ -14,6 +14,7 @@ use tonic_web_wasm_client::Client;
 use url::Url;
 
 use crate::{
+    endpoint_util::with_failover,
     signer::{GetPublicKey, Message, Signer},
     stag::StagContext,
     types::{
@@ -24,6 +25,12 @@ use crate::{
     },
 };
 
+use super::common::report_signer_failure;
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_state.id, request_id = request_id))
+)]
 pub async fn build<C, T>(
     context: &C,
     chain_state: &ChainState,
@@ -87,18 +94,27 @@ where
     C: StagContext,
     C::Signer: GetPublicKey,
 {
-    let mut query_client = get_auth_query_client(chain_state.config.grpc_addr.clone()).await?;
-
     let account_address = context.signer().to_account_address(&chain_state.id).await?;
 
-    let response = query_client
-        .account(QueryAccountRequest {
-            address: account_address.clone(),
-        })
-        .await?
-        .into_inner()
-        .account
-        .ok_or_else(|| anyhow!("unable to find account with address: {}", account_address))?;
+    let request = QueryAccountRequest {
+        address: account_address.clone(),
+    };
+
+    let response = with_failover(
+        chain_state.config.grpc_addrs(),
+        &chain_state.config.retry_policy,
+        |grpc_addr| {
+            let request = request.clone();
+
+            async move {
+                let mut query_client = get_auth_query_client(grpc_addr.clone()).await?;
+                Ok(query_client.account(request).await?.into_inner())
+            }
+        },
+    )
+    .await?
+    .account
+    .ok_or_else(|| anyhow!("unable to find account with address: {}", account_address))?;
 
     let account = Account::from_any(&response)?;
     let base_account = account
@@ -115,18 +131,20 @@ async fn build_auth_info<C>(
 ) -> Result<AuthInfo>
 where
     C: StagContext,
-    C::Signer: GetPublicKey,
+    C::Signer: Signer,
 {
     let signer_info = SignerInfo {
         public_key: Some(
             context
                 .signer()
-                .get_public_key(&chain_state.id)
+                .get_transaction_public_key(&chain_state.id)
                 .await?
                 .to_any()?,
         ),
         mode_info: Some(ModeInfo {
-            sum: Some(Sum::Single(Single { mode: 1 })),
+            sum: Some(Sum::Single(Single {
+                mode: context.signer().sign_mode().into(),
+            })),
         }),
         sequence: account_sequence,
     };
@@ -147,6 +165,10 @@ where
     })
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(chain_id = %chain_id, request_id = request_id))
+)]
 async fn build_signature<C>(
     context: &C,
     body_bytes: Vec<u8>,
@@ -168,10 +190,12 @@ where
 
     let sign_doc_bytes = proto_encode(&sign_doc)?;
 
-    context
+    let result = context
         .signer()
         .sign(request_id, chain_id, Message::SignDoc(&sign_doc_bytes))
-        .await
+        .await;
+
+    report_signer_failure(context, chain_id, request_id, result).await
 }
 
 #[cfg(feature = "wasm")]
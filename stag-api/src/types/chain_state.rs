@@ -3,12 +3,11 @@ use std::{collections::HashMap, time::Duration};
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
 use cosmos_sdk_proto::cosmos::bank::v1beta1::{
-    query_client::QueryClient as BankQueryClient, QueryBalanceRequest,
+    query_client::QueryClient as BankQueryClient, QueryBalanceRequest, QueryParamsRequest,
 };
 use num_rational::Ratio;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 use tendermint::node::Id as NodeId;
 #[cfg(all(not(feature = "wasm"), feature = "non-wasm"))]
 use tonic::transport::Channel;
@@ -17,6 +16,7 @@ use tonic_web_wasm_client::Client;
 use url::Url;
 
 use crate::{
+    endpoint_util::with_failover,
     signer::GetPublicKey,
     types::ics::core::ics24_host::{
         identifier::{ChainId, ChannelId, ClientId, ConnectionId, Identifier, PortId},
@@ -82,6 +82,16 @@ pub struct ChainConfig {
     pub grpc_addr: Url,
     /// RPC address
     pub rpc_addr: Url,
+    /// Fallback gRPC addresses (in decreasing order of priority) used when primary gRPC address is unavailable
+    #[serde(default)]
+    pub fallback_grpc_addrs: Vec<Url>,
+    /// Fallback RPC addresses (in decreasing order of priority) used when primary RPC address is unavailable
+    #[serde(default)]
+    pub fallback_rpc_addrs: Vec<Url>,
+    /// RPC addresses of witness nodes used only for cross-checking verified light blocks (primary and fallback RPC
+    /// addresses are never used as witnesses because light blocks may be fetched from any of them)
+    #[serde(default)]
+    pub witness_rpc_addrs: Vec<Url>,
     /// Fee and gas limits
     pub fee: Fee,
     /// Trust level (e.g. 1/3)
@@ -105,6 +115,117 @@ pub struct ChainConfig {
     pub trusted_hash: [u8; 32],
     /// Number of blocks after which a packet times out
     pub packet_timeout_height_offset: u64,
+    /// Retry policy for transient RPC and gRPC failures
+    #[serde(default)]
+    pub retry_policy: RetryPolicy,
+}
+
+/// Retry policy for transient RPC and gRPC failures
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts (including the first one) for a call
+    pub max_attempts: u32,
+    /// Backoff duration after the first failed attempt
+    #[serde(with = "humantime_serde")]
+    pub initial_backoff: Duration,
+    /// Maximum backoff duration between two attempts
+    #[serde(with = "humantime_serde")]
+    pub max_backoff: Duration,
+    /// Multiplier applied to backoff duration after every failed attempt
+    pub backoff_multiplier: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            backoff_multiplier: 2,
+        }
+    }
+}
+
+impl ChainConfig {
+    /// Returns all the gRPC addresses of chain (in decreasing order of priority)
+    pub fn grpc_addrs(&self) -> Vec<&Url> {
+        std::iter::once(&self.grpc_addr)
+            .chain(self.fallback_grpc_addrs.iter())
+            .collect()
+    }
+
+    /// Returns all the RPC addresses of chain (in decreasing order of priority)
+    pub fn rpc_addrs(&self) -> Vec<&Url> {
+        std::iter::once(&self.rpc_addr)
+            .chain(self.fallback_rpc_addrs.iter())
+            .collect()
+    }
+
+    /// Returns all the RPC addresses used as witnesses for cross-checking light blocks (i.e., witness RPC addresses
+    /// other than primary and fallback RPC addresses)
+    pub fn witness_addrs(&self) -> Vec<Url> {
+        let rpc_addrs = self.rpc_addrs();
+        let mut witness_addrs: Vec<Url> = Vec::new();
+
+        for witness_addr in self.witness_rpc_addrs.iter() {
+            if !rpc_addrs.contains(&witness_addr) && !witness_addrs.contains(witness_addr) {
+                witness_addrs.push(witness_addr.clone());
+            }
+        }
+
+        witness_addrs
+    }
+}
+
+impl RetryPolicy {
+    /// Returns a retry policy which never retries failed calls
+    pub fn no_retry() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the backoff duration to wait for after given failed attempt (starting from `1`)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let multiplier = self
+            .backoff_multiplier
+            .checked_pow(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+
+        self.initial_backoff
+            .checked_mul(multiplier)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+}
+
+/// Type of an endpoint of an IBC enabled chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointType {
+    /// gRPC endpoint
+    Grpc,
+    /// Tendermint RPC endpoint
+    Rpc,
+}
+
+/// Health of an endpoint of an IBC enabled chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EndpointHealth {
+    /// Type of endpoint
+    pub endpoint_type: EndpointType,
+    /// Address of endpoint
+    pub addr: Url,
+    /// Error returned by health check of endpoint (`None` if endpoint is healthy)
+    pub error: Option<String>,
+}
+
+impl EndpointHealth {
+    /// Returns true if endpoint is healthy
+    pub fn is_healthy(&self) -> bool {
+        self.error.is_none()
+    }
 }
 
 /// Fee and gas configuration
@@ -153,9 +274,7 @@ impl ChainState {
             denom,
         );
 
-        let hash = Sha256::digest(denom_trace.to_string().as_bytes());
-
-        Ok(format!("ibc/{}", hex::encode_upper(hash)))
+        Ok(denom_trace.to_ibc_denom())
     }
 
     /// Fetches on-chain balance of given IBC denom
@@ -213,24 +332,43 @@ impl ChainState {
         signer: &impl GetPublicKey,
         denom: String,
     ) -> Result<Decimal> {
-        let mut query_client = get_bank_query_client(self.config.grpc_addr.clone()).await?;
-
         let request = QueryBalanceRequest {
             address: signer.to_account_address(&self.id).await?,
             denom,
         };
 
-        Ok(query_client
-            .balance(request)
-            .await?
-            .into_inner()
-            .balance
-            .map(|coin| coin.amount.parse())
-            .transpose()?
-            .unwrap_or_default())
+        Ok(with_failover(
+            self.config.grpc_addrs(),
+            &self.config.retry_policy,
+            |grpc_addr| {
+                let request = request.clone();
+
+                async move {
+                    let mut query_client = get_bank_query_client(grpc_addr.clone()).await?;
+                    Ok(query_client.balance(request).await?.into_inner())
+                }
+            },
+        )
+        .await?
+        .balance
+        .map(|coin| coin.amount.parse())
+        .transpose()?
+        .unwrap_or_default())
     }
 }
 
+/// Checks if gRPC endpoint at given address is healthy (i.e., able to serve queries)
+pub(crate) async fn grpc_health_check(grpc_addr: &Url) -> Result<()> {
+    let mut query_client = get_bank_query_client(grpc_addr.clone()).await?;
+
+    query_client
+        .params(QueryParamsRequest::default())
+        .await
+        .context("failed to query bank params")?;
+
+    Ok(())
+}
+
 #[cfg(feature = "wasm")]
 async fn get_bank_query_client(grpc_addr: Url) -> Result<BankQueryClient<Client>> {
     let mut url = grpc_addr.to_string();
@@ -249,3 +387,36 @@ async fn get_bank_query_client(grpc_addr: Url) -> Result<BankQueryClient<Channel
         .await
         .context("error when initializing grpc client")
 }
+
+#[cfg(test)]
+impl ChainConfig {
+    /// Returns a chain config with given RPC and gRPC addresses and a fast retry policy (for tests)
+    pub(crate) fn for_test(rpc_addr: &str, grpc_addr: &str) -> Self {
+        Self {
+            grpc_addr: grpc_addr.parse().unwrap(),
+            rpc_addr: rpc_addr.parse().unwrap(),
+            fallback_grpc_addrs: Vec::new(),
+            fallback_rpc_addrs: Vec::new(),
+            witness_rpc_addrs: Vec::new(),
+            fee: Fee {
+                amount: "1000".parse().unwrap(),
+                denom: "atom".parse().unwrap(),
+                gas_limit: 300000,
+            },
+            trust_level: "1/3".parse().unwrap(),
+            trusting_period: Duration::from_secs(336 * 60 * 60),
+            max_clock_drift: Duration::from_secs(3),
+            rpc_timeout: Duration::from_secs(60),
+            diversifier: "stag".to_owned(),
+            trusted_height: 1,
+            trusted_hash: [0; 32],
+            packet_timeout_height_offset: 10,
+            retry_policy: RetryPolicy {
+                max_attempts: 2,
+                initial_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(1),
+                backoff_multiplier: 1,
+            },
+        }
+    }
+}
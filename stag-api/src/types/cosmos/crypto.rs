@@ -4,13 +4,21 @@ use std::convert::TryFrom;
 #[cfg(feature = "ethermint")]
 use anyhow::Error;
 use anyhow::{Context, Result};
+use ed25519_dalek::PublicKey as Ed25519VerifyingKey;
 use k256::ecdsa::VerifyingKey;
+use p256::ecdsa::VerifyingKey as Secp256r1VerifyingKey;
 
 #[cfg(feature = "ethermint")]
 pub use crate::types::proto::ethermint::crypto::v1::ethsecp256k1::PubKey as EthSecp256k1PubKey;
-pub use cosmos_sdk_proto::cosmos::crypto::secp256k1::PubKey as Secp256k1PubKey;
+pub use cosmos_sdk_proto::cosmos::crypto::{
+    ed25519::PubKey as Ed25519PubKey, multisig::LegacyAminoPubKey,
+    secp256k1::PubKey as Secp256k1PubKey, secp256r1::PubKey as Secp256r1PubKey,
+};
 
 pub const SECP256K1_PUB_KEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+pub const ED25519_PUB_KEY_TYPE_URL: &str = "/cosmos.crypto.ed25519.PubKey";
+pub const SECP256R1_PUB_KEY_TYPE_URL: &str = "/cosmos.crypto.secp256r1.PubKey";
+pub const LEGACY_AMINO_PUB_KEY_TYPE_URL: &str = "/cosmos.crypto.multisig.LegacyAminoPubKey";
 #[cfg(feature = "ethermint")]
 pub const ETH_SECP256K1_PUB_KEY_TYPE_URL: &str = "/ethermint.crypto.v1.ethsecp256k1.PubKey";
 
@@ -27,6 +35,34 @@ pub fn into_verifying_key(value: &Secp256k1PubKey) -> Result<VerifyingKey> {
 
 impl_any_conversion!(Secp256k1PubKey, SECP256K1_PUB_KEY_TYPE_URL);
 
+pub fn from_ed25519_public_key(public_key: &Ed25519VerifyingKey) -> Ed25519PubKey {
+    Ed25519PubKey {
+        key: public_key.as_bytes().to_vec(),
+    }
+}
+
+pub fn into_ed25519_public_key(value: &Ed25519PubKey) -> Result<Ed25519VerifyingKey> {
+    Ed25519VerifyingKey::from_bytes(&value.key)
+        .context("unable to parse ed25519 public key from bytes")
+}
+
+impl_any_conversion!(Ed25519PubKey, ED25519_PUB_KEY_TYPE_URL);
+
+pub fn from_secp256r1_verifying_key(verifying_key: &Secp256r1VerifyingKey) -> Secp256r1PubKey {
+    Secp256r1PubKey {
+        key: verifying_key.to_encoded_point(true).as_bytes().to_vec(),
+    }
+}
+
+pub fn into_secp256r1_verifying_key(value: &Secp256r1PubKey) -> Result<Secp256r1VerifyingKey> {
+    Secp256r1VerifyingKey::from_sec1_bytes(&value.key)
+        .context("unable to parse verifying key from sec1 bytes")
+}
+
+impl_any_conversion!(Secp256r1PubKey, SECP256R1_PUB_KEY_TYPE_URL);
+
+impl_any_conversion!(LegacyAminoPubKey, LEGACY_AMINO_PUB_KEY_TYPE_URL);
+
 #[cfg(feature = "ethermint")]
 impl From<&VerifyingKey> for EthSecp256k1PubKey {
     fn from(key: &VerifyingKey) -> Self {
@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{event::Event, types::ics::core::ics24_host::identifier::ChainId};
+
+/// An event persisted in storage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventRecord {
+    /// ID of event (IDs are assigned in increasing order of event generation)
+    pub id: i64,
+    /// Event
+    pub event: Event,
+    /// Time at which this event was stored
+    pub created_at: DateTime<Utc>,
+}
+
+/// Filters for querying persisted events
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventQuery {
+    /// Only returns events with ID greater than or equal to given ID
+    pub from_id: Option<i64>,
+    /// Only returns events of given chain (warnings do not carry a chain ID)
+    pub chain_id: Option<ChainId>,
+    /// Only returns events of given types (e.g., `TokensMinted`; events of all the types are returned when empty)
+    pub event_types: Vec<String>,
+    /// Only returns events with given request ID
+    pub request_id: Option<String>,
+}
+
+impl EventQuery {
+    /// Returns `true` if given event record is selected by the query
+    pub fn matches(&self, record: &EventRecord) -> bool {
+        if matches!(self.from_id, Some(from_id) if record.id < from_id) {
+            return false;
+        }
+
+        if self.chain_id.is_some() && record.event.chain_id() != self.chain_id.as_ref() {
+            return false;
+        }
+
+        if !self.event_types.is_empty()
+            && !self
+                .event_types
+                .iter()
+                .any(|event_type| event_type == record.event.event_type())
+        {
+            return false;
+        }
+
+        if self.request_id.is_some() && record.event.request_id() != self.request_id.as_deref() {
+            return false;
+        }
+
+        true
+    }
+}
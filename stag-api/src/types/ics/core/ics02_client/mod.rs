@@ -1,4 +1,5 @@
 pub mod client_type;
 pub mod height;
 pub mod msg_create_client;
+pub mod msg_submit_misbehaviour;
 pub mod msg_update_client;
@@ -0,0 +1,5 @@
+use cosmos_sdk_proto::ibc::core::client::v1::MsgSubmitMisbehaviour;
+
+const TYPE_URL: &str = "/ibc.core.client.v1.MsgSubmitMisbehaviour";
+
+impl_any_conversion!(MsgSubmitMisbehaviour, TYPE_URL);
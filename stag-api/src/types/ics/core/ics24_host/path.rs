@@ -6,10 +6,11 @@ use std::{
 
 use anyhow::{ensure, Error};
 use cosmos_sdk_proto::ibc::core::client::v1::Height;
+use sha2::{Digest, Sha256};
 
 use crate::types::ics::core::ics02_client::height::IHeight;
 
-use super::identifier::{ChannelId, ClientId, ConnectionId, Identifier, PortId};
+use super::identifier::{ChainId, ChannelId, ClientId, ConnectionId, Identifier, PortId};
 
 /// Path is used as a key for an object store in state
 ///
@@ -190,6 +191,12 @@ impl DenomTrace {
                 .unwrap(),
         )
     }
+
+    /// Returns the IBC denom (`ibc/{hash}`) of tokens with this denom trace
+    pub fn to_ibc_denom(&self) -> String {
+        let hash = Sha256::digest(self.to_string().as_bytes());
+        format!("ibc/{}", hex::encode_upper(hash))
+    }
 }
 
 impl_path!(
@@ -224,3 +231,18 @@ impl InterchainAccountAddressPath {
         )
     }
 }
+
+impl_path!(
+    "Path for storing signatures of solo machine",
+    SoloMachineSignaturePath
+);
+
+impl SoloMachineSignaturePath {
+    pub fn new(chain_id: &ChainId, sequence: u64) -> Self {
+        Self(
+            format!("signatures/chains/{}/sequences/{}", chain_id, sequence)
+                .parse()
+                .unwrap(),
+        )
+    }
+}
@@ -0,0 +1,5 @@
+use cosmos_sdk_proto::ibc::lightclients::solomachine::v2::Misbehaviour;
+
+const TYPE_URL: &str = "/ibc.lightclients.solomachine.v2.Misbehaviour";
+
+impl_any_conversion!(Misbehaviour, TYPE_URL);
@@ -1,3 +1,4 @@
 pub mod client_state;
 pub mod consensus_state;
 pub mod header;
+pub mod misbehaviour;
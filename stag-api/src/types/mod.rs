@@ -6,6 +6,7 @@ pub mod proto_util;
 
 pub mod chain_state;
 pub mod cosmos;
+pub mod event_record;
 pub mod ibc_data;
 pub mod ics;
 pub mod operation;
@@ -16,3 +16,12 @@ pub mod ethermint {
         }
     }
 }
+
+#[cfg(feature = "remote-signer")]
+pub mod stag {
+    pub mod signer {
+        pub mod v1 {
+            tonic::include_proto!("stag.signer.v1");
+        }
+    }
+}
@@ -1,10 +1,21 @@
 use std::{fmt, str::FromStr};
 
-use anyhow::{anyhow, Context, Error, Result};
+use anyhow::{anyhow, bail, ensure, Context, Error, Result};
 use bech32::{ToBase32, Variant};
-use k256::ecdsa::VerifyingKey;
+use cosmos_sdk_proto::cosmos::tx::signing::v1beta1::signature_descriptor::{
+    data::Sum as SignatureDataInner, Data as SignatureData,
+};
+use ed25519_dalek::PublicKey as Ed25519PublicKey;
 #[cfg(feature = "ethermint")]
-use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::ecdsa::signature::DigestVerifier;
+use k256::{
+    ecdsa::{
+        signature::{Signature as _, Verifier},
+        VerifyingKey,
+    },
+    elliptic_curve::sec1::ToEncodedPoint,
+};
+use p256::ecdsa::VerifyingKey as Secp256r1VerifyingKey;
 use prost::Message;
 use prost_types::Any;
 use ripemd::Ripemd160;
@@ -16,11 +27,18 @@ use sha3::Keccak256;
 #[cfg(feature = "ethermint")]
 use crate::types::cosmos::crypto::{EthSecp256k1PubKey, ETH_SECP256K1_PUB_KEY_TYPE_URL};
 use crate::types::{
-    cosmos::crypto::{Secp256k1PubKey, SECP256K1_PUB_KEY_TYPE_URL},
+    cosmos::crypto::{
+        Ed25519PubKey, LegacyAminoPubKey, Secp256k1PubKey, Secp256r1PubKey,
+        ED25519_PUB_KEY_TYPE_URL, LEGACY_AMINO_PUB_KEY_TYPE_URL, SECP256K1_PUB_KEY_TYPE_URL,
+        SECP256R1_PUB_KEY_TYPE_URL,
+    },
     proto_util::AnyConvert,
 };
 
-use super::cosmos::crypto::{from_verifying_key, into_verifying_key};
+use super::cosmos::crypto::{
+    from_ed25519_public_key, from_secp256r1_verifying_key, from_verifying_key,
+    into_ed25519_public_key, into_secp256r1_verifying_key, into_verifying_key,
+};
 
 /// Supported algorithms for address generation
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -30,6 +48,10 @@ pub enum PublicKeyAlgo {
     #[cfg(feature = "ethermint")]
     /// EthSecp256k1 (ethermint)
     EthSecp256k1,
+    /// Ed25519
+    Ed25519,
+    /// Secp256r1 (NIST P-256)
+    Secp256r1,
 }
 
 impl fmt::Display for PublicKeyAlgo {
@@ -38,6 +60,8 @@ impl fmt::Display for PublicKeyAlgo {
             Self::Secp256k1 => write!(f, "secp256k1"),
             #[cfg(feature = "ethermint")]
             Self::EthSecp256k1 => write!(f, "eth-secp256k1"),
+            Self::Ed25519 => write!(f, "ed25519"),
+            Self::Secp256r1 => write!(f, "secp256r1"),
         }
     }
 }
@@ -50,6 +74,8 @@ impl FromStr for PublicKeyAlgo {
             "secp256k1" => Ok(Self::Secp256k1),
             #[cfg(feature = "ethermint")]
             "eth-secp256k1" => Ok(Self::EthSecp256k1),
+            "ed25519" => Ok(Self::Ed25519),
+            "secp256r1" => Ok(Self::Secp256r1),
             _ => Err(anyhow!("invalid address generation algorithm: {}", s)),
         }
     }
@@ -64,20 +90,107 @@ pub enum PublicKey {
     #[cfg(feature = "ethermint")]
     /// EthSecp256k1 (ethermint)
     EthSecp256k1(VerifyingKey),
+    /// Ed25519
+    Ed25519(Ed25519PublicKey),
+    /// Secp256r1 (NIST P-256)
+    Secp256r1(Secp256r1VerifyingKey),
+    /// Threshold multisig (legacy amino)
+    Multisig(MultisigPublicKey),
+}
+
+/// Threshold multisig public key (signatures of at least `threshold` public keys are required)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultisigPublicKey {
+    threshold: u32,
+    public_keys: Vec<PublicKey>,
+}
+
+impl MultisigPublicKey {
+    /// Creates a new instance of multisig public key (nested multisig and secp256r1 public keys are not supported)
+    pub fn new(threshold: u32, public_keys: Vec<PublicKey>) -> Result<Self> {
+        ensure!(
+            threshold > 0,
+            "multisig threshold must be greater than zero"
+        );
+        ensure!(
+            threshold as usize <= public_keys.len(),
+            "multisig threshold ({}) must not be greater than the number of public keys ({})",
+            threshold,
+            public_keys.len()
+        );
+        ensure!(
+            public_keys
+                .iter()
+                .all(|public_key| !matches!(public_key, PublicKey::Multisig(_))),
+            "nested multisig public keys are not supported"
+        );
+        ensure!(
+            public_keys
+                .iter()
+                .all(|public_key| !matches!(public_key, PublicKey::Secp256r1(_))),
+            "secp256r1 public keys are not supported in multisig public keys"
+        );
+
+        Ok(Self {
+            threshold,
+            public_keys,
+        })
+    }
+
+    /// Returns the number of signatures required
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    /// Returns all the public keys of multisig (in order)
+    pub fn public_keys(&self) -> &[PublicKey] {
+        &self.public_keys
+    }
+
+    /// Returns amino encoded bytes of multisig public key (used for address generation)
+    fn amino_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = amino_prefix("tendermint/PubKeyMultisigThreshold").to_vec();
+
+        bytes.push(0x08);
+        encode_uvarint(self.threshold.into(), &mut bytes);
+
+        for public_key in self.public_keys.iter() {
+            let public_key_bytes = public_key.amino_bytes()?;
+
+            bytes.push(0x12);
+            encode_uvarint(public_key_bytes.len() as u64, &mut bytes);
+            bytes.extend_from_slice(&public_key_bytes);
+        }
+
+        Ok(bytes)
+    }
 }
 
 impl PublicKey {
+    /// Creates a new instance of PublicKey from hex encoded bytes (SEC1 bytes for ECDSA public keys and raw bytes for
+    /// ed25519 public keys)
     pub fn new(public_key: String, algo: PublicKeyAlgo) -> Result<Self> {
         let public_key_bytes =
             hex::decode(public_key).context("unable to decode new public key")?;
 
-        let verifying_key = VerifyingKey::from_sec1_bytes(&public_key_bytes)
-            .context("failed to decode sec1 bytes of new public key")?;
-
         match algo {
-            PublicKeyAlgo::Secp256k1 => Ok(Self::Secp256k1(verifying_key)),
+            PublicKeyAlgo::Secp256k1 => Ok(Self::Secp256k1(
+                VerifyingKey::from_sec1_bytes(&public_key_bytes)
+                    .context("failed to decode sec1 bytes of new public key")?,
+            )),
             #[cfg(feature = "ethermint")]
-            PublicKeyAlgo::EthSecp256k1 => Ok(Self::EthSecp256k1(verifying_key)),
+            PublicKeyAlgo::EthSecp256k1 => Ok(Self::EthSecp256k1(
+                VerifyingKey::from_sec1_bytes(&public_key_bytes)
+                    .context("failed to decode sec1 bytes of new public key")?,
+            )),
+            PublicKeyAlgo::Ed25519 => Ok(Self::Ed25519(
+                Ed25519PublicKey::from_bytes(&public_key_bytes)
+                    .context("failed to decode ed25519 bytes of new public key")?,
+            )),
+            PublicKeyAlgo::Secp256r1 => Ok(Self::Secp256r1(
+                Secp256r1VerifyingKey::from_sec1_bytes(&public_key_bytes)
+                    .context("failed to decode sec1 bytes of new public key")?,
+            )),
         }
     }
 
@@ -92,12 +205,53 @@ impl PublicKey {
         Self::EthSecp256k1(key)
     }
 
-    /// Returns the public key algorithm
-    pub fn algo(&self) -> PublicKeyAlgo {
+    /// Creates a new instance of PublicKey from an ed25519 public key
+    pub fn new_ed25519(key: Ed25519PublicKey) -> Self {
+        Self::Ed25519(key)
+    }
+
+    /// Creates a new instance of PublicKey from a secp256r1 verifying key
+    pub fn new_secp256r1(key: Secp256r1VerifyingKey) -> Self {
+        Self::Secp256r1(key)
+    }
+
+    /// Creates a new instance of threshold multisig PublicKey
+    pub fn new_multisig(threshold: u32, public_keys: Vec<PublicKey>) -> Result<Self> {
+        MultisigPublicKey::new(threshold, public_keys).map(Self::Multisig)
+    }
+
+    /// Returns the public key algorithm (`None` for multisig public keys, which do not have a single algorithm)
+    pub fn algo(&self) -> Option<PublicKeyAlgo> {
         match self {
-            Self::Secp256k1(_) => PublicKeyAlgo::Secp256k1,
+            Self::Secp256k1(_) => Some(PublicKeyAlgo::Secp256k1),
             #[cfg(feature = "ethermint")]
-            Self::EthSecp256k1(_) => PublicKeyAlgo::EthSecp256k1,
+            Self::EthSecp256k1(_) => Some(PublicKeyAlgo::EthSecp256k1),
+            Self::Ed25519(_) => Some(PublicKeyAlgo::Ed25519),
+            Self::Secp256r1(_) => Some(PublicKeyAlgo::Secp256r1),
+            Self::Multisig(_) => None,
+        }
+    }
+
+    /// Returns the secp256k1 verifying key of public key (`None` for public keys using other algorithms)
+    pub fn verifying_key(&self) -> Option<&VerifyingKey> {
+        match self {
+            Self::Secp256k1(key) => Some(key),
+            #[cfg(feature = "ethermint")]
+            Self::EthSecp256k1(key) => Some(key),
+            Self::Ed25519(_) | Self::Secp256r1(_) | Self::Multisig(_) => None,
+        }
+    }
+
+    /// Returns the encoded bytes of public key (compressed SEC1 bytes for ECDSA public keys and raw bytes for ed25519
+    /// public keys)
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        match self {
+            Self::Secp256k1(ref key) => Ok(key.to_bytes().to_vec()),
+            #[cfg(feature = "ethermint")]
+            Self::EthSecp256k1(ref key) => Ok(key.to_bytes().to_vec()),
+            Self::Ed25519(ref key) => Ok(key.as_bytes().to_vec()),
+            Self::Secp256r1(ref key) => Ok(key.to_encoded_point(true).as_bytes().to_vec()),
+            Self::Multisig(_) => Err(anyhow!("multisig public key cannot be encoded as bytes")),
         }
     }
 
@@ -126,23 +280,157 @@ impl PublicKey {
             Self::Secp256k1(ref key) => {
                 Ok(Ripemd160::digest(&Sha256::digest(&key.to_bytes())).to_vec())
             }
+            Self::Ed25519(ref key) => Ok(Sha256::digest(key.as_bytes())[..20].to_vec()),
+            Self::Secp256r1(_) => {
+                // Cosmos SDK uses ADR-28 address derivation for secp256r1 public keys
+                let type_hash = Sha256::digest(b"secp256r1");
+
+                let mut hasher = Sha256::new();
+                hasher.update(type_hash);
+                hasher.update(self.to_bytes()?);
+
+                Ok(hasher.finalize().to_vec())
+            }
+            Self::Multisig(ref key) => Ok(Sha256::digest(&key.amino_bytes()?)[..20].to_vec()),
         }
     }
-}
 
-impl AsRef<VerifyingKey> for PublicKey {
-    fn as_ref(&self) -> &VerifyingKey {
+    /// Verifies signature of given message (multisig public keys can only verify signature data)
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
         match self {
-            Self::Secp256k1(key) => key,
+            Self::Secp256k1(ref key) => {
+                let signature = k256::ecdsa::Signature::from_bytes(signature)
+                    .context("invalid secp256k1 signature")?;
+                key.verify(message, &signature)
+                    .context("secp256k1 signature verification failed")
+            }
             #[cfg(feature = "ethermint")]
-            Self::EthSecp256k1(key) => key,
+            Self::EthSecp256k1(ref key) => {
+                let signature = k256::ecdsa::Signature::from_bytes(signature)
+                    .context("invalid eth-secp256k1 signature")?;
+                key.verify_digest(Keccak256::new_with_prefix(message), &signature)
+                    .context("eth-secp256k1 signature verification failed")
+            }
+            Self::Ed25519(ref key) => {
+                let signature = ed25519_dalek::Signature::from_bytes(signature)
+                    .context("invalid ed25519 signature")?;
+                key.verify(message, &signature)
+                    .context("ed25519 signature verification failed")
+            }
+            Self::Secp256r1(ref key) => {
+                let signature = p256::ecdsa::Signature::from_bytes(signature)
+                    .context("invalid secp256r1 signature")?;
+                key.verify(message, &signature)
+                    .context("secp256r1 signature verification failed")
+            }
+            Self::Multisig(_) => bail!("multisig public key can only verify multi signature data"),
+        }
+    }
+
+    /// Verifies signature data of given message (multi signature data must contain signatures of at least threshold
+    /// public keys of a multisig public key, each of which signs the same message)
+    pub fn verify_signature_data(
+        &self,
+        message: &[u8],
+        signature_data: &SignatureData,
+    ) -> Result<()> {
+        match (self, signature_data.sum.as_ref()) {
+            (Self::Multisig(ref key), Some(SignatureDataInner::Multi(multi))) => {
+                let bitarray = multi
+                    .bitarray
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("multi signature data does not contain bitarray"))?;
+
+                let bits = match bitarray.extra_bits_stored {
+                    0 => bitarray.elems.len() * 8,
+                    extra_bits => {
+                        (bitarray.elems.len().saturating_sub(1) * 8) + extra_bits as usize
+                    }
+                };
+
+                ensure!(
+                    bits == key.public_keys.len(),
+                    "bitarray of multi signature data does not match number of public keys of multisig"
+                );
+
+                let signers = (0..bits)
+                    .filter(|index| bitarray.elems[index / 8] & (1 << (7 - (index % 8))) != 0)
+                    .collect::<Vec<_>>();
+
+                ensure!(
+                    signers.len() == multi.signatures.len(),
+                    "number of signatures does not match bitarray of multi signature data"
+                );
+                ensure!(
+                    signers.len() >= key.threshold as usize,
+                    "multi signature data contains only {} of {} required signatures",
+                    signers.len(),
+                    key.threshold
+                );
+
+                for (index, signature) in signers.into_iter().zip(multi.signatures.iter()) {
+                    key.public_keys[index].verify_signature_data(message, signature)?;
+                }
+
+                Ok(())
+            }
+            (Self::Multisig(_), _) => {
+                bail!("expected multi signature data for multisig public key")
+            }
+            (_, Some(SignatureDataInner::Single(single))) => {
+                self.verify(message, &single.signature)
+            }
+            _ => bail!("expected single signature data for public key"),
         }
     }
+
+    /// Returns amino encoded bytes of public key
+    fn amino_bytes(&self) -> Result<Vec<u8>> {
+        let name = match self {
+            Self::Secp256k1(_) => "tendermint/PubKeySecp256k1",
+            #[cfg(feature = "ethermint")]
+            Self::EthSecp256k1(_) => "ethermint/PubKeyEthSecp256k1",
+            Self::Ed25519(_) => "tendermint/PubKeyEd25519",
+            Self::Secp256r1(_) => bail!("secp256r1 public keys do not support amino encoding"),
+            Self::Multisig(ref key) => return key.amino_bytes(),
+        };
+
+        let key_bytes = self.to_bytes()?;
+
+        let mut bytes = amino_prefix(name).to_vec();
+        encode_uvarint(key_bytes.len() as u64, &mut bytes);
+        bytes.extend_from_slice(&key_bytes);
+
+        Ok(bytes)
+    }
 }
 
 impl fmt::Display for PublicKey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", hex::encode_upper(self.as_ref().to_bytes()))
+        match self {
+            Self::Secp256k1(ref key) => write!(f, "{}", hex::encode_upper(key.to_bytes())),
+            #[cfg(feature = "ethermint")]
+            Self::EthSecp256k1(ref key) => write!(f, "{}", hex::encode_upper(key.to_bytes())),
+            Self::Ed25519(ref key) => write!(f, "{}", hex::encode_upper(key.as_bytes())),
+            Self::Secp256r1(ref key) => write!(
+                f,
+                "{}",
+                hex::encode_upper(key.to_encoded_point(true).as_bytes())
+            ),
+            Self::Multisig(ref key) => {
+                write!(f, "multisig({}/{})[", key.threshold, key.public_keys.len())?;
+
+                for (i, public_key) in key.public_keys.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+
+                    write!(f, "{}", public_key)?;
+                }
+
+                write!(f, "]")
+            }
+        }
     }
 }
 
@@ -159,6 +447,27 @@ impl AnyConvert for PublicKey {
                 let public_key: Secp256k1PubKey = Secp256k1PubKey::decode(value.value.as_slice())?;
                 Ok(Self::Secp256k1(into_verifying_key(&public_key)?))
             }
+            ED25519_PUB_KEY_TYPE_URL => {
+                let public_key: Ed25519PubKey = Ed25519PubKey::decode(value.value.as_slice())?;
+                Ok(Self::Ed25519(into_ed25519_public_key(&public_key)?))
+            }
+            SECP256R1_PUB_KEY_TYPE_URL => {
+                let public_key: Secp256r1PubKey = Secp256r1PubKey::decode(value.value.as_slice())?;
+                Ok(Self::Secp256r1(into_secp256r1_verifying_key(&public_key)?))
+            }
+            LEGACY_AMINO_PUB_KEY_TYPE_URL => {
+                let public_key: LegacyAminoPubKey =
+                    LegacyAminoPubKey::decode(value.value.as_slice())?;
+
+                Self::new_multisig(
+                    public_key.threshold,
+                    public_key
+                        .public_keys
+                        .iter()
+                        .map(Self::from_any)
+                        .collect::<Result<_>>()?,
+                )
+            }
             other => Err(anyhow!("unknown type url for `Any` type: `{}`", other)),
         }
     }
@@ -174,10 +483,58 @@ impl AnyConvert for PublicKey {
                 let public_key: Secp256k1PubKey = from_verifying_key(key);
                 public_key.to_any()
             }
+            Self::Ed25519(ref key) => {
+                let public_key: Ed25519PubKey = from_ed25519_public_key(key);
+                public_key.to_any()
+            }
+            Self::Secp256r1(ref key) => {
+                let public_key: Secp256r1PubKey = from_secp256r1_verifying_key(key);
+                public_key.to_any()
+            }
+            Self::Multisig(ref key) => {
+                let public_key = LegacyAminoPubKey {
+                    threshold: key.threshold,
+                    public_keys: key
+                        .public_keys
+                        .iter()
+                        .map(AnyConvert::to_any)
+                        .collect::<Result<_>>()?,
+                };
+                public_key.to_any()
+            }
         }
     }
 }
 
+/// Computes amino prefix bytes for given registered amino type name
+fn amino_prefix(name: &str) -> [u8; 4] {
+    let hash = Sha256::digest(name.as_bytes());
+
+    // Skip leading zero bytes and 3 disambiguation bytes, then skip leading zero bytes again
+    let mut bytes = hash
+        .iter()
+        .skip_while(|byte| **byte == 0)
+        .skip(3)
+        .skip_while(|byte| **byte == 0);
+
+    let mut prefix = [0; 4];
+    for byte in prefix.iter_mut() {
+        *byte = *bytes.next().expect("sha256 hash has enough bytes");
+    }
+
+    prefix
+}
+
+/// Encodes given value as unsigned varint
+fn encode_uvarint(mut value: u64, bytes: &mut Vec<u8>) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+
+    bytes.push(value as u8);
+}
+
 // fn serialize_verifying_key<S>(key: &VerifyingKey, serializer: S) -> Result<S::Ok, S::Error>
 // where
 //     S: Serializer,
@@ -192,3 +549,101 @@ impl AnyConvert for PublicKey {
 //     let bytes: Vec<u8> = hex::deserialize(deserializer)?;
 //     VerifyingKey::from_sec1_bytes(&bytes).map_err(serde::de::Error::custom)
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBLIC_KEYS: [&str; 2] = [
+        "02A1633CAFCC01EBFB6D78E39F687A1F0995C62FC95F51EAD10A02EE0BE551B5DC",
+        "03B8D4B3A5A1F1C5D7A2D8D1A24F5E7DAB2F0E1C4E5C8D7A3B2F1E0D9C8B7A6F5E",
+    ];
+
+    fn public_keys() -> Vec<PublicKey> {
+        PUBLIC_KEYS
+            .iter()
+            .map(|key| PublicKey::new(key.to_string(), PublicKeyAlgo::Secp256k1).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_amino_prefix() {
+        assert_eq!(
+            amino_prefix("tendermint/PubKeySecp256k1"),
+            [0xEB, 0x5A, 0xE9, 0x87]
+        );
+        assert_eq!(
+            amino_prefix("tendermint/PubKeyMultisigThreshold"),
+            [0x22, 0xC1, 0xF7, 0xE2]
+        );
+    }
+
+    #[test]
+    fn test_multisig_public_key() {
+        let public_key = PublicKey::new_multisig(2, public_keys()).unwrap();
+        assert_eq!(public_key.algo(), None);
+        assert!(public_key.verifying_key().is_none());
+        assert!(public_key.account_address("cosmos").is_ok());
+
+        let any = public_key.to_any().unwrap();
+        assert_eq!(any.type_url, LEGACY_AMINO_PUB_KEY_TYPE_URL);
+        assert_eq!(
+            PublicKey::from_any(&any).unwrap().to_string(),
+            public_key.to_string()
+        );
+    }
+
+    #[test]
+    fn test_invalid_multisig_public_key() {
+        assert!(PublicKey::new_multisig(0, public_keys()).is_err());
+        assert!(PublicKey::new_multisig(3, public_keys()).is_err());
+
+        let multisig = PublicKey::new_multisig(1, public_keys()).unwrap();
+        assert!(PublicKey::new_multisig(1, vec![multisig]).is_err());
+    }
+
+    #[test]
+    fn test_ed25519_public_key() {
+        let public_key = PublicKey::new(
+            "D75A980182B10AB7D54BFED3C964073A0EE172F3DAA62325AF021A68F707511A".to_string(),
+            PublicKeyAlgo::Ed25519,
+        )
+        .unwrap();
+        assert_eq!(public_key.algo(), Some(PublicKeyAlgo::Ed25519));
+        assert_eq!(public_key.address().unwrap().len(), 40);
+
+        let any = public_key.to_any().unwrap();
+        assert_eq!(any.type_url, ED25519_PUB_KEY_TYPE_URL);
+        assert_eq!(
+            PublicKey::from_any(&any).unwrap().to_string(),
+            public_key.to_string()
+        );
+
+        // Ed25519 public keys can be a part of multisig public key
+        assert!(PublicKey::new_multisig(1, vec![public_key])
+            .unwrap()
+            .address()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_secp256r1_public_key() {
+        let public_key = PublicKey::new(
+            "036B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296".to_string(),
+            PublicKeyAlgo::Secp256r1,
+        )
+        .unwrap();
+        assert_eq!(public_key.algo(), Some(PublicKeyAlgo::Secp256r1));
+        assert_eq!(public_key.address().unwrap().len(), 64);
+
+        let any = public_key.to_any().unwrap();
+        assert_eq!(any.type_url, SECP256R1_PUB_KEY_TYPE_URL);
+        assert_eq!(
+            PublicKey::from_any(&any).unwrap().to_string(),
+            public_key.to_string()
+        );
+
+        // Secp256r1 public keys do not have amino encoding
+        assert!(PublicKey::new_multisig(1, vec![public_key]).is_err());
+    }
+}
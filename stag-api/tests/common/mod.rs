@@ -72,6 +72,9 @@ pub async fn get_chain_config() -> Result<ChainConfig> {
     Ok(ChainConfig {
         grpc_addr: Url::parse(get_grpc_addr()).unwrap(),
         rpc_addr: Url::parse("http://127.0.0.1:26657").unwrap(),
+        fallback_grpc_addrs: Vec::new(),
+        fallback_rpc_addrs: Vec::new(),
+        witness_rpc_addrs: Vec::new(),
         fee: Fee {
             amount: "1000".parse().unwrap(),
             denom: "stake".parse().unwrap(),
@@ -85,6 +88,7 @@ pub async fn get_chain_config() -> Result<ChainConfig> {
         trusted_height: 1,
         trusted_hash: get_trusted_hash().await?,
         packet_timeout_height_offset: 10,
+        retry_policy: Default::default(),
     })
 }
 
@@ -1,17 +1,24 @@
-use std::{
-    path::{Path, PathBuf},
-    time::Duration,
-};
+use std::{path::PathBuf, time::Duration};
 
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
 use clap::{Subcommand, ValueEnum};
-use stag_api::types::{
-    chain_state::{ChainConfig, Fee},
-    ics::core::ics24_host::identifier::{ChainId, PortId},
-    public_key::{PublicKey, PublicKeyAlgo},
+use stag_api::{
+    event::EventHandlerConfig as _,
+    service::KeyRotationStatus,
+    signer::{keystore::Keystore, SignerConfig as _, Zeroizing},
+    types::{
+        chain_state::{ChainConfig, Fee},
+        ics::core::ics24_host::identifier::{ChainId, PortId},
+        public_key::{PublicKey, PublicKeyAlgo},
+    },
 };
 
-use crate::util::stag;
+use crate::{
+    signer_config::{SignerConfig, SignerSource},
+    util::{stag, stag_with_storage, StagOptions},
+};
+
+use super::query_command::EventQueryArgs;
 
 #[derive(Debug, Subcommand)]
 pub enum CoreCommand {
@@ -45,16 +52,58 @@ pub enum CoreCommand {
         /// An optional request ID for tracking purposes
         #[clap(long)]
         request_id: Option<String>,
-        /// Hex encoded SEC1 bytes of public key
-        #[clap(short, long)]
-        new_public_key: String,
+        /// Hex encoded SEC1 bytes of public key or raw bytes for ed25519 public keys (can be repeated along with
+        /// `--multisig-threshold` to rotate to a multisig public key)
+        #[clap(short, long, required = true)]
+        new_public_key: Vec<String>,
         /// Algorithm used for address generation
         #[clap(short, long, default_value = "secp256k1")]
         algo: PublicKeyAlgo,
+        /// Number of signatures required for multisig public key composed of all the new public keys (in the given
+        /// order)
+        #[clap(long)]
+        multisig_threshold: Option<u32>,
+        /// Memo value to be used in cosmos sdk transaction
+        #[clap(long)]
+        memo: Option<String>,
+    },
+    /// Rotates signer key on all the connected chains to a new mnemonic (prompted from terminal unless `--generate` is
+    /// provided) and switches signer config (or keystore) to new key for the chains on which solo machine client was
+    /// updated
+    RotateKey {
+        /// An optional request ID for tracking purposes
+        #[clap(long)]
+        request_id: Option<String>,
+        /// HD path of new key (defaults to current HD path of each chain)
+        #[clap(long)]
+        hd_path: Option<String>,
+        /// Algorithm used for address generation (defaults to current algorithm of each chain)
+        #[clap(short, long)]
+        algo: Option<PublicKeyAlgo>,
+        /// Generates a new mnemonic instead of prompting for it
+        #[clap(long)]
+        generate: bool,
+        /// Memo value to be used in cosmos sdk transaction
+        #[clap(long)]
+        memo: Option<String>,
+    },
+    /// Freezes solo machine client on given chain by submitting misbehaviour (should only be used in emergencies, e.g.,
+    /// when signer's key is compromised, as a frozen client cannot be used anymore)
+    FreezeClient {
+        /// Chain ID
+        chain_id: ChainId,
+        /// An optional request ID for tracking purposes
+        #[clap(long)]
+        request_id: Option<String>,
         /// Memo value to be used in cosmos sdk transaction
         #[clap(long)]
         memo: Option<String>,
     },
+    /// Re-sends events stored by `storage` event handler to webhook URLs (e.g., after a webhook outage)
+    ReplayEvents {
+        #[clap(flatten)]
+        query: EventQueryArgs,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -99,7 +148,7 @@ pub enum ChannelType {
 }
 
 impl CoreCommand {
-    pub async fn run(self, signer: impl AsRef<Path>, db_uri: &str) -> Result<()> {
+    pub async fn run(self, signer: &SignerSource, options: &StagOptions) -> Result<()> {
         match self {
             Self::SampleChainConfig => {
                 let chain_config = get_sample_config();
@@ -117,7 +166,7 @@ impl CoreCommand {
                 let chain_config: ChainConfig = serde_yaml::from_slice(&chain_config_bytes)
                     .context(format!("failed to deserialize {}", config.display()))?;
 
-                let chain_id = stag(signer, db_uri)
+                let chain_id = stag(signer, options)
                     .await?
                     .add_chain(&chain_config)
                     .await
@@ -133,7 +182,7 @@ impl CoreCommand {
                 memo,
                 force,
             } => {
-                stag(signer, db_uri)
+                stag(signer, options)
                     .await?
                     .connect(
                         chain_id.clone(),
@@ -147,17 +196,32 @@ impl CoreCommand {
 
                 Ok(())
             }
-            Self::Channel { subcommand } => subcommand.run(signer, db_uri).await,
+            Self::Channel { subcommand } => subcommand.run(signer, options).await,
             Self::UpdateSigner {
                 chain_id,
                 request_id,
                 new_public_key,
                 algo,
+                multisig_threshold,
                 memo,
             } => {
-                let new_public_key = PublicKey::new(new_public_key, algo)?;
+                let mut public_keys = new_public_key
+                    .into_iter()
+                    .map(|public_key| PublicKey::new(public_key, algo))
+                    .collect::<Result<Vec<_>>>()?;
+
+                let new_public_key = match multisig_threshold {
+                    Some(threshold) => PublicKey::new_multisig(threshold, public_keys)?,
+                    None => {
+                        ensure!(
+                            public_keys.len() == 1,
+                            "multiple public keys can only be provided with `--multisig-threshold`"
+                        );
+                        public_keys.remove(0)
+                    }
+                };
 
-                stag(signer, db_uri)
+                stag(signer, options)
                     .await?
                     .update_signer(
                         chain_id.clone(),
@@ -169,6 +233,111 @@ impl CoreCommand {
 
                 println!("successfully updated signer for {}", chain_id);
 
+                Ok(())
+            }
+            Self::RotateKey {
+                request_id,
+                hd_path,
+                algo,
+                generate,
+                memo,
+            } => {
+                let mnemonic = if generate {
+                    Keystore::generate_mnemonic()
+                } else {
+                    Zeroizing::new(
+                        rpassword::prompt_password("New mnemonic: ")
+                            .context("failed to read mnemonic")?
+                            .trim()
+                            .to_owned(),
+                    )
+                };
+
+                let chain_configs = signer
+                    .rotated_chain_configs(&mnemonic, hd_path.as_deref(), algo)
+                    .await?;
+
+                // Generated mnemonic is shown before broadcasting so that the new key is never lost (even when
+                // rotation fails midway or the signer config cannot be updated afterwards)
+                if generate {
+                    println!("write down the following mnemonic and keep it safe (it will not be shown again):");
+                    println!();
+                    println!("{}", mnemonic.as_str());
+                    println!();
+                }
+
+                let new_signer = SignerConfig {
+                    chains: chain_configs.clone(),
+                }
+                .get_mnemonic_signer()?
+                .into_signer()?;
+
+                let report = stag(signer, options)
+                    .await?
+                    .rotate_key(&new_signer, request_id, memo.unwrap_or_default())
+                    .await?;
+
+                let updated_chain_configs = chain_configs
+                    .into_iter()
+                    .filter(|chain_config| report.should_switch(&chain_config.chain_id))
+                    .collect::<Vec<_>>();
+
+                signer
+                    .apply_rotated_chain_configs(&updated_chain_configs)
+                    .await
+                    .context("key is rotated on chains but failed to update signer config")?;
+
+                for chain in report.chains.iter() {
+                    match chain.status {
+                        KeyRotationStatus::Rotated => {
+                            println!("{}: rotated", chain.chain_id)
+                        }
+                        KeyRotationStatus::Unverified(ref err) => {
+                            println!("{}: rotated but not verified: {}", chain.chain_id, err)
+                        }
+                        KeyRotationStatus::Failed(ref err) => {
+                            println!("{}: failed: {}", chain.chain_id, err)
+                        }
+                    }
+                }
+
+                ensure!(
+                    report.is_complete(),
+                    "key rotation failed on some of the chains (signer config is updated only for rotated chains)"
+                );
+
+                Ok(())
+            }
+            Self::FreezeClient {
+                chain_id,
+                request_id,
+                memo,
+            } => {
+                stag(signer, options)
+                    .await?
+                    .freeze_client(chain_id.clone(), request_id, memo.unwrap_or_default())
+                    .await?;
+
+                println!("successfully froze solo machine client on {}", chain_id);
+
+                Ok(())
+            }
+            Self::ReplayEvents { query } => {
+                let storage = options.storage();
+                let webhook = options
+                    .webhook_event_handler(&storage)?
+                    .into_event_handler();
+
+                let last_id = stag_with_storage(signer, options, storage)
+                    .await?
+                    .replay_events(&query.into(), &webhook)
+                    .await?;
+
+                match last_id {
+                    None => println!("no events to replay"),
+                    Some(last_id) => println!("successfully replayed events up to ID {}", last_id),
+                }
+
                 Ok(())
             }
         }
@@ -176,7 +345,7 @@ impl CoreCommand {
 }
 
 impl ChannelCommand {
-    pub async fn run(self, signer: impl AsRef<Path>, db_uri: &str) -> Result<()> {
+    pub async fn run(self, signer: &SignerSource, options: &StagOptions) -> Result<()> {
         match self {
             Self::Create {
                 channel_type,
@@ -185,7 +354,7 @@ impl ChannelCommand {
                 memo,
             } => match channel_type {
                 ChannelType::Transfer => {
-                    stag(signer, db_uri)
+                    stag(signer, options)
                         .await?
                         .create_transfer_channel(
                             chain_id.clone(),
@@ -199,7 +368,7 @@ impl ChannelCommand {
                     Ok(())
                 }
                 ChannelType::Ica => {
-                    stag(signer, db_uri)
+                    stag(signer, options)
                         .await?
                         .create_ica_channel(chain_id.clone(), request_id, memo.unwrap_or_default())
                         .await?;
@@ -220,7 +389,7 @@ impl ChannelCommand {
                     ChannelType::Ica => PortId::ica_controller(),
                 };
 
-                stag(signer, db_uri)
+                stag(signer, options)
                     .await?
                     .close_channel(
                         chain_id.clone(),
@@ -245,6 +414,9 @@ fn get_sample_config() -> ChainConfig {
     ChainConfig {
         grpc_addr: "http://0.0.0.0:9090".parse().unwrap(),
         rpc_addr: "http://0.0.0.0:26657".parse().unwrap(),
+        fallback_grpc_addrs: Vec::new(),
+        fallback_rpc_addrs: Vec::new(),
+        witness_rpc_addrs: Vec::new(),
         fee: Fee {
             amount: "1000".parse().unwrap(),
             denom: "stake".parse().unwrap(),
@@ -258,5 +430,6 @@ fn get_sample_config() -> ChainConfig {
         trusted_height: 1,
         trusted_hash: [0; 32],
         packet_timeout_height_offset: 20,
+        retry_policy: Default::default(),
     }
 }
@@ -1,11 +1,13 @@
-use std::path::Path;
-
 use anyhow::{Context, Result};
 use clap::Subcommand;
 use primitive_types::U256;
 use stag_api::types::ics::core::ics24_host::identifier::{ChainId, Identifier, PortId};
 
-use crate::{u256::U256Parser, util::stag};
+use crate::{
+    signer_config::SignerSource,
+    u256::U256Parser,
+    util::{stag, StagOptions},
+};
 
 #[derive(Debug, Subcommand)]
 pub enum BankCommand {
@@ -33,7 +35,7 @@ pub enum BankCommand {
 }
 
 impl BankCommand {
-    pub async fn run(self, signer: impl AsRef<Path>, db_uri: &str) -> Result<()> {
+    pub async fn run(self, signer: &SignerSource, options: &StagOptions) -> Result<()> {
         match self {
             Self::Send {
                 chain_id,
@@ -44,7 +46,7 @@ impl BankCommand {
                 ibc_denom,
                 memo,
             } => {
-                let stag = stag(signer, db_uri).await?;
+                let stag = stag(signer, options).await?;
 
                 if ibc_denom {
                     denom = stag
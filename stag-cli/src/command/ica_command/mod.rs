@@ -1,11 +1,11 @@
 pub mod bank_command;
 pub mod staking_command;
 
-use std::path::Path;
-
 use anyhow::Result;
 use clap::Subcommand;
 
+use crate::{signer_config::SignerSource, util::StagOptions};
+
 use self::{bank_command::BankCommand, staking_command::StakingCommand};
 
 #[derive(Debug, Subcommand)]
@@ -23,10 +23,10 @@ pub enum IcaCommand {
 }
 
 impl IcaCommand {
-    pub async fn run(self, signer: impl AsRef<Path>, db_uri: &str) -> Result<()> {
+    pub async fn run(self, signer: &SignerSource, options: &StagOptions) -> Result<()> {
         match self {
-            Self::Bank { subcommand } => subcommand.run(signer, db_uri).await,
-            Self::Staking { subcommand } => subcommand.run(signer, db_uri).await,
+            Self::Bank { subcommand } => subcommand.run(signer, options).await,
+            Self::Staking { subcommand } => subcommand.run(signer, options).await,
         }
     }
 }
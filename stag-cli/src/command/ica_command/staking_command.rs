@@ -1,11 +1,13 @@
-use std::path::Path;
-
 use anyhow::Result;
 use clap::Subcommand;
 use primitive_types::U256;
 use stag_api::types::ics::core::ics24_host::identifier::{ChainId, Identifier};
 
-use crate::{u256::U256Parser, util::stag};
+use crate::{
+    signer_config::SignerSource,
+    u256::U256Parser,
+    util::{stag, StagOptions},
+};
 
 #[derive(Debug, Subcommand)]
 pub enum StakingCommand {
@@ -48,7 +50,7 @@ pub enum StakingCommand {
 }
 
 impl StakingCommand {
-    pub async fn run(self, signer: impl AsRef<Path>, db_uri: &str) -> Result<()> {
+    pub async fn run(self, signer: &SignerSource, options: &StagOptions) -> Result<()> {
         match self {
             Self::Delegate {
                 chain_id,
@@ -58,7 +60,7 @@ impl StakingCommand {
                 denom,
                 memo,
             } => {
-                stag(signer, db_uri)
+                stag(signer, options)
                     .await?
                     .ica_delegate(
                         chain_id,
@@ -85,7 +87,7 @@ impl StakingCommand {
                 denom,
                 memo,
             } => {
-                stag(signer, db_uri)
+                stag(signer, options)
                     .await?
                     .ica_undelegate(
                         chain_id,
@@ -1,10 +1,14 @@
-use std::path::Path;
-
 use anyhow::{Context, Result};
-use clap::Subcommand;
-use stag_api::types::ics::core::ics24_host::identifier::{ChainId, Identifier, PortId};
+use clap::{Args, Subcommand};
+use stag_api::types::{
+    event_record::EventQuery,
+    ics::core::ics24_host::identifier::{ChainId, Identifier, PortId},
+};
 
-use crate::util::stag;
+use crate::{
+    signer_config::SignerSource,
+    util::{stag, StagOptions},
+};
 
 #[derive(Debug, Subcommand)]
 pub enum QueryCommand {
@@ -39,6 +43,11 @@ pub enum QueryCommand {
         /// Denom on solo machine
         denom: Identifier,
     },
+    /// Checks health of all the RPC and gRPC endpoints of given chain
+    Endpoints {
+        /// Chain ID
+        chain_id: ChainId,
+    },
     /// Fetches on-chain ICA (Interchain Account) address
     IcaAddress {
         /// Chain ID
@@ -66,10 +75,49 @@ pub enum QueryCommand {
         #[clap(long)]
         offset: Option<u32>,
     },
+    /// Fetches events stored by `storage` event handler (in increasing order of ID)
+    Events {
+        #[clap(flatten)]
+        query: EventQueryArgs,
+        /// Number of items to fetch
+        #[clap(long)]
+        limit: Option<u32>,
+        /// Offset of items to fetch
+        #[clap(long)]
+        offset: Option<u32>,
+    },
+}
+
+/// Filters for selecting stored events
+#[derive(Debug, Args)]
+pub struct EventQueryArgs {
+    /// Only select events with ID greater than or equal to given ID
+    #[clap(long)]
+    from_id: Option<i64>,
+    /// Only select events of given chain (warnings do not carry a chain ID)
+    #[clap(long)]
+    chain_id: Option<ChainId>,
+    /// Only select events of given types (e.g., `TokensMinted`; can be provided multiple times)
+    #[clap(long, use_value_delimiter = true)]
+    event_type: Vec<String>,
+    /// Only select events with given request ID
+    #[clap(long)]
+    request_id: Option<String>,
+}
+
+impl From<EventQueryArgs> for EventQuery {
+    fn from(args: EventQueryArgs) -> Self {
+        Self {
+            from_id: args.from_id,
+            chain_id: args.chain_id,
+            event_types: args.event_type,
+            request_id: args.request_id,
+        }
+    }
 }
 
 impl QueryCommand {
-    pub async fn run(self, signer: impl AsRef<Path>, db_uri: &str) -> Result<()> {
+    pub async fn run(self, signer: &SignerSource, options: &StagOptions) -> Result<()> {
         match self {
             Self::Balance {
                 chain_id,
@@ -77,12 +125,12 @@ impl QueryCommand {
                 ibc_denom,
             } => {
                 let balance = if ibc_denom {
-                    stag(signer, db_uri)
+                    stag(signer, options)
                         .await?
                         .get_ibc_balance(&chain_id, &PortId::transfer(), &denom)
                         .await
                 } else {
-                    stag(signer, db_uri)
+                    stag(signer, options)
                         .await?
                         .get_balance(&chain_id, &denom)
                         .await
@@ -93,7 +141,7 @@ impl QueryCommand {
                 Ok(())
             }
             Self::Chain { chain_id } => {
-                let chain_state = stag(signer, db_uri).await?.get_chain(&chain_id).await?;
+                let chain_state = stag(signer, options).await?.get_chain(&chain_id).await?;
 
                 match chain_state {
                     None => println!("chain with ID {} not found", chain_id),
@@ -108,7 +156,7 @@ impl QueryCommand {
                 Ok(())
             }
             Self::AllChains { limit, offset } => {
-                let chains = stag(signer, db_uri)
+                let chains = stag(signer, options)
                     .await?
                     .get_all_chains(limit, offset)
                     .await
@@ -122,7 +170,7 @@ impl QueryCommand {
                 Ok(())
             }
             Self::IbcDenom { chain_id, denom } => {
-                let denom = stag(signer, db_uri)
+                let denom = stag(signer, options)
                     .await?
                     .get_ibc_denom(&chain_id, &PortId::transfer(), &denom)
                     .await
@@ -132,8 +180,22 @@ impl QueryCommand {
 
                 Ok(())
             }
+            Self::Endpoints { chain_id } => {
+                let endpoints = stag(signer, options)
+                    .await?
+                    .check_chain_endpoints(&chain_id)
+                    .await
+                    .context("failed to check endpoints")?;
+
+                let endpoints =
+                    serde_yaml::to_string(&endpoints).context("failed to serialize endpoints")?;
+
+                println!("{endpoints}");
+
+                Ok(())
+            }
             Self::IcaAddress { chain_id } => {
-                let ica_address = stag(signer, db_uri)
+                let ica_address = stag(signer, options)
                     .await?
                     .get_ica_address(&chain_id)
                     .await
@@ -148,7 +210,7 @@ impl QueryCommand {
                 limit,
                 offset,
             } => {
-                let history = stag(signer, db_uri)
+                let history = stag(signer, options)
                     .await?
                     .get_history(&chain_id, limit, offset)
                     .await
@@ -166,7 +228,7 @@ impl QueryCommand {
                 limit,
                 offset,
             } => {
-                let public_keys = stag(signer, db_uri)
+                let public_keys = stag(signer, options)
                     .await?
                     .get_public_keys(&chain_id, limit, offset)
                     .await
@@ -177,6 +239,24 @@ impl QueryCommand {
 
                 println!("{public_keys}");
 
+                Ok(())
+            }
+            Self::Events {
+                query,
+                limit,
+                offset,
+            } => {
+                let events = stag(signer, options)
+                    .await?
+                    .get_events(&query.into(), limit, offset)
+                    .await
+                    .context("failed to fetch events")?;
+
+                let events =
+                    serde_yaml::to_string(&events).context("failed to serialize events")?;
+
+                println!("{events}");
+
                 Ok(())
             }
         }
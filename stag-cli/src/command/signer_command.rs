@@ -1,21 +1,73 @@
-use std::path::Path;
-
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::Subcommand;
+use serde::Serialize;
+use stag_api::{
+    signer::{keystore::Keystore, KeystoreSigner, SignerConfig as _, Zeroizing},
+    types::{ics::core::ics24_host::identifier::ChainId, public_key::PublicKeyAlgo},
+};
 
-use crate::signer_config::{ChainSignerConfig, SignerConfig};
+use crate::signer_config::{keystore_password, ChainSignerConfig, SignerConfig, SignerSource};
 
 #[derive(Debug, Subcommand)]
 pub enum SignerCommand {
     /// Prints sample signer configuration
     SampleConfig,
-    /// Validates signer configuration
+    /// Validates signer configuration (or keystore when `--keystore` is provided)
     ValidateConfig,
+    /// Password encrypted keystore commands
+    Keystore {
+        #[clap(subcommand)]
+        subcommand: KeystoreCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum KeystoreCommand {
+    /// Generates a new mnemonic and adds it to keystore for given chain
+    Create {
+        /// Chain ID
+        chain_id: ChainId,
+        /// HD path of signer
+        #[clap(long)]
+        hd_path: Option<String>,
+        /// Bech32 account prefix of signer
+        #[clap(long)]
+        account_prefix: Option<String>,
+        /// Algorithm used for address generation
+        #[clap(short, long)]
+        algo: Option<PublicKeyAlgo>,
+    },
+    /// Imports an existing mnemonic (prompted from terminal) to keystore for given chain
+    Import {
+        /// Chain ID
+        chain_id: ChainId,
+        /// HD path of signer
+        #[clap(long)]
+        hd_path: Option<String>,
+        /// Bech32 account prefix of signer
+        #[clap(long)]
+        account_prefix: Option<String>,
+        /// Algorithm used for address generation
+        #[clap(short, long)]
+        algo: Option<PublicKeyAlgo>,
+    },
+    /// Lists all the keys in keystore (does not require password)
+    List,
+}
+
+/// Public details of a key in keystore
+#[derive(Debug, Serialize)]
+struct KeyDetails<'a> {
+    chain_id: &'a ChainId,
+    address: &'a str,
+    hd_path: Option<&'a str>,
+    account_prefix: Option<&'a str>,
+    algo: Option<&'a str>,
 }
 
 impl SignerCommand {
     /// Runs signer command
-    pub async fn run(self, path: impl AsRef<Path>) -> Result<()> {
+    pub async fn run(self, signer: &SignerSource) -> Result<()> {
         match self {
             Self::SampleConfig => {
                 let signer_config = get_sample_config();
@@ -25,9 +77,118 @@ impl SignerCommand {
                 println!("{}", output);
                 Ok(())
             }
-            Self::ValidateConfig => {
-                SignerConfig::create_mnemonic_signer(&path).await?;
-                println!("{} is valid", path.as_ref().display());
+            Self::ValidateConfig => match signer {
+                SignerSource::Config(path) => {
+                    SignerConfig::create_mnemonic_signer(path).await?;
+                    println!("{} is valid", path.display());
+                    Ok(())
+                }
+                SignerSource::Keystore(path) => {
+                    KeystoreSigner::new(path, keystore_password(false)?.as_str()).into_signer()?;
+                    println!("{} is valid", path.display());
+                    Ok(())
+                }
+                SignerSource::Remote(url) => bail!(
+                    "signer configuration of remote signer ({}) is managed by the signing service",
+                    url
+                ),
+                SignerSource::Offline(dir) => bail!(
+                    "signer configuration of offline signer ({}) is managed on the offline machine",
+                    dir.display()
+                ),
+            },
+            Self::Keystore { subcommand } => subcommand.run(signer),
+        }
+    }
+}
+
+impl KeystoreCommand {
+    /// Runs keystore command
+    pub fn run(self, signer: &SignerSource) -> Result<()> {
+        let path = signer.keystore_path()?;
+
+        match self {
+            Self::Create {
+                chain_id,
+                hd_path,
+                account_prefix,
+                algo,
+            } => {
+                let mut keystore = Keystore::read_or_new(path)?;
+                let password = keystore_password(keystore.keys().is_empty())?;
+                let mnemonic = Keystore::generate_mnemonic();
+
+                let address = keystore
+                    .add_key(
+                        chain_id.clone(),
+                        &mnemonic,
+                        hd_path.as_deref(),
+                        account_prefix.as_deref(),
+                        algo,
+                        &password,
+                    )?
+                    .address
+                    .clone();
+
+                keystore.write(path)?;
+
+                println!("successfully created key for {}: {}", chain_id, address);
+                println!();
+                println!("write down the following mnemonic and keep it safe (it will not be shown again):");
+                println!();
+                println!("{}", mnemonic.as_str());
+
+                Ok(())
+            }
+            Self::Import {
+                chain_id,
+                hd_path,
+                account_prefix,
+                algo,
+            } => {
+                let mut keystore = Keystore::read_or_new(path)?;
+                let mnemonic = Zeroizing::new(
+                    rpassword::prompt_password("Mnemonic: ").context("failed to read mnemonic")?,
+                );
+                let password = keystore_password(keystore.keys().is_empty())?;
+
+                let address = keystore
+                    .add_key(
+                        chain_id.clone(),
+                        mnemonic.trim(),
+                        hd_path.as_deref(),
+                        account_prefix.as_deref(),
+                        algo,
+                        &password,
+                    )?
+                    .address
+                    .clone();
+
+                keystore.write(path)?;
+
+                println!("successfully imported key for {}: {}", chain_id, address);
+
+                Ok(())
+            }
+            Self::List => {
+                let keystore = Keystore::read(path)?;
+
+                let keys = keystore
+                    .keys()
+                    .iter()
+                    .map(|key| KeyDetails {
+                        chain_id: &key.chain_id,
+                        address: &key.address,
+                        hd_path: key.hd_path.as_deref(),
+                        account_prefix: key.account_prefix.as_deref(),
+                        algo: key.algo.as_deref(),
+                    })
+                    .collect::<Vec<_>>();
+
+                let keys = serde_yaml::to_string(&keys).context("failed to serialize keys")?;
+
+                println!("{keys}");
+
                 Ok(())
             }
         }
@@ -1,11 +1,13 @@
-use std::path::Path;
-
 use anyhow::Result;
 use clap::Subcommand;
 use primitive_types::U256;
 use stag_api::types::ics::core::ics24_host::identifier::{ChainId, Identifier};
 
-use crate::{u256::U256Parser, util::stag};
+use crate::{
+    signer_config::SignerSource,
+    u256::U256Parser,
+    util::{stag, StagOptions},
+};
 
 #[derive(Debug, Subcommand)]
 pub enum TransferCommand {
@@ -47,7 +49,7 @@ pub enum TransferCommand {
 }
 
 impl TransferCommand {
-    pub async fn run(self, signer: impl AsRef<Path>, db_uri: &str) -> Result<()> {
+    pub async fn run(self, signer: &SignerSource, options: &StagOptions) -> Result<()> {
         match self {
             Self::Mint {
                 chain_id,
@@ -57,7 +59,7 @@ impl TransferCommand {
                 receiver,
                 memo,
             } => {
-                stag(signer, db_uri)
+                stag(signer, options)
                     .await?
                     .mint(
                         chain_id.clone(),
@@ -80,7 +82,7 @@ impl TransferCommand {
                 denom,
                 memo,
             } => {
-                stag(signer, db_uri)
+                stag(signer, options)
                     .await?
                     .burn(
                         chain_id.clone(),
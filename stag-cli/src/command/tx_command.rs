@@ -0,0 +1,103 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Subcommand;
+use stag_api::signer::{OfflineRequest, OfflineResponse};
+
+use crate::{
+    signer_config::SignerSource,
+    util::{signer, StagOptions},
+};
+
+use super::{
+    core_command::CoreCommand, ica_command::IcaCommand, transfer_command::TransferCommand,
+};
+
+#[derive(Debug, Subcommand)]
+pub enum TxCommand {
+    /// Runs a command on online machine with all the signing requests written to requests directory (command waits
+    /// until responses to signing requests are submitted)
+    Prepare {
+        /// Directory for exchanging signing requests and responses with offline signer
+        #[clap(long, default_value = "offline-requests")]
+        dir: PathBuf,
+        #[clap(subcommand)]
+        subcommand: PrepareCommand,
+    },
+    /// Signs a signing request on offline machine using local signer and writes the response to a file
+    Sign {
+        /// Path to signing request file
+        request: PathBuf,
+        /// Path to output response file (defaults to `<request id>.response.json` in current directory)
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Submits a response generated on offline machine, which resumes the command waiting for it
+    Submit {
+        /// Path to response file
+        response: PathBuf,
+        /// Directory for exchanging signing requests and responses with offline signer
+        #[clap(long, default_value = "offline-requests")]
+        dir: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PrepareCommand {
+    /// Core commands
+    Core {
+        #[clap(subcommand)]
+        subcommand: CoreCommand,
+    },
+    /// Transfer channel commands
+    Transfer {
+        #[clap(subcommand)]
+        subcommand: TransferCommand,
+    },
+    /// ICA channel commands
+    Ica {
+        #[clap(subcommand)]
+        subcommand: IcaCommand,
+    },
+}
+
+impl TxCommand {
+    pub async fn run(self, signer_source: &SignerSource, options: &StagOptions) -> Result<()> {
+        match self {
+            Self::Prepare { dir, subcommand } => {
+                let signer_source = SignerSource::Offline(dir);
+
+                match subcommand {
+                    PrepareCommand::Core { subcommand } => {
+                        subcommand.run(&signer_source, options).await
+                    }
+                    PrepareCommand::Transfer { subcommand } => {
+                        subcommand.run(&signer_source, options).await
+                    }
+                    PrepareCommand::Ica { subcommand } => {
+                        subcommand.run(&signer_source, options).await
+                    }
+                }
+            }
+            Self::Sign { request, output } => {
+                let request = OfflineRequest::read(request).await?;
+                let response = request.respond(&signer(signer_source).await?).await?;
+
+                let output = output
+                    .unwrap_or_else(|| PathBuf::from(format!("{}.response.json", request.id)));
+                response.write(&output).await?;
+
+                println!("response written to {}", output.display());
+
+                Ok(())
+            }
+            Self::Submit { response, dir } => {
+                OfflineResponse::read(response).await?.submit(dir).await?;
+
+                println!("response submitted successfully");
+
+                Ok(())
+            }
+        }
+    }
+}
@@ -1,8 +1,192 @@
-use std::{path::Path, str::FromStr};
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, ensure, Context, Result};
 use serde::{Deserialize, Serialize};
-use stag_api::{signer::MnemonicSigner, types::ics::core::ics24_host::identifier::ChainId};
+use stag_api::{
+    signer::{
+        keystore::{self, Keystore},
+        MnemonicSigner, Zeroizing,
+    },
+    types::{ics::core::ics24_host::identifier::ChainId, public_key::PublicKeyAlgo},
+};
+use url::Url;
+
+/// Environment variable containing password of keystore (password is prompted when it is not set)
+const KEYSTORE_PASSWORD_ENV: &str = "SOLO_KEYSTORE_PASSWORD";
+
+/// Source of signers used by CLI
+#[derive(Debug)]
+pub enum SignerSource {
+    /// Signer config (yaml format) containing mnemonics in plaintext
+    Config(PathBuf),
+    /// Password encrypted keystore
+    Keystore(PathBuf),
+    /// External signing service (gRPC address)
+    Remote(Url),
+    /// Directory for exchanging signing requests and responses with a signer on an offline machine
+    Offline(PathBuf),
+}
+
+impl SignerSource {
+    /// Creates a signer source which uses remote signer (if provided), keystore (if provided) or signer config
+    pub fn new(signer: PathBuf, keystore: Option<PathBuf>, remote_signer: Option<Url>) -> Self {
+        match (remote_signer, keystore) {
+            (Some(remote_signer), _) => Self::Remote(remote_signer),
+            (None, Some(keystore)) => Self::Keystore(keystore),
+            (None, None) => Self::Config(signer),
+        }
+    }
+
+    /// Returns the path of keystore
+    pub fn keystore_path(&self) -> Result<&Path> {
+        match self {
+            Self::Keystore(path) => Ok(path),
+            Self::Config(_) | Self::Remote(_) | Self::Offline(_) => bail!(
+                "path of keystore must be provided using `--keystore` option or `SOLO_KEYSTORE` environment variable"
+            ),
+        }
+    }
+
+    /// Returns signer configs of all the chains with given new mnemonic (HD path and algo default to the current values
+    /// of each chain)
+    pub async fn rotated_chain_configs(
+        &self,
+        mnemonic: &str,
+        hd_path: Option<&str>,
+        algo: Option<PublicKeyAlgo>,
+    ) -> Result<Vec<ChainSignerConfig>> {
+        let chain_configs = match self {
+            Self::Config(path) => SignerConfig::read(path)
+                .await?
+                .chains
+                .into_iter()
+                .map(|chain| {
+                    (
+                        chain.chain_id,
+                        chain.hd_path,
+                        chain.account_prefix,
+                        chain.algo,
+                    )
+                })
+                .collect::<Vec<_>>(),
+            Self::Keystore(path) => Keystore::read(path)?
+                .keys()
+                .iter()
+                .map(|key| {
+                    (
+                        key.chain_id.clone(),
+                        key.hd_path.clone(),
+                        key.account_prefix.clone(),
+                        key.algo.clone(),
+                    )
+                })
+                .collect(),
+            Self::Remote(url) => bail!(
+                "keys of remote signer ({}) are managed by the signing service",
+                url
+            ),
+            Self::Offline(dir) => bail!(
+                "keys of offline signer ({}) are managed on the offline machine",
+                dir.display()
+            ),
+        };
+
+        Ok(chain_configs
+            .into_iter()
+            .map(
+                |(chain_id, current_hd_path, account_prefix, current_algo)| ChainSignerConfig {
+                    chain_id,
+                    mnemonic: mnemonic.to_owned(),
+                    hd_path: hd_path.map(ToOwned::to_owned).or(current_hd_path),
+                    account_prefix,
+                    algo: algo.map(|algo| algo.to_string()).or(current_algo),
+                },
+            )
+            .collect())
+    }
+
+    /// Replaces signers of chains in signer config (or keystore) with given rotated signer configs
+    pub async fn apply_rotated_chain_configs(
+        &self,
+        chain_configs: &[ChainSignerConfig],
+    ) -> Result<()> {
+        match self {
+            Self::Config(path) => {
+                let mut signer_config = SignerConfig::read(path).await?;
+
+                for chain_config in chain_configs {
+                    let chain = signer_config
+                        .chains
+                        .iter_mut()
+                        .find(|chain| chain.chain_id == chain_config.chain_id)
+                        .ok_or_else(|| {
+                            anyhow!(
+                                "signer config for chain id {} does not exist in {}",
+                                chain_config.chain_id,
+                                path.display()
+                            )
+                        })?;
+
+                    *chain = chain_config.clone();
+                }
+
+                signer_config.write(path).await
+            }
+            Self::Keystore(path) => {
+                let mut keystore = Keystore::read(path)?;
+                let password = keystore_password(false)?;
+
+                for chain_config in chain_configs {
+                    keystore.remove_key(&chain_config.chain_id)?;
+                    keystore.add_key(
+                        chain_config.chain_id.clone(),
+                        &chain_config.mnemonic,
+                        chain_config.hd_path.as_deref(),
+                        chain_config.account_prefix.as_deref(),
+                        chain_config.algo()?,
+                        &password,
+                    )?;
+                }
+
+                keystore.write(path)
+            }
+            Self::Remote(url) => bail!(
+                "keys of remote signer ({}) are managed by the signing service",
+                url
+            ),
+            Self::Offline(dir) => bail!(
+                "keys of offline signer ({}) are managed on the offline machine",
+                dir.display()
+            ),
+        }
+    }
+}
+
+/// Reads password of keystore from environment variable or prompts for it (and asks for confirmation when `confirm`
+/// is `true`)
+pub fn keystore_password(confirm: bool) -> Result<Zeroizing<String>> {
+    if let Ok(password) = std::env::var(KEYSTORE_PASSWORD_ENV) {
+        return Ok(Zeroizing::new(password));
+    }
+
+    let password = Zeroizing::new(
+        rpassword::prompt_password("Keystore password: ").context("failed to read password")?,
+    );
+
+    if confirm {
+        let confirmation = Zeroizing::new(
+            rpassword::prompt_password("Confirm keystore password: ")
+                .context("failed to read password")?,
+        );
+
+        ensure!(*password == *confirmation, "passwords do not match");
+    }
+
+    Ok(password)
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SignerConfig {
@@ -34,8 +218,19 @@ impl SignerConfig {
         serde_yaml::from_slice(&bytes).context("failed to parse signer config")
     }
 
+    /// Writes the signer config to given yaml file (atomically and only readable by current user on unix systems)
+    async fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        let bytes = serde_yaml::to_string(self).context("failed to serialize signer config")?;
+        let path = path.as_ref().to_path_buf();
+
+        tokio::task::spawn_blocking(move || keystore::write_private_file(path, bytes.as_bytes()))
+            .await
+            .context("failed to write signer config")?
+            .context("failed to write signer config")
+    }
+
     /// Gets the mnemonic signer for current signer config
-    fn get_mnemonic_signer(&self) -> Result<MnemonicSigner> {
+    pub fn get_mnemonic_signer(&self) -> Result<MnemonicSigner> {
         let mut mnemonic_signer = MnemonicSigner::new();
         self.add_to_signer(&mut mnemonic_signer)?;
 
@@ -43,7 +238,7 @@ impl SignerConfig {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChainSignerConfig {
     /// Chain ID
     pub chain_id: ChainId,
@@ -53,11 +248,20 @@ pub struct ChainSignerConfig {
     pub hd_path: Option<String>,
     /// Bech32 account prefix of signer
     pub account_prefix: Option<String>,
-    /// Algorithm used for address generation [possible values: `secp256k1`, `eth-secp256k1`]
+    /// Algorithm used for address generation [possible values: `secp256k1`, `eth-secp256k1`, `ed25519`, `secp256r1`]
     pub algo: Option<String>,
 }
 
 impl ChainSignerConfig {
+    /// Parses the algorithm used for address generation
+    fn algo(&self) -> Result<Option<PublicKeyAlgo>> {
+        self.algo
+            .as_deref()
+            .map(FromStr::from_str)
+            .transpose()
+            .context(format!("invalid algo for chain id: {}", self.chain_id))
+    }
+
     /// Adds chain signer config to given mnemonic signer
     fn add_to_signer(&self, mnemonic_signer: &mut MnemonicSigner) -> Result<()> {
         mnemonic_signer
@@ -66,11 +270,7 @@ impl ChainSignerConfig {
                 &self.mnemonic,
                 self.hd_path.as_deref(),
                 self.account_prefix.as_deref(),
-                self.algo
-                    .as_deref()
-                    .map(FromStr::from_str)
-                    .transpose()
-                    .context(format!("invalid algo for chain id: {}", self.chain_id))?,
+                self.algo()?,
             )
             .context(format!(
                 "invalid signer config for chain id: {}",
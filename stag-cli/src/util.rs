@@ -1,58 +1,163 @@
-use std::path::Path;
+use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{ensure, Result};
+use clap::ValueEnum;
 #[cfg(feature = "postgres-storage")]
 use stag_api::storage::Postgres;
 #[cfg(feature = "sqlite-storage")]
 use stag_api::storage::Sqlite;
 use stag_api::{
-    event::{EventHandlerConfig, TracingEventHandler},
-    signer::{MnemonicSigner, SignerConfig},
+    event::{
+        EventFilter, EventHandler, EventHandlerConfig, MultiEventHandler, StorageEventHandler,
+        TracingEventHandler, WebhookEventHandler,
+    },
+    signer::{KeystoreSigner, OfflineSigner, RemoteSigner, Signer, SignerConfig},
     stag::{Stag, StagBuilder},
     storage::StorageConfig,
     tendermint::{JsonRpcConfig, ReqwestClient},
 };
+use url::Url;
 
-use crate::signer_config::SignerConfig as SignerConfigParser;
+use crate::signer_config::{keystore_password, SignerConfig as SignerConfigParser, SignerSource};
 
 #[cfg(feature = "sqlite-storage")]
-pub type CliStag = Stag<
-    StagBuilder<
-        <MnemonicSigner as SignerConfig>::Signer,
-        <Sqlite as StorageConfig>::Storage,
-        <ReqwestClient as JsonRpcConfig>::Client,
-        <TracingEventHandler as EventHandlerConfig>::EventHandler,
-    >,
->;
+pub type CliStorage = Sqlite;
 
 #[cfg(feature = "postgres-storage")]
+pub type CliStorage = Postgres;
+
 pub type CliStag = Stag<
     StagBuilder<
-        <MnemonicSigner as SignerConfig>::Signer,
-        <Postgres as StorageConfig>::Storage,
+        Arc<dyn Signer>,
+        <CliStorage as StorageConfig>::Storage,
         <ReqwestClient as JsonRpcConfig>::Client,
-        <TracingEventHandler as EventHandlerConfig>::EventHandler,
+        Arc<dyn EventHandler>,
     >,
 >;
 
-/// Builds stag instance from signer configuration file and db uri
-pub async fn stag(signer: impl AsRef<Path>, db_uri: &str) -> Result<CliStag> {
-    let signer = SignerConfigParser::create_mnemonic_signer(signer).await?;
+/// Event handler backend
+#[derive(Debug, ValueEnum, Clone, Copy, PartialEq, Eq)]
+pub enum EventHandlerKind {
+    /// Logs events using tracing
+    Tracing,
+    /// Sends events to webhook URLs
+    Webhook,
+    /// Stores events in database (stored events can be fetched using `stag query events` and re-sent to webhooks
+    /// using `stag core replay-events`)
+    Storage,
+}
+
+/// Options (other than signer) for building stag instance
+#[derive(Debug)]
+pub struct StagOptions {
+    /// Database connection string
+    pub db_uri: String,
+    /// Enabled event handler backends
+    pub event_handlers: Vec<EventHandlerKind>,
+    /// Webhook URLs to send events to (when `webhook` event handler is enabled)
+    pub webhook_urls: Vec<Url>,
+    /// Secret for signing webhook requests
+    pub webhook_secret: Option<String>,
+    /// Types of events to send to webhooks (all the events except telemetry events are sent when empty)
+    pub webhook_events: Vec<String>,
+}
+
+impl StagOptions {
+    /// Builds storage config from options (clones of storage config share the same connection pool)
+    pub fn storage(&self) -> CliStorage {
+        CliStorage::new(&self.db_uri)
+    }
+
+    /// Builds event handler from options (events and webhook deliveries are stored in given storage)
+    pub fn event_handler(&self, storage: &CliStorage) -> Result<Arc<dyn EventHandler>> {
+        let mut event_handler = MultiEventHandler::new();
 
-    cfg_if::cfg_if! {
-        if #[cfg(feature = "sqlite-storage")] {
-            let storage = Sqlite::new(db_uri);
-        } else if #[cfg(feature = "postgres-storage")] {
-            let storage = Postgres::new(db_uri);
+        if self.event_handlers.contains(&EventHandlerKind::Tracing) {
+            event_handler = event_handler.with_handler(TracingEventHandler);
         }
+
+        if self.event_handlers.contains(&EventHandlerKind::Webhook) {
+            let filter = if self.webhook_events.is_empty() {
+                EventFilter::All
+            } else {
+                EventFilter::only(self.webhook_events.iter().cloned())
+            };
+
+            event_handler =
+                event_handler.with_filtered_handler(self.webhook_event_handler(storage)?, filter);
+        }
+
+        if self.event_handlers.contains(&EventHandlerKind::Storage) {
+            event_handler = event_handler.with_handler(StorageEventHandler::new(storage.clone()));
+        }
+
+        Ok(Arc::new(event_handler.into_event_handler()))
+    }
+
+    /// Builds webhook event handler from options (pending deliveries are persisted in given storage)
+    pub fn webhook_event_handler(&self, storage: &CliStorage) -> Result<WebhookEventHandler> {
+        ensure!(
+            !self.webhook_urls.is_empty(),
+            "at least one `--webhook-url` must be provided for webhook event handler"
+        );
+
+        let mut webhook = WebhookEventHandler::new(self.webhook_urls.clone()).with_outbox(storage);
+
+        if let Some(ref secret) = self.webhook_secret {
+            webhook = webhook.with_secret(secret.as_bytes());
+        }
+
+        Ok(webhook)
     }
+}
+
+/// Builds signer from signer source
+pub async fn signer(signer: &SignerSource) -> Result<Arc<dyn Signer>> {
+    let signer: Arc<dyn Signer> = match signer {
+        SignerSource::Config(path) => Arc::new(
+            SignerConfigParser::create_mnemonic_signer(path)
+                .await?
+                .into_signer()?,
+        ),
+        SignerSource::Keystore(path) => Arc::new(
+            KeystoreSigner::new(path, keystore_password(false)?.as_str()).into_signer()?,
+        ),
+        SignerSource::Remote(url) => Arc::new(RemoteSigner::new(url.clone()).into_signer()?),
+        SignerSource::Offline(dir) => Arc::new(
+            OfflineSigner::new(dir)
+                .with_request_hook(|path| {
+                    println!(
+                        "waiting for offline signer: sign {} using `stag tx sign` on offline machine and submit the response using `stag tx submit`",
+                        path.display()
+                    )
+                })
+                .into_signer()?,
+        ),
+    };
+
+    Ok(signer)
+}
+
+/// Builds stag instance from signer source and options
+pub async fn stag(signer: &SignerSource, options: &StagOptions) -> Result<CliStag> {
+    stag_with_storage(signer, options, options.storage()).await
+}
+
+/// Builds stag instance from signer source and options which uses given storage
+pub async fn stag_with_storage(
+    signer: &SignerSource,
+    options: &StagOptions,
+    storage: CliStorage,
+) -> Result<CliStag> {
+    let signer = self::signer(signer).await?;
+    let event_handler = options.event_handler(&storage)?;
 
     let stag = Stag::builder()
         .with_signer(signer)?
         .with_storage(storage)
         .await?
         .with_rpc_client(ReqwestClient)
-        .with_event_handler(TracingEventHandler)
+        .with_event_handler(event_handler)
         .build();
 
     Ok(stag)
@@ -6,6 +6,7 @@ fn main() -> Result<(), Box<dyn Error>> {
             tonic_build::configure().compile(
                 &[
                     "./proto/core.proto",
+                    "./proto/events.proto",
                     "./proto/transfer.proto",
                     "./proto/ica/bank.proto",
                     "./proto/ica/staking.proto",
@@ -18,6 +19,7 @@ fn main() -> Result<(), Box<dyn Error>> {
             tonic_build::configure().compile(
                 &[
                     "./proto/core.proto",
+                    "./proto/events.proto",
                     "./proto/transfer.proto",
                     "./proto/ica/bank.proto",
                     "./proto/ica/staking.proto",
@@ -1 +1,258 @@
+//! Helpers for connecting to Stag gRPC server
+use anyhow::{Context, Result};
+use tonic::{
+    codegen::InterceptedService,
+    transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity},
+};
+use url::Url;
 
+pub use crate::telemetry::TraceContextInterceptor;
+
+/// Channel which adds W3C trace context of current span to `traceparent` metadata of every request
+pub type TracedChannel = InterceptedService<Channel, TraceContextInterceptor>;
+
+/// Configuration for connecting to Stag gRPC server (TLS is used when URL scheme is `https`)
+///
+/// Connected channel can be used with any of the generated clients, e.g., `CoreClient::new(channel)`.
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    url: Url,
+    ca_certificate: Option<Vec<u8>>,
+    identity: Option<(Vec<u8>, Vec<u8>)>,
+    domain_name: Option<String>,
+}
+
+impl ClientConfig {
+    /// Creates a new client config for server at given URL
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            ca_certificate: None,
+            identity: None,
+            domain_name: None,
+        }
+    }
+
+    /// Sets PEM encoded CA certificate used to verify server's certificate (webpki roots are used by default)
+    pub fn with_ca_certificate(mut self, ca_certificate: impl Into<Vec<u8>>) -> Self {
+        self.ca_certificate = Some(ca_certificate.into());
+        self
+    }
+
+    /// Sets PEM encoded client certificate and private key presented to server (required when server uses mutual
+    /// TLS)
+    pub fn with_identity(mut self, cert: impl Into<Vec<u8>>, key: impl Into<Vec<u8>>) -> Self {
+        self.identity = Some((cert.into(), key.into()));
+        self
+    }
+
+    /// Sets the domain name against which server's certificate is verified (host of URL is used by default)
+    pub fn with_domain_name(mut self, domain_name: impl Into<String>) -> Self {
+        self.domain_name = Some(domain_name.into());
+        self
+    }
+
+    /// Returns endpoint configured with TLS options of this config (without connecting to server)
+    pub fn endpoint(&self) -> Result<Endpoint> {
+        let endpoint = Endpoint::from_shared(self.url.to_string()).context("invalid server url")?;
+
+        if self.url.scheme() != "https" {
+            return Ok(endpoint);
+        }
+
+        let mut tls_config = ClientTlsConfig::new();
+
+        if let Some(ref ca_certificate) = self.ca_certificate {
+            tls_config = tls_config.ca_certificate(Certificate::from_pem(ca_certificate));
+        }
+
+        if let Some((ref cert, ref key)) = self.identity {
+            tls_config = tls_config.identity(Identity::from_pem(cert, key));
+        }
+
+        if let Some(ref domain_name) = self.domain_name {
+            tls_config = tls_config.domain_name(domain_name);
+        }
+
+        endpoint
+            .tls_config(tls_config)
+            .context("invalid tls configuration")
+    }
+
+    /// Connects to server (requests sent over returned channel carry trace context of current span)
+    pub async fn connect(&self) -> Result<TracedChannel> {
+        let channel = self
+            .endpoint()?
+            .connect()
+            .await
+            .with_context(|| format!("failed to connect to {}", self.url))?;
+
+        Ok(InterceptedService::new(channel, TraceContextInterceptor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry::{
+        sdk::trace::TracerProvider,
+        trace::{TraceContextExt, TracerProvider as _},
+    };
+    use stag_api::types::proto::stag::signer::v1::{
+        remote_signer_client::RemoteSignerClient,
+        remote_signer_server::{RemoteSigner, RemoteSignerServer},
+        GetPublicKeyRequest, GetPublicKeyResponse, SignRequest, SignResponse,
+    };
+    use tokio::net::TcpListener;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tonic::{
+        transport::{Server, ServerTlsConfig},
+        Request, Response, Status,
+    };
+    use tracing::Instrument;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    const CA_CERTIFICATE: &[u8] = include_bytes!("../tests/data/tls/ca.pem");
+    const SERVER_CERTIFICATE: &[u8] = include_bytes!("../tests/data/tls/server.pem");
+    const SERVER_KEY: &[u8] = include_bytes!("../tests/data/tls/server.key");
+    const CLIENT_CERTIFICATE: &[u8] = include_bytes!("../tests/data/tls/client.pem");
+    const CLIENT_KEY: &[u8] = include_bytes!("../tests/data/tls/client.key");
+
+    /// Service which returns the same public key for all the chains (or `traceparent` metadata of request in place of
+    /// public key when present)
+    struct MockService;
+
+    #[tonic::async_trait]
+    impl RemoteSigner for MockService {
+        async fn get_public_key(
+            &self,
+            request: Request<GetPublicKeyRequest>,
+        ) -> Result<Response<GetPublicKeyResponse>, Status> {
+            let public_key = request
+                .metadata()
+                .get("traceparent")
+                .and_then(|traceparent| traceparent.to_str().ok())
+                .unwrap_or("public-key");
+
+            Ok(Response::new(GetPublicKeyResponse {
+                public_key: public_key.to_owned(),
+                algo: "secp256k1".to_owned(),
+                account_address: "cosmos1address".to_owned(),
+            }))
+        }
+
+        async fn sign(&self, _: Request<SignRequest>) -> Result<Response<SignResponse>, Status> {
+            Err(Status::unimplemented("sign"))
+        }
+    }
+
+    /// Starts a server over TLS (certificate is issued for `localhost` by test CA) which requires client certificates
+    /// signed by test CA when `mutual` is true
+    async fn start_server(mutual: bool) -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let mut tls_config =
+            ServerTlsConfig::new().identity(Identity::from_pem(SERVER_CERTIFICATE, SERVER_KEY));
+
+        if mutual {
+            tls_config = tls_config.client_ca_root(Certificate::from_pem(CA_CERTIFICATE));
+        }
+
+        tokio::spawn(
+            Server::builder()
+                .tls_config(tls_config)
+                .unwrap()
+                .add_service(RemoteSignerServer::new(MockService))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+
+        format!("https://{}", addr).parse().unwrap()
+    }
+
+    async fn get_public_key(config: &ClientConfig) -> Result<GetPublicKeyResponse> {
+        let channel = config.connect().await?;
+
+        Ok(RemoteSignerClient::new(channel)
+            .get_public_key(GetPublicKeyRequest {
+                chain_id: "test-1".to_owned(),
+            })
+            .await?
+            .into_inner())
+    }
+
+    #[tokio::test]
+    async fn test_https_with_ca_certificate() {
+        let url = start_server(false).await;
+
+        let config = ClientConfig::new(url.clone())
+            .with_ca_certificate(CA_CERTIFICATE)
+            .with_domain_name("localhost");
+        let response = get_public_key(&config).await.unwrap();
+        assert_eq!(response.account_address, "cosmos1address");
+
+        // Server's certificate is not trusted without test CA
+        let config = ClientConfig::new(url.clone()).with_domain_name("localhost");
+        assert!(get_public_key(&config).await.is_err());
+
+        // Server's certificate is not issued for the host of URL (i.e., `127.0.0.1`)
+        let config = ClientConfig::new(url).with_ca_certificate(CA_CERTIFICATE);
+        assert!(get_public_key(&config).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mutual_tls() {
+        let url = start_server(true).await;
+
+        let config = ClientConfig::new(url.clone())
+            .with_ca_certificate(CA_CERTIFICATE)
+            .with_identity(CLIENT_CERTIFICATE, CLIENT_KEY)
+            .with_domain_name("localhost");
+        let response = get_public_key(&config).await.unwrap();
+        assert_eq!(response.account_address, "cosmos1address");
+
+        // Clients without a certificate are rejected
+        let config = ClientConfig::new(url)
+            .with_ca_certificate(CA_CERTIFICATE)
+            .with_domain_name("localhost");
+        assert!(get_public_key(&config).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_trace_context_propagation() {
+        // Tracer only holds a weak reference to its provider
+        let provider = TracerProvider::builder().build();
+        let tracer = provider.tracer("test");
+        let subscriber =
+            tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let config = ClientConfig::new(start_server(false).await)
+            .with_ca_certificate(CA_CERTIFICATE)
+            .with_domain_name("localhost");
+
+        // Requests sent outside of a span do not carry trace context
+        let response = get_public_key(&config).await.unwrap();
+        assert_eq!(response.public_key, "public-key");
+
+        let span = tracing::info_span!("test");
+        let trace_id = span.context().span().span_context().trace_id();
+
+        let response = get_public_key(&config).instrument(span).await.unwrap();
+        assert!(response
+            .public_key
+            .starts_with(&format!("00-{}-", trace_id)));
+    }
+
+    #[test]
+    fn test_endpoint() {
+        let config = ClientConfig::new("http://localhost:8000".parse().unwrap())
+            .with_ca_certificate(b"invalid".to_vec());
+        assert_eq!(
+            config.endpoint().unwrap().uri().to_string(),
+            "http://localhost:8000/"
+        );
+    }
+}
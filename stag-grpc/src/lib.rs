@@ -1,4 +1,14 @@
+pub mod client;
 pub mod proto;
 mod server;
+#[cfg(feature = "mnemonic-signer")]
+mod signer_store;
+mod telemetry;
 
-pub use self::server::Server;
+pub use self::server::{
+    auth::{AuthPolicy, Role},
+    Server,
+};
+#[cfg(feature = "mnemonic-signer")]
+pub use self::signer_store::SignerStore;
+pub use self::telemetry::{init_tracing, shutdown_tracing};
@@ -12,6 +12,10 @@ pub mod core {
     tonic::include_proto!("core");
 }
 
+pub mod events {
+    tonic::include_proto!("events");
+}
+
 #[cfg(feature = "mnemonic-signer")]
 pub mod mnemonic_signer {
     tonic::include_proto!("mnemonic_signer");
@@ -0,0 +1,844 @@
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    task::{Context as TaskContext, Poll},
+};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tonic::{
+    body::BoxBody,
+    codegen::{
+        http::{header::AUTHORIZATION, HeaderMap, Request, Response},
+        BoxFuture,
+    },
+    Code, Status,
+};
+use tower::{Layer, Service};
+
+/// Target of audit log entries (can be used to filter audit logs, e.g., `RUST_LOG=stag_grpc::audit=info`)
+const AUDIT_TARGET: &str = "stag_grpc::audit";
+
+/// Services which do not mutate any state (calls to these services are not audit logged)
+const READ_ONLY_SERVICES: &[&str] = &["query.Query", "events.Events"];
+
+/// Role of a caller (each role is allowed to do everything lower roles are allowed to do)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Can call `Query` and `Events` services
+    Query,
+    /// Can additionally call `Transfer`, `IcaBank` and `IcaStaking` services
+    Operator,
+    /// Can call all the services (including `Core` and `MnemonicSigner`)
+    Admin,
+}
+
+impl Role {
+    /// Returns the default role required for calling methods of given service
+    fn required_for(service: &str) -> Self {
+        match service {
+            "query.Query" | "events.Events" => Self::Query,
+            "transfer.Transfer" | "ica.bank.IcaBank" | "ica.staking.IcaStaking" => Self::Operator,
+            _ => Self::Admin,
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Query => write!(f, "query"),
+            Self::Operator => write!(f, "operator"),
+            Self::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+/// Authentication and authorization policy of gRPC server
+///
+/// Callers authenticate by sending an API token or a JWT in `authorization` header (`Bearer <token>`). Policy file
+/// (yaml format) looks like:
+///
+/// ```yaml
+/// # API tokens (only SHA-256 hashes of tokens are stored, e.g., `echo -n "<token>" | sha256sum`)
+/// tokens:
+///   - name: dashboard
+///     sha256: 9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08
+///     role: query
+///
+/// # JWTs (`sub` claim is used as caller's identity and role is read from `role_claim`)
+/// jwt:
+///   algorithm: HS256 # `secret` is used for HS* algorithms and `public_key_file` (PEM) for RS*, PS* and ES*
+///   secret: my-secret
+///   issuer: https://auth.example.com # optional
+///   audience: stag # optional
+///   role_claim: role # optional (default: `role`)
+///
+/// # Overrides of roles required for calling a service (e.g., `transfer.Transfer`) or a method (e.g.,
+/// # `transfer.Transfer/Mint`)
+/// permissions:
+///   transfer.Transfer/Burn: admin
+/// ```
+///
+/// By default, `query` role is required for `Query` and `Events` services, `operator` role for `Transfer`, `IcaBank`
+/// and `IcaStaking` services and `admin` role for all the other services.
+pub struct AuthPolicy {
+    tokens: HashMap<String, Caller>,
+    jwt: Option<JwtVerifier>,
+    permissions: HashMap<String, Role>,
+}
+
+impl AuthPolicy {
+    /// Reads policy from given file (yaml format)
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read auth policy from {}", path.display()))?;
+        let policy_file: PolicyFile = serde_yaml::from_str(&contents)
+            .with_context(|| format!("failed to parse auth policy in {}", path.display()))?;
+
+        Self::try_from(policy_file)
+    }
+
+    /// Authenticates the caller of given request and checks if it is allowed to call requested method (returns the
+    /// reason for rejecting the call otherwise)
+    fn authorize<B>(&self, request: &Request<B>) -> Result<Caller, Rejection> {
+        let method = request.uri().path();
+        let required_role = self.required_role(method);
+
+        let caller = match self.authenticate(request.headers()) {
+            Ok(caller) => caller,
+            Err(err) => {
+                tracing::warn!(target: AUDIT_TARGET, method, "unauthenticated call: {}", err);
+                return Err(Rejection::Unauthenticated(err.to_string()));
+            }
+        };
+
+        if caller.role < required_role {
+            tracing::warn!(
+                target: AUDIT_TARGET,
+                caller = %caller.name,
+                role = %caller.role,
+                method,
+                "permission denied"
+            );
+
+            return Err(Rejection::PermissionDenied(format!(
+                "{} role is required for calling {}",
+                required_role, method
+            )));
+        }
+
+        Ok(caller)
+    }
+
+    fn authenticate(&self, headers: &HeaderMap) -> Result<Caller> {
+        let token = headers
+            .get(AUTHORIZATION)
+            .ok_or_else(|| anyhow!("authorization header is missing"))?
+            .to_str()
+            .ok()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| anyhow!("authorization header must be a bearer token"))?
+            .trim();
+
+        if let Some(caller) = self.tokens.get(&hash_token(token)) {
+            return Ok(caller.clone());
+        }
+
+        match self.jwt {
+            Some(ref jwt) => jwt.verify(token).context("invalid token"),
+            None => Err(anyhow!("invalid token")),
+        }
+    }
+
+    fn required_role(&self, method: &str) -> Role {
+        let method = method.trim_start_matches('/');
+        let service = service_name(method);
+
+        self.permissions
+            .get(method)
+            .or_else(|| self.permissions.get(service))
+            .copied()
+            .unwrap_or_else(|| Role::required_for(service))
+    }
+}
+
+impl TryFrom<PolicyFile> for AuthPolicy {
+    type Error = anyhow::Error;
+
+    fn try_from(policy_file: PolicyFile) -> Result<Self, Self::Error> {
+        ensure!(
+            !policy_file.tokens.is_empty() || policy_file.jwt.is_some(),
+            "auth policy must contain at least one token or jwt configuration"
+        );
+
+        let mut tokens = HashMap::with_capacity(policy_file.tokens.len());
+
+        for token in policy_file.tokens {
+            let hash = token.sha256.to_lowercase();
+
+            ensure!(
+                hex::decode(&hash).is_ok_and(|bytes| bytes.len() == 32),
+                "invalid sha256 hash of token {}",
+                token.name
+            );
+
+            let caller = Caller {
+                name: token.name,
+                role: token.role,
+            };
+
+            if let Some(existing) = tokens.insert(hash, caller) {
+                return Err(anyhow!("duplicate token hash for {}", existing.name));
+            }
+        }
+
+        let jwt = policy_file.jwt.map(JwtVerifier::try_from).transpose()?;
+
+        let permissions = policy_file
+            .permissions
+            .into_iter()
+            .map(|(method, role)| (method.trim_start_matches('/').to_owned(), role))
+            .collect();
+
+        Ok(Self {
+            tokens,
+            jwt,
+            permissions,
+        })
+    }
+}
+
+/// Reason for rejecting a gRPC call
+#[derive(Debug)]
+enum Rejection {
+    Unauthenticated(String),
+    PermissionDenied(String),
+}
+
+impl From<Rejection> for Status {
+    fn from(rejection: Rejection) -> Self {
+        match rejection {
+            Rejection::Unauthenticated(message) => Status::unauthenticated(message),
+            Rejection::PermissionDenied(message) => Status::permission_denied(message),
+        }
+    }
+}
+
+/// Authenticated caller of a gRPC method
+#[derive(Debug, Clone)]
+struct Caller {
+    name: String,
+    role: Role,
+}
+
+struct JwtVerifier {
+    key: DecodingKey,
+    validation: Validation,
+    role_claim: String,
+}
+
+impl JwtVerifier {
+    fn verify(&self, token: &str) -> Result<Caller> {
+        let mut claims =
+            jsonwebtoken::decode::<HashMap<String, Value>>(token, &self.key, &self.validation)?
+                .claims;
+
+        let name = match claims.remove("sub") {
+            Some(Value::String(sub)) => sub,
+            _ => return Err(anyhow!("`sub` claim must be a string")),
+        };
+
+        let role = claims
+            .remove(&self.role_claim)
+            .ok_or_else(|| anyhow!("`{}` claim is missing", self.role_claim))
+            .and_then(|role| {
+                serde_json::from_value(role)
+                    .with_context(|| format!("invalid `{}` claim", self.role_claim))
+            })?;
+
+        Ok(Caller { name, role })
+    }
+}
+
+impl TryFrom<JwtConfig> for JwtVerifier {
+    type Error = anyhow::Error;
+
+    fn try_from(config: JwtConfig) -> Result<Self, Self::Error> {
+        let key = match config.algorithm {
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+                let secret = config
+                    .secret
+                    .ok_or_else(|| anyhow!("jwt secret is required for {:?}", config.algorithm))?;
+                DecodingKey::from_secret(secret.as_bytes())
+            }
+            algorithm => {
+                let path = config.public_key_file.ok_or_else(|| {
+                    anyhow!("jwt public key file is required for {:?}", algorithm)
+                })?;
+                let pem = fs::read(&path).with_context(|| {
+                    format!("failed to read jwt public key from {}", path.display())
+                })?;
+
+                match algorithm {
+                    Algorithm::ES256 | Algorithm::ES384 => DecodingKey::from_ec_pem(&pem),
+                    Algorithm::EdDSA => DecodingKey::from_ed_pem(&pem),
+                    _ => DecodingKey::from_rsa_pem(&pem),
+                }
+                .context("invalid jwt public key")?
+            }
+        };
+
+        let mut validation = Validation::new(config.algorithm);
+        validation.set_required_spec_claims(&["exp", "sub"]);
+
+        if let Some(ref issuer) = config.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+
+        if let Some(ref audience) = config.audience {
+            validation.set_audience(&[audience]);
+        }
+
+        Ok(Self {
+            key,
+            validation,
+            role_claim: config.role_claim,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PolicyFile {
+    #[serde(default)]
+    tokens: Vec<TokenConfig>,
+    jwt: Option<JwtConfig>,
+    #[serde(default)]
+    permissions: HashMap<String, Role>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TokenConfig {
+    name: String,
+    sha256: String,
+    role: Role,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JwtConfig {
+    #[serde(default = "default_jwt_algorithm")]
+    algorithm: Algorithm,
+    secret: Option<String>,
+    public_key_file: Option<PathBuf>,
+    issuer: Option<String>,
+    audience: Option<String>,
+    #[serde(default = "default_role_claim")]
+    role_claim: String,
+}
+
+fn default_jwt_algorithm() -> Algorithm {
+    Algorithm::HS256
+}
+
+fn default_role_claim() -> String {
+    "role".to_owned()
+}
+
+/// Returns the service name from a method path (e.g., `core.Core` for `/core.Core/AddChain`)
+fn service_name(method: &str) -> &str {
+    let method = method.trim_start_matches('/');
+    method
+        .split_once('/')
+        .map_or(method, |(service, _)| service)
+}
+
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Tower layer which rejects gRPC calls not allowed by auth policy
+#[derive(Clone)]
+pub struct AuthLayer {
+    policy: Arc<AuthPolicy>,
+}
+
+impl AuthLayer {
+    pub fn new(policy: Arc<AuthPolicy>) -> Self {
+        Self { policy }
+    }
+}
+
+impl<S> Layer<S> for AuthLayer {
+    type Service = AuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthService {
+            policy: self.policy.clone(),
+            inner,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AuthService<S> {
+    policy: Arc<AuthPolicy>,
+    inner: S,
+}
+
+impl<S, B> Service<Request<B>> for AuthService<S>
+where
+    S: Service<Request<B>, Response = Response<BoxBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<BoxBody>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let caller = match self.policy.authorize(&request) {
+            Ok(caller) => caller,
+            Err(rejection) => {
+                let status = Status::from(rejection);
+                return Box::pin(async move { Ok(status.to_http()) });
+            }
+        };
+
+        let method = request.uri().path().to_owned();
+        let response = self.inner.call(request);
+
+        if READ_ONLY_SERVICES.contains(&service_name(&method)) {
+            return Box::pin(response);
+        }
+
+        Box::pin(async move {
+            let response = response.await;
+            audit_call(&caller, &method, &response);
+            response
+        })
+    }
+}
+
+/// Logs the outcome of an authorized call (tonic sends errors as trailers-only responses, so, the status is read from
+/// response headers and a response without status in headers is considered successful)
+fn audit_call<E>(caller: &Caller, method: &str, response: &Result<Response<BoxBody>, E>) {
+    let status = match response {
+        Ok(response) => Status::from_header_map(response.headers()),
+        Err(_) => {
+            tracing::warn!(
+                target: AUDIT_TARGET,
+                caller = %caller.name,
+                role = %caller.role,
+                method,
+                "authorized call failed without a response"
+            );
+            return;
+        }
+    };
+
+    match status {
+        Some(status) if status.code() != Code::Ok => tracing::warn!(
+            target: AUDIT_TARGET,
+            caller = %caller.name,
+            role = %caller.role,
+            method,
+            status = ?status.code(),
+            "authorized call failed: {}",
+            status.message()
+        ),
+        _ => tracing::info!(
+            target: AUDIT_TARGET,
+            caller = %caller.name,
+            role = %caller.role,
+            method,
+            status = ?Code::Ok,
+            "authorized call"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        io,
+        sync::Mutex,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use jsonwebtoken::{EncodingKey, Header};
+    use serde_json::json;
+    use tonic::codegen::empty_body;
+    use tower::{service_fn, ServiceExt};
+
+    use super::*;
+
+    const ADMIN_TOKEN: &str = "admin-token";
+    const QUERY_TOKEN: &str = "query-token";
+    const JWT_SECRET: &str = "jwt-secret";
+
+    fn policy(yaml: &str) -> Result<AuthPolicy> {
+        AuthPolicy::try_from(serde_yaml::from_str::<PolicyFile>(yaml)?)
+    }
+
+    fn token_policy() -> AuthPolicy {
+        policy(&format!(
+            r#"
+tokens:
+  - name: admin
+    sha256: {}
+    role: admin
+  - name: dashboard
+    sha256: {}
+    role: query
+permissions:
+  /transfer.Transfer/Burn: admin
+  ica.bank.IcaBank: query
+"#,
+            hash_token(ADMIN_TOKEN),
+            hash_token(QUERY_TOKEN)
+        ))
+        .unwrap()
+    }
+
+    fn jwt_policy() -> AuthPolicy {
+        policy(&format!(
+            r#"
+jwt:
+  secret: {}
+  issuer: https://auth.example.com
+  audience: stag
+  role_claim: stag_role
+"#,
+            JWT_SECRET
+        ))
+        .unwrap()
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    fn jwt(claims: Value, secret: &str) -> String {
+        jsonwebtoken::encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(secret.as_bytes()),
+        )
+        .unwrap()
+    }
+
+    fn claims() -> Value {
+        json!({
+            "sub": "alice",
+            "exp": now() + 3600,
+            "iss": "https://auth.example.com",
+            "aud": "stag",
+            "stag_role": "operator",
+        })
+    }
+
+    fn headers(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, format!("Bearer {}", token).parse().unwrap());
+        headers
+    }
+
+    fn request(method: &str, token: Option<&str>) -> Request<()> {
+        let mut request = Request::builder().uri(method).body(()).unwrap();
+
+        if let Some(token) = token {
+            *request.headers_mut() = headers(token);
+        }
+
+        request
+    }
+
+    fn response_status(response: &Response<BoxBody>) -> Code {
+        Status::from_header_map(response.headers()).map_or(Code::Ok, |status| status.code())
+    }
+
+    /// Returns a service which fails calls to `Burn` method with an internal error (and succeeds all the other calls)
+    fn auth_service(
+        policy: AuthPolicy,
+    ) -> AuthService<
+        impl Service<
+                Request<()>,
+                Response = Response<BoxBody>,
+                Error = Infallible,
+                Future = impl Send,
+            > + Clone,
+    > {
+        let inner = service_fn(|request: Request<()>| async move {
+            if request.uri().path().ends_with("/Burn") {
+                Ok::<_, Infallible>(Status::internal("insufficient balance").to_http())
+            } else {
+                Ok(Response::new(empty_body()))
+            }
+        });
+
+        AuthLayer::new(Arc::new(policy)).layer(inner)
+    }
+
+    #[derive(Clone, Default)]
+    struct LogWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl LogWriter {
+        fn logs(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl io::Write for LogWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_policy_validation() {
+        let err = policy("permissions: {}").err().unwrap();
+        assert!(err.to_string().contains("at least one token"));
+
+        let err = policy(
+            r#"
+tokens:
+  - name: admin
+    sha256: abcd
+    role: admin
+"#,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(err.to_string(), "invalid sha256 hash of token admin");
+
+        // hashes are compared case-insensitively
+        let err = policy(&format!(
+            r#"
+tokens:
+  - name: admin
+    sha256: {}
+    role: admin
+  - name: dashboard
+    sha256: {}
+    role: query
+"#,
+            hash_token(ADMIN_TOKEN),
+            hash_token(ADMIN_TOKEN).to_uppercase()
+        ))
+        .err()
+        .unwrap();
+        assert_eq!(err.to_string(), "duplicate token hash for admin");
+
+        let err = policy("jwt: { algorithm: HS256 }").err().unwrap();
+        assert_eq!(err.to_string(), "jwt secret is required for HS256");
+
+        assert!(policy("jwt: { secret: secret }").is_ok());
+    }
+
+    #[test]
+    fn test_required_role() {
+        let policy = token_policy();
+
+        assert_eq!(policy.required_role("/query.Query/GetChain"), Role::Query);
+        assert_eq!(
+            policy.required_role("/events.Events/Subscribe"),
+            Role::Query
+        );
+        assert_eq!(
+            policy.required_role("/transfer.Transfer/Mint"),
+            Role::Operator
+        );
+        assert_eq!(
+            policy.required_role("/ica.staking.IcaStaking/Delegate"),
+            Role::Operator
+        );
+        assert_eq!(policy.required_role("/core.Core/AddChain"), Role::Admin);
+
+        // method override (leading `/` in policy file is ignored)
+        assert_eq!(policy.required_role("/transfer.Transfer/Burn"), Role::Admin);
+        // service override
+        assert_eq!(policy.required_role("/ica.bank.IcaBank/Send"), Role::Query);
+    }
+
+    #[test]
+    fn test_token_authentication() {
+        let policy = token_policy();
+
+        let caller = policy.authenticate(&headers(QUERY_TOKEN)).unwrap();
+        assert_eq!(caller.name, "dashboard");
+        assert_eq!(caller.role, Role::Query);
+
+        let caller = policy
+            .authenticate(&headers(&format!(" {} ", ADMIN_TOKEN)))
+            .unwrap();
+        assert_eq!(caller.name, "admin");
+        assert_eq!(caller.role, Role::Admin);
+
+        let err = policy.authenticate(&headers("unknown-token")).unwrap_err();
+        assert_eq!(err.to_string(), "invalid token");
+
+        let err = policy.authenticate(&HeaderMap::new()).unwrap_err();
+        assert_eq!(err.to_string(), "authorization header is missing");
+
+        let mut basic = HeaderMap::new();
+        basic.insert(AUTHORIZATION, ADMIN_TOKEN.parse().unwrap());
+        let err = policy.authenticate(&basic).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "authorization header must be a bearer token"
+        );
+    }
+
+    #[test]
+    fn test_jwt_authentication() {
+        let policy = jwt_policy();
+
+        let caller = policy
+            .authenticate(&headers(&jwt(claims(), JWT_SECRET)))
+            .unwrap();
+        assert_eq!(caller.name, "alice");
+        assert_eq!(caller.role, Role::Operator);
+
+        let invalid_claims = [
+            ("exp", json!(now() - 3600), "ExpiredSignature"),
+            ("iss", json!("https://other.example.com"), "InvalidIssuer"),
+            ("aud", json!("other"), "InvalidAudience"),
+            ("stag_role", json!("superuser"), "invalid `stag_role` claim"),
+            ("sub", json!(1), "Missing required claim: sub"),
+        ];
+
+        for (claim, value, error) in invalid_claims {
+            let mut claims = claims();
+            claims[claim] = value;
+
+            let err = policy
+                .authenticate(&headers(&jwt(claims, JWT_SECRET)))
+                .unwrap_err();
+            assert!(format!("{:#}", err).contains(error), "{}: {:#}", claim, err);
+        }
+
+        let mut missing_role = claims();
+        missing_role.as_object_mut().unwrap().remove("stag_role");
+        let err = policy
+            .authenticate(&headers(&jwt(missing_role, JWT_SECRET)))
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("`stag_role` claim is missing"));
+
+        let err = policy
+            .authenticate(&headers(&jwt(claims(), "other-secret")))
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("InvalidSignature"));
+    }
+
+    #[tokio::test]
+    async fn test_auth_service() {
+        let service = auth_service(token_policy());
+
+        let response = service
+            .clone()
+            .oneshot(request("/core.Core/AddChain", None))
+            .await
+            .unwrap();
+        assert_eq!(response_status(&response), Code::Unauthenticated);
+
+        let response = service
+            .clone()
+            .oneshot(request("/core.Core/AddChain", Some("unknown-token")))
+            .await
+            .unwrap();
+        assert_eq!(response_status(&response), Code::Unauthenticated);
+
+        let response = service
+            .clone()
+            .oneshot(request("/core.Core/AddChain", Some(QUERY_TOKEN)))
+            .await
+            .unwrap();
+        assert_eq!(response_status(&response), Code::PermissionDenied);
+
+        let response = service
+            .clone()
+            .oneshot(request("/query.Query/GetChain", Some(QUERY_TOKEN)))
+            .await
+            .unwrap();
+        assert_eq!(response_status(&response), Code::Ok);
+
+        let response = service
+            .clone()
+            .oneshot(request("/core.Core/AddChain", Some(ADMIN_TOKEN)))
+            .await
+            .unwrap();
+        assert_eq!(response_status(&response), Code::Ok);
+
+        // status of inner service is passed through
+        let response = service
+            .oneshot(request("/transfer.Transfer/Burn", Some(ADMIN_TOKEN)))
+            .await
+            .unwrap();
+        assert_eq!(response_status(&response), Code::Internal);
+    }
+
+    #[tokio::test]
+    async fn test_audit_log() {
+        let writer = LogWriter::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer({
+                let writer = writer.clone();
+                move || writer.clone()
+            })
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let service = auth_service(token_policy());
+
+        for (method, token) in [
+            ("/query.Query/GetChain", QUERY_TOKEN),
+            ("/core.Core/AddChain", ADMIN_TOKEN),
+            ("/transfer.Transfer/Burn", ADMIN_TOKEN),
+            ("/transfer.Transfer/Mint", QUERY_TOKEN),
+        ] {
+            service
+                .clone()
+                .oneshot(request(method, Some(token)))
+                .await
+                .unwrap();
+        }
+
+        let logs = writer.logs();
+        let lines = logs.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3, "{}", logs);
+
+        // calls to read-only services are not logged
+        assert!(lines[0].contains(
+            "authorized call caller=admin role=admin method=\"/core.Core/AddChain\" status=Ok"
+        ));
+        assert!(lines[1].contains(
+            "authorized call failed: insufficient balance caller=admin role=admin method=\"/transfer.Transfer/Burn\" status=Internal"
+        ));
+        assert!(lines[2].contains(
+            "permission denied caller=dashboard role=query method=\"/transfer.Transfer/Mint\""
+        ));
+    }
+}
@@ -7,7 +7,7 @@ use stag_api::{
     storage::TransactionProvider,
     tendermint::JsonRpcClient,
     types::{
-        chain_state::{ChainConfig, Fee},
+        chain_state::{ChainConfig, Fee, RetryPolicy},
         ics::core::ics24_host::identifier::PortId,
         public_key::{PublicKey, PublicKeyAlgo},
     },
@@ -18,7 +18,8 @@ use tonic::{async_trait, Request, Response, Status};
 use crate::proto::core::{
     core_server::Core, AddChainRequest, AddChainResponse, CloseChannelRequest,
     CloseChannelResponse, ConnectChainRequest, ConnectChainResponse, CreateChannelRequest,
-    CreateChannelResponse, FeeConfig, UpdateSignerRequest, UpdateSignerResponse,
+    CreateChannelResponse, FeeConfig, FreezeClientRequest, FreezeClientResponse, RetryPolicyConfig,
+    UpdateSignerRequest, UpdateSignerResponse,
 };
 
 const DEFAULT_GRPC_ADDR: &str = "http://0.0.0.0:9090";
@@ -242,8 +243,23 @@ where
             .map_err(|err: Error| Status::invalid_argument(err.to_string()))?
             .unwrap_or(PublicKeyAlgo::Secp256k1);
 
-        let new_public_key = PublicKey::new(request.new_public_key, new_public_key_algo)
-            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        let new_public_key = match request.new_multisig_threshold {
+            None => {
+                if !request.new_multisig_public_keys.is_empty() {
+                    return Err(Status::invalid_argument(
+                        "multisig public keys can only be provided with multisig threshold",
+                    ));
+                }
+
+                PublicKey::new(request.new_public_key, new_public_key_algo)
+            }
+            Some(threshold) => std::iter::once(request.new_public_key)
+                .chain(request.new_multisig_public_keys)
+                .map(|public_key| PublicKey::new(public_key, new_public_key_algo))
+                .collect::<Result<Vec<_>>>()
+                .and_then(|public_keys| PublicKey::new_multisig(threshold, public_keys)),
+        }
+        .map_err(|err| Status::invalid_argument(err.to_string()))?;
 
         let memo = request.memo.unwrap_or_default();
 
@@ -256,6 +272,32 @@ where
 
         Ok(Response::new(UpdateSignerResponse {}))
     }
+
+    async fn freeze_client(
+        &self,
+        request: Request<FreezeClientRequest>,
+    ) -> Result<Response<FreezeClientResponse>, Status> {
+        let request = request.into_inner();
+
+        let chain_id = request
+            .chain_id
+            .parse()
+            .context("invalid chain id")
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        let request_id = request.request_id;
+        let memo = request.memo.unwrap_or_default();
+
+        let transaction_hash = self
+            .stag
+            .read()
+            .await
+            .freeze_client(chain_id, request_id, memo)
+            .await
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        Ok(Response::new(FreezeClientResponse { transaction_hash }))
+    }
 }
 
 impl TryFrom<AddChainRequest> for ChainConfig {
@@ -274,6 +316,24 @@ impl TryFrom<AddChainRequest> for ChainConfig {
             .parse()
             .context("invalid RPC address")?;
 
+        let fallback_grpc_addrs = value
+            .fallback_grpc_addrs
+            .into_iter()
+            .map(|grpc_addr| grpc_addr.parse().context("invalid fallback gRPC address"))
+            .collect::<Result<_>>()?;
+
+        let fallback_rpc_addrs = value
+            .fallback_rpc_addrs
+            .into_iter()
+            .map(|rpc_addr| rpc_addr.parse().context("invalid fallback RPC address"))
+            .collect::<Result<_>>()?;
+
+        let witness_rpc_addrs = value
+            .witness_rpc_addrs
+            .into_iter()
+            .map(|rpc_addr| rpc_addr.parse().context("invalid witness RPC address"))
+            .collect::<Result<_>>()?;
+
         let fee = value
             .fee_config
             .map(|fee_config: FeeConfig| -> Result<Fee> {
@@ -339,9 +399,18 @@ impl TryFrom<AddChainRequest> for ChainConfig {
             .packet_timeout_height_offset
             .unwrap_or(DEFAULT_PACKET_TIMEOUT_HEIGHT_OFFSET);
 
+        let retry_policy = value
+            .retry_policy
+            .map(RetryPolicy::try_from)
+            .transpose()?
+            .unwrap_or_default();
+
         Ok(Self {
             grpc_addr,
             rpc_addr,
+            fallback_grpc_addrs,
+            fallback_rpc_addrs,
+            witness_rpc_addrs,
             fee,
             trust_level,
             trusting_period,
@@ -351,6 +420,43 @@ impl TryFrom<AddChainRequest> for ChainConfig {
             trusted_height,
             trusted_hash,
             packet_timeout_height_offset,
+            retry_policy,
+        })
+    }
+}
+
+impl TryFrom<RetryPolicyConfig> for RetryPolicy {
+    type Error = Error;
+
+    fn try_from(value: RetryPolicyConfig) -> Result<Self, Self::Error> {
+        let default = RetryPolicy::default();
+
+        let max_attempts = value.max_attempts.unwrap_or(default.max_attempts);
+        ensure!(max_attempts > 0, "max attempts should be greater than zero");
+
+        let initial_backoff = value
+            .initial_backoff
+            .map(|initial_backoff| {
+                Duration::try_from(initial_backoff).context("invalid initial backoff")
+            })
+            .transpose()?
+            .unwrap_or(default.initial_backoff);
+
+        let max_backoff = value
+            .max_backoff
+            .map(|max_backoff| Duration::try_from(max_backoff).context("invalid max backoff"))
+            .transpose()?
+            .unwrap_or(default.max_backoff);
+
+        let backoff_multiplier = value
+            .backoff_multiplier
+            .unwrap_or(default.backoff_multiplier);
+
+        Ok(Self {
+            max_attempts,
+            initial_backoff,
+            max_backoff,
+            backoff_multiplier,
         })
     }
 }
@@ -0,0 +1,312 @@
+use std::{collections::HashSet, pin::Pin};
+
+use anyhow::{Context, Result};
+use stag_api::{
+    event::{Event, EventHandler},
+    types::ics::core::ics24_host::identifier::ChainId,
+};
+use tokio::sync::broadcast::{channel, Sender};
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    Stream, StreamExt,
+};
+use tonic::{async_trait, Request, Response, Status};
+
+use crate::proto::events::{events_server::Events, Event as EventMessage, SubscribeRequest};
+
+const DEFAULT_SUBSCRIPTION_CAPACITY: usize = 1024;
+
+/// Event handler which broadcasts events to all the active subscribers of `Events` service
+#[derive(Clone)]
+pub struct BroadcastEventHandler {
+    sender: Sender<Event>,
+}
+
+impl BroadcastEventHandler {
+    pub fn new() -> Self {
+        let (sender, _) = channel(DEFAULT_SUBSCRIPTION_CAPACITY);
+        Self { sender }
+    }
+}
+
+#[async_trait]
+impl EventHandler for BroadcastEventHandler {
+    async fn handle_event(&self, event: Event) -> Result<()> {
+        // Sending only fails when there are no subscribers, in which case, event can be discarded
+        let _ = self.sender.send(event);
+        Ok(())
+    }
+}
+
+pub struct EventsService {
+    event_handler: BroadcastEventHandler,
+}
+
+impl EventsService {
+    pub fn new(event_handler: BroadcastEventHandler) -> Self {
+        Self { event_handler }
+    }
+}
+
+#[async_trait]
+impl Events for EventsService {
+    type SubscribeStream = Pin<Box<dyn Stream<Item = Result<EventMessage, Status>> + Send>>;
+
+    async fn subscribe(
+        &self,
+        request: Request<SubscribeRequest>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        let filter = SubscribeFilter::try_from(request.into_inner())
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        let receiver = self.event_handler.sender.subscribe();
+
+        let stream = BroadcastStream::new(receiver).filter_map(move |event| match event {
+            Ok(event) if filter.matches(&event) => {
+                Some(to_event_message(&event).map_err(|err| Status::internal(err.to_string())))
+            }
+            Ok(_) => None,
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                Some(Err(Status::resource_exhausted(format!(
+                    "subscriber lagged behind and missed {} events",
+                    skipped
+                ))))
+            }
+        });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+struct SubscribeFilter {
+    chain_id: Option<ChainId>,
+    event_types: HashSet<String>,
+    request_id: Option<String>,
+}
+
+impl TryFrom<SubscribeRequest> for SubscribeFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(request: SubscribeRequest) -> Result<Self, Self::Error> {
+        let chain_id = request
+            .chain_id
+            .map(|chain_id| chain_id.parse())
+            .transpose()
+            .context("invalid chain id")?;
+
+        Ok(Self {
+            chain_id,
+            event_types: request.event_types.into_iter().collect(),
+            request_id: request.request_id,
+        })
+    }
+}
+
+impl SubscribeFilter {
+    fn matches(&self, event: &Event) -> bool {
+        if !self.event_types.is_empty() && !self.event_types.contains(event.event_type()) {
+            return false;
+        }
+
+        if self.chain_id.is_some() && event.chain_id() != self.chain_id.as_ref() {
+            return false;
+        }
+
+        if let Some(ref request_id) = self.request_id {
+            if event.request_id() != Some(request_id.as_str()) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn to_event_message(event: &Event) -> Result<EventMessage> {
+    let data = serde_json::to_string(event).context("failed to serialize event")?;
+
+    Ok(EventMessage {
+        event_type: event.event_type().to_owned(),
+        chain_id: event.chain_id().map(ToString::to_string),
+        request_id: event.request_id().map(ToOwned::to_owned),
+        data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use stag_api::types::ics::core::ics24_host::identifier::ClientId;
+
+    use super::*;
+
+    fn chain_added(chain_id: &str) -> Event {
+        Event::ChainAdded {
+            chain_id: chain_id.parse().unwrap(),
+        }
+    }
+
+    fn created_solo_machine_client(chain_id: &str, request_id: Option<&str>) -> Event {
+        Event::CreatedSoloMachineClient {
+            chain_id: chain_id.parse().unwrap(),
+            request_id: request_id.map(ToOwned::to_owned),
+            client_id: "06-solomachine-0".parse::<ClientId>().unwrap(),
+        }
+    }
+
+    fn warning() -> Event {
+        Event::Warning {
+            message: "warning".to_owned(),
+        }
+    }
+
+    fn filter(
+        chain_id: Option<&str>,
+        event_types: &[&str],
+        request_id: Option<&str>,
+    ) -> SubscribeFilter {
+        SubscribeFilter::try_from(SubscribeRequest {
+            chain_id: chain_id.map(ToOwned::to_owned),
+            event_types: event_types.iter().map(ToString::to_string).collect(),
+            request_id: request_id.map(ToOwned::to_owned),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_subscribe_filter_invalid_chain_id() {
+        let result = SubscribeFilter::try_from(SubscribeRequest {
+            chain_id: Some("invalid chain id".to_owned()),
+            event_types: Vec::new(),
+            request_id: None,
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_subscribe_filter_matches() {
+        let filter_all = filter(None, &[], None);
+        assert!(filter_all.matches(&chain_added("test-1")));
+        assert!(filter_all.matches(&warning()));
+
+        let by_chain_id = filter(Some("test-1"), &[], None);
+        assert!(by_chain_id.matches(&chain_added("test-1")));
+        assert!(by_chain_id.matches(&created_solo_machine_client("test-1", None)));
+        assert!(!by_chain_id.matches(&chain_added("test-2")));
+        assert!(!by_chain_id.matches(&warning()));
+
+        let by_event_type = filter(None, &["ChainAdded", "Warning"], None);
+        assert!(by_event_type.matches(&chain_added("test-1")));
+        assert!(by_event_type.matches(&warning()));
+        assert!(!by_event_type.matches(&created_solo_machine_client("test-1", None)));
+
+        let by_request_id = filter(None, &[], Some("request-1"));
+        assert!(by_request_id.matches(&created_solo_machine_client("test-1", Some("request-1"))));
+        assert!(!by_request_id.matches(&created_solo_machine_client("test-1", Some("request-2"))));
+        assert!(!by_request_id.matches(&created_solo_machine_client("test-1", None)));
+        assert!(!by_request_id.matches(&chain_added("test-1")));
+
+        let combined = filter(
+            Some("test-1"),
+            &["CreatedSoloMachineClient"],
+            Some("request-1"),
+        );
+        assert!(combined.matches(&created_solo_machine_client("test-1", Some("request-1"))));
+        assert!(!combined.matches(&created_solo_machine_client("test-2", Some("request-1"))));
+        assert!(!combined.matches(&chain_added("test-1")));
+    }
+
+    #[test]
+    fn test_to_event_message() {
+        let message =
+            to_event_message(&created_solo_machine_client("test-1", Some("request-1"))).unwrap();
+
+        assert_eq!(message.event_type, "CreatedSoloMachineClient");
+        assert_eq!(message.chain_id.as_deref(), Some("test-1"));
+        assert_eq!(message.request_id.as_deref(), Some("request-1"));
+
+        let data: serde_json::Value = serde_json::from_str(&message.data).unwrap();
+        assert_eq!(data["type"], "CreatedSoloMachineClient");
+        assert_eq!(data["client_id"], "06-solomachine-0");
+
+        let message = to_event_message(&warning()).unwrap();
+        assert_eq!(message.chain_id, None);
+        assert_eq!(message.request_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_event_handler() {
+        let event_handler = BroadcastEventHandler::new();
+
+        // Events are discarded when there are no subscribers
+        event_handler
+            .handle_event(chain_added("test-1"))
+            .await
+            .unwrap();
+
+        let service = EventsService::new(event_handler.clone());
+        let mut stream = service
+            .subscribe(Request::new(SubscribeRequest {
+                chain_id: Some("test-1".to_owned()),
+                event_types: Vec::new(),
+                request_id: None,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        event_handler
+            .handle_event(chain_added("test-2"))
+            .await
+            .unwrap();
+        event_handler.handle_event(warning()).await.unwrap();
+        event_handler
+            .handle_event(created_solo_machine_client("test-1", Some("request-1")))
+            .await
+            .unwrap();
+
+        let message = stream.next().await.unwrap().unwrap();
+        assert_eq!(message.event_type, "CreatedSoloMachineClient");
+        assert_eq!(message.request_id.as_deref(), Some("request-1"));
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_event_handler_lagged_subscriber() {
+        let event_handler = BroadcastEventHandler::new();
+        let service = EventsService::new(event_handler.clone());
+
+        let mut stream = service
+            .subscribe(Request::new(SubscribeRequest::default()))
+            .await
+            .unwrap()
+            .into_inner();
+
+        for _ in 0..DEFAULT_SUBSCRIPTION_CAPACITY + 1 {
+            event_handler
+                .handle_event(chain_added("test-1"))
+                .await
+                .unwrap();
+        }
+
+        let status = stream.next().await.unwrap().unwrap_err();
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+
+        // Subscriber continues to receive the events which are still buffered
+        let message = stream.next().await.unwrap().unwrap();
+        assert_eq!(message.event_type, "ChainAdded");
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_invalid_chain_id() {
+        let service = EventsService::new(BroadcastEventHandler::new());
+
+        let status = service
+            .subscribe(Request::new(SubscribeRequest {
+                chain_id: Some("invalid chain id".to_owned()),
+                event_types: Vec::new(),
+                request_id: None,
+            }))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+}
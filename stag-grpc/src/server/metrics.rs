@@ -0,0 +1,120 @@
+use std::{convert::Infallible, sync::Arc};
+
+use anyhow::Result;
+use hyper::{
+    header::CONTENT_TYPE,
+    server::conn::AddrIncoming,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, StatusCode,
+};
+use prometheus::{Encoder, IntGaugeVec, Opts, Registry, TextEncoder};
+use stag_api::{
+    signer::Signer,
+    stag::{Stag, StagContext},
+    storage::Storage,
+};
+use tokio::sync::RwLock;
+
+/// Serves Prometheus metrics on `/metrics` endpoint
+pub struct MetricsServer<C>
+where
+    C: StagContext + 'static,
+    C::Signer: Signer,
+    C::Storage: Storage,
+{
+    stag: Arc<RwLock<Stag<C>>>,
+    registry: Registry,
+    solo_machine_sequence: IntGaugeVec,
+}
+
+impl<C> MetricsServer<C>
+where
+    C: StagContext + 'static,
+    C::Signer: Signer,
+    C::Storage: Storage,
+{
+    /// Creates a new metrics server which exports metrics of given registry along with sequence of solo machine on
+    /// each chain (collected from Stag's storage at scrape time)
+    pub fn new(stag: Arc<RwLock<Stag<C>>>, registry: Registry) -> Result<Self> {
+        let solo_machine_sequence = IntGaugeVec::new(
+            Opts::new(
+                "stag_solo_machine_sequence",
+                "Current sequence of solo machine on each chain",
+            ),
+            &["chain_id"],
+        )?;
+        registry.register(Box::new(solo_machine_sequence.clone()))?;
+
+        Ok(Self {
+            stag,
+            registry,
+            solo_machine_sequence,
+        })
+    }
+
+    /// Serves metrics on given incoming connections until the server fails
+    pub async fn serve(self, incoming: AddrIncoming) -> Result<()> {
+        let metrics_server = Arc::new(self);
+
+        let make_service = make_service_fn(move |_| {
+            let metrics_server = metrics_server.clone();
+
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    let metrics_server = metrics_server.clone();
+                    async move { Ok::<_, Infallible>(metrics_server.handle(request).await) }
+                }))
+            }
+        });
+
+        hyper::Server::builder(incoming).serve(make_service).await?;
+
+        Ok(())
+    }
+
+    async fn handle(&self, request: Request<Body>) -> Response<Body> {
+        if request.uri().path() != "/metrics" {
+            return status_response(StatusCode::NOT_FOUND);
+        }
+
+        if request.method() != Method::GET {
+            return status_response(StatusCode::METHOD_NOT_ALLOWED);
+        }
+
+        if let Err(err) = self.update_solo_machine_sequence().await {
+            tracing::warn!("failed to update solo machine sequence metrics: {:#}", err);
+        }
+
+        let encoder = TextEncoder::new();
+        let mut buffer = Vec::new();
+
+        match encoder.encode(&self.registry.gather(), &mut buffer) {
+            Ok(()) => Response::builder()
+                .header(CONTENT_TYPE, encoder.format_type())
+                .body(Body::from(buffer))
+                .unwrap_or_else(|_| status_response(StatusCode::INTERNAL_SERVER_ERROR)),
+            Err(err) => {
+                tracing::error!("failed to encode metrics: {}", err);
+                status_response(StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        }
+    }
+
+    async fn update_solo_machine_sequence(&self) -> Result<()> {
+        let chains = self.stag.read().await.get_all_chains(None, None).await?;
+
+        for chain in chains {
+            self.solo_machine_sequence
+                .with_label_values(&[&chain.id.to_string()])
+                .set(chain.sequence.into());
+        }
+
+        Ok(())
+    }
+}
+
+fn status_response(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
@@ -1,7 +1,8 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use anyhow::{Context, Error};
 use stag_api::{
+    service::KeyRotationStatus,
     signer::{MnemonicSigner, Signer, SignerConfig},
     stag::{Stag, StagContext, WithTransaction},
     storage::TransactionProvider,
@@ -11,9 +12,14 @@ use stag_api::{
 use tokio::sync::{Mutex, RwLock};
 use tonic::{async_trait, Request, Response, Status};
 
-use crate::proto::mnemonic_signer::{
-    mnemonic_signer_server, AddChainConfigRequest, AddChainConfigResponse,
-    UpdateChainConfigRequest, UpdateChainConfigResponse,
+use crate::{
+    proto::mnemonic_signer::{
+        mnemonic_signer_server, AddChainConfigRequest, AddChainConfigResponse, ChainConfig,
+        ChainKeyRotation, ListChainConfigsRequest, ListChainConfigsResponse,
+        RemoveChainConfigRequest, RemoveChainConfigResponse, RotateKeyRequest, RotateKeyResponse,
+        UpdateChainConfigRequest, UpdateChainConfigResponse,
+    },
+    signer_store::SignerStore,
 };
 
 pub struct MnemonicSignerService<C>
@@ -25,6 +31,7 @@ where
     C::EventHandler: Clone,
 {
     signer: Mutex<MnemonicSigner>,
+    store: Option<SignerStore>,
     stag: Arc<RwLock<Stag<C>>>,
 }
 
@@ -36,9 +43,15 @@ where
     C::RpcClient: JsonRpcClient + Clone,
     C::EventHandler: Clone,
 {
-    pub fn new(signer: MnemonicSigner, stag: Arc<RwLock<Stag<C>>>) -> Self {
+    /// Creates a new mnemonic signer service (chain configs added at runtime are persisted to `store` when provided)
+    pub fn new(
+        signer: MnemonicSigner,
+        store: Option<SignerStore>,
+        stag: Arc<RwLock<Stag<C>>>,
+    ) -> Self {
         Self {
             signer: Mutex::new(signer),
+            store,
             stag,
         }
     }
@@ -59,7 +72,7 @@ where
     ) -> Result<Response<AddChainConfigResponse>, Status> {
         let request = request.into_inner();
 
-        let chain_id = request
+        let chain_id: ChainId = request
             .chain_id
             .parse()
             .context("invalid chain id")
@@ -79,10 +92,11 @@ where
             .map_err(|err: Error| Status::invalid_argument(err.to_string()))?;
 
         let mut signer = self.signer.lock().await;
+        let mut new_signer = signer.clone();
 
-        signer
+        new_signer
             .add_chain_config(
-                chain_id,
+                chain_id.clone(),
                 &mnemonic,
                 hd_path.as_deref(),
                 account_prefix.as_deref(),
@@ -91,6 +105,21 @@ where
             .context("failed to add chain config")
             .map_err(|err| Status::internal(err.to_string()))?;
 
+        if let Some(ref store) = self.store {
+            store
+                .add_chain_config(
+                    &chain_id,
+                    &mnemonic,
+                    hd_path.as_deref(),
+                    account_prefix.as_deref(),
+                    algo,
+                )
+                .context("failed to persist chain config")
+                .map_err(|err| Status::internal(err.to_string()))?;
+        }
+
+        *signer = new_signer;
+
         self.stag
             .write()
             .await
@@ -164,7 +193,7 @@ where
 
         signer
             .update_chain_config(
-                chain_id,
+                chain_id.clone(),
                 &mnemonic,
                 hd_path.as_deref(),
                 account_prefix.as_deref(),
@@ -179,6 +208,211 @@ where
             .set_signer(signer.clone())
             .map_err(|err| Status::internal(err.to_string()))?;
 
+        if let Some(ref store) = self.store {
+            // Signer is already updated on chain at this point, so the error only reports failure of persistence
+            store
+                .update_chain_config(
+                    &chain_id,
+                    &mnemonic,
+                    hd_path.as_deref(),
+                    account_prefix.as_deref(),
+                    algo,
+                )
+                .context("signer is updated but failed to persist chain config")
+                .map_err(|err| Status::internal(err.to_string()))?;
+        }
+
         Ok(Response::new(UpdateChainConfigResponse {}))
     }
+
+    async fn list_chain_configs(
+        &self,
+        _request: Request<ListChainConfigsRequest>,
+    ) -> Result<Response<ListChainConfigsResponse>, Status> {
+        let mut chain_configs = self
+            .signer
+            .lock()
+            .await
+            .get_chain_configs()
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        chain_configs.sort_by_key(|chain_config| chain_config.chain_id.to_string());
+
+        let chain_configs = chain_configs
+            .into_iter()
+            .map(|chain_config| ChainConfig {
+                chain_id: chain_config.chain_id.to_string(),
+                account_address: chain_config.account_address,
+                hd_path: chain_config.hd_path,
+                account_prefix: chain_config.account_prefix,
+                algo: chain_config.algo.to_string(),
+            })
+            .collect();
+
+        Ok(Response::new(ListChainConfigsResponse { chain_configs }))
+    }
+
+    async fn remove_chain_config(
+        &self,
+        request: Request<RemoveChainConfigRequest>,
+    ) -> Result<Response<RemoveChainConfigResponse>, Status> {
+        let chain_id: ChainId = request
+            .into_inner()
+            .chain_id
+            .parse()
+            .context("invalid chain id")
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        let mut signer = self.signer.lock().await;
+        let mut new_signer = signer.clone();
+
+        new_signer
+            .remove_chain_config(&chain_id)
+            .map_err(|err| Status::not_found(err.to_string()))?;
+
+        if let Some(ref store) = self.store {
+            store
+                .remove_chain_config(&chain_id)
+                .context("failed to persist removal of chain config")
+                .map_err(|err| Status::internal(err.to_string()))?;
+        }
+
+        *signer = new_signer;
+
+        self.stag
+            .write()
+            .await
+            .set_signer(signer.clone())
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        Ok(Response::new(RemoveChainConfigResponse {}))
+    }
+
+    async fn rotate_key(
+        &self,
+        request: Request<RotateKeyRequest>,
+    ) -> Result<Response<RotateKeyResponse>, Status> {
+        let request = request.into_inner();
+
+        let request_id = request.request_id;
+
+        let mnemonic = request.mnemonic;
+
+        let hd_path = request.hd_path;
+
+        let algo = request
+            .algo
+            .map(|algo| algo.parse())
+            .transpose()
+            .context("invalid algo")
+            .map_err(|err: Error| Status::invalid_argument(err.to_string()))?;
+
+        let memo = request.memo.unwrap_or_default();
+
+        // Signer stays locked for the whole rotation so that chain configs cannot change in between
+        let mut signer = self.signer.lock().await;
+
+        let chain_configs = signer
+            .get_chain_configs()
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        let mut new_signer = MnemonicSigner::new();
+
+        for chain_config in chain_configs.iter() {
+            new_signer
+                .add_chain_config(
+                    chain_config.chain_id.clone(),
+                    &mnemonic,
+                    Some(hd_path.as_deref().unwrap_or(&chain_config.hd_path)),
+                    Some(&chain_config.account_prefix),
+                    Some(algo.unwrap_or(chain_config.algo)),
+                )
+                .context("invalid new signer config")
+                .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        }
+
+        let report = self
+            .stag
+            .read()
+            .await
+            .rotate_key(
+                &new_signer
+                    .into_signer()
+                    .map_err(|err| Status::internal(err.to_string()))?,
+                request_id,
+                memo,
+            )
+            .await
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        let mut persistence_errors = HashMap::new();
+
+        for chain_config in chain_configs {
+            if !report.should_switch(&chain_config.chain_id) {
+                continue;
+            }
+
+            let hd_path = hd_path.as_deref().unwrap_or(&chain_config.hd_path);
+            let algo = algo.unwrap_or(chain_config.algo);
+
+            // Signer is already updated on chain at this point, so the errors are reported per chain instead of
+            // failing the whole request (which would hide the outcome of rotation on other chains)
+            if let Err(err) = signer.update_chain_config(
+                chain_config.chain_id.clone(),
+                &mnemonic,
+                Some(hd_path),
+                Some(&chain_config.account_prefix),
+                Some(algo),
+            ) {
+                persistence_errors.insert(
+                    chain_config.chain_id,
+                    format!("key is rotated but failed to update chain config: {}", err),
+                );
+                continue;
+            }
+
+            if let Some(ref store) = self.store {
+                if let Err(err) = store.update_chain_config(
+                    &chain_config.chain_id,
+                    &mnemonic,
+                    Some(hd_path),
+                    Some(&chain_config.account_prefix),
+                    Some(algo),
+                ) {
+                    persistence_errors.insert(
+                        chain_config.chain_id,
+                        format!("key is rotated but failed to persist chain config: {}", err),
+                    );
+                }
+            }
+        }
+
+        let chains = report
+            .chains
+            .into_iter()
+            .map(|chain| {
+                let (updated, verified, error) = match chain.status {
+                    KeyRotationStatus::Rotated => (true, true, None),
+                    KeyRotationStatus::Unverified(err) => (true, false, Some(err)),
+                    KeyRotationStatus::Failed(err) => (false, false, Some(err)),
+                };
+
+                ChainKeyRotation {
+                    error: persistence_errors.remove(&chain.chain_id).or(error),
+                    chain_id: chain.chain_id.to_string(),
+                    new_public_key: chain.new_public_key.as_ref().map(ToString::to_string),
+                    updated,
+                    verified,
+                }
+            })
+            .collect();
+
+        self.stag
+            .write()
+            .await
+            .set_signer(signer.clone())
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        Ok(Response::new(RotateKeyResponse { chains }))
+    }
 }
@@ -1,5 +1,8 @@
+pub mod auth;
 pub mod core;
+pub mod events;
 pub mod ica;
+pub mod metrics;
 #[cfg(feature = "mnemonic-signer")]
 pub mod mnemonic_signer;
 pub mod query;
@@ -7,50 +10,200 @@ pub mod transfer;
 
 use std::{net::SocketAddr, sync::Arc};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use hyper::server::conn::AddrIncoming;
+use prometheus::Registry;
+#[cfg(feature = "mnemonic-signer")]
+use stag_api::signer::MnemonicSigner;
 #[cfg(feature = "postgres-storage")]
 use stag_api::storage::Postgres;
 #[cfg(feature = "sqlite-storage")]
 use stag_api::storage::Sqlite;
 use stag_api::{
-    event::TracingEventHandler, signer::MnemonicSigner, stag::Stag, tendermint::ReqwestClient,
+    event::{
+        EventDispatcher, EventFilter, EventHandler, EventHandlerConfig, MetricsEventHandler,
+        StorageEventHandler, TracingEventHandler, WebhookEventHandler,
+    },
+    signer::{RemoteSigner, Signer, SignerConfig},
+    stag::Stag,
+    tendermint::ReqwestClient,
 };
 use tokio::sync::RwLock;
+use tonic::transport::{Certificate, Identity, ServerTlsConfig};
+use tower::util::option_layer;
+use url::Url;
 
 #[cfg(feature = "mnemonic-signer")]
 use self::mnemonic_signer::MnemonicSignerService;
 use self::{
+    auth::{AuthLayer, AuthPolicy},
     core::CoreService,
+    events::{BroadcastEventHandler, EventsService},
     ica::{bank::IcaBankService, staking::IcaStakingService},
+    metrics::MetricsServer,
     query::QueryService,
     transfer::TransferService,
 };
 #[cfg(feature = "mnemonic-signer")]
-use crate::proto::mnemonic_signer::mnemonic_signer_server::MnemonicSignerServer;
-use crate::proto::{
-    core::core_server::CoreServer,
-    ica::{bank::ica_bank_server::IcaBankServer, staking::ica_staking_server::IcaStakingServer},
-    query::query_server::QueryServer,
-    transfer::transfer_server::TransferServer,
+use crate::{
+    proto::mnemonic_signer::mnemonic_signer_server::MnemonicSignerServer, signer_store::SignerStore,
+};
+use crate::{
+    proto::{
+        core::core_server::CoreServer,
+        events::events_server::EventsServer,
+        ica::{
+            bank::ica_bank_server::IcaBankServer, staking::ica_staking_server::IcaStakingServer,
+        },
+        query::query_server::QueryServer,
+        transfer::transfer_server::TransferServer,
+    },
+    telemetry::request_span,
 };
 
 pub struct Server {
     addr: SocketAddr,
     db_uri: String,
+    remote_signer: Option<RemoteSigner>,
+    log_events: bool,
+    store_events: bool,
+    webhook_event_handler: Option<(WebhookEventHandler, EventFilter)>,
+    metrics_addr: Option<SocketAddr>,
+    tls_config: Option<ServerTlsConfig>,
+    auth_policy: Option<Arc<AuthPolicy>>,
+    #[cfg(feature = "mnemonic-signer")]
+    signer_store: Option<SignerStore>,
+    #[cfg(feature = "mnemonic-signer")]
+    persist_signer: bool,
 }
 
 impl Server {
     pub fn new(addr: SocketAddr, db_uri: String) -> Self {
-        Self { addr, db_uri }
+        Self {
+            addr,
+            db_uri,
+            remote_signer: None,
+            log_events: true,
+            store_events: false,
+            webhook_event_handler: None,
+            metrics_addr: None,
+            tls_config: None,
+            auth_policy: None,
+            #[cfg(feature = "mnemonic-signer")]
+            signer_store: None,
+            #[cfg(feature = "mnemonic-signer")]
+            persist_signer: false,
+        }
+    }
+
+    /// Forwards all the signing requests to given external signing service (`MnemonicSigner` service is not available
+    /// when remote signer is used)
+    pub fn with_remote_signer(mut self, remote_signer: RemoteSigner) -> Self {
+        self.remote_signer = Some(remote_signer);
+        self
+    }
+
+    /// Enables or disables logging of events using tracing (enabled by default)
+    pub fn with_event_logging(mut self, enabled: bool) -> Self {
+        self.log_events = enabled;
+        self
+    }
+
+    /// Enables or disables storing of events in server's database (disabled by default)
+    pub fn with_event_storage(mut self, enabled: bool) -> Self {
+        self.store_events = enabled;
+        self
+    }
+
+    /// Sends events of given types (all the events except telemetry events when `events` is empty) to given webhook
+    /// URLs with requests signed using given secret (pending deliveries are persisted in server's database)
+    pub fn with_webhook_event_handler(
+        mut self,
+        urls: Vec<Url>,
+        secret: Option<String>,
+        events: Vec<String>,
+    ) -> Self {
+        let mut webhook_event_handler = WebhookEventHandler::new(urls);
+
+        if let Some(secret) = secret {
+            webhook_event_handler = webhook_event_handler.with_secret(secret);
+        }
+
+        let filter = if events.is_empty() {
+            EventFilter::All
+        } else {
+            EventFilter::only(events)
+        };
+
+        self.webhook_event_handler = Some((webhook_event_handler, filter));
+        self
+    }
+
+    /// Exports Prometheus metrics on `/metrics` HTTP endpoint of given address
+    pub fn with_metrics(mut self, addr: SocketAddr) -> Self {
+        self.metrics_addr = Some(addr);
+        self
+    }
+
+    /// Serves gRPC over TLS using given server identity (clients must present a certificate signed by given CA when
+    /// `client_ca` is provided, i.e., mutual TLS)
+    pub fn with_tls(mut self, identity: Identity, client_ca: Option<Certificate>) -> Self {
+        let mut tls_config = ServerTlsConfig::new().identity(identity);
+
+        if let Some(client_ca) = client_ca {
+            tls_config = tls_config.client_ca_root(client_ca);
+        }
+
+        self.tls_config = Some(tls_config);
+        self
+    }
+
+    /// Authenticates and authorizes all the gRPC calls using given policy (all the calls are allowed by default)
+    pub fn with_auth_policy(mut self, auth_policy: AuthPolicy) -> Self {
+        self.auth_policy = Some(Arc::new(auth_policy));
+        self
+    }
+
+    /// Loads mnemonic signer from given store at startup (chain configs added, updated or removed using
+    /// `MnemonicSigner` service are written back to the store when `persist` is `true`)
+    #[cfg(feature = "mnemonic-signer")]
+    pub fn with_signer_store(mut self, signer_store: SignerStore, persist: bool) -> Self {
+        self.signer_store = Some(signer_store);
+        self.persist_signer = persist;
+        self
     }
 
     pub async fn run(&self) -> Result<()> {
         cfg_if::cfg_if! {
             if #[cfg(feature = "mnemonic-signer")] {
-                let signer = MnemonicSigner::new();
+                let mnemonic_signer = match self.signer_store {
+                    Some(ref signer_store) => signer_store.load().with_context(|| {
+                        format!("failed to load signer from {}", signer_store.path().display())
+                    })?,
+                    None => MnemonicSigner::new(),
+                };
+
+                let signer_store = if self.persist_signer {
+                    self.signer_store.clone()
+                } else {
+                    None
+                };
             }
         }
 
+        let signer: Arc<dyn Signer> = match self.remote_signer {
+            Some(ref remote_signer) => Arc::new(remote_signer.clone().into_signer()?),
+            None => {
+                cfg_if::cfg_if! {
+                    if #[cfg(feature = "mnemonic-signer")] {
+                        Arc::new(mnemonic_signer.clone().into_signer()?)
+                    } else {
+                        anyhow::bail!("remote signer must be provided when mnemonic signer is disabled")
+                    }
+                }
+            }
+        };
+
         cfg_if::cfg_if! {
             if #[cfg(feature = "sqlite-storage")] {
                 let storage = Sqlite::new(&self.db_uri);
@@ -59,28 +212,102 @@ impl Server {
             }
         }
 
+        // Broadcasts events to subscribers of `Events` service
+        let broadcast_event_handler = BroadcastEventHandler::new();
+
+        // Events are handled in background so that slow webhooks do not delay responses
+        let mut event_dispatcher = EventDispatcher::new()
+            .with_handler(Arc::new(broadcast_event_handler.clone()) as Arc<dyn EventHandler>);
+
+        if self.log_events {
+            event_dispatcher = event_dispatcher.with_handler(TracingEventHandler);
+        }
+
+        if self.store_events {
+            // Storage event handler selects all the events (including telemetry events) by default
+            event_dispatcher =
+                event_dispatcher.with_handler(StorageEventHandler::new(storage.clone()));
+        }
+
+        if let Some((ref webhook_event_handler, ref filter)) = self.webhook_event_handler {
+            // Outbox shares the connection pool of server's storage (a separate pool would use a different database
+            // for in-memory SQLite)
+            event_dispatcher = event_dispatcher.with_filtered_handler(
+                webhook_event_handler.clone().with_outbox(&storage),
+                filter.clone(),
+            );
+        }
+
+        let metrics_registry = match self.metrics_addr {
+            Some(_) => {
+                let registry = Registry::new();
+
+                let metrics_event_handler = MetricsEventHandler::new()?;
+                metrics_event_handler.register(&registry)?;
+                event_dispatcher = event_dispatcher.with_handler(metrics_event_handler);
+
+                Some(registry)
+            }
+            None => None,
+        };
+
+        let event_handler: Arc<dyn EventHandler> = Arc::new(
+            event_dispatcher
+                .with_failure_hook(|failure| tracing::warn!("{}", failure))
+                .into_event_handler(),
+        );
+
         let stag = Arc::new(RwLock::new(
             Stag::builder()
-                .with_signer(signer.clone())?
+                .with_signer(signer)?
                 .with_storage(storage)
                 .await?
                 .with_rpc_client(ReqwestClient)
-                .with_event_handler(TracingEventHandler)
+                .with_event_handler(event_handler)
                 .build(),
         ));
 
-        let mut service = tonic::transport::Server::builder()
+        if let (Some(addr), Some(registry)) = (self.metrics_addr, metrics_registry) {
+            let metrics_server = MetricsServer::new(stag.clone(), registry)?;
+            let incoming = AddrIncoming::bind(&addr)
+                .with_context(|| format!("failed to bind metrics server to {}", addr))?;
+
+            tokio::spawn(async move {
+                if let Err(err) = metrics_server.serve(incoming).await {
+                    tracing::error!("metrics server failed: {:#}", err);
+                }
+            });
+        }
+
+        let mut server = tonic::transport::Server::builder();
+
+        if let Some(ref tls_config) = self.tls_config {
+            server = server
+                .tls_config(tls_config.clone())
+                .context("invalid tls configuration")?;
+        }
+
+        let service = server
+            .trace_fn(request_span)
+            .layer(option_layer(self.auth_policy.clone().map(AuthLayer::new)))
             .add_service(CoreServer::new(CoreService::new(stag.clone())))
+            .add_service(EventsServer::new(EventsService::new(
+                broadcast_event_handler,
+            )))
             .add_service(TransferServer::new(TransferService::new(stag.clone())))
             .add_service(IcaBankServer::new(IcaBankService::new(stag.clone())))
             .add_service(IcaStakingServer::new(IcaStakingService::new(stag.clone())))
             .add_service(QueryServer::new(QueryService::new(stag.clone())));
 
-        cfg_if::cfg_if! {
-            if #[cfg(feature = "mnemonic-signer")] {
-                service = service.add_service(MnemonicSignerServer::new(MnemonicSignerService::new(signer, stag)));
-            }
-        }
+        // `MnemonicSigner` service is not available when remote signer is used
+        #[cfg(feature = "mnemonic-signer")]
+        let service = service.add_optional_service(self.remote_signer.is_none().then(|| {
+            MnemonicSignerServer::new(MnemonicSignerService::new(
+                mnemonic_signer,
+                signer_store,
+                stag,
+            ))
+        }));
 
         service.serve(self.addr).await?;
 
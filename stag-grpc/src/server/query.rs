@@ -1,12 +1,14 @@
-use std::{str::FromStr, sync::Arc};
+use std::{str::FromStr, sync::Arc, time::Duration};
 
-use anyhow::{Context, Error};
-use prost_types::Timestamp;
+use anyhow::{Context, Error, Result};
+use chrono::{DateTime, Utc};
+use prost_types::{Duration as DurationProto, Timestamp};
 use stag_api::{
     signer::Signer,
     stag::{Stag, StagContext},
     storage::Storage,
     types::{
+        chain_state,
         ics::core::ics24_host::identifier::PortId,
         operation::{Operation, OperationType},
     },
@@ -15,9 +17,13 @@ use tokio::sync::RwLock;
 use tonic::{async_trait, Request, Response, Status};
 
 use crate::proto::query::{
-    op::OpType, query_server::Query, BurnOperation, GetBalanceRequest, GetBalanceResponse,
-    GetHistoryRequest, GetHistoryResponse, GetIbcDenomRequest, GetIbcDenomResponse,
-    IcaDelegateOperation, IcaSendOperation, IcaUndelegateOperation, MintOperation, Op,
+    op::OpType, query_server::Query, BurnOperation, Chain, ChainConfig, ChainKey, ChannelDetails,
+    ConnectionDetails, Fee, GetBalanceRequest, GetBalanceResponse, GetChainRequest,
+    GetChainResponse, GetHistoryRequest, GetHistoryResponse, GetIbcBalanceRequest,
+    GetIbcBalanceResponse, GetIbcDenomRequest, GetIbcDenomResponse, GetIcaAddressRequest,
+    GetIcaAddressResponse, GetPublicKeysRequest, GetPublicKeysResponse, IcaDelegateOperation,
+    IcaSendOperation, IcaUndelegateOperation, ListChainsRequest, ListChainsResponse, MintOperation,
+    Op, RetryPolicy,
 };
 
 pub struct QueryService<C>
@@ -150,6 +156,253 @@ where
 
         Ok(Response::new(GetIbcDenomResponse { ibc_denom }))
     }
+
+    async fn get_chain(
+        &self,
+        request: Request<GetChainRequest>,
+    ) -> Result<Response<GetChainResponse>, Status> {
+        let request = request.into_inner();
+
+        let chain_id = request
+            .chain_id
+            .parse()
+            .context("invalid chain id")
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        let chain_state = self
+            .stag
+            .read()
+            .await
+            .get_chain(&chain_id)
+            .await
+            .map_err(|err| Status::internal(err.to_string()))?
+            .ok_or_else(|| Status::not_found(format!("chain with id {} not found", chain_id)))?;
+
+        Ok(Response::new(GetChainResponse {
+            chain: Some(
+                chain_state
+                    .try_into()
+                    .map_err(|err: Error| Status::internal(err.to_string()))?,
+            ),
+        }))
+    }
+
+    async fn list_chains(
+        &self,
+        request: Request<ListChainsRequest>,
+    ) -> Result<Response<ListChainsResponse>, Status> {
+        let request = request.into_inner();
+
+        let chain_states = self
+            .stag
+            .read()
+            .await
+            .get_all_chains(request.limit, request.offset)
+            .await
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        let chains = chain_states
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_>>()
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        Ok(Response::new(ListChainsResponse { chains }))
+    }
+
+    async fn get_public_keys(
+        &self,
+        request: Request<GetPublicKeysRequest>,
+    ) -> Result<Response<GetPublicKeysResponse>, Status> {
+        let request = request.into_inner();
+
+        let chain_id = request
+            .chain_id
+            .parse()
+            .context("invalid chain id")
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        let chain_keys = self
+            .stag
+            .read()
+            .await
+            .get_public_keys(&chain_id, request.limit, request.offset)
+            .await
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        let public_keys = chain_keys
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_>>()
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        Ok(Response::new(GetPublicKeysResponse { public_keys }))
+    }
+
+    async fn get_ica_address(
+        &self,
+        request: Request<GetIcaAddressRequest>,
+    ) -> Result<Response<GetIcaAddressResponse>, Status> {
+        let request = request.into_inner();
+
+        let chain_id = request
+            .chain_id
+            .parse()
+            .context("invalid chain id")
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        let ica_address = self
+            .stag
+            .read()
+            .await
+            .get_ica_address(&chain_id)
+            .await
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        Ok(Response::new(GetIcaAddressResponse { ica_address }))
+    }
+
+    async fn get_ibc_balance(
+        &self,
+        request: Request<GetIbcBalanceRequest>,
+    ) -> Result<Response<GetIbcBalanceResponse>, Status> {
+        let request = request.into_inner();
+
+        let chain_id = request
+            .chain_id
+            .parse()
+            .context("invalid chain id")
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        let denom = request
+            .denom
+            .parse()
+            .context("invalid denom")
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        let port_id = match request.port_id {
+            Some(port_id) => port_id
+                .parse()
+                .context("invalid port id")
+                .map_err(|err| Status::invalid_argument(err.to_string()))?,
+            None => PortId::transfer(),
+        };
+
+        let balance = self
+            .stag
+            .read()
+            .await
+            .get_ibc_balance(&chain_id, &port_id, &denom)
+            .await
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        Ok(Response::new(GetIbcBalanceResponse {
+            balance: balance.to_string(),
+        }))
+    }
+}
+
+impl TryFrom<chain_state::ChainState> for Chain {
+    type Error = Error;
+
+    fn try_from(chain_state: chain_state::ChainState) -> Result<Self, Self::Error> {
+        Ok(Chain {
+            id: chain_state.id.to_string(),
+            node_id: chain_state.node_id.to_string(),
+            config: Some(chain_state.config.try_into()?),
+            consensus_timestamp: Some(to_timestamp(chain_state.consensus_timestamp)?),
+            sequence: chain_state.sequence,
+            connection_details: chain_state.connection_details.map(Into::into),
+            created_at: Some(to_timestamp(chain_state.created_at)?),
+            updated_at: Some(to_timestamp(chain_state.updated_at)?),
+        })
+    }
+}
+
+impl TryFrom<chain_state::ChainConfig> for ChainConfig {
+    type Error = Error;
+
+    fn try_from(config: chain_state::ChainConfig) -> Result<Self, Self::Error> {
+        Ok(ChainConfig {
+            grpc_addr: config.grpc_addr.to_string(),
+            rpc_addr: config.rpc_addr.to_string(),
+            fallback_grpc_addrs: config
+                .fallback_grpc_addrs
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            fallback_rpc_addrs: config
+                .fallback_rpc_addrs
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            witness_rpc_addrs: config
+                .witness_rpc_addrs
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            fee: Some(Fee {
+                amount: config.fee.amount.to_string(),
+                denom: config.fee.denom.to_string(),
+                gas_limit: config.fee.gas_limit,
+            }),
+            trust_level: config.trust_level.to_string(),
+            trusting_period: Some(to_duration(config.trusting_period)?),
+            max_clock_drift: Some(to_duration(config.max_clock_drift)?),
+            rpc_timeout: Some(to_duration(config.rpc_timeout)?),
+            diversifier: config.diversifier,
+            trusted_height: config.trusted_height,
+            trusted_hash: hex::encode(config.trusted_hash),
+            packet_timeout_height_offset: config.packet_timeout_height_offset,
+            retry_policy: Some(RetryPolicy {
+                max_attempts: config.retry_policy.max_attempts,
+                initial_backoff: Some(to_duration(config.retry_policy.initial_backoff)?),
+                max_backoff: Some(to_duration(config.retry_policy.max_backoff)?),
+                backoff_multiplier: config.retry_policy.backoff_multiplier,
+            }),
+        })
+    }
+}
+
+impl From<chain_state::ConnectionDetails> for ConnectionDetails {
+    fn from(connection_details: chain_state::ConnectionDetails) -> Self {
+        ConnectionDetails {
+            solo_machine_client_id: connection_details.solo_machine_client_id.to_string(),
+            tendermint_client_id: connection_details.tendermint_client_id.to_string(),
+            solo_machine_connection_id: connection_details.solo_machine_connection_id.to_string(),
+            tendermint_connection_id: connection_details.tendermint_connection_id.to_string(),
+            channels: connection_details
+                .channels
+                .into_iter()
+                .map(|(port_id, channel_details)| (port_id.to_string(), channel_details.into()))
+                .collect(),
+        }
+    }
+}
+
+impl From<chain_state::ChannelDetails> for ChannelDetails {
+    fn from(channel_details: chain_state::ChannelDetails) -> Self {
+        ChannelDetails {
+            packet_sequence: channel_details.packet_sequence,
+            solo_machine_port_id: channel_details.solo_machine_port_id.to_string(),
+            tendermint_port_id: channel_details.tendermint_port_id.to_string(),
+            solo_machine_channel_id: channel_details.solo_machine_channel_id.to_string(),
+            tendermint_channel_id: channel_details.tendermint_channel_id.to_string(),
+        }
+    }
+}
+
+impl TryFrom<chain_state::ChainKey> for ChainKey {
+    type Error = Error;
+
+    fn try_from(chain_key: chain_state::ChainKey) -> Result<Self, Self::Error> {
+        Ok(ChainKey {
+            id: chain_key.id,
+            chain_id: chain_key.chain_id.to_string(),
+            public_key: chain_key.public_key,
+            created_at: Some(to_timestamp(chain_key.created_at)?),
+        })
+    }
 }
 
 impl TryFrom<Vec<Operation>> for GetHistoryResponse {
@@ -166,10 +419,7 @@ impl TryFrom<Vec<Operation>> for GetHistoryResponse {
                 port_id: operation.port_id.to_string(),
                 transaction_hash: operation.transaction_hash,
                 op_type: Some(operation.operation_type.into()),
-                created_at: Some(
-                    Timestamp::from_str(&operation.created_at.to_rfc3339())
-                        .context("unable to parse protobuf timestamp")?,
-                ),
+                created_at: Some(to_timestamp(operation.created_at)?),
             };
 
             ops.push(op);
@@ -222,3 +472,490 @@ impl From<OperationType> for OpType {
         }
     }
 }
+
+fn to_timestamp(time: DateTime<Utc>) -> Result<Timestamp> {
+    Timestamp::from_str(&time.to_rfc3339()).context("unable to parse protobuf timestamp")
+}
+
+fn to_duration(duration: Duration) -> Result<DurationProto> {
+    DurationProto::try_from(duration).context("unable to convert duration to protobuf duration")
+}
+
+#[cfg(all(test, feature = "mnemonic-signer", feature = "sqlite-storage"))]
+mod tests {
+    use cosmos_sdk_proto::cosmos::{
+        bank::v1beta1::{
+            query_server::{Query as BankQuery, QueryServer as BankQueryServer},
+            QueryAllBalancesRequest, QueryAllBalancesResponse, QueryBalanceRequest,
+            QueryBalanceResponse, QueryDenomMetadataRequest, QueryDenomMetadataResponse,
+            QueryDenomsMetadataRequest, QueryDenomsMetadataResponse, QueryParamsRequest,
+            QueryParamsResponse, QuerySpendableBalancesRequest, QuerySpendableBalancesResponse,
+            QuerySupplyOfRequest, QuerySupplyOfResponse, QueryTotalSupplyRequest,
+            QueryTotalSupplyResponse,
+        },
+        base::v1beta1::Coin,
+    };
+    use stag_api::{
+        signer::MnemonicSigner,
+        stag::StagBuilder,
+        storage::Sqlite,
+        types::{
+            chain_state::{ChainState, Fee as ChainFee, RetryPolicy as ChainRetryPolicy},
+            ics::core::ics24_host::identifier::ChainId,
+        },
+    };
+    use tendermint::node::Id as NodeId;
+    use tokio::net::TcpListener;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tonic::{transport::Server, Code};
+    use url::Url;
+
+    use super::*;
+
+    const CHAIN_ID: &str = "test-1";
+    const MNEMONIC: &str = "practice empty client sauce pistol work ticket casual romance appear army fault palace coyote fox super salute slim catch kite wrist three hedgehog sign";
+
+    /// Bank query service which returns a balance of 100 for IBC denoms (and no balance for other denoms)
+    struct MockBankService;
+
+    #[async_trait]
+    impl BankQuery for MockBankService {
+        async fn balance(
+            &self,
+            request: Request<QueryBalanceRequest>,
+        ) -> Result<Response<QueryBalanceResponse>, Status> {
+            let request = request.into_inner();
+
+            let balance = request.denom.starts_with("ibc/").then(|| Coin {
+                denom: request.denom,
+                amount: "100".to_owned(),
+            });
+
+            Ok(Response::new(QueryBalanceResponse { balance }))
+        }
+
+        async fn all_balances(
+            &self,
+            _: Request<QueryAllBalancesRequest>,
+        ) -> Result<Response<QueryAllBalancesResponse>, Status> {
+            Err(Status::unimplemented("all_balances"))
+        }
+
+        async fn spendable_balances(
+            &self,
+            _: Request<QuerySpendableBalancesRequest>,
+        ) -> Result<Response<QuerySpendableBalancesResponse>, Status> {
+            Err(Status::unimplemented("spendable_balances"))
+        }
+
+        async fn total_supply(
+            &self,
+            _: Request<QueryTotalSupplyRequest>,
+        ) -> Result<Response<QueryTotalSupplyResponse>, Status> {
+            Err(Status::unimplemented("total_supply"))
+        }
+
+        async fn supply_of(
+            &self,
+            _: Request<QuerySupplyOfRequest>,
+        ) -> Result<Response<QuerySupplyOfResponse>, Status> {
+            Err(Status::unimplemented("supply_of"))
+        }
+
+        async fn params(
+            &self,
+            _: Request<QueryParamsRequest>,
+        ) -> Result<Response<QueryParamsResponse>, Status> {
+            Err(Status::unimplemented("params"))
+        }
+
+        async fn denom_metadata(
+            &self,
+            _: Request<QueryDenomMetadataRequest>,
+        ) -> Result<Response<QueryDenomMetadataResponse>, Status> {
+            Err(Status::unimplemented("denom_metadata"))
+        }
+
+        async fn denoms_metadata(
+            &self,
+            _: Request<QueryDenomsMetadataRequest>,
+        ) -> Result<Response<QueryDenomsMetadataResponse>, Status> {
+            Err(Status::unimplemented("denoms_metadata"))
+        }
+    }
+
+    async fn start_bank_service() -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(
+            Server::builder()
+                .add_service(BankQueryServer::new(MockBankService))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+
+        format!("http://{}", addr).parse().unwrap()
+    }
+
+    fn chain_config(grpc_addr: Url) -> chain_state::ChainConfig {
+        chain_state::ChainConfig {
+            grpc_addr,
+            rpc_addr: "http://127.0.0.1:26657".parse().unwrap(),
+            fallback_grpc_addrs: Vec::new(),
+            fallback_rpc_addrs: Vec::new(),
+            witness_rpc_addrs: vec!["http://127.0.0.1:26658".parse().unwrap()],
+            fee: ChainFee {
+                amount: "1000".parse().unwrap(),
+                denom: "stake".parse().unwrap(),
+                gas_limit: 300000,
+            },
+            trust_level: "1/3".parse().unwrap(),
+            trusting_period: Duration::from_secs(1209600),
+            max_clock_drift: Duration::from_secs(3),
+            rpc_timeout: Duration::from_secs(60),
+            diversifier: "stag".to_owned(),
+            trusted_height: 1,
+            trusted_hash: [1; 32],
+            packet_timeout_height_offset: 20,
+            retry_policy: ChainRetryPolicy {
+                max_attempts: 1,
+                initial_backoff: Duration::from_millis(100),
+                max_backoff: Duration::from_secs(1),
+                backoff_multiplier: 2,
+            },
+        }
+    }
+
+    /// Returns a query service backed by an in-memory storage containing `test-1` (connected with a transfer channel
+    /// and an ICA address when `connected` is true) and `test-2` chains
+    async fn query_service(
+        connected: bool,
+    ) -> QueryService<impl StagContext<Signer = impl Signer, Storage = impl Storage>> {
+        let mut signer = MnemonicSigner::new();
+        signer
+            .add_chain_config(CHAIN_ID.parse().unwrap(), MNEMONIC, None, None, None)
+            .unwrap();
+
+        let context = StagBuilder::default()
+            .with_signer(signer)
+            .unwrap()
+            .with_storage(Sqlite::new("sqlite::memory:"))
+            .await
+            .unwrap();
+
+        let grpc_addr = start_bank_service().await;
+
+        for chain_id in [CHAIN_ID, "test-2"] {
+            context
+                .storage()
+                .add_chain_state(
+                    chain_id.parse().unwrap(),
+                    NodeId::new([1; 20]),
+                    chain_config(grpc_addr.clone()),
+                )
+                .await
+                .unwrap();
+        }
+
+        context
+            .storage()
+            .add_chain_key(&CHAIN_ID.parse().unwrap(), "public-key-1")
+            .await
+            .unwrap();
+        context
+            .storage()
+            .add_chain_key(&CHAIN_ID.parse().unwrap(), "public-key-2")
+            .await
+            .unwrap();
+
+        if connected {
+            let mut chain_state = get_chain_state(&context).await;
+
+            chain_state.connection_details = Some(chain_state::ConnectionDetails {
+                solo_machine_client_id: "07-tendermint-0".parse().unwrap(),
+                tendermint_client_id: "06-solomachine-0".parse().unwrap(),
+                solo_machine_connection_id: "connection-0".parse().unwrap(),
+                tendermint_connection_id: "connection-1".parse().unwrap(),
+                channels: [(
+                    PortId::transfer(),
+                    chain_state::ChannelDetails {
+                        packet_sequence: 3,
+                        solo_machine_port_id: PortId::transfer(),
+                        tendermint_port_id: PortId::transfer(),
+                        solo_machine_channel_id: "channel-0".parse().unwrap(),
+                        tendermint_channel_id: "channel-1".parse().unwrap(),
+                    },
+                )]
+                .into_iter()
+                .collect(),
+            });
+
+            context
+                .storage()
+                .update_chain_state(&chain_state)
+                .await
+                .unwrap();
+            context
+                .storage()
+                .add_ica_address(
+                    &"connection-0".parse().unwrap(),
+                    &PortId::ica_controller(),
+                    "cosmos1ica",
+                )
+                .await
+                .unwrap();
+        }
+
+        QueryService::new(Arc::new(RwLock::new(context.build())))
+    }
+
+    async fn get_chain_state<C>(context: &C) -> ChainState
+    where
+        C: StagContext,
+        C::Storage: Storage,
+    {
+        context
+            .storage()
+            .get_chain_state(&CHAIN_ID.parse::<ChainId>().unwrap())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_chain() {
+        let service = query_service(true).await;
+
+        let chain = service
+            .get_chain(Request::new(GetChainRequest {
+                chain_id: CHAIN_ID.to_owned(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .chain
+            .unwrap();
+
+        assert_eq!(chain.id, CHAIN_ID);
+        assert_eq!(chain.node_id, hex::encode([1; 20]));
+        assert_eq!(chain.sequence, 1);
+        assert!(chain.consensus_timestamp.is_some());
+        assert!(chain.created_at.is_some());
+        assert!(chain.updated_at.is_some());
+
+        let config = chain.config.unwrap();
+        assert_eq!(config.rpc_addr, "http://127.0.0.1:26657/");
+        assert_eq!(config.witness_rpc_addrs, vec!["http://127.0.0.1:26658/"]);
+        assert_eq!(
+            config.fee,
+            Some(Fee {
+                amount: "1000".to_owned(),
+                denom: "stake".to_owned(),
+                gas_limit: 300000,
+            })
+        );
+        assert_eq!(config.trust_level, "1/3");
+        assert_eq!(config.trusting_period.unwrap().seconds, 1209600);
+        assert_eq!(config.max_clock_drift.unwrap().seconds, 3);
+        assert_eq!(config.trusted_hash, hex::encode([1; 32]));
+        assert_eq!(config.packet_timeout_height_offset, 20);
+
+        let retry_policy = config.retry_policy.unwrap();
+        assert_eq!(retry_policy.max_attempts, 1);
+        assert_eq!(retry_policy.initial_backoff.unwrap().nanos, 100_000_000);
+        assert_eq!(retry_policy.max_backoff.unwrap().seconds, 1);
+        assert_eq!(retry_policy.backoff_multiplier, 2);
+
+        let connection_details = chain.connection_details.unwrap();
+        assert_eq!(connection_details.solo_machine_client_id, "07-tendermint-0");
+        assert_eq!(connection_details.tendermint_connection_id, "connection-1");
+        assert_eq!(
+            connection_details.channels.get("transfer"),
+            Some(&ChannelDetails {
+                packet_sequence: 3,
+                solo_machine_port_id: "transfer".to_owned(),
+                tendermint_port_id: "transfer".to_owned(),
+                solo_machine_channel_id: "channel-0".to_owned(),
+                tendermint_channel_id: "channel-1".to_owned(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_chain_errors() {
+        let service = query_service(false).await;
+
+        let status = service
+            .get_chain(Request::new(GetChainRequest {
+                chain_id: "test-3".to_owned(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+
+        let status = service
+            .get_chain(Request::new(GetChainRequest {
+                chain_id: "invalid chain id".to_owned(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        let chain = service
+            .get_chain(Request::new(GetChainRequest {
+                chain_id: CHAIN_ID.to_owned(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .chain
+            .unwrap();
+        assert_eq!(chain.connection_details, None);
+    }
+
+    #[tokio::test]
+    async fn test_list_chains() {
+        let service = query_service(false).await;
+
+        let chains = service
+            .list_chains(Request::new(ListChainsRequest {
+                limit: None,
+                offset: None,
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .chains;
+        let ids = chains
+            .iter()
+            .map(|chain| chain.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![CHAIN_ID, "test-2"]);
+
+        let chains = service
+            .list_chains(Request::new(ListChainsRequest {
+                limit: Some(1),
+                offset: Some(1),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .chains;
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].id, "test-2");
+    }
+
+    #[tokio::test]
+    async fn test_get_public_keys() {
+        let service = query_service(false).await;
+
+        let public_keys = service
+            .get_public_keys(Request::new(GetPublicKeysRequest {
+                chain_id: CHAIN_ID.to_owned(),
+                limit: None,
+                offset: None,
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .public_keys;
+
+        assert_eq!(public_keys.len(), 2);
+        assert!(public_keys
+            .iter()
+            .all(|chain_key| chain_key.chain_id == CHAIN_ID && chain_key.created_at.is_some()));
+
+        let mut keys = public_keys
+            .iter()
+            .map(|chain_key| chain_key.public_key.as_str())
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        assert_eq!(keys, vec!["public-key-1", "public-key-2"]);
+
+        let public_keys = service
+            .get_public_keys(Request::new(GetPublicKeysRequest {
+                chain_id: CHAIN_ID.to_owned(),
+                limit: Some(1),
+                offset: None,
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .public_keys;
+        assert_eq!(public_keys.len(), 1);
+
+        let public_keys = service
+            .get_public_keys(Request::new(GetPublicKeysRequest {
+                chain_id: "test-2".to_owned(),
+                limit: None,
+                offset: None,
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .public_keys;
+        assert!(public_keys.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_ica_address() {
+        let service = query_service(true).await;
+
+        let ica_address = service
+            .get_ica_address(Request::new(GetIcaAddressRequest {
+                chain_id: CHAIN_ID.to_owned(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .ica_address;
+        assert_eq!(ica_address, "cosmos1ica");
+
+        // `test-2` is not connected
+        let status = service
+            .get_ica_address(Request::new(GetIcaAddressRequest {
+                chain_id: "test-2".to_owned(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::Internal);
+    }
+
+    #[tokio::test]
+    async fn test_get_ibc_balance() {
+        let service = query_service(true).await;
+
+        let balance = service
+            .get_ibc_balance(Request::new(GetIbcBalanceRequest {
+                chain_id: CHAIN_ID.to_owned(),
+                denom: "gld".to_owned(),
+                port_id: None,
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .balance;
+        assert_eq!(balance, "100");
+
+        // ICA channel is not created with the chain
+        let status = service
+            .get_ibc_balance(Request::new(GetIbcBalanceRequest {
+                chain_id: CHAIN_ID.to_owned(),
+                denom: "gld".to_owned(),
+                port_id: Some(PortId::ica_controller().to_string()),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::Internal);
+
+        let status = service
+            .get_ibc_balance(Request::new(GetIbcBalanceRequest {
+                chain_id: CHAIN_ID.to_owned(),
+                denom: "gld".to_owned(),
+                port_id: Some("invalid port id".to_owned()),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+}
@@ -0,0 +1,270 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+use stag_api::{
+    signer::{
+        keystore::{self, Keystore},
+        MnemonicSigner, Zeroizing,
+    },
+    types::{ics::core::ics24_host::identifier::ChainId, public_key::PublicKeyAlgo},
+};
+
+/// Persistent storage of mnemonic signer configuration used by gRPC server
+#[derive(Clone)]
+pub enum SignerStore {
+    /// Signer config file (yaml format, same as `stag` CLI) containing mnemonics in plaintext
+    Config(PathBuf),
+    /// Password encrypted keystore
+    Keystore {
+        /// Path of keystore file
+        path: PathBuf,
+        /// Password of keystore
+        password: Zeroizing<String>,
+    },
+}
+
+impl SignerStore {
+    /// Creates a signer store backed by signer config file (yaml format)
+    pub fn config(path: impl Into<PathBuf>) -> Self {
+        Self::Config(path.into())
+    }
+
+    /// Creates a signer store backed by password encrypted keystore
+    pub fn keystore(path: impl Into<PathBuf>, password: impl Into<String>) -> Self {
+        Self::Keystore {
+            path: path.into(),
+            password: Zeroizing::new(password.into()),
+        }
+    }
+
+    /// Returns the path of underlying file
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Config(path) => path,
+            Self::Keystore { path, .. } => path,
+        }
+    }
+
+    /// Loads mnemonic signer from store (returns an empty mnemonic signer if the file does not exist)
+    pub fn load(&self) -> Result<MnemonicSigner> {
+        match self {
+            Self::Config(path) => SignerConfig::read_or_default(path)?.get_mnemonic_signer(),
+            Self::Keystore { path, password } => Keystore::read_or_new(path)?.decrypt(password),
+        }
+    }
+
+    /// Adds configuration for a chain id to store
+    pub fn add_chain_config(
+        &self,
+        chain_id: &ChainId,
+        mnemonic: &str,
+        hd_path: Option<&str>,
+        account_prefix: Option<&str>,
+        algo: Option<PublicKeyAlgo>,
+    ) -> Result<()> {
+        match self {
+            Self::Config(path) => {
+                let mut signer_config = SignerConfig::read_or_default(path)?;
+
+                ensure!(
+                    signer_config.position(chain_id).is_none(),
+                    "signer config for chain id {} already exists in {}",
+                    chain_id,
+                    path.display()
+                );
+
+                signer_config.chains.push(ChainSignerConfig::new(
+                    chain_id,
+                    mnemonic,
+                    hd_path,
+                    account_prefix,
+                    algo,
+                ));
+
+                signer_config.write(path)
+            }
+            Self::Keystore { path, password } => {
+                let mut keystore = Keystore::read_or_new(path)?;
+
+                keystore.add_key(
+                    chain_id.clone(),
+                    mnemonic,
+                    hd_path,
+                    account_prefix,
+                    algo,
+                    password,
+                )?;
+
+                keystore.write(path)
+            }
+        }
+    }
+
+    /// Replaces configuration for a chain id in store
+    pub fn update_chain_config(
+        &self,
+        chain_id: &ChainId,
+        mnemonic: &str,
+        hd_path: Option<&str>,
+        account_prefix: Option<&str>,
+        algo: Option<PublicKeyAlgo>,
+    ) -> Result<()> {
+        match self {
+            Self::Config(path) => {
+                let mut signer_config = SignerConfig::read_or_default(path)?;
+
+                let position = signer_config.position(chain_id).ok_or_else(|| {
+                    anyhow!(
+                        "signer config for chain id {} does not exist in {}",
+                        chain_id,
+                        path.display()
+                    )
+                })?;
+
+                signer_config.chains[position] =
+                    ChainSignerConfig::new(chain_id, mnemonic, hd_path, account_prefix, algo);
+
+                signer_config.write(path)
+            }
+            Self::Keystore { path, password } => {
+                let mut keystore = Keystore::read_or_new(path)?;
+
+                keystore.remove_key(chain_id)?;
+                keystore.add_key(
+                    chain_id.clone(),
+                    mnemonic,
+                    hd_path,
+                    account_prefix,
+                    algo,
+                    password,
+                )?;
+
+                keystore.write(path)
+            }
+        }
+    }
+
+    /// Removes configuration for a chain id from store
+    pub fn remove_chain_config(&self, chain_id: &ChainId) -> Result<()> {
+        match self {
+            Self::Config(path) => {
+                let mut signer_config = SignerConfig::read_or_default(path)?;
+
+                let position = signer_config.position(chain_id).ok_or_else(|| {
+                    anyhow!(
+                        "signer config for chain id {} does not exist in {}",
+                        chain_id,
+                        path.display()
+                    )
+                })?;
+
+                signer_config.chains.remove(position);
+
+                signer_config.write(path)
+            }
+            Self::Keystore { path, .. } => {
+                let mut keystore = Keystore::read_or_new(path)?;
+                keystore.remove_key(chain_id)?;
+                keystore.write(path)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SignerConfig {
+    /// Signer config for all chains
+    chains: Vec<ChainSignerConfig>,
+}
+
+impl SignerConfig {
+    /// Reads the signer config from given yaml file (returns an empty signer config if the file does not exist)
+    fn read_or_default(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Default::default());
+        }
+
+        let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+
+        serde_yaml::from_slice(&bytes)
+            .with_context(|| format!("failed to parse signer config {}", path.display()))
+    }
+
+    /// Writes the signer config to given yaml file (only readable by current user on unix systems)
+    fn write(&self, path: &Path) -> Result<()> {
+        let bytes = serde_yaml::to_string(self).context("failed to serialize signer config")?;
+
+        keystore::write_private_file(path, bytes.as_bytes())
+    }
+
+    /// Returns the position of signer config for given chain id
+    fn position(&self, chain_id: &ChainId) -> Option<usize> {
+        self.chains
+            .iter()
+            .position(|chain| &chain.chain_id == chain_id)
+    }
+
+    /// Gets the mnemonic signer for current signer config
+    fn get_mnemonic_signer(&self) -> Result<MnemonicSigner> {
+        let mut mnemonic_signer = MnemonicSigner::new();
+
+        for chain in self.chains.iter() {
+            mnemonic_signer
+                .add_chain_config(
+                    chain.chain_id.clone(),
+                    &chain.mnemonic,
+                    chain.hd_path.as_deref(),
+                    chain.account_prefix.as_deref(),
+                    chain
+                        .algo
+                        .as_deref()
+                        .map(str::parse)
+                        .transpose()
+                        .with_context(|| {
+                            format!("invalid algo for chain id: {}", chain.chain_id)
+                        })?,
+                )
+                .with_context(|| {
+                    format!("invalid signer config for chain id: {}", chain.chain_id)
+                })?;
+        }
+
+        Ok(mnemonic_signer)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ChainSignerConfig {
+    /// Chain ID
+    chain_id: ChainId,
+    /// Mnemonic phrase of signer
+    mnemonic: String,
+    /// HD path of signer
+    hd_path: Option<String>,
+    /// Bech32 account prefix of signer
+    account_prefix: Option<String>,
+    /// Algorithm used for address generation [possible values: `secp256k1`, `eth-secp256k1`, `ed25519`, `secp256r1`]
+    algo: Option<String>,
+}
+
+impl ChainSignerConfig {
+    fn new(
+        chain_id: &ChainId,
+        mnemonic: &str,
+        hd_path: Option<&str>,
+        account_prefix: Option<&str>,
+        algo: Option<PublicKeyAlgo>,
+    ) -> Self {
+        Self {
+            chain_id: chain_id.clone(),
+            mnemonic: mnemonic.to_owned(),
+            hd_path: hd_path.map(ToOwned::to_owned),
+            account_prefix: account_prefix.map(ToOwned::to_owned),
+            algo: algo.map(|algo| algo.to_string()),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use anyhow::{Context, Result};
+use opentelemetry::{
+    global,
+    propagation::{Extractor, Injector, TextMapPropagator},
+    runtime::Tokio,
+    sdk::{propagation::TraceContextPropagator, trace, Resource},
+    KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+use tonic::{
+    codegen::http::{HeaderMap, Request},
+    metadata::{MetadataKey, MetadataMap, MetadataValue},
+    service::Interceptor,
+    Status,
+};
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{filter::LevelFilter, layer::SubscriberExt, util::SubscriberInitExt};
+use url::Url;
+
+/// Initializes tracing subscriber which logs to stdout and, when an OTLP endpoint is provided, exports spans to an
+/// OpenTelemetry collector at given gRPC endpoint
+pub fn init_tracing(otlp_endpoint: Option<&Url>) -> Result<()> {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let opentelemetry_layer = match otlp_endpoint {
+        Some(otlp_endpoint) => {
+            let tracer = opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .tonic()
+                        .with_endpoint(otlp_endpoint.as_str()),
+                )
+                .with_trace_config(trace::config().with_resource(Resource::new(vec![
+                    KeyValue::new("service.name", "stag-grpc"),
+                ])))
+                .install_batch(Tokio)
+                .context("failed to install OTLP exporter")?;
+
+            Some(tracing_opentelemetry::layer().with_tracer(tracer))
+        }
+        None => None,
+    };
+
+    tracing_subscriber::registry()
+        .with(LevelFilter::INFO)
+        .with(tracing_subscriber::fmt::layer())
+        .with(opentelemetry_layer)
+        .try_init()
+        .context("failed to initialize tracing subscriber")
+}
+
+/// Exports all the pending spans and shuts down OTLP exporter (if any)
+pub fn shutdown_tracing() {
+    global::shutdown_tracer_provider();
+}
+
+/// Creates a span for an incoming gRPC request (W3C trace context in `traceparent` header of request, if any, is used
+/// as the parent of span so that traces started by clients continue on server)
+pub fn request_span<B>(request: &Request<B>) -> Span {
+    let span = tracing::info_span!("grpc_request", path = %request.uri().path());
+
+    let parent = global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(request.headers()))
+    });
+    span.set_parent(parent);
+
+    span
+}
+
+/// Interceptor which adds W3C trace context of current span to `traceparent` metadata of outgoing gRPC requests (so
+/// that traces started by clients continue on server)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TraceContextInterceptor;
+
+impl Interceptor for TraceContextInterceptor {
+    fn call(&mut self, mut request: tonic::Request<()>) -> Result<tonic::Request<()>, Status> {
+        // Propagator is used directly (instead of the global one) so that clients do not need to call `init_tracing`
+        TraceContextPropagator::new().inject_context(
+            &Span::current().context(),
+            &mut MetadataInjector(request.metadata_mut()),
+        );
+
+        Ok(request)
+    }
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
+
+struct MetadataInjector<'a>(&'a mut MetadataMap);
+
+impl Injector for MetadataInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(key), Ok(value)) = (
+            MetadataKey::from_bytes(key.as_bytes()),
+            MetadataValue::try_from(&value),
+        ) {
+            self.0.insert(key, value);
+        }
+    }
+}
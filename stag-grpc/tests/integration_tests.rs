@@ -76,6 +76,9 @@ async fn test_stag_grpc_transfer_flow() {
         .add_chain(AddChainRequest {
             grpc_addr: None,
             rpc_addr: None,
+            fallback_grpc_addrs: Vec::new(),
+            fallback_rpc_addrs: Vec::new(),
+            witness_rpc_addrs: Vec::new(),
             fee_config: None,
             trust_level: None,
             trusting_period: None,
@@ -87,6 +90,7 @@ async fn test_stag_grpc_transfer_flow() {
                 .await
                 .expect("unable to fetch trusted hash"),
             packet_timeout_height_offset: None,
+            retry_policy: None,
         })
         .await
         .expect("failed to add chain")
@@ -47,6 +47,9 @@ impl State {
         Ok(ChainConfig {
             grpc_addr: self.grpc_addr.parse().context("Invalid gRPC address")?,
             rpc_addr: self.rpc_addr.parse().context("Invalid RPC address")?,
+            fallback_grpc_addrs: Vec::new(),
+            fallback_rpc_addrs: Vec::new(),
+            witness_rpc_addrs: Vec::new(),
             fee: Fee {
                 amount: self.fee_amount.parse().context("Invalid fee amount")?,
                 denom: self.fee_denom.parse().context("Invalid fee denom")?,
@@ -68,6 +71,7 @@ impl State {
                 .packet_timeout_height_offset
                 .parse()
                 .context("Invalid packet timeout height offset")?,
+            retry_policy: Default::default(),
         })
     }
 
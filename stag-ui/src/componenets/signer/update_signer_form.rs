@@ -215,7 +215,7 @@ pub fn update_signer_form(props: &Props) -> Html {
                 <TextInput class={classes!("mb-4")} placeholder="Mnemonic phrase" value={ state.mnemonic.clone() } />
                 <TextInput class={classes!("mb-4")} placeholder="HD Path to derive bech32 addresses (default: 'm/44'/118'/0'/0/0')" value={ state.hd_path.clone() } />
                 <TextInput class={classes!("mb-4")} placeholder="Bech32 address prefix (default: 'cosmos')" value={ state.account_prefix.clone() } />
-                <TextInput class={classes!("mb-4")} placeholder="Public key algorithm for chain (e.g. 'secp256k1' | 'eth-secp256k1') (default: 'secp256k1')" value={ state.algo.clone() } />
+                <TextInput class={classes!("mb-4")} placeholder="Public key algorithm for chain (e.g. 'secp256k1' | 'eth-secp256k1' | 'ed25519' | 'secp256r1') (default: 'secp256k1')" value={ state.algo.clone() } />
                 <TextInput class={classes!("mb-4")} placeholder="Memo to send in IBC transaction (optional)" value={ state.memo.clone() } />
                 <button type="submit" class={classes!(BUTTON_CLASSES)}>{ "Submit" }</button>
                 <button type="button" class={classes!(BUTTON_CLASSES, "ml-6")} onclick={